@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
 use regex::Regex;
-use rexile::Pattern;
+use rexile::{CaptureLocations, Pattern, PatternSet};
 
 struct SearchWorkload {
     name: &'static str,
@@ -61,6 +61,11 @@ const SEARCH_WORKLOADS: &[SearchWorkload] = &[
         pattern: r"\d{4}",
         text: "year 2026 and code 1234",
     },
+    SearchWorkload {
+        name: "iso_dates",
+        pattern: r"\d{4}-\d{2}-\d{2}",
+        text: "id 4411 at 2024-01-15, id 4412 at 2024-02-29, ref 12345-67-890",
+    },
     SearchWorkload {
         name: "case_insensitive_literal",
         pattern: r"(?i)error",
@@ -261,6 +266,213 @@ fn cached_api_benchmark(c: &mut Criterion) {
         b.iter(|| black_box(rexile::find("test", black_box(text)).unwrap()))
     });
 
+    // A lookahead searches for its literal with the matcher's own finder,
+    // built when the pattern compiles rather than on each call
+    let haystack = "hay hay hay hay hay hay hay hay hay hay hay hay hay hay hay hay hay needle";
+    group.bench_function("rexile/find_cached_literal_lookahead", |b| {
+        b.iter(|| black_box(rexile::find("(?=needle)", black_box(haystack)).unwrap()))
+    });
+
+    group.finish();
+}
+
+fn captures_read_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("captures_read");
+    configure_group(&mut group);
+
+    // One `CaptureLocations` serves every line, where `captures` builds a
+    // new `Captures` each time
+    let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    let lines: Vec<String> = (0..1000).map(|i| format!("key{i}={i}")).collect();
+
+    group.bench_function("rexile/captures", |b| {
+        b.iter(|| {
+            let mut total = 0;
+            for line in &lines {
+                if let Some(caps) = pattern.captures(black_box(line)) {
+                    total += caps.get_range(2).unwrap().1;
+                }
+            }
+            black_box(total)
+        })
+    });
+    group.bench_function("rexile/captures_read", |b| {
+        let mut locs = CaptureLocations::new();
+        b.iter(|| {
+            let mut total = 0;
+            for line in &lines {
+                if pattern.captures_read(&mut locs, black_box(line)) {
+                    total += locs.get(2).unwrap().1;
+                }
+            }
+            black_box(total)
+        })
+    });
+
+    group.finish();
+}
+
+fn large_input_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("large_input");
+    configure_group(&mut group);
+
+    // 1 MB of short runs: quantified find_all must stay linear in the input size
+    let runs = "aaab ".repeat(1 << 18);
+    let long_run = "a".repeat(1 << 20);
+    // Counted digit runs broken by single dashes: bounded repetition all the way
+    let ranges = "12-3 4567 89-1 ".repeat(1 << 16);
+    // Log lines with several short counted fields, about 1 MB
+    let log = "INFO 200 ab3f GET /index.html took 12ms\nWARN 404 c0de miss /favicon.ico\n"
+        .repeat(1 << 14);
+    // A capture pattern whose leading literal turns up once every 64 KB
+    let sparse = format!("{}foo42 ", "x".repeat(1 << 16)).repeat(16);
+
+    for (name, pattern, text) in [
+        ("quantified_plus_runs", "a+", runs.as_str()),
+        ("quantified_plus_long_run", "a+", long_run.as_str()),
+        ("quantified_at_least_short_runs", "a{4,}", runs.as_str()),
+        ("bounded_ranges", r"\d{2,4}-\d{2,4}", ranges.as_str()),
+        (
+            "bounded_log_fields",
+            r"[A-Z]{2,4} [0-9]{2,4} [a-f0-9]{2,4}",
+            log.as_str(),
+        ),
+        ("capture_sparse_literal", r"foo(\d+)", sparse.as_str()),
+    ] {
+        let rexile = Pattern::new(pattern).unwrap();
+        let regex = Regex::new(pattern).unwrap();
+
+        group.bench_with_input(BenchmarkId::new("rexile", name), &text, |b, &text| {
+            b.iter(|| black_box(rexile.find_all(black_box(text))))
+        });
+        group.bench_with_input(BenchmarkId::new("regex", name), &text, |b, &text| {
+            b.iter(|| {
+                black_box(
+                    regex
+                        .find_iter(black_box(text))
+                        .map(|mat| (mat.start(), mat.end()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+        });
+    }
+
+    group.finish();
+}
+
+fn anchored_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("anchored");
+    configure_group(&mut group);
+
+    // `^` sequences run an anchored DFA from position 0 only; the capture
+    // group form still goes through the anchored group machinery
+    let dated = format!("2024-01-15 {}", "log line without a date ".repeat(64));
+    let undated = format!("x {}", dated);
+    let regex = Regex::new(r"^\d{4}-\d{2}-\d{2}").unwrap();
+
+    for (name, text) in [("match", dated.as_str()), ("no_match", undated.as_str())] {
+        for (engine, pattern) in [
+            ("anchored_dfa", r"^\d{4}-\d{2}-\d{2}"),
+            ("anchored_group", r"^(\d{4}-\d{2}-\d{2})"),
+        ] {
+            let rexile = Pattern::new(pattern).unwrap();
+            group.bench_with_input(BenchmarkId::new(engine, name), &text, |b, &text| {
+                b.iter(|| black_box(rexile.find(black_box(text))))
+            });
+        }
+
+        group.bench_with_input(BenchmarkId::new("regex", name), &text, |b, &text| {
+            b.iter(|| {
+                black_box(
+                    regex
+                        .find(black_box(text))
+                        .map(|mat| (mat.start(), mat.end())),
+                )
+            })
+        });
+    }
+
+    // `$` sequences check the trailing literal and read the rest backward,
+    // instead of scanning every start position
+    let sources = format!("{}main.rs", "some/path/to/a/file/".repeat(64));
+    let other = format!("{}main.rsx", "some/path/to/a/file/".repeat(64));
+    let rexile = Pattern::new(r"\w+\.rs$").unwrap();
+    let regex = Regex::new(r"\w+\.rs$").unwrap();
+    for (name, text) in [
+        ("suffix_match", sources.as_str()),
+        ("suffix_no_match", other.as_str()),
+    ] {
+        group.bench_with_input(BenchmarkId::new("reverse_dfa", name), &text, |b, &text| {
+            b.iter(|| black_box(rexile.find(black_box(text))))
+        });
+        group.bench_with_input(BenchmarkId::new("regex", name), &text, |b, &text| {
+            b.iter(|| {
+                black_box(
+                    regex
+                        .find(black_box(text))
+                        .map(|mat| (mat.start(), mat.end())),
+                )
+            })
+        });
+    }
+
+    // `^...$` sequences stop reading at the first char that can't continue a
+    // whole-text match, however long the input
+    let not_digits = "x".repeat(1 << 20);
+    let rexile = Pattern::new(r"^\d{3}$").unwrap();
+    let regex = Regex::new(r"^\d{3}$").unwrap();
+    group.bench_with_input(
+        BenchmarkId::new("entire_dfa", "1mb_no_match"),
+        &not_digits.as_str(),
+        |b, &text| b.iter(|| black_box(rexile.is_match(black_box(text)))),
+    );
+    group.bench_with_input(
+        BenchmarkId::new("regex", "1mb_no_match"),
+        &not_digits.as_str(),
+        |b, &text| b.iter(|| black_box(regex.is_match(black_box(text)))),
+    );
+
+    group.finish();
+}
+
+fn pattern_set_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("pattern_set");
+    configure_group(&mut group);
+
+    // 200 literal-led patterns over 1 MB: the set scans the text once for all
+    // their literals, the loop once per pattern
+    let sources: Vec<String> = (0..200).map(|i| format!(r"key{i:03}=\d+")).collect();
+    let text = format!("{}key123=7", "lorem ipsum dolor sit amet ".repeat(1 << 15));
+    let set = PatternSet::new(&sources).unwrap();
+    let patterns: Vec<Pattern> = sources.iter().map(|p| Pattern::new(p).unwrap()).collect();
+    let regex_set = regex::RegexSet::new(&sources).unwrap();
+
+    group.bench_function("set", |b| {
+        b.iter(|| black_box(set.matches(black_box(&text))))
+    });
+    group.bench_function("loop", |b| {
+        b.iter(|| {
+            black_box(
+                patterns
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, pattern)| pattern.is_match(black_box(&text)))
+                    .map(|(index, _)| index)
+                    .collect::<Vec<_>>(),
+            )
+        })
+    });
+    group.bench_function("regex_set", |b| {
+        b.iter(|| {
+            black_box(
+                regex_set
+                    .matches(black_box(&text))
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+            )
+        })
+    });
+
     group.finish();
 }
 
@@ -272,6 +484,10 @@ criterion_group!(
     find_all_benchmark,
     replacement_and_split_benchmark,
     cached_api_benchmark,
+    captures_read_benchmark,
+    large_input_benchmark,
+    anchored_benchmark,
+    pattern_set_benchmark,
 );
 
 criterion_main!(benches);
@@ -25,7 +25,9 @@
 //! }
 //! ```
 
-use std::ops::Index;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Index;
 
 /// A capture group in the pattern
 #[derive(Debug, Clone, PartialEq)]
@@ -68,6 +70,28 @@ impl Group {
 }
 
 /// A set of captured substrings from a single match
+///
+/// The slices [`get`](Self::get), [`as_str`](Self::as_str) and
+/// [`iter`](Self::iter) hand out borrow the searched text for `'t`, not the
+/// `Captures` value, so they can outlive it (and the [`Pattern`] that made
+/// it). Only `&caps[i]` is tied to `caps`, as `Index` requires.
+///
+/// # Example
+/// ```
+/// use rexile::Pattern;
+///
+/// fn keys(text: &str) -> Vec<&str> {
+///     let pattern = Pattern::new(r"(\w+)=").unwrap();
+///     pattern
+///         .captures_iter(text)
+///         .filter_map(|caps| caps.get(1))
+///         .collect()
+/// }
+///
+/// assert_eq!(keys("a=1 b=2"), vec!["a", "b"]);
+/// ```
+///
+/// [`Pattern`]: crate::Pattern
 #[derive(Debug, Clone)]
 pub struct Captures<'t> {
     /// The original text that was matched against
@@ -76,19 +100,69 @@ pub struct Captures<'t> {
     /// Index 0 is always the full match
     /// Indices 1+ are the capture groups
     positions: Vec<Option<(usize, usize)>>,
+    /// Which branch of a top-level alternation matched
+    branch: Option<usize>,
 }
 
 impl<'t> Captures<'t> {
     /// Create a new Captures with the full match
     pub fn new(text: &'t str, full_match: (usize, usize), num_groups: usize) -> Self {
-        let mut positions = vec![None; num_groups + 1];
+        Self::reusing(text, full_match, num_groups, &mut Vec::new())
+    }
+
+    /// Like [`new`](Self::new), but with the slots taken from `buffer`, so
+    /// its allocation is reused; `buffer` is left empty
+    pub(crate) fn reusing(
+        text: &'t str,
+        full_match: (usize, usize),
+        num_groups: usize,
+        buffer: &mut Vec<Option<(usize, usize)>>,
+    ) -> Self {
+        let mut positions = core::mem::take(buffer);
+        positions.clear();
+        positions.resize(num_groups + 1, None);
         positions[0] = Some(full_match);
-        Self { text, positions }
+        Self {
+            text,
+            positions,
+            branch: None,
+        }
+    }
+
+    /// The slots, for a later [`reusing`](Self::reusing)
+    pub(crate) fn into_positions(self) -> Vec<Option<(usize, usize)>> {
+        self.positions
+    }
+
+    /// Group slots 1 and up, for an engine to fill in place
+    pub(crate) fn groups_mut(&mut self) -> &mut [Option<(usize, usize)>] {
+        &mut self.positions[1..]
     }
 
     /// Get the matched substring for a capture group
     ///
-    /// Index 0 returns the full match, indices 1+ return capture groups
+    /// Index 0 returns the full match, indices 1+ return capture groups.
+    /// Returns `None` for a group that did not participate in the match
+    /// (like group 1 of `(a)?(b)` on `"b"`) and for an index past the last
+    /// group; `&caps[index]` panics in both cases instead. The slice borrows
+    /// the searched text, not `self`.
+    ///
+    /// A group that repeats, like `(,\d+)*`, holds what its last iteration
+    /// matched, and a group inside one keeps its value from the last
+    /// iteration that set it.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let caps = Pattern::new(r"(a)?(b)").unwrap().captures("b").unwrap();
+    /// assert_eq!(caps.get(1), None);
+    /// assert_eq!(caps.get(2), Some("b"));
+    /// assert_eq!(caps.get(3), None);
+    ///
+    /// let caps = Pattern::new(r"(\d+)(,\d+)*").unwrap().captures("1,2,3").unwrap();
+    /// assert_eq!(caps.get(2), Some(",3"));
+    /// ```
     pub fn get(&self, index: usize) -> Option<&'t str> {
         self.positions
             .get(index)?
@@ -100,6 +174,24 @@ impl<'t> Captures<'t> {
         self.positions.get(index).and_then(|&pos| pos)
     }
 
+    /// Get the byte range (start, end) of a capture group in the text
+    ///
+    /// The span counterpart of [`get`](Self::get), with the same `None`
+    /// cases: an unmatched group or an index past the last group.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let caps = Pattern::new(r"(a)?(b)").unwrap().captures("xb").unwrap();
+    /// assert_eq!(caps.get_range(0), Some((1, 2)));
+    /// assert_eq!(caps.get_range(1), None);
+    /// assert_eq!(caps.get_range(2), Some((1, 2)));
+    /// ```
+    pub fn get_range(&self, index: usize) -> Option<(usize, usize)> {
+        self.pos(index)
+    }
+
     /// Get the full matched text (equivalent to get(0))
     pub fn as_str(&self) -> &'t str {
         self.get(0).unwrap_or("")
@@ -112,6 +204,35 @@ impl<'t> Captures<'t> {
         }
     }
 
+    /// Index of the top-level alternation branch that matched
+    ///
+    /// For a pattern like `(\d+)|([a-z]+)|(\s+)` this says which alternative
+    /// fired without checking which group is set, so one pattern can drive a
+    /// tokenizer. `None` when the pattern is not a top-level alternation.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(\d+)|([a-z]+)|(\s+)").unwrap();
+    /// let kinds: Vec<_> = pattern
+    ///     .captures_iter("let x 42")
+    ///     .map(|caps| caps.branch())
+    ///     .collect();
+    /// assert_eq!(kinds, vec![Some(1), Some(2), Some(1), Some(2), Some(0)]);
+    ///
+    /// let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    /// assert_eq!(pattern.captures("a=1").unwrap().branch(), None);
+    /// ```
+    pub fn branch(&self) -> Option<usize> {
+        self.branch
+    }
+
+    /// Record which top-level alternation branch matched
+    pub(crate) fn set_branch(&mut self, index: usize) {
+        self.branch = Some(index);
+    }
+
     /// Number of capture groups (including the full match at index 0)
     pub fn len(&self) -> usize {
         self.positions.len()
@@ -122,41 +243,259 @@ impl<'t> Captures<'t> {
         self.positions.is_empty()
     }
 
-    /// Iterate over all captured substrings
-    pub fn iter(&self) -> CapturesIter<'_, 't> {
-        CapturesIter {
-            captures: self,
-            index: 0,
+    /// Number of capture groups that took part in this match
+    ///
+    /// The full match isn't counted, and neither are groups left unset, like
+    /// an optional group that was skipped or the groups of an alternation
+    /// branch that didn't fire. A group that matched the empty string did
+    /// take part and counts.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(a)?(b*)c").unwrap();
+    /// assert_eq!(pattern.captures("abc").unwrap().matched_groups(), 2);
+    /// // (b*) matched empty, (a)? didn't match at all
+    /// assert_eq!(pattern.captures("c").unwrap().matched_groups(), 1);
+    /// ```
+    pub fn matched_groups(&self) -> usize {
+        self.positions[1..]
+            .iter()
+            .filter(|pos| pos.is_some())
+            .count()
+    }
+
+    /// Expand `template` with this capture set and append the result to `dst`
+    ///
+    /// - `$1`..`$9` insert the corresponding group
+    /// - `${N}` inserts group `N` (any number of digits)
+    /// - `${name}` inserts a named group; the parser does not record group
+    ///   names yet, so these expand to nothing
+    /// - `$$` inserts a literal `$`
+    ///
+    /// Groups that did not participate in the match expand to nothing, as do
+    /// references to groups the pattern doesn't have (use
+    /// [`try_expand`](Self::try_expand) to reject those). A `$` that does not
+    /// start a reference is copied as is.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    /// let caps = pattern.captures("a=1").unwrap();
+    ///
+    /// let mut out = String::new();
+    /// caps.expand("$2-$1 costs $$${2}", &mut out);
+    /// assert_eq!(out, "1-a costs $1");
+    /// ```
+    pub fn expand(&self, template: &str, dst: &mut String) {
+        for piece in TemplatePieces::new(template) {
+            match piece {
+                TemplatePiece::Text(text) => dst.push_str(text),
+                TemplatePiece::Group(name) => {
+                    if let Some(group_text) = name.parse().ok().and_then(|n| self.get(n)) {
+                        dst.push_str(group_text);
+                    }
+                }
+            }
         }
     }
+
+    /// Like [`expand`](Self::expand), but a reference to a group the pattern
+    /// doesn't have is an error and nothing is appended
+    ///
+    /// Groups that exist but did not participate in the match still expand
+    /// to nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(\d+)?(\w+)").unwrap();
+    /// let caps = pattern.captures("abc").unwrap();
+    ///
+    /// let mut out = String::new();
+    /// caps.try_expand("[$1$2]", &mut out).unwrap();
+    /// assert_eq!(out, "[abc]");
+    ///
+    /// let err = caps.try_expand("$3", &mut out).unwrap_err();
+    /// assert_eq!(err.to_string(), "no capture group 3 (the pattern has 2 groups)");
+    /// ```
+    pub fn try_expand(&self, template: &str, dst: &mut String) -> Result<(), UnknownGroup> {
+        check_template(template, self.len() - 1)?;
+        self.expand(template, dst);
+        Ok(())
+    }
+
+    /// Iterate over every group in order, starting with the full match
+    ///
+    /// Yields `len()` items; groups that did not participate in the match
+    /// yield `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(a)|(b)").unwrap();
+    /// let caps = pattern.captures("b").unwrap();
+    /// let groups: Vec<_> = caps.iter().collect();
+    /// assert_eq!(groups, vec![Some("b"), None, Some("b")]);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = Option<&'t str>> + '_ {
+        (0..self.len()).map(move |index| self.get(index))
+    }
 }
 
-/// Allow indexing Captures by group number
-impl<'t> Index<usize> for Captures<'t> {
-    type Output = str;
+/// A replacement template reference to a group the pattern doesn't have
+///
+/// Returned by [`Captures::try_expand`] and
+/// [`Pattern::replace_all_strict`](crate::Pattern::replace_all_strict).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownGroup {
+    /// The reference as written, without `$` or braces: `"3"`, `"name"`
+    pub reference: String,
+    /// Number of capture groups the pattern has
+    pub groups: usize,
+}
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index)
-            .unwrap_or_else(|| panic!("no capture group at index {}", index))
+impl core::fmt::Display for UnknownGroup {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "no capture group {} (the pattern has {} groups)",
+            self.reference, self.groups
+        )
     }
 }
 
-/// Iterator over captured substrings
-pub struct CapturesIter<'c, 't> {
-    captures: &'c Captures<'t>,
-    index: usize,
+#[cfg(feature = "std")]
+impl std::error::Error for UnknownGroup {}
+
+/// Check that every reference in `template` names one of `groups` groups
+pub(crate) fn check_template(template: &str, groups: usize) -> Result<(), UnknownGroup> {
+    for piece in TemplatePieces::new(template) {
+        if let TemplatePiece::Group(name) = piece {
+            if !name.parse().is_ok_and(|n: usize| n <= groups) {
+                return Err(UnknownGroup {
+                    reference: name.into(),
+                    groups,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Part of a replacement template
+enum TemplatePiece<'a> {
+    /// Copied as is
+    Text(&'a str),
+    /// A `$N` or `${...}` reference, without `$` or braces
+    Group(&'a str),
+}
+
+/// Splits a replacement template into text and group references
+struct TemplatePieces<'a> {
+    rest: &'a str,
 }
 
-impl<'c, 't> Iterator for CapturesIter<'c, 't> {
-    type Item = Option<&'t str>;
+impl<'a> TemplatePieces<'a> {
+    fn new(template: &'a str) -> Self {
+        Self { rest: template }
+    }
+}
+
+impl<'a> Iterator for TemplatePieces<'a> {
+    type Item = TemplatePiece<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.index >= self.captures.len() {
+        let rest = self.rest;
+        if rest.is_empty() {
             return None;
         }
-        let result = self.captures.get(self.index);
-        self.index += 1;
-        Some(result)
+        let Some(after) = rest.strip_prefix('$') else {
+            // Text up to the next `$`
+            let text_end = rest.find('$').unwrap_or(rest.len());
+            self.rest = &rest[text_end..];
+            return Some(TemplatePiece::Text(&rest[..text_end]));
+        };
+
+        if after.starts_with('$') {
+            self.rest = &after[1..];
+            Some(TemplatePiece::Text("$"))
+        } else if after.starts_with(|ch: char| ch.is_ascii_digit()) {
+            self.rest = &after[1..];
+            Some(TemplatePiece::Group(&after[..1]))
+        } else if let Some((name, tail)) = after
+            .strip_prefix('{')
+            .and_then(|braced| braced.split_once('}'))
+        {
+            self.rest = tail;
+            Some(TemplatePiece::Group(name))
+        } else {
+            self.rest = after;
+            Some(TemplatePiece::Text("$"))
+        }
+    }
+}
+
+/// Reusable buffer of capture group positions
+///
+/// Filled by [`Pattern::captures_read`](crate::Pattern::captures_read), which
+/// has the capture engines write into it directly, so its allocation is kept
+/// across calls. Slots hold byte ranges into the text that was searched;
+/// index 0 is the full match.
+#[derive(Debug, Clone, Default)]
+pub struct CaptureLocations {
+    pub(crate) positions: Vec<Option<(usize, usize)>>,
+}
+
+impl CaptureLocations {
+    /// Create an empty buffer; it is sized by the first `captures_read`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the position (start, end) of a capture group
+    pub fn get(&self, index: usize) -> Option<(usize, usize)> {
+        self.positions.get(index).and_then(|&pos| pos)
+    }
+
+    /// Number of slots (including the full match at index 0)
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Check if the buffer holds no slots
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Clear all slots, keeping the allocation
+    pub(crate) fn clear(&mut self) {
+        self.positions.clear();
+    }
+}
+
+/// Allow indexing Captures by group number
+///
+/// Panics if the pattern has no group `index` or the group did not
+/// participate in the match; use [`Captures::get`] to get `None` instead.
+impl<'t> Index<usize> for Captures<'t> {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        match self.positions.get(index) {
+            Some(Some((start, end))) => &self.text[*start..*end],
+            Some(None) => panic!("capture group {} did not participate in the match", index),
+            None => panic!(
+                "no capture group {} (the pattern has {} groups)",
+                index,
+                self.positions.len() - 1
+            ),
+        }
     }
 }
 
@@ -167,7 +506,7 @@ pub struct CapturesMatches<'r, 't> {
     num_groups: usize,
     // This would hold a reference to the compiled pattern
     // For now, we'll keep it simple
-    _phantom: std::marker::PhantomData<&'r ()>,
+    _phantom: core::marker::PhantomData<&'r ()>,
 }
 
 impl<'r, 't> CapturesMatches<'r, 't> {
@@ -177,7 +516,7 @@ impl<'r, 't> CapturesMatches<'r, 't> {
             text,
             last_end: 0,
             num_groups,
-            _phantom: std::marker::PhantomData,
+            _phantom: core::marker::PhantomData,
         }
     }
 }
@@ -233,6 +572,30 @@ mod tests {
         assert_eq!(&caps[2], "123");
     }
 
+    #[test]
+    #[should_panic(expected = "capture group 1 did not participate in the match")]
+    fn test_captures_indexing_unmatched_group() {
+        let caps = Captures::new("b", (0, 1), 2);
+        let _ = &caps[1];
+    }
+
+    #[test]
+    #[should_panic(expected = "no capture group 3 (the pattern has 2 groups)")]
+    fn test_captures_indexing_missing_group() {
+        let caps = Captures::new("b", (0, 1), 2);
+        let _ = &caps[3];
+    }
+
+    #[test]
+    fn test_captures_matched_groups() {
+        let mut caps = Captures::new("ab", (0, 2), 3);
+        assert_eq!(caps.matched_groups(), 0);
+        caps.set(1, 0, 2);
+        caps.set(3, 2, 2); // empty, but set
+        assert_eq!(caps.matched_groups(), 2);
+        assert_eq!(Captures::new("", (0, 0), 0).matched_groups(), 0);
+    }
+
     #[test]
     fn test_captures_pos() {
         let text = "abc123";
@@ -246,6 +609,62 @@ mod tests {
         assert_eq!(caps.pos(3), None);
     }
 
+    #[test]
+    fn test_captures_iter_includes_unset_groups() {
+        let text = "foo=123";
+        let mut caps = Captures::new(text, (0, 7), 3);
+        caps.set(1, 0, 3);
+        caps.set(3, 4, 7);
+
+        let groups: Vec<_> = caps.iter().collect();
+        assert_eq!(
+            groups,
+            vec![Some("foo=123"), Some("foo"), None, Some("123")]
+        );
+        assert_eq!(groups.len(), caps.len());
+    }
+
+    #[test]
+    fn test_captures_expand() {
+        let text = "foo=123";
+        let mut caps = Captures::new(text, (0, 7), 3);
+        caps.set(1, 0, 3);
+        caps.set(2, 4, 7);
+
+        let mut out = String::from(">");
+        caps.expand("$2-$1", &mut out);
+        assert_eq!(out, ">123-foo");
+
+        out.clear();
+        caps.expand("${1}x $$1 [$3] ${name} $x $", &mut out);
+        assert_eq!(out, "foox $1 []  $x $");
+
+        // Unterminated brace is copied literally
+        out.clear();
+        caps.expand("${1", &mut out);
+        assert_eq!(out, "${1");
+    }
+
+    #[test]
+    fn test_captures_reusing_keeps_the_buffer() {
+        let text = "abc123";
+        let mut buffer = Vec::with_capacity(8);
+        buffer.push(Some((9, 9)));
+        let ptr = buffer.as_ptr();
+
+        let mut caps = Captures::reusing(text, (0, 6), 2, &mut buffer);
+        assert!(buffer.is_empty());
+        caps.groups_mut()[1] = Some((3, 6));
+        assert_eq!(caps.len(), 3);
+        assert_eq!(caps.pos(0), Some((0, 6)));
+        assert_eq!(caps.pos(1), None);
+        assert_eq!(caps.pos(2), Some((3, 6)));
+
+        let positions = caps.into_positions();
+        assert_eq!(positions.as_ptr(), ptr);
+        assert_eq!(positions, vec![Some((0, 6)), None, Some((3, 6))]);
+    }
+
     #[test]
     fn test_group_types() {
         let capturing = Group::new(1);
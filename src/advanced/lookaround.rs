@@ -11,6 +11,10 @@
 //! - `(?<=pattern)` - Positive lookbehind: succeeds if pattern matches behind
 //! - `(?<!pattern)` - Negative lookbehind: succeeds if pattern does NOT match behind
 //!
+//! A lookbehind's pattern may vary in length, like `(?<=a{1,3})`, as long as
+//! it has a longest match; `(?<=\d+)` is rejected with
+//! [`UnsupportedFeature::VariableLengthLookbehind`](crate::UnsupportedFeature).
+//!
 //! # Examples
 //! ```
 //! use rexile::Pattern;
@@ -26,7 +30,9 @@
 //! assert!(!pattern.is_match("foobar"));
 //! ```
 
-use crate::{Ast, Matcher};
+use crate::{max_char_len, Ast, Matcher, PatternError, UnsupportedFeature};
+use alloc::boxed::Box;
+use memchr::memmem;
 
 /// Type of lookaround assertion
 #[derive(Debug, Clone, PartialEq)]
@@ -46,15 +52,31 @@ pub enum LookaroundType {
 pub struct Lookaround {
     pub lookaround_type: LookaroundType,
     pub pattern: Box<Ast>,
+    /// Most chars the inner pattern can span, `None` if unbounded
+    pub max_len: Option<usize>,
 }
 
 impl Lookaround {
     /// Create a new lookaround assertion
-    pub fn new(lookaround_type: LookaroundType, pattern: Ast) -> Self {
-        Self {
+    ///
+    /// Fails for a lookbehind whose pattern has no longest match, since every
+    /// earlier position in the text would be a candidate start.
+    pub fn new(lookaround_type: LookaroundType, pattern: Ast) -> Result<Self, PatternError> {
+        let max_len = max_char_len(&pattern);
+        let behind = matches!(
             lookaround_type,
-            pattern: Box::new(pattern),
+            LookaroundType::PositiveLookbehind | LookaroundType::NegativeLookbehind
+        );
+        if behind && max_len.is_none() {
+            return Err(PatternError::UnsupportedFeature(
+                UnsupportedFeature::VariableLengthLookbehind,
+            ));
         }
+        Ok(Self {
+            lookaround_type,
+            max_len,
+            pattern: Box::new(pattern),
+        })
     }
 
     /// Check if the lookaround matches at the given position
@@ -63,8 +85,12 @@ impl Lookaround {
     /// For lookbehind: checks if pattern matches ending at `pos`
     ///
     /// Returns true if the assertion succeeds (pattern matches for positive,
-    /// or doesn't match for negative lookaround)
+    /// or doesn't match for negative lookaround). Positions inside a
+    /// multi-byte char never match.
     pub fn matches_at(&self, text: &str, pos: usize, matcher: &Matcher) -> bool {
+        if !text.is_char_boundary(pos) {
+            return false;
+        }
         match self.lookaround_type {
             LookaroundType::PositiveLookahead => {
                 // Check if pattern matches starting at pos
@@ -85,6 +111,40 @@ impl Lookaround {
         }
     }
 
+    /// First position at or after `from` where the assertion holds
+    ///
+    /// A positive assertion over a non-empty literal (or, for lookahead, a
+    /// literal alternation) jumps between occurrences of the literal;
+    /// otherwise each char boundary is tried in turn.
+    pub fn find_from(&self, text: &str, from: usize, matcher: &Matcher) -> Option<usize> {
+        if from > text.len() {
+            return None;
+        }
+        match (&self.lookaround_type, matcher) {
+            (LookaroundType::PositiveLookahead, Matcher::Literal(lit)) if !lit.is_empty() => {
+                lit.find(&text[from..]).map(|at| from + at)
+            }
+            (
+                LookaroundType::PositiveLookahead,
+                Matcher::MultiLiteral {
+                    searcher, literals, ..
+                },
+            ) if literals.iter().all(|lit| !lit.is_empty()) => {
+                // The leftmost literal occurrence is the first place any starts
+                searcher
+                    .find_at(text.as_bytes(), from)
+                    .map(|(start, _)| start)
+            }
+            (LookaroundType::PositiveLookbehind, Matcher::Literal(lit)) if !lit.is_empty() => {
+                let search_from = from.saturating_sub(lit.len());
+                memmem::find_iter(&text.as_bytes()[search_from..], lit.as_bytes())
+                    .map(|at| search_from + at + lit.len())
+                    .find(|&end| end >= from)
+            }
+            _ => (from..=text.len()).find(|&pos| self.matches_at(text, pos, matcher)),
+        }
+    }
+
     /// Check lookahead assertion
     fn check_lookahead(&self, text: &str, pos: usize, matcher: &Matcher, positive: bool) -> bool {
         if pos > text.len() {
@@ -125,11 +185,23 @@ impl Lookaround {
     /// Check if pattern matches at the given position
     fn pattern_matches_at(&self, text: &str, pos: usize, matcher: &Matcher) -> bool {
         // Check if the pattern matches AT THE START of text[pos..]
-        if pos > text.len() {
+        let Some(remaining) = text.get(pos..) else {
             return false;
+        };
+        // Literals only need comparing here, not searching for
+        match matcher {
+            Matcher::Literal(lit) => return remaining.starts_with(lit.as_str()),
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive: false,
+                ..
+            } => {
+                return literals
+                    .iter()
+                    .any(|lit| remaining.starts_with(lit.as_str()))
+            }
+            _ => {}
         }
-
-        let remaining = &text[pos..];
         // Use find() and check if it starts at position 0
         if let Some((start, _end)) = matcher.find(remaining) {
             start == 0
@@ -140,45 +212,53 @@ impl Lookaround {
 
     /// Find if any match ends exactly at the given position
     fn find_match_ending_at(&self, text: &str, pos: usize, matcher: &Matcher) -> bool {
-        // Try all possible starting positions that could end at `pos`
-        for start in 0..=pos {
-            if self.check_match_span(text, start, pos, matcher) {
-                return true;
-            }
+        let Some(before) = text.get(..pos) else {
+            return false;
+        };
+        match matcher {
+            Matcher::Literal(lit) => return before.ends_with(lit.as_str()),
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive: false,
+                ..
+            } => return literals.iter().any(|lit| before.ends_with(lit.as_str())),
+            _ => {}
         }
-        false
+
+        // Walk back one char at a time, no further than the inner pattern
+        // can reach
+        let starts = core::iter::once(pos).chain(before.char_indices().rev().map(|(i, _)| i));
+        let reach = self.max_len.map_or(usize::MAX, |max| max + 1);
+        starts
+            .take(reach)
+            .any(|start| self.check_match_span(text, start, pos, matcher))
     }
 
     /// Check if pattern matches exactly from start to end position
     fn check_match_span(&self, text: &str, start: usize, end: usize, matcher: &Matcher) -> bool {
-        if start > text.len() || end > text.len() || start > end {
+        // Out of range, or not on char boundaries
+        let Some(span) = text.get(start..end) else {
             return false;
-        }
-
-        let span = &text[start..end];
+        };
 
-        // Check if the pattern matches exactly this span
-        if let Some((match_start, match_end)) = matcher.find(span) {
-            // Must match the entire span (start at 0, end at span.len())
-            match_start == 0 && match_end == span.len()
-        } else {
-            false
-        }
+        // The pattern must cover the whole span, whichever branch does it
+        Matcher::matches_entire(matcher, span)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::Matcher;
+    use crate::{LiteralMatcher, Matcher};
 
     #[test]
     fn test_positive_lookahead() {
         let lookaround = Lookaround::new(
             LookaroundType::PositiveLookahead,
             Ast::Literal("bar".to_string()),
-        );
-        let matcher = Matcher::Literal("bar".to_string());
+        )
+        .unwrap();
+        let matcher = Matcher::Literal(LiteralMatcher::new("bar".to_string()));
 
         // "foobar" at position 3 - lookahead should match "bar"
         assert!(lookaround.matches_at("foobar", 3, &matcher));
@@ -192,8 +272,9 @@ mod tests {
         let lookaround = Lookaround::new(
             LookaroundType::NegativeLookahead,
             Ast::Literal("bar".to_string()),
-        );
-        let matcher = Matcher::Literal("bar".to_string());
+        )
+        .unwrap();
+        let matcher = Matcher::Literal(LiteralMatcher::new("bar".to_string()));
 
         // "foobaz" at position 3 - negative lookahead should succeed (bar not ahead)
         assert!(lookaround.matches_at("foobaz", 3, &matcher));
@@ -207,8 +288,9 @@ mod tests {
         let lookaround = Lookaround::new(
             LookaroundType::PositiveLookbehind,
             Ast::Literal("foo".to_string()),
-        );
-        let matcher = Matcher::Literal("foo".to_string());
+        )
+        .unwrap();
+        let matcher = Matcher::Literal(LiteralMatcher::new("foo".to_string()));
 
         // "foobar" at position 3 - lookbehind should match "foo"
         assert!(lookaround.matches_at("foobar", 3, &matcher));
@@ -222,8 +304,9 @@ mod tests {
         let lookaround = Lookaround::new(
             LookaroundType::NegativeLookbehind,
             Ast::Literal("foo".to_string()),
-        );
-        let matcher = Matcher::Literal("foo".to_string());
+        )
+        .unwrap();
+        let matcher = Matcher::Literal(LiteralMatcher::new("foo".to_string()));
 
         // "bazbar" at position 3 - negative lookbehind should succeed (foo not behind)
         assert!(lookaround.matches_at("bazbar", 3, &matcher));
@@ -231,4 +314,21 @@ mod tests {
         // "foobar" at position 3 - negative lookbehind should fail (foo is behind)
         assert!(!lookaround.matches_at("foobar", 3, &matcher));
     }
+
+    #[test]
+    fn test_lookbehind_max_len() {
+        let max_len = |pattern: &str| {
+            let ast = crate::parse_pattern(pattern).unwrap();
+            Lookaround::new(LookaroundType::PositiveLookbehind, ast).map(|l| l.max_len)
+        };
+        assert_eq!(max_len("foo"), Ok(Some(3)));
+        assert_eq!(max_len("é{2}"), Ok(Some(2)));
+        assert_eq!(max_len(r"\$\s?"), Ok(Some(2)));
+        assert_eq!(max_len("a{1,3}b"), Ok(Some(4)));
+        let unbounded = Err(PatternError::UnsupportedFeature(
+            UnsupportedFeature::VariableLengthLookbehind,
+        ));
+        assert_eq!(max_len(r"\$\s*"), unbounded);
+        assert_eq!(max_len(r"\d+"), unbounded);
+    }
 }
@@ -8,5 +8,5 @@ pub mod captures;
 pub mod lookaround;
 
 // Re-export public types
-pub use captures::{Captures, Group as CaptureGroup};
+pub use captures::{CaptureLocations, Captures, Group as CaptureGroup, UnknownGroup};
 pub use lookaround::{Lookaround, LookaroundType};
@@ -0,0 +1,129 @@
+//! Searching byte slices that may not be valid UTF-8
+//!
+//! The matchers all work on `&str`, so a haystack with invalid UTF-8 is
+//! searched through a stand-in text: valid runs are copied as they are and
+//! every byte that isn't part of a valid UTF-8 sequence becomes one char from
+//! the top of the private use planes (`U+10FF80..=U+10FFFF`, one per byte
+//! value). No literal, escape or ranged class written in a pattern reaches
+//! those chars, but `.` and negated classes do, so `.` matches any single
+//! invalid byte except `\n` (which is always valid) the same way it matches
+//! any other char. Offsets are mapped back to the original bytes, and a
+//! haystack that is valid UTF-8 is searched in place.
+
+use crate::Pattern;
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// First stand-in char; an invalid byte `b` (always `>= 0x80`) becomes
+/// `STAND_IN_BASE + b`
+const STAND_IN_BASE: u32 = 0x10FF00;
+
+/// Extra bytes a stand-in char takes up over the byte it replaces
+const STAND_IN_EXTRA: usize = 3;
+
+/// A byte haystack as text the matchers can search
+struct ByteText<'h> {
+    text: Cow<'h, str>,
+    /// Offsets into `text` of each stand-in char, in order
+    stand_ins: Vec<usize>,
+}
+
+impl<'h> ByteText<'h> {
+    fn new(haystack: &'h [u8]) -> Self {
+        let mut rest = match core::str::from_utf8(haystack) {
+            Ok(text) => {
+                return ByteText {
+                    text: Cow::Borrowed(text),
+                    stand_ins: Vec::new(),
+                }
+            }
+            Err(_) => haystack,
+        };
+
+        let mut text = String::with_capacity(haystack.len());
+        let mut stand_ins = Vec::new();
+        while !rest.is_empty() {
+            let (valid, invalid) = match core::str::from_utf8(rest) {
+                Ok(valid) => (valid, 0),
+                Err(err) => {
+                    let valid_len = err.valid_up_to();
+                    // Truncated sequences at the end count byte by byte
+                    let invalid_len = err.error_len().unwrap_or(rest.len() - valid_len);
+                    // Just validated, so this can't fail
+                    let valid = core::str::from_utf8(&rest[..valid_len]).unwrap_or_default();
+                    (valid, invalid_len)
+                }
+            };
+            text.push_str(valid);
+            let invalid_start = valid.len();
+            for &byte in &rest[invalid_start..invalid_start + invalid] {
+                stand_ins.push(text.len());
+                text.push(stand_in(byte));
+            }
+            rest = &rest[invalid_start + invalid..];
+        }
+
+        ByteText {
+            text: Cow::Owned(text),
+            stand_ins,
+        }
+    }
+
+    /// Offset into the original bytes of char boundary `pos` in `text`
+    fn original_offset(&self, pos: usize) -> usize {
+        let before = self.stand_ins.partition_point(|&at| at < pos);
+        pos - before * STAND_IN_EXTRA
+    }
+}
+
+/// The char standing in for invalid byte `byte`
+fn stand_in(byte: u8) -> char {
+    char::from_u32(STAND_IN_BASE + u32::from(byte)).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+impl Pattern {
+    /// Find the first match in a byte slice, which needn't be valid UTF-8
+    ///
+    /// Offsets are into `haystack`. Valid UTF-8 is matched exactly as
+    /// [`find`](Self::find) would; each byte outside a valid sequence counts
+    /// as one char that only `.` and negated classes match.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new("a.c").unwrap();
+    /// assert_eq!(pattern.find_bytes(b"xx a\xFFc"), Some((3, 6)));
+    /// assert_eq!(pattern.find_bytes(b"a\nc"), None);
+    /// ```
+    pub fn find_bytes(&self, haystack: &[u8]) -> Option<(usize, usize)> {
+        let bytes = ByteText::new(haystack);
+        let (start, end) = self.find(&bytes.text)?;
+        Some((bytes.original_offset(start), bytes.original_offset(end)))
+    }
+
+    /// Find all non-overlapping matches in a byte slice, like
+    /// [`find_all`](Self::find_all) (see [`find_bytes`](Self::find_bytes))
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"=.").unwrap();
+    /// assert_eq!(pattern.find_all_bytes(b"=\x80 =a"), vec![(0, 2), (3, 5)]);
+    /// ```
+    pub fn find_all_bytes(&self, haystack: &[u8]) -> Vec<(usize, usize)> {
+        let bytes = ByteText::new(haystack);
+        self.find_all(&bytes.text)
+            .into_iter()
+            .map(|(start, end)| (bytes.original_offset(start), bytes.original_offset(end)))
+            .collect()
+    }
+
+    /// Whether the pattern matches anywhere in a byte slice (see
+    /// [`find_bytes`](Self::find_bytes))
+    pub fn is_match_bytes(&self, haystack: &[u8]) -> bool {
+        self.is_match(&ByteText::new(haystack).text)
+    }
+}
@@ -0,0 +1,35 @@
+//! Process-wide cache of compiled patterns for the free functions
+//!
+//! [`is_match`] and [`find`] compile each distinct pattern string once and
+//! reuse it on later calls. Cached patterns are shared behind an [`Arc`], so a
+//! hit costs a reference count bump rather than a copy of the compiled
+//! matcher. Needs the `std` feature for its lock.
+
+use crate::{Pattern, PatternError};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+static CACHE: OnceLock<Mutex<HashMap<String, Arc<Pattern>>>> = OnceLock::new();
+
+fn get_cache() -> &'static Mutex<HashMap<String, Arc<Pattern>>> {
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// The cached compiled form of `pattern`, compiling it on first use
+pub fn get_pattern(pattern: &str) -> Result<Arc<Pattern>, PatternError> {
+    let mut cache = get_cache().lock().unwrap();
+    if let Some(p) = cache.get(pattern) {
+        return Ok(Arc::clone(p));
+    }
+    let compiled = Arc::new(Pattern::new(pattern)?);
+    cache.insert(pattern.to_string(), Arc::clone(&compiled));
+    Ok(compiled)
+}
+
+pub fn is_match(pattern: &str, text: &str) -> Result<bool, PatternError> {
+    Ok(get_pattern(pattern)?.is_match(text))
+}
+
+pub fn find(pattern: &str, text: &str) -> Result<Option<(usize, usize)>, PatternError> {
+    Ok(get_pattern(pattern)?.find(text))
+}
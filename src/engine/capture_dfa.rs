@@ -7,6 +7,12 @@
 //! This matches the regex crate's approach and avoids the position-dependent
 //! performance issue (fast when match is early, slow when match is late).
 
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
 /// A state in the capture DFA
 #[derive(Debug, Clone)]
 struct State {
@@ -18,6 +24,17 @@ struct State {
     capture_actions: Vec<CaptureAction>, // Actions to perform when entering this state
 }
 
+impl State {
+    /// Whether some byte can take more than one of the transitions
+    fn is_ambiguous(&self) -> bool {
+        self.transitions.iter().enumerate().any(|(i, (pred, _))| {
+            self.transitions[i + 1..]
+                .iter()
+                .any(|(other, _)| (0..=u8::MAX).any(|b| pred.matches(b) && other.matches(b)))
+        })
+    }
+}
+
 /// What kind of input can trigger this transition?
 #[derive(Debug, Clone)]
 enum TransitionPredicate {
@@ -47,12 +64,15 @@ impl TransitionPredicate {
                 matches!(byte, b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'_')
             }
             Self::Digit => matches!(byte, b'0'..=b'9'),
-            Self::Whitespace => matches!(byte, b' ' | b'\t' | b'\n' | b'\r'),
+            Self::Whitespace => matches!(byte, b'\t' | b'\n' | 0x0B | 0x0C | b'\r' | b' '),
             Self::Any => true,
         }
     }
 }
 
+/// End of a group slot that has been opened but not closed yet
+const OPEN_GROUP: usize = usize::MAX;
+
 #[derive(Debug, Clone)]
 enum CaptureAction {
     StartCapture(usize), // Start capturing group N
@@ -67,6 +87,7 @@ pub struct CaptureDFA {
     total_groups: usize,          // Number of capture groups (excluding group 0)
     literal_hint: Option<String>, // Optional literal for prefiltering
     reverse_dfa: Option<Box<ReverseDFA>>, // Reverse DFA for finding match start
+    exact_captures: bool,         // Whether group spans from try_match_at are exact
 }
 
 /// Reverse DFA for scanning backwards to find match start
@@ -92,6 +113,7 @@ impl CaptureDFA {
             total_groups,
             literal_hint: None,
             reverse_dfa: None,
+            exact_captures: true,
         }
     }
 
@@ -154,9 +176,12 @@ impl CaptureDFA {
             return self.find_with_literal_prefilter(text, literal);
         }
 
-        // Fallback: try to match starting from each position
-        // This is O(n²) but necessary when no literal is available
+        // Fallback: try to match starting from each position that can begin a match
+        // This is O(n²) in the worst case but necessary when no literal is available
         for start_pos in 0..=bytes.len() {
+            if start_pos < bytes.len() && !self.can_start_with(bytes[start_pos]) {
+                continue;
+            }
             if let Some((end_pos, captures)) = self.try_match_at(bytes, start_pos) {
                 return Some((start_pos, end_pos, captures));
             }
@@ -291,14 +316,12 @@ impl CaptureDFA {
                             .all(|(i, &b)| bytes[literal_pos + i] == b);
 
                         if matches {
-                            // Find word boundary before literal (max 20 chars back)
+                            // Try starts before the literal (max 20 chars back),
+                            // leftmost first
                             let lookback = 20.min(literal_pos);
                             for i in (0..=lookback).rev() {
                                 let start_pos = literal_pos - i;
-                                if start_pos == 0
-                                    || (!bytes[start_pos - 1].is_ascii_alphanumeric()
-                                        && bytes[start_pos - 1] != b'_')
-                                {
+                                if self.can_start_with(bytes[start_pos]) {
                                     if let Some(end_pos) =
                                         self.try_match_at_no_captures(bytes, start_pos)
                                     {
@@ -318,8 +341,11 @@ impl CaptureDFA {
             return None;
         }
 
-        // Fallback: try each position
+        // Fallback: try each position that can begin a match
         for start_pos in 0..=bytes.len() {
+            if start_pos < bytes.len() && !self.can_start_with(bytes[start_pos]) {
+                continue;
+            }
             if let Some(end_pos) = self.try_match_at_no_captures(bytes, start_pos) {
                 return Some((start_pos, end_pos));
             }
@@ -327,6 +353,87 @@ impl CaptureDFA {
         None
     }
 
+    /// Write the groups of the match `start..end` into `groups` (group 1 at
+    /// index 0), returning false if the DFA doesn't accept that span
+    ///
+    /// Used to read the groups of a span already located by [`CaptureDFA::find`],
+    /// so extracting captures costs a single forward pass over the match and
+    /// no allocation. Only valid when [`CaptureDFA::has_exact_captures`]: each
+    /// group is then opened once, so an open group can be kept in its own
+    /// slot until it closes.
+    pub fn captures_into(
+        &self,
+        text: &str,
+        start: usize,
+        end: usize,
+        groups: &mut [Option<(usize, usize)>],
+    ) -> bool {
+        let bytes = text.as_bytes();
+        let mut current_state = self.start_state;
+        self.record_captures(current_state, start, groups);
+
+        for pos in start..end {
+            let state = &self.states[current_state];
+            let byte = bytes[pos];
+            let Some(next_state) = state
+                .transitions
+                .iter()
+                .find_map(|(pred, next)| pred.matches(byte).then_some(*next))
+            else {
+                return false;
+            };
+            current_state = next_state;
+            // Groups open and close after the byte that led into the state
+            self.record_captures(current_state, pos + 1, groups);
+        }
+
+        // A group still open at the end didn't take part
+        for group in groups.iter_mut() {
+            if matches!(group, Some((_, OPEN_GROUP))) {
+                *group = None;
+            }
+        }
+        self.states[current_state].is_accepting
+    }
+
+    /// Apply the capture actions of `state`, entered at `pos`, to `groups`
+    fn record_captures(&self, state: usize, pos: usize, groups: &mut [Option<(usize, usize)>]) {
+        for action in &self.states[state].capture_actions {
+            match *action {
+                CaptureAction::StartCapture(id) if id > 0 && id <= groups.len() => {
+                    groups[id - 1] = Some((pos, OPEN_GROUP));
+                }
+                CaptureAction::EndCapture(id) if id > 0 && id <= groups.len() => {
+                    if let Some((start, _)) = groups[id - 1] {
+                        groups[id - 1] = Some((start, pos));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Whether [`CaptureDFA::captures_at`] reports exact group spans
+    ///
+    /// Group starts are recorded on entering a state, so a state that opens a
+    /// group and also loops on itself would keep moving the start forward.
+    /// Callers fall back to backtracking for such patterns.
+    pub fn has_exact_captures(&self) -> bool {
+        self.exact_captures
+    }
+
+    /// Number of capture groups (excluding group 0)
+    pub fn total_groups(&self) -> usize {
+        self.total_groups
+    }
+
+    /// Check whether a match can begin with this byte
+    #[inline(always)]
+    fn can_start_with(&self, byte: u8) -> bool {
+        let state = &self.states[self.start_state];
+        state.is_accepting || state.transitions.iter().any(|(pred, _)| pred.matches(byte))
+    }
+
     /// Fast match at position without capture tracking
     #[inline]
     fn try_match_at_no_captures(&self, bytes: &[u8], start_pos: usize) -> Option<usize> {
@@ -462,9 +569,9 @@ impl CaptureDFA {
                 for action in &self.states[current_state].capture_actions {
                     match action {
                         CaptureAction::StartCapture(id) => {
-                            // Start capture at current position (before consuming byte)
+                            // The group opens after the byte that led into this state
                             if *id <= self.total_groups {
-                                capture_starts[*id] = Some(pos);
+                                capture_starts[*id] = Some(pos + 1);
                             }
                         }
                         CaptureAction::EndCapture(id) => {
@@ -541,9 +648,24 @@ pub fn compile_capture_pattern(elements: &[crate::CompiledCaptureElement]) -> Op
         }
     }
 
+    // Transitions are taken first come first served, so a byte that could
+    // go two ways (as when \w+ is followed by `a`) would need backtracking
+    if dfa.states.iter().any(State::is_ambiguous) {
+        return None;
+    }
+
     // Mark final state as accepting
     dfa.set_accepting(current_state);
 
+    dfa.exact_captures = dfa.states.iter().all(|state| {
+        let opens_group = state
+            .capture_actions
+            .iter()
+            .any(|action| matches!(action, CaptureAction::StartCapture(id) if *id > 0));
+        let loops = state.transitions.iter().any(|(_, next)| *next == state.id);
+        !(opens_group && loops)
+    });
+
     // Note: Reverse DFA is not used - boundary heuristics work better for common patterns
     // Reverse DFA is complex to implement correctly and boundary scanning is simpler and fast enough
     // dfa.build_reverse_dfa();
@@ -655,6 +777,11 @@ fn compile_quantified(
         _ => return None, // Complex elements not supported
     };
 
+    // Transitions can't express stopping as early as possible
+    if qp.quantifier.is_lazy() {
+        return None;
+    }
+
     let min = qp.quantifier.min_matches();
     let max_opt = qp.quantifier.max_matches();
 
@@ -674,13 +801,10 @@ fn compile_quantified(
     if max_opt == usize::MAX {
         // Unbounded (+, *): add self-loop
         dfa.add_transition(current, pred, current);
-    } else {
-        // Bounded: create optional chain up to max
-        for _ in min..max_opt {
-            let next = dfa.add_state();
-            dfa.add_transition(current, pred.clone(), next);
-            current = next;
-        }
+    } else if max_opt > min {
+        // Bounded ranges like {2,4} would need every optional state to exit
+        // into the next element, which a single end state can't express
+        return None;
     }
 
     Some(current)
@@ -755,7 +879,7 @@ fn extract_literal_hint(elements: &[crate::CompiledCaptureElement]) -> Option<St
 /// Extract literal from a matcher (recursive)
 fn extract_literal_from_matcher(matcher: &crate::Matcher) -> Option<String> {
     match matcher {
-        crate::Matcher::Literal(s) => Some(s.clone()),
+        crate::Matcher::Literal(s) => Some(s.to_string()),
         crate::Matcher::PatternWithCaptures { elements, .. } => {
             // Recursively extract from nested pattern
             for elem in elements {
@@ -5,6 +5,8 @@
 use crate::parser::charclass::CharClass;
 use crate::parser::quantifier::Quantifier;
 use crate::parser::sequence::{Sequence, SequenceElement};
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// A simple DFA state machine for sequence matching
 #[derive(Debug, Clone)]
@@ -70,9 +72,7 @@ impl CharClassId {
                     || (b'0'..=b'9').contains(&byte)
                     || byte == b'_'
             }
-            CharClassId::Whitespace => {
-                byte == b' ' || byte == b'\t' || byte == b'\n' || byte == b'\r'
-            }
+            CharClassId::Whitespace => matches!(byte, b'\t' | b'\n' | 0x0B | 0x0C | b'\r' | b' '),
             CharClassId::Custom(cc) => cc.matches(ch),
         }
     }
@@ -81,6 +81,10 @@ impl CharClassId {
 impl DFA {
     /// Try to compile a sequence into a DFA
     /// Returns None if the sequence is too complex for DFA optimization
+    ///
+    /// Only `+` and `*` repetitions get states here; counted ones like
+    /// `[a-f]{2,4}` are unrolled by [`LazyDFA`](super::lazy_dfa::LazyDFA)
+    /// instead, which declines once the unrolled program gets too large.
     pub fn try_compile(seq: &Sequence) -> Option<Self> {
         // Only handle simple patterns for now
         // Pattern: quantified_element literal quantified_element literal ...
@@ -90,6 +94,21 @@ impl DFA {
             return None;
         }
 
+        // The DFA only reports the longest run; lazy quantifiers want the
+        // shortest, which the sequence matcher's backtracking gives them
+        let has_lazy = seq.elements.iter().any(|elem| {
+            matches!(
+                elem,
+                SequenceElement::QuantifiedChar(_, q)
+                    | SequenceElement::QuantifiedCharClass(_, q)
+                    | SequenceElement::QuantifiedGroup(_, q)
+                    if q.is_lazy()
+            )
+        });
+        if has_lazy {
+            return None;
+        }
+
         // Check if pattern is simple enough
         if !Self::is_dfa_compilable(seq) {
             return None;
@@ -333,7 +352,7 @@ impl DFA {
             CharClassId::Word
         } else if Self::is_digit_class(cc) {
             CharClassId::Digit
-        } else if Self::is_whitespace_class(cc) {
+        } else if cc.is_whitespace_class() {
             CharClassId::Whitespace
         } else {
             CharClassId::Custom(cc.clone())
@@ -348,104 +367,91 @@ impl DFA {
             && cc.ranges.contains(&('A', 'Z'))
             && cc.ranges.contains(&('0', '9'))
             && cc.chars.contains(&'_')
+            && cc.properties.is_empty()
     }
 
     fn is_digit_class(cc: &CharClass) -> bool {
         // Check if this matches \d: [0-9]
-        !cc.negated && cc.ranges.len() == 1 && cc.ranges[0] == ('0', '9') && cc.chars.is_empty()
-    }
-
-    fn is_whitespace_class(cc: &CharClass) -> bool {
-        // Check if this matches \s: [ \t\n\r]
         !cc.negated
-            && cc.ranges.is_empty()
-            && cc.chars.contains(&' ')
-            && cc.chars.contains(&'\t')
-            && cc.chars.contains(&'\n')
-            && cc.chars.contains(&'\r')
+            && cc.ranges.len() == 1
+            && cc.ranges[0] == ('0', '9')
+            && cc.chars.is_empty()
+            && cc.properties.is_empty()
     }
 
     /// Find first match using DFA with prefilter optimization
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
-        if text.is_empty() {
+        self.find_from(text, 0)
+    }
+
+    /// Find first match starting at or after byte offset `start`
+    ///
+    /// Offsets are absolute within `text`, so callers can resume after a
+    /// previous match without re-slicing the input.
+    pub fn find_from(&self, text: &str, start: usize) -> Option<(usize, usize)> {
+        self.find_from_with(text, start, &self.get_first_chars())
+    }
+
+    /// Match only at the start of `text`, never searching past it
+    pub fn find_anchored(&self, text: &str) -> Option<(usize, usize)> {
+        self.match_from_bytes(text, 0).map(|end| (0, end))
+    }
+
+    /// Iterate over all non-overlapping matches in a single left-to-right pass
+    pub fn find_iter<'d, 't>(&'d self, text: &'t str) -> DFAFindIter<'d, 't> {
+        DFAFindIter {
+            dfa: self,
+            text,
+            pos: 0,
+            first_chars: self.get_first_chars(),
+        }
+    }
+
+    /// `find_from` with the first-char prefilter already computed
+    fn find_from_with(
+        &self,
+        text: &str,
+        start: usize,
+        first_chars: &[u8],
+    ) -> Option<(usize, usize)> {
+        if start >= text.len() {
             return None;
         }
 
         // Optimization: Use memchr to find candidate positions for digit patterns
         // For pattern like \d+.\d+.\d+, use memchr to find digits quickly
-        let first_chars = self.get_first_chars();
-
-        if !first_chars.is_empty() {
-            // Use memchr to find candidates
-            match first_chars.len() {
-                1 => {
-                    // Single char - use memchr
-                    let ch = first_chars[0];
-                    let mut pos = 0;
-                    while pos < text.len() {
-                        if let Some(found) = memchr::memchr(ch, &text.as_bytes()[pos..]) {
-                            let byte_start = pos + found;
-                            if let Some(byte_end) = self.match_from_bytes(text, byte_start) {
-                                return Some((byte_start, byte_end));
-                            }
-                            pos = byte_start + 1;
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                2 => {
-                    // Two chars - use memchr2
-                    let mut pos = 0;
-                    while pos < text.len() {
-                        if let Some(found) =
-                            memchr::memchr2(first_chars[0], first_chars[1], &text.as_bytes()[pos..])
-                        {
-                            let byte_start = pos + found;
-                            if let Some(byte_end) = self.match_from_bytes(text, byte_start) {
-                                return Some((byte_start, byte_end));
-                            }
-                            pos = byte_start + 1;
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                3 => {
-                    // Three chars - use memchr3
-                    let mut pos = 0;
-                    while pos < text.len() {
-                        if let Some(found) = memchr::memchr3(
-                            first_chars[0],
-                            first_chars[1],
-                            first_chars[2],
-                            &text.as_bytes()[pos..],
-                        ) {
-                            let byte_start = pos + found;
-                            if let Some(byte_end) = self.match_from_bytes(text, byte_start) {
-                                return Some((byte_start, byte_end));
-                            }
-                            pos = byte_start + 1;
-                        } else {
-                            break;
-                        }
-                    }
-                }
-                _ => {
-                    // Too many chars, fall back to position-by-position
-                    return self.find_fallback(text);
-                }
+        if first_chars.is_empty() || first_chars.len() > 3 {
+            // No first chars optimization available (or too many chars), use fallback
+            return self.find_fallback(text, start);
+        }
+
+        let bytes = text.as_bytes();
+        let mut pos = start;
+        while pos < bytes.len() {
+            // Single char - memchr, two chars - memchr2, three chars - memchr3
+            let found = match first_chars.len() {
+                1 => memchr::memchr(first_chars[0], &bytes[pos..]),
+                2 => memchr::memchr2(first_chars[0], first_chars[1], &bytes[pos..]),
+                _ => memchr::memchr3(
+                    first_chars[0],
+                    first_chars[1],
+                    first_chars[2],
+                    &bytes[pos..],
+                ),
+            };
+            let byte_start = pos + found?;
+            if let Some(byte_end) = self.match_from_bytes(text, byte_start) {
+                return Some((byte_start, byte_end));
             }
-            None
-        } else {
-            // No first chars optimization available, use fallback
-            self.find_fallback(text)
+            pos = byte_start + 1;
         }
+        None
     }
 
     /// Fallback: scan position by position
-    fn find_fallback(&self, text: &str) -> Option<(usize, usize)> {
-        for (byte_start, _) in text.char_indices() {
+    fn find_fallback(&self, text: &str, start: usize) -> Option<(usize, usize)> {
+        for (offset, _) in text[start..].char_indices() {
+            let byte_start = start + offset;
             if let Some(byte_end) = self.match_from_bytes(text, byte_start) {
                 return Some((byte_start, byte_end));
             }
@@ -729,25 +735,78 @@ impl DFA {
             }
             false
         } else {
-            // Multiple chars: use aho-corasick
-            let patterns: Vec<Vec<u8>> = first_chars.iter().map(|&c| vec![c]).collect();
-            if let Ok(ac) = aho_corasick::AhoCorasick::new(&patterns) {
-                for mat in ac.find_iter(bytes) {
-                    if self.match_from_bytes(text, mat.start()).is_some() {
-                        return true;
-                    }
-                }
-            }
-            false
+            // Multiple chars: check every position starting with one of them
+            (0..bytes.len()).any(|pos| {
+                first_chars.contains(&bytes[pos]) && self.match_from_bytes(text, pos).is_some()
+            })
         }
     }
 }
 
+/// Iterator over non-overlapping DFA matches, created by [`DFA::find_iter`]
+///
+/// The DFA restarts from its start state at the end of each accepted match,
+/// so the whole input is walked once.
+pub struct DFAFindIter<'d, 't> {
+    dfa: &'d DFA,
+    text: &'t str,
+    pos: usize,
+    first_chars: Vec<u8>,
+}
+
+impl<'d, 't> Iterator for DFAFindIter<'d, 't> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, end) = self
+            .dfa
+            .find_from_with(self.text, self.pos, &self.first_chars)?;
+        self.pos = if end > start {
+            end
+        } else {
+            // Empty match: step over one char to guarantee progress
+            start
+                + self.text[start..]
+                    .chars()
+                    .next()
+                    .map_or(1, |c| c.len_utf8())
+        };
+        Some((start, end))
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dfa_find_from_and_iter() {
+        let seq = Sequence::new(vec![SequenceElement::QuantifiedCharClass(
+            CharClass::parse("0-9").unwrap(),
+            Quantifier::OneOrMore,
+        )]);
+        let dfa = DFA::try_compile(&seq).expect("digit run compiles to DFA");
+        let text = "v1 and 10, then 345";
+
+        assert_eq!(dfa.find(text), Some((1, 2)));
+        assert_eq!(dfa.find_from(text, 2), Some((7, 9)));
+        assert_eq!(
+            dfa.find_iter(text).collect::<Vec<_>>(),
+            vec![(1, 2), (7, 9), (16, 19)]
+        );
+        assert_eq!(dfa.find_from(text, text.len()), None);
+    }
 
     #[test]
-    fn test_dfa_simple() {
-        // TODO: Add tests when integrated
+    fn test_dfa_declines_lazy_quantifiers() {
+        let digits = |quantifier| {
+            Sequence::new(vec![SequenceElement::QuantifiedCharClass(
+                CharClass::parse("0-9").unwrap(),
+                quantifier,
+            )])
+        };
+        assert!(DFA::try_compile(&digits(Quantifier::OneOrMore)).is_some());
+        assert!(DFA::try_compile(&digits(Quantifier::OneOrMoreLazy)).is_none());
+        assert!(DFA::try_compile(&digits(Quantifier::ZeroOrMoreLazy)).is_none());
     }
 }
@@ -1,11 +1,26 @@
 //! Lazy DFA - compile states on-demand for O(n) matching
 //!
-//! Like regex crate's hybrid engine: NFA simulation with cached DFA states
+//! Like regex crate's hybrid engine: NFA simulation with cached DFA states.
+//! A DFA state is the priority-ordered list of NFA threads alive at a
+//! position; it is built the first time a search reaches it and kept in a
+//! bounded cache shared by every search on the pattern, so memory grows with
+//! the states the input actually visits rather than with the full DFA.
 
+use super::Map;
 use crate::parser::charclass::CharClass;
+use crate::parser::group::{Group, GroupContent};
 use crate::parser::quantifier::Quantifier;
 use crate::parser::sequence::{Sequence, SequenceElement};
-use std::collections::HashMap;
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, TryLockError};
+
+/// DFA states kept before the cache is flushed and rebuilt from scratch
+const MAX_CACHED_STATES: usize = 1024;
+
+/// NFA programs larger than this stay with the sequence matcher
+const MAX_INSTRUCTIONS: usize = 512;
 
 /// Helper to get quantifier bounds
 fn quantifier_bounds(q: &Quantifier) -> (usize, usize) {
@@ -20,29 +35,53 @@ fn quantifier_bounds(q: &Quantifier) -> (usize, usize) {
 }
 
 /// Lazy DFA that compiles states on-demand
-#[derive(Clone, Debug)]
+///
+/// Matches leftmost-first like the backtracking sequence matcher: greedy
+/// quantifiers prefer to repeat, lazy ones to stop. A reverse program (see
+/// [`try_compile_reverse`](Self::try_compile_reverse)) instead reads the text
+/// backward and reports the longest match.
 pub struct LazyDFA {
     /// NFA instructions
     instructions: Vec<Instruction>,
-    /// Cache of compiled DFA states: (nfa_state_set) -> dfa_state_id
-    /// State set represented as sorted Vec for hashing
-    state_cache: HashMap<Vec<usize>, StateId>,
-    /// Transition cache: (dfa_state, byte) -> dfa_state
-    transition_cache: HashMap<(StateId, u8), StateId>,
-    /// Next state ID to allocate
-    next_state_id: StateId,
-    /// Accepting DFA states
-    accept_states: HashMap<StateId, bool>,
+    /// Keep every thread alive past an Accept (longest match, for reverse
+    /// programs) instead of cutting lower-priority ones (leftmost-first)
+    longest: bool,
+    /// States built so far; a search that finds the cache busy (another
+    /// thread is using it) runs on a private, empty one instead. Without
+    /// `std` there is no lock to share it behind, so every search starts empty
+    #[cfg(feature = "std")]
+    cache: Mutex<Cache>,
+}
+
+impl Clone for LazyDFA {
+    /// Clones share the program but start with an empty cache
+    fn clone(&self) -> Self {
+        LazyDFA::new(self.instructions.clone(), self.longest)
+    }
+}
+
+impl core::fmt::Debug for LazyDFA {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LazyDFA")
+            .field("instructions", &self.instructions.len())
+            .field("longest", &self.longest)
+            .finish()
+    }
 }
 
 type StateId = u32;
 
+/// Transition not computed yet
+const UNKNOWN: StateId = StateId::MAX;
+/// No thread survives: the search from this start is over
+const DEAD: StateId = StateId::MAX - 1;
+
 /// NFA instruction (like Thompson's NFA)
 #[derive(Debug, Clone)]
 enum Instruction {
     /// Match a character and advance
     Match(MatchType),
-    /// Split into multiple paths (for alternation or quantifiers)
+    /// Try `first`, then `second` (lower priority)
     Split { first: usize, second: usize },
     /// Jump to another instruction
     Jump(usize),
@@ -54,14 +93,10 @@ enum Instruction {
 enum MatchType {
     /// Match a literal character
     Literal(char),
-    /// Match word char (\w)
-    Word,
+    /// Match any character except newline (.)
+    Any,
     /// Match digit (\d)
     Digit,
-    /// Match whitespace (\s)
-    Whitespace,
-    /// Match any character (.)
-    Any,
     /// Match custom character class
     Class(CharClass),
 }
@@ -71,205 +106,331 @@ impl MatchType {
     fn matches(&self, ch: char) -> bool {
         match self {
             MatchType::Literal(c) => ch == *c,
-            MatchType::Word => ch.is_alphanumeric() || ch == '_',
-            MatchType::Digit => ch.is_ascii_digit(),
-            MatchType::Whitespace => ch.is_whitespace(),
             MatchType::Any => ch != '\n',
-            MatchType::Class(cc) => cc.matches(ch),
-        }
-    }
-
-    #[inline]
-    fn matches_byte(&self, byte: u8) -> bool {
-        if !byte.is_ascii() {
-            return false;
-        }
-        let ch = byte as char;
-        match self {
-            MatchType::Literal(c) => ch == *c,
-            MatchType::Word => ch.is_ascii_alphanumeric() || ch == '_',
             MatchType::Digit => ch.is_ascii_digit(),
-            MatchType::Whitespace => matches!(ch, ' ' | '\t' | '\n' | '\r'),
-            MatchType::Any => ch != '\n',
             MatchType::Class(cc) => cc.matches(ch),
         }
     }
 }
 
+/// Cached DFA states and their transitions
+#[derive(Debug, Default)]
+struct Cache {
+    states: Vec<State>,
+    /// Thread list -> state, so equal lists share one state
+    ids: Map<Vec<usize>, StateId>,
+    /// State every search position starts in
+    start: Option<StateId>,
+}
+
+#[derive(Debug)]
+struct State {
+    /// NFA threads (Match or Accept instructions) in priority order
+    threads: Vec<usize>,
+    /// Whether a thread has reached Accept
+    accepting: bool,
+    /// Transitions on ASCII chars
+    ascii: [StateId; 128],
+    /// Transitions on other chars
+    other: Map<char, StateId>,
+}
+
 impl LazyDFA {
     /// Try to compile a sequence into a Lazy DFA
+    ///
+    /// Returns None for elements the NFA can't express, and for sequences
+    /// the regular sequence matcher handles better: those starting with a
+    /// literal (found with memchr) and those without a counted repetition
+    /// like `{4}` or `{2,8}`, which is where backtracking gets expensive.
     pub fn try_compile(seq: &Sequence) -> Option<Self> {
-        let mut compiler = NFACompiler::new();
+        let starts_with_literal = matches!(
+            seq.elements.first(),
+            None | Some(SequenceElement::Char(_) | SequenceElement::Literal(_))
+        );
+        let has_counted_repetition = seq.elements.iter().any(|elem| {
+            matches!(
+                elem,
+                SequenceElement::QuantifiedChar(_, q) | SequenceElement::QuantifiedCharClass(_, q)
+                    if matches!(q, Quantifier::Exactly(n) | Quantifier::AtLeast(n) if *n > 1)
+                        || matches!(q, Quantifier::Between(..))
+            )
+        });
+        if starts_with_literal || !has_counted_repetition {
+            return None;
+        }
+
+        Self::try_compile_anchored(seq)
+    }
+
+    /// Try to compile a sequence that is only matched at the start of the text
+    ///
+    /// Without a search for the match start there is nothing for the sequence
+    /// matcher's literal scans to speed up, so any sequence the NFA can
+    /// express is accepted. Use [`find_anchored`](Self::find_anchored).
+    pub fn try_compile_anchored(seq: &Sequence) -> Option<Self> {
+        Self::compile_forward(seq, false)
+    }
+
+    /// Try to compile a sequence that has to match the whole text (`^...$`)
+    ///
+    /// Every thread stays alive past an Accept: which branch matches doesn't
+    /// matter, only whether one ends at the end of the text. Use
+    /// [`matches_entire`](Self::matches_entire).
+    pub fn try_compile_entire(seq: &Sequence) -> Option<Self> {
+        Self::compile_forward(seq, true)
+    }
+
+    fn compile_forward(seq: &Sequence, longest: bool) -> Option<Self> {
+        let mut compiler = NFACompiler::new(false);
 
         // Compile sequence to NFA instructions
         for elem in &seq.elements {
             compiler.compile_element(elem)?;
+            compiler.check_size()?;
         }
 
         // Add accept instruction
         compiler.add_accept();
 
-        Some(LazyDFA {
-            instructions: compiler.instructions,
-            state_cache: HashMap::new(),
-            transition_cache: HashMap::new(),
-            next_state_id: 1,
-            accept_states: HashMap::new(),
-        })
+        Some(LazyDFA::new(compiler.instructions, longest))
+    }
+
+    /// Try to compile `elements` to be read backward, for matches that must
+    /// end at the end of the text
+    ///
+    /// Elements go in reverse order with literals reversed. Laziness is
+    /// dropped: once the end is fixed, only where a match can start matters,
+    /// which also lets non-capturing groups like `(?:ab|c)+` in: the longest
+    /// match doesn't depend on which alternative a backtracker would try
+    /// first. Use [`rfind_start`](Self::rfind_start).
+    pub fn try_compile_reverse(elements: &[SequenceElement]) -> Option<Self> {
+        let mut compiler = NFACompiler::new(true);
+        for elem in elements.iter().rev() {
+            compiler.compile_element(elem)?;
+            compiler.check_size()?;
+        }
+        compiler.add_accept();
+
+        Some(LazyDFA::new(compiler.instructions, true))
+    }
+
+    fn new(instructions: Vec<Instruction>, longest: bool) -> Self {
+        LazyDFA {
+            instructions,
+            longest,
+            #[cfg(feature = "std")]
+            cache: Mutex::new(Cache::default()),
+        }
     }
 
     /// Find first match using lazy DFA compilation
-    pub fn find(&mut self, text: &str) -> Option<(usize, usize)> {
-        let bytes = text.as_bytes();
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.with_cache(|cache| {
+            // Try starting match at each char boundary, the end included
+            let starts = text.char_indices().map(|(i, _)| i);
+            starts
+                .chain(core::iter::once(text.len()))
+                .find_map(|start| Some((start, self.match_at(cache, text, start)?)))
+        })
+    }
+
+    /// Match only at the start of `text`, never searching past it
+    pub fn find_anchored(&self, text: &str) -> Option<(usize, usize)> {
+        self.with_cache(|cache| self.match_at(cache, text, 0).map(|end| (0, end)))
+    }
 
-        // Try starting match at each position
-        for start in 0..text.len() {
-            if !text.is_char_boundary(start) {
-                continue;
+    /// Whether all of `text` matches a program from
+    /// [`try_compile_entire`](Self::try_compile_entire)
+    ///
+    /// Reading stops at the first char no thread can take, so text that
+    /// fails early is rejected without being scanned.
+    pub fn matches_entire(&self, text: &str) -> bool {
+        self.with_cache(|cache| {
+            let mut state = self.start_state(cache);
+            for ch in text.chars() {
+                state = self.next_state(cache, state, ch);
+                if state == DEAD {
+                    return false;
+                }
             }
+            cache.states[state as usize].accepting
+        })
+    }
 
-            // Get initial NFA state set
-            let mut nfa_states = vec![0];
-            self.epsilon_closure(&mut nfa_states);
+    /// Leftmost start of a match of a reverse program that ends at the end of
+    /// `text`, reading backward from there
+    pub fn rfind_start(&self, text: &str) -> Option<usize> {
+        self.with_cache(|cache| {
+            let mut state = self.start_state(cache);
+            let mut first_start = cache.states[state as usize].accepting.then_some(text.len());
+
+            for (pos, ch) in text.char_indices().rev() {
+                state = self.next_state(cache, state, ch);
+                if state == DEAD {
+                    break;
+                }
+                if cache.states[state as usize].accepting {
+                    first_start = Some(pos);
+                }
+            }
 
-            // Convert to DFA state
-            let mut dfa_state = self.get_or_create_dfa_state(&nfa_states);
+            first_start
+        })
+    }
 
-            let mut pos = start;
-            let mut last_match = None;
+    /// Run `search` on the shared cache, or on a private one if it's busy
+    #[cfg(feature = "std")]
+    fn with_cache<T>(&self, search: impl FnOnce(&mut Cache) -> T) -> T {
+        match self.cache.try_lock() {
+            Ok(mut cache) => search(&mut cache),
+            Err(TryLockError::Poisoned(poisoned)) => search(&mut poisoned.into_inner()),
+            Err(TryLockError::WouldBlock) => search(&mut Cache::default()),
+        }
+    }
 
-            // Check if initial state is accepting
-            if self.is_accepting_state(dfa_state) {
-                last_match = Some(pos);
+    /// Run `search` on a fresh cache
+    #[cfg(not(feature = "std"))]
+    fn with_cache<T>(&self, search: impl FnOnce(&mut Cache) -> T) -> T {
+        search(&mut Cache::default())
+    }
+
+    /// End of the leftmost-first match starting exactly at `start`
+    fn match_at(&self, cache: &mut Cache, text: &str, start: usize) -> Option<usize> {
+        let mut state = self.start_state(cache);
+        let mut last_match = cache.states[state as usize].accepting.then_some(start);
+
+        for (offset, ch) in text[start..].char_indices() {
+            state = self.next_state(cache, state, ch);
+            if state == DEAD {
+                break;
+            }
+            if cache.states[state as usize].accepting {
+                last_match = Some(start + offset + ch.len_utf8());
             }
+        }
 
-            // Process input
-            while pos < text.len() {
-                let byte = bytes[pos];
+        last_match
+    }
 
-                // Try to get cached transition
-                if let Some(&next_state) = self.transition_cache.get(&(dfa_state, byte)) {
-                    dfa_state = next_state;
-                } else {
-                    // Compute new DFA state
-                    let nfa_states = self.get_nfa_states(dfa_state);
-                    let mut next_nfa_states = Vec::new();
-
-                    // Simulate NFA step
-                    for &nfa_state in &nfa_states {
-                        if let Some(Instruction::Match(match_type)) =
-                            self.instructions.get(nfa_state)
-                        {
-                            if match_type.matches_byte(byte) {
-                                next_nfa_states.push(nfa_state + 1);
-                            }
-                        }
-                    }
-
-                    if next_nfa_states.is_empty() {
-                        break;
-                    }
-
-                    self.epsilon_closure(&mut next_nfa_states);
-                    let next_dfa_state = self.get_or_create_dfa_state(&next_nfa_states);
-
-                    // Cache transition
-                    self.transition_cache
-                        .insert((dfa_state, byte), next_dfa_state);
-                    dfa_state = next_dfa_state;
-                }
+    /// State before any char is read
+    fn start_state(&self, cache: &mut Cache) -> StateId {
+        if let Some(start) = cache.start {
+            return start;
+        }
+        let mut threads = Vec::new();
+        self.add_thread(&mut threads, &mut vec![false; self.instructions.len()], 0);
+        let start = self.intern(cache, threads);
+        cache.start = Some(start);
+        start
+    }
 
-                pos += 1;
+    /// Follow (and cache) the transition out of `state` on `ch`
+    fn next_state(&self, cache: &mut Cache, state: StateId, ch: char) -> StateId {
+        let known = match cache.states[state as usize] {
+            ref s if ch.is_ascii() => s.ascii[ch as usize],
+            ref s => s.other.get(&ch).copied().unwrap_or(UNKNOWN),
+        };
+        if known != UNKNOWN {
+            return known;
+        }
 
-                // Check if current state is accepting
-                if self.is_accepting_state(dfa_state) {
-                    last_match = Some(pos);
+        // Step every thread over `ch`, in priority order
+        let mut next = Vec::new();
+        let mut seen = vec![false; self.instructions.len()];
+        for &pc in &cache.states[state as usize].threads {
+            if let Instruction::Match(match_type) = &self.instructions[pc] {
+                if match_type.matches(ch) {
+                    self.add_thread(&mut next, &mut seen, pc + 1);
                 }
             }
+        }
 
-            // If we found a match from this start position, return it
-            if let Some(end) = last_match {
-                return Some((start, end));
+        let target = if next.is_empty() {
+            DEAD
+        } else {
+            let flushes = cache.states.len() >= MAX_CACHED_STATES && !cache.ids.contains_key(&next);
+            let target = self.intern(cache, next);
+            if flushes {
+                // `state` went with the flush; nothing left to record it on
+                return target;
             }
+            target
+        };
+
+        let from = &mut cache.states[state as usize];
+        if ch.is_ascii() {
+            from.ascii[ch as usize] = target;
+        } else {
+            from.other.insert(ch, target);
         }
-
-        None
+        target
     }
 
-    /// Get or create DFA state for a set of NFA states
-    fn get_or_create_dfa_state(&mut self, nfa_states: &[usize]) -> StateId {
-        let mut sorted_states = nfa_states.to_vec();
-        sorted_states.sort_unstable();
-        sorted_states.dedup();
-
-        if let Some(&state_id) = self.state_cache.get(&sorted_states) {
-            return state_id;
+    /// Look up or create the state for a thread list
+    fn intern(&self, cache: &mut Cache, threads: Vec<usize>) -> StateId {
+        if let Some(&id) = cache.ids.get(&threads) {
+            return id;
         }
 
-        let state_id = self.next_state_id;
-        self.next_state_id += 1;
+        // Bounded cache: start over rather than grow without limit
+        if cache.states.len() >= MAX_CACHED_STATES {
+            cache.states.clear();
+            cache.ids.clear();
+            cache.start = None;
+        }
 
-        // Check if this is an accepting state
-        let is_accept = sorted_states
+        let id = cache.states.len() as StateId;
+        let accepting = threads
             .iter()
-            .any(|&s| matches!(self.instructions.get(s), Some(Instruction::Accept)));
-
-        self.state_cache.insert(sorted_states.clone(), state_id);
-        self.accept_states.insert(state_id, is_accept);
-
-        state_id
+            .any(|&pc| matches!(self.instructions[pc], Instruction::Accept));
+        cache.ids.insert(threads.clone(), id);
+        cache.states.push(State {
+            threads,
+            accepting,
+            ascii: [UNKNOWN; 128],
+            other: Map::new(),
+        });
+        id
     }
 
-    /// Get NFA states represented by a DFA state
-    fn get_nfa_states(&self, dfa_state: StateId) -> Vec<usize> {
-        for (nfa_states, &state_id) in &self.state_cache {
-            if state_id == dfa_state {
-                return nfa_states.clone();
-            }
+    /// Add `pc` and everything reachable from it without reading a char
+    ///
+    /// Split targets are added first-then-second, so the list stays in
+    /// priority order. For leftmost-first, nothing is added after an Accept:
+    /// lower-priority threads can't produce the match once one has accepted.
+    fn add_thread(&self, threads: &mut Vec<usize>, seen: &mut [bool], pc: usize) {
+        if seen[pc]
+            || !self.longest
+                && threads
+                    .last()
+                    .is_some_and(|&last| matches!(self.instructions[last], Instruction::Accept))
+        {
+            return;
         }
-        vec![]
-    }
-
-    /// Compute epsilon closure (follow all epsilon transitions)
-    fn epsilon_closure(&self, states: &mut Vec<usize>) {
-        let mut i = 0;
-        while i < states.len() {
-            let state = states[i];
-            if let Some(instr) = self.instructions.get(state) {
-                match instr {
-                    Instruction::Split { first, second } => {
-                        if !states.contains(first) {
-                            states.push(*first);
-                        }
-                        if !states.contains(second) {
-                            states.push(*second);
-                        }
-                    }
-                    Instruction::Jump(target) if !states.contains(target) => {
-                        states.push(*target);
-                    }
-                    _ => {}
-                }
+        seen[pc] = true;
+        match self.instructions[pc] {
+            Instruction::Split { first, second } => {
+                self.add_thread(threads, seen, first);
+                self.add_thread(threads, seen, second);
             }
-            i += 1;
+            Instruction::Jump(target) => self.add_thread(threads, seen, target),
+            Instruction::Match(_) | Instruction::Accept => threads.push(pc),
         }
     }
-
-    /// Check if DFA state is accepting
-    fn is_accepting_state(&self, state: StateId) -> bool {
-        self.accept_states.get(&state).copied().unwrap_or(false)
-    }
 }
 
 /// NFA compiler helper
 struct NFACompiler {
     instructions: Vec<Instruction>,
+    /// Building a program that reads the text backward
+    reverse: bool,
 }
 
 impl NFACompiler {
-    fn new() -> Self {
+    fn new(reverse: bool) -> Self {
         NFACompiler {
             instructions: Vec::new(),
+            reverse,
         }
     }
 
@@ -299,86 +460,166 @@ impl NFACompiler {
             }
 
             SequenceElement::Literal(s) => {
-                for ch in s.chars() {
+                let chars: Vec<char> = if self.reverse {
+                    s.chars().rev().collect()
+                } else {
+                    s.chars().collect()
+                };
+                for ch in chars {
                     self.instructions
                         .push(Instruction::Match(MatchType::Literal(ch)));
                 }
             }
 
+            // Forward programs leave groups to the backtracking matchers,
+            // whose alternative order decides where a match ends
+            SequenceElement::Group(group) if self.reverse && !group.capturing => {
+                match &group.quantifier {
+                    Some(q) => self.compile_repeated(q, &mut |c| c.compile_group(group))?,
+                    None => self.compile_group(group)?,
+                }
+            }
+
+            SequenceElement::QuantifiedGroup(group, q) if self.reverse && !group.capturing => {
+                let group = Group {
+                    quantifier: None,
+                    ..group.clone()
+                };
+                self.compile_repeated(q, &mut |c| c.compile_group(&group))?;
+            }
+
             _ => return None, // Other elements not supported yet
         }
         Some(())
     }
 
-    fn compile_quantified(&mut self, match_type: MatchType, quantifier: &Quantifier) -> Option<()> {
-        let (min, max) = quantifier_bounds(quantifier);
-
-        match (min, max) {
-            (0, usize::MAX) => {
-                // * quantifier: split(match, skip), match -> jump back
-                let split_pos = self.instructions.len();
-                let match_pos = split_pos + 1;
-                let after_pos = split_pos + 3;
-
-                self.instructions.push(Instruction::Split {
-                    first: match_pos,
-                    second: after_pos,
-                });
-                self.instructions.push(Instruction::Match(match_type));
-                self.instructions.push(Instruction::Jump(split_pos));
+    /// Group content, without the group's own quantifier
+    fn compile_group(&mut self, group: &Group) -> Option<()> {
+        match &group.content {
+            GroupContent::Single(literal) => self.compile_group_literal(literal),
+            GroupContent::Alternation(literals) => self
+                .compile_alternatives(literals.len(), &mut |c, i| {
+                    c.compile_group_literal(&literals[i])
+                }),
+            GroupContent::Sequence(seq) => self.compile_sequence(seq),
+            GroupContent::ParsedAlternation(seqs) => {
+                self.compile_alternatives(seqs.len(), &mut |c, i| c.compile_sequence(&seqs[i]))
             }
+        }
+    }
+
+    /// Literal group text; text the parser couldn't make sense of (still
+    /// holding pattern syntax) is left to the group matcher
+    fn compile_group_literal(&mut self, literal: &str) -> Option<()> {
+        if literal.contains([
+            '\\', '.', '+', '*', '?', '(', ')', '|', '[', ']', '{', '}', '^', '$',
+        ]) {
+            return None;
+        }
+        self.compile_element(&SequenceElement::Literal(literal.into()))
+    }
 
-            (1, usize::MAX) => {
-                // + quantifier: match, split(match, skip), match -> jump back
-                let match_pos = self.instructions.len();
-                let split_pos = match_pos + 1;
+    fn compile_sequence(&mut self, seq: &Sequence) -> Option<()> {
+        let reverse = self.reverse;
+        let mut compile = |elem| {
+            self.compile_element(elem)?;
+            self.check_size()
+        };
+        if reverse {
+            seq.elements.iter().rev().try_for_each(&mut compile)
+        } else {
+            seq.elements.iter().try_for_each(&mut compile)
+        }
+    }
 
-                self.instructions
-                    .push(Instruction::Match(match_type.clone()));
-                self.instructions.push(Instruction::Split {
-                    first: match_pos,
-                    second: split_pos + 1,
-                });
+    /// `count` alternatives in priority order: split(alt, rest), alt -> jump end
+    fn compile_alternatives(
+        &mut self,
+        count: usize,
+        alternative: &mut dyn FnMut(&mut Self, usize) -> Option<()>,
+    ) -> Option<()> {
+        let mut jumps = Vec::new();
+        for i in 0..count {
+            if i + 1 == count {
+                alternative(self, i)?;
+                break;
             }
+            let split_pos = self.instructions.len();
+            self.instructions.push(Instruction::Jump(0));
+            alternative(self, i)?;
+            jumps.push(self.instructions.len());
+            self.instructions.push(Instruction::Jump(0));
+            self.instructions[split_pos] = Instruction::Split {
+                first: split_pos + 1,
+                second: self.instructions.len(),
+            };
+            self.check_size()?;
+        }
+        let end = self.instructions.len();
+        for jump in jumps {
+            self.instructions[jump] = Instruction::Jump(end);
+        }
+        Some(())
+    }
 
-            (0, 1) => {
-                // ? quantifier: split(match, skip)
-                let split_pos = self.instructions.len();
-                let match_pos = split_pos + 1;
-                let after_pos = match_pos + 1;
+    /// Split that tries repeating first when greedy, skipping first when lazy
+    fn split(repeat: usize, skip: usize, lazy: bool) -> Instruction {
+        let (first, second) = if lazy { (skip, repeat) } else { (repeat, skip) };
+        Instruction::Split { first, second }
+    }
 
-                self.instructions.push(Instruction::Split {
-                    first: match_pos,
-                    second: after_pos,
-                });
-                self.instructions.push(Instruction::Match(match_type));
-            }
+    fn compile_quantified(&mut self, match_type: MatchType, quantifier: &Quantifier) -> Option<()> {
+        self.compile_repeated(quantifier, &mut |c| {
+            c.instructions.push(Instruction::Match(match_type.clone()));
+            Some(())
+        })
+    }
 
-            _ => {
-                // {n,m} quantifier: repeat min times, then optional max-min times
-                for _ in 0..min {
-                    self.instructions
-                        .push(Instruction::Match(match_type.clone()));
-                }
+    /// `body` repeated as `quantifier` says
+    fn compile_repeated(
+        &mut self,
+        quantifier: &Quantifier,
+        body: &mut dyn FnMut(&mut Self) -> Option<()>,
+    ) -> Option<()> {
+        let (min, max) = quantifier_bounds(quantifier);
+        let lazy = quantifier.is_lazy() && !self.reverse;
 
-                if max > min && max != usize::MAX {
-                    for _ in 0..(max - min) {
-                        let split_pos = self.instructions.len();
-                        let match_pos = split_pos + 1;
-                        let after_pos = match_pos + 1;
-
-                        self.instructions.push(Instruction::Split {
-                            first: match_pos,
-                            second: after_pos,
-                        });
-                        self.instructions
-                            .push(Instruction::Match(match_type.clone()));
-                    }
-                }
+        // Required repetitions
+        for _ in 0..min {
+            body(self)?;
+            self.check_size()?;
+        }
+
+        if max == usize::MAX {
+            // Unbounded tail: split(body, skip), body -> jump back
+            let split_pos = self.instructions.len();
+            self.instructions.push(Instruction::Jump(0));
+            body(self)?;
+            self.instructions.push(Instruction::Jump(split_pos));
+            self.instructions[split_pos] =
+                Self::split(split_pos + 1, self.instructions.len(), lazy);
+        } else {
+            // Up to max - min optional repetitions, each skipping the rest
+            let mut splits = Vec::new();
+            for _ in min..max {
+                splits.push(self.instructions.len());
+                self.instructions.push(Instruction::Jump(0));
+                body(self)?;
+                self.check_size()?;
+            }
+            let end = self.instructions.len();
+            for split_pos in splits {
+                self.instructions[split_pos] = Self::split(split_pos + 1, end, lazy);
             }
         }
 
-        Some(())
+        self.check_size()
+    }
+
+    /// None once the program is past [`MAX_INSTRUCTIONS`], so big counted
+    /// repetitions give up early instead of building it all first
+    fn check_size(&self) -> Option<()> {
+        (self.instructions.len() <= MAX_INSTRUCTIONS).then_some(())
     }
 
     fn add_accept(&mut self) {
@@ -386,25 +627,12 @@ impl NFACompiler {
     }
 
     fn charclass_to_match_type(cc: &CharClass) -> MatchType {
-        // Check for predefined classes
-        if !cc.negated {
-            if cc.chars.is_empty() && cc.ranges.len() == 1 && cc.ranges[0] == ('0', '9') {
-                return MatchType::Digit;
-            }
-
-            // Check for \w pattern
-            if cc.ranges.contains(&('a', 'z'))
-                && cc.ranges.contains(&('A', 'Z'))
-                && cc.ranges.contains(&('0', '9'))
-                && cc.chars.contains(&'_')
-            {
-                return MatchType::Word;
-            }
-        } else if cc.chars.len() == 1 && cc.chars[0] == '\n' && cc.ranges.is_empty() {
-            // Negated [^\n] is dot
+        if cc.is_dot_class() {
             return MatchType::Any;
         }
-
+        if cc.is_digit_class() {
+            return MatchType::Digit;
+        }
         MatchType::Class(cc.clone())
     }
 }
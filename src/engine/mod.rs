@@ -13,6 +13,12 @@ pub mod lazy_dfa;
 pub mod nfa;
 pub mod simple_nfa;
 
+// Hashed collections need std; `no_std` builds fall back to the B-tree ones
+#[cfg(not(feature = "std"))]
+use alloc::collections::{BTreeMap as Map, BTreeSet as Set};
+#[cfg(feature = "std")]
+use std::collections::{HashMap as Map, HashSet as Set};
+
 // Re-export engine types
 pub use dfa::DFA;
-// CaptureDFA is only used internally via fast_path, no need to export
+// CaptureDFA is only used internally (fast_path and captures), no need to export
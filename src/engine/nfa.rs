@@ -3,10 +3,12 @@
 //! Implements Thompson's NFA construction for regex patterns.
 //! Compiles sequences like `\w+\s*>=\s*\d+` into state machines for O(n) scanning.
 
+use super::Set;
 use crate::parser::charclass::CharClass;
 use crate::parser::quantifier::Quantifier;
 use crate::parser::sequence::{Sequence, SequenceElement};
-use std::collections::HashSet;
+use alloc::vec;
+use alloc::vec::Vec;
 
 /// NFA state machine for pattern matching
 #[derive(Debug, Clone)]
@@ -226,7 +228,7 @@ impl NFA {
         if cc.matches('0') && cc.matches('9') && !cc.matches('a') {
             return CharClassMatcher::Digit;
         }
-        if cc.matches(' ') && cc.matches('\t') && cc.matches('\n') {
+        if cc.is_whitespace_class() {
             return CharClassMatcher::Whitespace;
         }
 
@@ -236,7 +238,7 @@ impl NFA {
     /// Find first match in text using NFA simulation (single-pass, O(n))
     /// Optimized: no Vec allocation, direct char iteration
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
-        let mut current_states = HashSet::new();
+        let mut current_states = Set::new();
         let mut match_start: Option<usize> = None;
         let mut match_start_byte: Option<usize> = None;
 
@@ -257,7 +259,7 @@ impl NFA {
                 }
             }
 
-            let mut next_states = HashSet::new();
+            let mut next_states = Set::new();
 
             // Process all current states with this character
             for &state_id in &current_states {
@@ -282,7 +284,7 @@ impl NFA {
                 self.add_epsilon_closure(&mut current_states, self.start_state);
 
                 // Try to match this character with fresh start
-                let mut fresh_next = HashSet::new();
+                let mut fresh_next = Set::new();
                 for &state_id in &current_states {
                     if state_id < self.states.len() {
                         for transition in &self.states[state_id].transitions {
@@ -314,7 +316,7 @@ impl NFA {
 
     /// Try to match at a specific position, returns match length in chars if successful
     fn try_match_at(&self, chars: &[char], start_pos: usize) -> Option<usize> {
-        let mut current_states = HashSet::new();
+        let mut current_states = Set::new();
 
         // Start with epsilon closure of start state
         self.add_epsilon_closure(&mut current_states, self.start_state);
@@ -333,7 +335,7 @@ impl NFA {
             }
 
             let ch = chars[pos];
-            let mut next_states = HashSet::new();
+            let mut next_states = Set::new();
 
             // Process all current states
             for &state_id in &current_states {
@@ -372,7 +374,7 @@ impl NFA {
     }
 
     /// Add all states reachable via epsilon transitions
-    fn add_epsilon_closure(&self, states: &mut HashSet<usize>, state_id: usize) {
+    fn add_epsilon_closure(&self, states: &mut Set<usize>, state_id: usize) {
         if states.contains(&state_id) {
             return;
         }
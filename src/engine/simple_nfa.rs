@@ -1,9 +1,11 @@
 //! Simple NFA (Non-deterministic Finite Automaton) for pattern matching
 //! Implements Thompson's construction for basic regex patterns
 
+use super::Set;
 use crate::parser::charclass::CharClass;
 use crate::parser::quantifier::Quantifier;
 use crate::parser::sequence::{Sequence, SequenceElement};
+use alloc::vec::Vec;
 
 /// Simple NFA state
 #[derive(Debug, Clone)]
@@ -118,8 +120,10 @@ impl SimpleNFA {
             // Boundary is zero-width, so it doesn't add a state
             // SimpleNFA doesn't support boundaries - return None to fallback to other engines
             SequenceElement::Boundary(_) => None,
-            // Groups not supported in simple NFA
-            SequenceElement::Group(_) | SequenceElement::QuantifiedGroup(_, _) => None,
+            // Groups and the two-char \R alternative not supported in simple NFA
+            SequenceElement::Group(_)
+            | SequenceElement::QuantifiedGroup(_, _)
+            | SequenceElement::LineBreak => None,
         }
     }
 
@@ -289,9 +293,7 @@ impl SimpleNFA {
 
     /// Try to match at specific position
     fn match_at(&self, text: &str, start_pos: usize) -> Option<usize> {
-        use std::collections::HashSet;
-
-        let mut current_states = HashSet::new();
+        let mut current_states = Set::new();
         current_states.insert(self.start_state);
 
         // Follow epsilon transitions
@@ -301,7 +303,7 @@ impl SimpleNFA {
         let chars: Vec<char> = text[start_pos..].chars().collect();
 
         for ch in chars.iter() {
-            let mut next_states = HashSet::new();
+            let mut next_states = Set::new();
 
             for &state_id in &current_states {
                 match &self.states[state_id] {
@@ -337,7 +339,7 @@ impl SimpleNFA {
     }
 
     /// Add epsilon closure to state set
-    fn epsilon_closure(&self, states: &mut std::collections::HashSet<usize>) {
+    fn epsilon_closure(&self, states: &mut Set<usize>) {
         let mut to_process: Vec<usize> = states.iter().copied().collect();
 
         while let Some(state_id) = to_process.pop() {
@@ -0,0 +1,106 @@
+//! Patterns compiled once, on first use, for `static` items
+//!
+//! A [`LazyPattern`] holds a pattern string and compiles it the first time
+//! it is used; every later use borrows the same [`Pattern`] without locking
+//! or cloning. [`lazy_pattern!`](crate::lazy_pattern) declares one in place
+//! for a single call site. Patterns that are only known at run time belong
+//! in the cache behind [`get_pattern`](crate::get_pattern) instead. Needs the
+//! `std` feature for [`OnceLock`].
+
+use crate::{Pattern, PatternError};
+use std::sync::OnceLock;
+
+/// A pattern compiled on first use, meant for `static` items
+///
+/// # Example
+/// ```
+/// use rexile::LazyPattern;
+///
+/// static DATE: LazyPattern = LazyPattern::new(r"\d{4}-\d{2}-\d{2}");
+///
+/// assert!(DATE.is_match("due 2024-01-15"));
+/// assert_eq!(DATE.find("on 2024-02-29"), Some((3, 13)));
+/// ```
+pub struct LazyPattern {
+    source: &'static str,
+    compiled: OnceLock<Result<Pattern, PatternError>>,
+}
+
+impl LazyPattern {
+    /// A pattern that compiles `source` the first time it is used
+    pub const fn new(source: &'static str) -> Self {
+        LazyPattern {
+            source,
+            compiled: OnceLock::new(),
+        }
+    }
+
+    /// The compiled pattern, or the error compiling it gave
+    ///
+    /// Compiles on the first call; later calls return the same result.
+    pub fn try_get(&self) -> Result<&Pattern, &PatternError> {
+        self.compiled
+            .get_or_init(|| Pattern::new(self.source))
+            .as_ref()
+    }
+
+    /// The compiled pattern
+    ///
+    /// # Panics
+    ///
+    /// Panics if the pattern doesn't compile, which for a pattern written in
+    /// the source is a bug at the call site.
+    pub fn get(&self) -> &Pattern {
+        match self.try_get() {
+            Ok(pattern) => pattern,
+            Err(err) => panic!("invalid pattern {:?}: {}", self.source, err),
+        }
+    }
+
+    /// The pattern string
+    pub fn as_str(&self) -> &'static str {
+        self.source
+    }
+}
+
+impl core::ops::Deref for LazyPattern {
+    type Target = Pattern;
+
+    fn deref(&self) -> &Pattern {
+        self.get()
+    }
+}
+
+impl core::fmt::Debug for LazyPattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LazyPattern")
+            .field("source", &self.source)
+            .field("compiled", &self.compiled.get().is_some())
+            .finish()
+    }
+}
+
+/// A `&'static Pattern` compiled the first time this call site runs
+///
+/// Expands to a [`LazyPattern`] in a hidden `static`, so each call site
+/// compiles its pattern once and then only borrows it. Panics on first use
+/// if the pattern doesn't compile.
+///
+/// # Example
+/// ```
+/// use rexile::lazy_pattern;
+///
+/// fn is_version(text: &str) -> bool {
+///     lazy_pattern!(r"^v\d+\.\d+$").is_match(text)
+/// }
+///
+/// assert!(is_version("v1.2"));
+/// assert!(!is_version("version 1"));
+/// ```
+#[macro_export]
+macro_rules! lazy_pattern {
+    ($pattern:expr $(,)?) => {{
+        static PATTERN: $crate::LazyPattern = $crate::LazyPattern::new($pattern);
+        PATTERN.get()
+    }};
+}
@@ -96,13 +96,54 @@
 //! For patterns used repeatedly in hot loops:
 //!
 //! ```rust
+//! # #[cfg(feature = "std")] {
 //! use rexile;
 //!
 //! // Automatically cached - compile once, reuse forever
 //! assert!(rexile::is_match("test", "this is a test").unwrap());
 //! assert_eq!(rexile::find("world", "hello world").unwrap(), Some((6, 11)));
+//! # }
 //! ```
 //!
+//! Patterns written in the source can instead be compiled once into a
+//! `static` and borrowed from then on, with no lookup at all:
+//!
+//! ```rust
+//! # #[cfg(feature = "std")] {
+//! use rexile::{lazy_pattern, LazyPattern};
+//!
+//! static WORD: LazyPattern = LazyPattern::new(r"\w+");
+//! assert_eq!(WORD.find("  hi"), Some((2, 4)));
+//! assert!(lazy_pattern!(r"\d+").is_match("a1"));
+//! # }
+//! ```
+//!
+//! ## Match Semantics
+//!
+//! Matching is leftmost-first, like the `regex` crate and PCRE: the earliest
+//! starting match wins, and among matches starting there the alternative
+//! written first is taken, even when a later one would match more text.
+//! Every route through the engine (fast paths, the literal-alternation
+//! searcher, the backtracker) agrees on this.
+//!
+//! ```rust
+//! use rexile::Pattern;
+//!
+//! assert_eq!(Pattern::new("a|ab").unwrap().find("xab"), Some((1, 2)));
+//! assert_eq!(Pattern::new("ab|a").unwrap().find("xab"), Some((1, 3)));
+//! ```
+//!
+//! ## `no_std`
+//!
+//! With `default-features = false` ReXile builds against `core` and `alloc`
+//! only. `Pattern` and everything on it still work; the cached free functions
+//! above need the `std` feature (on by default).
+//!
+//! The `aho-corasick` feature (also on by default) backs literal alternations
+//! like `foo|bar|baz` with a single automaton. Turn it off to drop the
+//! dependency: each alternative is then searched with its own `memmem`
+//! finder, which gives the same matches but slows down as alternatives pile up.
+//!
 //! ## Architecture
 //!
 //! ```text
@@ -115,7 +156,7 @@
 //!                                     ... 6 more fast paths
 //! ```
 //!
-//! **Dependencies:** Only `memchr` and `aho-corasick` for SIMD primitives
+//! **Dependencies:** Only `memchr` and `aho-corasick` (optional) for SIMD primitives
 //!
 //! ## When to Use ReXile vs regex
 //!
@@ -125,39 +166,65 @@
 //! - Identifiers (`[a-zA-Z_]\w*`) - Much faster
 //! - Dynamic pattern compilation - 21x faster
 //! - Memory-constrained environments - 15x less memory
+//! - Lookaround and backreferences - not in the regex crate
 //!
 //! **Choose regex crate for:**
 //! - Complex alternations (ReXile 2x slower)
-//! - Unicode properties (`\p{L}` - not yet supported)
-//! - Advanced features (lookahead, backreferences - not yet supported)
+//! - Unicode scripts (`\p{Greek}`) - only general categories like `\p{L}` are supported
+//! - Named and atomic groups, unbounded lookbehind (`(?<=a+)`) - not yet supported
 //!
 //! ## License
 //!
 //! Licensed under either of MIT or Apache-2.0 at your option.
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 // Module organization
 mod advanced; // Advanced features: captures, lookaround
+mod bytes; // Searching byte slices that may not be valid UTF-8
+#[cfg(feature = "std")]
+mod cache; // Compiled-pattern cache behind the free functions (feature = "std")
 mod engine; // Matching engines: NFA, DFA, Lazy DFA
+#[cfg(feature = "std")]
+mod lazy; // Patterns compiled once on first use (feature = "std")
 pub mod optimization; // Fast paths and optimizations
+#[cfg(feature = "parallel")]
+mod parallel; // Chunked find_all across threads (feature = "parallel")
 mod parser; // Pattern parsing: escape, charclass, quantifier, etc.
+#[cfg(feature = "serde")]
+mod serde_impl; // Serialize/Deserialize for Pattern (feature = "serde")
+mod set; // Matching many patterns at once
+mod stream; // Streaming search over chunked input
 
 // External dependencies
-use aho_corasick::AhoCorasick;
+use alloc::borrow::Cow;
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
 use memchr::memmem;
-use std::collections::HashMap;
-use std::sync::{Mutex, OnceLock};
 
 // Internal imports using new module structure
 use advanced::{Lookaround, LookaroundType};
 use engine::DFA;
+use optimization::LiteralSearcher;
 use parser::{
     is_sequence_pattern, parse_escape, parse_quantified_pattern, parse_sequence,
     starts_with_escape, BoundaryType, CharClass, Flags, Group, QuantifiedPattern, Sequence,
 };
 
 // Re-export public types
-pub use advanced::{CaptureGroup, Captures};
+pub use advanced::{CaptureGroup, CaptureLocations, Captures, UnknownGroup};
+#[cfg(feature = "std")]
+pub use cache::{find, get_pattern, is_match};
+#[cfg(feature = "std")]
+pub use lazy::LazyPattern;
 pub use optimization::{literal, prefilter};
+pub use set::PatternSet;
+pub use stream::{StreamMatch, StreamMatcher, DEFAULT_STREAM_WINDOW};
 
 /// Main ReXile pattern type
 #[derive(Debug, Clone)]
@@ -168,8 +235,8 @@ pub struct Pattern {
         optimization::literal::LiteralKind,
     )>,
     fast_path: Option<optimization::fast_path::FastPath>, // JIT-style fast path
-    #[allow(dead_code)]
-    flags: Flags,                  // Regex flags: (?i), (?m), (?s)
+    flags: Flags,                                         // Regex flags: (?i), (?m), (?s), (?R)
+    source: String, // Original pattern string, as passed to Pattern::new
 }
 
 /// Type alias for convenience
@@ -186,6 +253,159 @@ fn safe_slice_range(text: &str, start: usize, end: usize) -> Option<&str> {
     text.get(start..end)
 }
 
+/// Byte position just past the char starting at `pos` (`pos + 1` at the end)
+#[inline]
+pub(crate) fn next_char_boundary(text: &str, pos: usize) -> usize {
+    pos + text[pos..].chars().next().map_or(1, |c| c.len_utf8())
+}
+
+/// `text` lowercased for case-insensitive matching, remembering which char
+/// of `text` each lowercase byte came from
+///
+/// Lowercasing can change a char's length (`ẞ` is 3 bytes, `ß` is 2), so
+/// spans found in the lowercase text go through [`span`](Self::span) to get
+/// back to offsets in `text`.
+struct Lowercased {
+    lower: String,
+    /// Offset in `text` of the char behind each byte of `lower`
+    origins: Vec<usize>,
+    text_len: usize,
+}
+
+impl Lowercased {
+    fn new(text: &str) -> Self {
+        let mut lower = String::with_capacity(text.len());
+        let mut origins = Vec::with_capacity(text.len());
+        for (at, c) in text.char_indices() {
+            let before = lower.len();
+            lower.extend(c.to_lowercase());
+            origins.resize(origins.len() + lower.len() - before, at);
+        }
+        Lowercased {
+            lower,
+            origins,
+            text_len: text.len(),
+        }
+    }
+
+    /// `(start, end)` in the lowercase text as offsets in `text`; an end in
+    /// the middle of one char's lowercase form takes in all of that char
+    fn span(&self, text: &str, (start, end): (usize, usize)) -> (usize, usize) {
+        let origin = |at: usize| self.origins.get(at).copied().unwrap_or(self.text_len);
+        let from = origin(start);
+        let to = if end == start {
+            from
+        } else if end >= self.lower.len() {
+            self.text_len
+        } else if self.origins[end] != self.origins[end - 1] {
+            self.origins[end]
+        } else {
+            next_char_boundary(text, self.origins[end])
+        };
+        (from, to)
+    }
+}
+
+/// Search position shared by the match iterators
+///
+/// Matches are leftmost-first and never overlap. An empty match is reported
+/// at most once per position, and never where the previous match ended, so
+/// `a*` over `"baaab"` yields `0..0`, `1..4` and `5..5` like `regex` does.
+/// After an empty match the search moves on by one char, keeping every
+/// offset on a UTF-8 boundary; the end of the text is a position too.
+#[derive(Debug, Clone, Copy)]
+struct SearchCursor {
+    /// Where the next search starts
+    pos: usize,
+    /// End of the last reported match
+    last_end: Option<usize>,
+}
+
+impl SearchCursor {
+    fn new() -> Self {
+        SearchCursor {
+            pos: 0,
+            last_end: None,
+        }
+    }
+
+    /// Whether a search may still start at `pos`
+    fn in_text(&self, text: &str) -> bool {
+        self.pos <= text.len()
+    }
+
+    /// Step past a match found from `pos`; false if the match must be skipped
+    /// (an empty match touching the previous one) and the search retried
+    fn accept(&mut self, text: &str, (start, end): (usize, usize)) -> bool {
+        if start == end {
+            self.pos = next_char_boundary(text, end);
+            if self.last_end == Some(start) {
+                return false;
+            }
+        } else {
+            self.pos = end;
+        }
+        self.last_end = Some(end);
+        true
+    }
+}
+
+/// Leftmost-first search over alternation branches, resumed at positions
+/// that never go back
+///
+/// Each branch's next match is kept between searches: it stays the branch's
+/// leftmost match from any later position up to its start, so only branches
+/// whose match starts before the new position search again, and one with no
+/// match left is done. Stepping through `\d+|0x[0-9a-f]+` then reads the
+/// text about once per branch rather than once per match.
+struct BranchSearch<'m> {
+    branches: &'m [Matcher],
+    /// Per branch: `None` until it is searched, then its next match (`None`
+    /// once it has no more)
+    next: Vec<Option<Option<(usize, usize)>>>,
+}
+
+impl<'m> BranchSearch<'m> {
+    fn new(branches: &'m [Matcher]) -> Self {
+        BranchSearch {
+            branches,
+            next: vec![None; branches.len()],
+        }
+    }
+
+    /// Leftmost match of any branch at or after `from`
+    ///
+    /// When several branches match at the leftmost start, the first one in
+    /// pattern order wins whatever the lengths (leftmost-first, like
+    /// `regex`): `(abc)|(ab)` matches "abc" in full, `(ab)|(abc)` only "ab".
+    fn find_from(&mut self, text: &str, from: usize) -> Option<(usize, usize)> {
+        let mut best: Option<(usize, usize)> = None;
+        for (branch, next) in self.branches.iter().zip(&mut self.next) {
+            let found = match *next {
+                Some(Some(found)) if found.0 >= from => found,
+                Some(None) => continue,
+                _ => {
+                    let found = branch.find_from(text, from);
+                    *next = Some(found);
+                    match found {
+                        Some(found) => found,
+                        None => continue,
+                    }
+                }
+            };
+            // Only a strictly earlier start replaces an earlier branch
+            if !matches!(best, Some((best_start, _)) if best_start <= found.0) {
+                best = Some(found);
+            }
+        }
+        best
+    }
+}
+
+/// Longest repetition (in bytes) searched exhaustively for the iterations a
+/// quantified group's captures come from
+const MAX_REPETITION_SPLIT_LEN: usize = 256;
+
 /// Get all valid char boundary positions in a string slice from start_pos to end
 #[inline]
 #[allow(dead_code)]
@@ -195,18 +415,138 @@ fn char_boundaries(text: &str, start_pos: usize) -> impl Iterator<Item = usize>
 
 impl Pattern {
     pub fn new(pattern: &str) -> Result<Self, PatternError> {
+        Self::with_flags(pattern, Flags::new())
+    }
+
+    /// Use `matcher` to search for `pattern` instead of the built-in engine
+    ///
+    /// Patterns later compiled from exactly this string, byte for byte, call
+    /// `matcher` for [`is_match`](Self::is_match), [`find`](Self::find) and
+    /// [`find_all`](Self::find_all); captures still come from the compiled
+    /// pattern. `matcher` gets the text to search, which is the rest of the
+    /// haystack when iterating, and returns the first match in it. Anchors
+    /// and boundaries in the pattern are its job to check. The registry is
+    /// process-wide and guarded by a lock, so registering from any thread is
+    /// safe; patterns compiled before the call (including those in the
+    /// [`get_pattern`] cache) keep their old search path. Registering the
+    /// same string again replaces the matcher.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// // Ticket IDs like "TK-1234"
+    /// fn find_ticket(text: &str) -> Option<(usize, usize)> {
+    ///     let start = text.find("TK-")?;
+    ///     let digits = text[start + 3..].bytes().take_while(u8::is_ascii_digit).count();
+    ///     (digits == 4).then_some((start, start + 7))
+    /// }
+    ///
+    /// Pattern::register_fast_path(r"TK-\d{4}", find_ticket);
+    /// let pattern = Pattern::new(r"TK-\d{4}").unwrap();
+    /// assert!(pattern.explain().starts_with("fast path: Custom"));
+    /// assert_eq!(pattern.find("see TK-0042"), Some((4, 11)));
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn register_fast_path(pattern: &'static str, matcher: fn(&str) -> Option<(usize, usize)>) {
+        optimization::fast_path::register_fast_path(pattern, matcher);
+    }
+
+    /// Compile `pattern` with `base_flags` enabled in addition to its inline flags
+    fn with_flags(source: &str, base_flags: Flags) -> Result<Self, PatternError> {
+        Self::compile(source, base_flags, false)
+    }
+
+    /// [`with_flags`](Self::with_flags), with `strict_end` set once `$` has
+    /// to mean the very end of the text (`\z`) rather than also holding before
+    /// a final `\n`
+    fn compile(source: &str, base_flags: Flags, strict_end: bool) -> Result<Self, PatternError> {
+        // Classes with set operations, like [a-z&&[^aeiou]], are rewritten to
+        // the plain class they stand for before anything else scans them
+        let expanded =
+            parser::class_set::expand_set_operations(source).map_err(|(offset, message)| {
+                PatternError::parse_at(message, offset, &source[offset..])
+            })?;
+        let pattern = expanded.as_deref().unwrap_or(source);
+
+        // \Z is `$`; \z is `$` that never skips a final `\n`
+        let rewritten = rewrite_end_of_text_escapes(pattern)?;
+        let strict_end = strict_end || rewritten.as_ref().is_some_and(|(_, strict)| *strict);
+        let pattern = rewritten
+            .as_ref()
+            .map_or(pattern, |(text, _)| text.as_str());
+
+        if let Some(feature) = find_unsupported_feature(pattern) {
+            return Err(PatternError::UnsupportedFeature(feature));
+        }
+        check_syntax(pattern)?;
+
+        // a{1}, (?:abc) and [.] become a, abc and \. so they reach the same
+        // fast paths as their plain forms
+        let simplified = parser::simplify::simplify(pattern);
+        let pattern = simplified.as_deref().unwrap_or(pattern);
+
         // Parse inline flags like (?i), (?m), (?s) at the start of the pattern
         let (flags, effective_pattern) =
             if let Some((parsed_flags, rest)) = Flags::parse_from_pattern(pattern) {
-                (parsed_flags, rest)
+                (base_flags.union(parsed_flags), rest)
             } else {
-                (Flags::new(), pattern)
+                (base_flags, pattern)
             };
 
-        // Check for anchors
-        let has_start_anchor = effective_pattern.starts_with('^');
-        let has_end_anchor =
-            effective_pattern.ends_with('$') && !effective_pattern.ends_with("\\$");
+        // Anchored search is \G in front of the whole pattern, alternation
+        // included
+        if flags.anchored {
+            let unanchored = Flags {
+                anchored: false,
+                ..base_flags
+            };
+            let inner = Pattern::compile(source, unanchored, strict_end)?;
+            return Ok(Pattern {
+                matcher: Matcher::ContinueAnchored(Box::new(inner)),
+                prefilter: None,
+                fast_path: None,
+                flags,
+                source: source.to_string(),
+            });
+        }
+
+        // \G: the rest compiles on its own; each search only takes a match
+        // that starts right where it began
+        if let Some(rest) = effective_pattern.strip_prefix(r"\G") {
+            if split_by_alternation(rest).is_some() {
+                return Err(PatternError::UnsupportedFeature(UnsupportedFeature::Other(
+                    "\\G before a top-level alternation".to_string(),
+                )));
+            }
+            let inner = Pattern::compile(rest, flags, strict_end)
+                .map_err(|e| e.offset_by(pattern.len() - rest.len()))?;
+            return Ok(Pattern {
+                matcher: Matcher::ContinueAnchored(Box::new(inner)),
+                prefilter: None,
+                fast_path: None,
+                flags,
+                source: source.to_string(),
+            });
+        }
+
+        // `$` also holds just before a final `\n`: the same pattern with a
+        // strict `$` runs on the text with and without that newline
+        if !strict_end && !flags.multiline && contains_end_anchor(effective_pattern) {
+            let inner = Pattern::compile(source, base_flags, true)?;
+            return Ok(Pattern {
+                matcher: Matcher::FinalNewlineEnd(Box::new(inner)),
+                prefilter: None,
+                fast_path: None,
+                flags,
+                source: source.to_string(),
+            });
+        }
+
+        // Check for anchors; in ^a|b$ they belong to the branches instead
+        let whole_pattern_anchors = split_by_alternation(effective_pattern).is_none();
+        let has_start_anchor = whole_pattern_anchors && effective_pattern.starts_with('^');
+        let has_end_anchor = whole_pattern_anchors && ends_with_end_anchor(effective_pattern);
 
         // Strip anchors to get inner pattern
         let inner_pattern = {
@@ -220,20 +560,51 @@ impl Pattern {
             p
         };
 
+        // A custom line terminator has to be a whole char of its own
+        if !flags.terminator().is_ascii() {
+            return Err(PatternError::parse("Line terminator must be an ASCII byte"));
+        }
+
+        // (?s): every `.` matches newlines too, whichever parser handles it;
+        // CRLF mode: `.` must not match `\r` either; a custom line terminator
+        // replaces `\n` as the one char `.` excludes
+        let dot_pattern;
+        let inner_pattern = if flags.dot_matches_newline {
+            dot_pattern = replace_dots(inner_pattern, DOTALL_CLASS);
+            dot_pattern.as_str()
+        } else if flags.crlf {
+            dot_pattern = replace_dots(inner_pattern, CRLF_DOT_CLASS);
+            dot_pattern.as_str()
+        } else if flags.terminator() != b'\n' {
+            dot_pattern = replace_dots(inner_pattern, &terminator_dot_class(flags.terminator()));
+            dot_pattern.as_str()
+        } else {
+            inner_pattern
+        };
+
+        // (?-u): every \b/\B only looks at ASCII word chars
+        let ascii_pattern;
+        let inner_pattern = if flags.ascii_word_boundary {
+            ascii_pattern = ascii_word_boundaries(inner_pattern);
+            ascii_pattern.as_str()
+        } else {
+            inner_pattern
+        };
+
         // Check for capture groups, but exclude special patterns like (?:...), (?=...), (?!...), etc.
-        let has_captures = inner_pattern.contains('(')
-            && !inner_pattern.contains("(?:")
-            && !inner_pattern.contains("(?=")
-            && !inner_pattern.contains("(?!")
-            && !inner_pattern.contains("(?<=")
-            && !inner_pattern.contains("(?<!");
-
-        // Parse the inner pattern (without anchors)
+        let has_captures = contains_capture_group(inner_pattern)
+            && !["(?:", "(?=", "(?!", "(?<=", "(?<!"]
+                .iter()
+                .any(|opener| contains_group_opener(inner_pattern, opener));
+
+        // Parse the inner pattern (without anchors); errors report offsets into `pattern`
+        let inner_offset = pattern.len() - effective_pattern.len() + usize::from(has_start_anchor);
         let inner_ast = if has_captures {
-            parse_pattern_with_captures_with_flags(inner_pattern, &flags)?
+            parse_pattern_with_captures_with_flags(inner_pattern, &flags)
         } else {
-            parse_pattern_with_flags(inner_pattern, &flags)?
-        };
+            parse_pattern_with_flags(inner_pattern, &flags)
+        }
+        .map_err(|e| e.offset_by(inner_offset))?;
 
         // Wrap with anchor constraints if needed
         let ast = if has_start_anchor || has_end_anchor {
@@ -247,24 +618,65 @@ impl Pattern {
         };
         let mut matcher = compile_ast(&ast)?;
 
-        // Apply flags to matcher (avoid double-wrapping if AST already wrapped)
-        if flags.case_insensitive && !matches!(matcher, Matcher::CaseInsensitive(_)) {
+        // Apply flags to matcher (avoid double-wrapping if AST already wrapped;
+        // a case-insensitive AST compiles to a matcher that already folds case)
+        if flags.case_insensitive
+            && !matches!(ast, Ast::CaseInsensitive(_))
+            && !matches!(matcher, Matcher::CaseInsensitive(_))
+        {
             matcher = Matcher::CaseInsensitive(Box::new(matcher));
         }
 
+        // (?m): the anchors hold at line boundaries, so apply the whole-text
+        // anchored matcher to each line in turn
+        if flags.multiline && (has_start_anchor || has_end_anchor) {
+            let line = Pattern {
+                matcher,
+                prefilter: None,
+                fast_path: None,
+                flags,
+                source: source.to_string(),
+            };
+            matcher = Matcher::LineAnchored {
+                line: Box::new(line),
+                start: has_start_anchor,
+                crlf: flags.crlf,
+                terminator: flags.terminator(),
+            };
+        }
+
         // Try to detect fast path first (JIT-style optimization)
-        // Note: fast path supports case_insensitive flag but not multiline/dot_matches_newline
-        // Skip fast-path only if multiline or dot_matches_newline flags are set
-        let fast_path = if flags.multiline || flags.dot_matches_newline {
+        // Note: fast path supports case_insensitive flag but not multiline/dot_matches_newline/crlf
+        // Skip fast-path only if multiline, dot_matches_newline, crlf or a line terminator is set,
+        // or case-insensitivity wasn't written inline (fast paths only see `(?i)` in the text)
+        // A matcher registered for this exact string comes first
+        let registered = if base_flags == Flags::new() {
+            optimization::fast_path::registered_fast_path(source)
+        } else {
+            None
+        };
+        let fast_path = if registered.is_some() {
+            registered
+        } else if flags.multiline
+            || flags.dot_matches_newline
+            || flags.crlf
+            || flags.line_terminator.is_some()
+            || base_flags.case_insensitive
+        {
             None
         } else {
             // First check if we can compile a CaptureDFA for patterns with captures
             if let Matcher::PatternWithCaptures { ref elements, .. } = matcher {
-                // Try to compile DFA
-                if let Some(dfa) = engine::capture_dfa::compile_capture_pattern(elements) {
+                // Numbers like -?\d+(\.\d+)? scan faster in one byte loop;
+                // their groups are still filled by the matcher
+                if let Some(number) =
+                    optimization::fast_path::detect_signed_number(effective_pattern)
+                {
+                    Some(number)
+                } else if let Some(dfa) = engine::capture_dfa::compile_capture_pattern(elements) {
                     // Successfully compiled DFA - use it as fast path
                     Some(optimization::fast_path::FastPath::CaptureDFA(
-                        std::sync::Arc::new(dfa),
+                        alloc::sync::Arc::new(dfa),
                     ))
                 } else {
                     // DFA compilation failed - fall back to normal fast path detection
@@ -283,6 +695,7 @@ impl Pattern {
                     effective_pattern
                 };
                 optimization::fast_path::detect_fast_path(fast_path_pattern)
+                    .or_else(|| class_run_fast_path(&matcher))
             }
         };
 
@@ -292,15 +705,17 @@ impl Pattern {
         // Only use prefilter for Prefix literals and patterns without groups
         // Groups can cause incorrect literal extraction that breaks leftmost-first semantics
         // Inner literals require expensive bounded verification
-        // Also disable prefilter when multiline or dot_matches_newline flags are set
-        // (case_insensitive is OK for prefilter)
-        let has_groups = effective_pattern.contains("(?:")
-            || (effective_pattern.contains('(') && !effective_pattern.contains("(?"));
+        // Also disable prefilter when multiline, dot_matches_newline or
+        // case_insensitive flags are set (the literals are searched as written)
+        let has_groups = contains_group_opener(effective_pattern, "(?:")
+            || (contains_capture_group(effective_pattern)
+                && !contains_group_opener(effective_pattern, "(?"));
         let prefilter = if !literals.is_empty()
             && literals.kind == optimization::literal::LiteralKind::Prefix
             && !has_groups
             && !flags.multiline
             && !flags.dot_matches_newline
+            && !flags.case_insensitive
         {
             let pf = optimization::prefilter::Prefilter::from_literals(&literals);
             if pf.is_available() {
@@ -317,9 +732,164 @@ impl Pattern {
             prefilter,
             fast_path,
             flags,
+            source: source.to_string(),
         })
     }
 
+    /// Describe the execution strategy chosen for this pattern
+    ///
+    /// Reports, in the order they are tried, the selected fast path (if any),
+    /// the prefilter strategy and where its literals sit in the pattern, and
+    /// the top-level matcher, then why flags turned optimizations off, if they
+    /// did (see [`optimizations_disabled_reason`](Self::optimizations_disabled_reason)).
+    /// Intended for performance debugging; the exact wording is not stable.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"\d+").unwrap();
+    /// println!("{}", pattern.explain());
+    /// // fast path: DigitRun
+    /// // prefilter: none
+    /// // matcher: DigitRun
+    /// ```
+    pub fn explain(&self) -> String {
+        let fast_path = self.fast_path.as_ref().map_or("none", |fp| fp.name());
+        let prefilter = match &self.prefilter {
+            Some((prefilter, kind)) => format!("{} ({:?})", prefilter.strategy_name(), kind),
+            None => "none".to_string(),
+        };
+        let mut explanation = format!(
+            "fast path: {}\nprefilter: {}\nmatcher: {}",
+            fast_path,
+            prefilter,
+            self.matcher.name()
+        );
+        if let Some(reason) = self.optimizations_disabled_reason() {
+            explanation.push('\n');
+            explanation.push_str(reason);
+        }
+        explanation
+    }
+
+    /// Why the pattern's flags keep it off the fast paths or the prefilter,
+    /// or `None` if they don't
+    ///
+    /// Fast paths are only tried without multi-line, dot-matches-newline or
+    /// CRLF mode, a custom line terminator, or case-insensitivity set through
+    /// [`PatternBuilder`]; the prefilter searches for its literals as written,
+    /// so any case-insensitivity (inline `(?i)` included) turns it off too.
+    /// A pattern that has neither for other reasons gets `None`.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(?i)error: \w+").unwrap();
+    /// assert_eq!(
+    ///     pattern.optimizations_disabled_reason(),
+    ///     Some("prefilter disabled: case-insensitive flag set")
+    /// );
+    /// assert_eq!(Pattern::new(r"error: \w+").unwrap().optimizations_disabled_reason(), None);
+    /// ```
+    pub fn optimizations_disabled_reason(&self) -> Option<&'static str> {
+        let flags = self.flags;
+        if flags.multiline {
+            Some("fast path and prefilter disabled: multi-line flag set")
+        } else if flags.dot_matches_newline {
+            Some("fast path and prefilter disabled: dot-matches-newline flag set")
+        } else if flags.crlf {
+            Some("fast path disabled: CRLF flag set")
+        } else if flags.line_terminator.is_some() {
+            Some("fast path disabled: custom line terminator set")
+        } else if flags.case_insensitive {
+            let inline = Flags::parse_from_pattern(&self.source)
+                .is_some_and(|(inline, _)| inline.case_insensitive);
+            Some(if inline {
+                "prefilter disabled: case-insensitive flag set"
+            } else {
+                "fast path and prefilter disabled: case-insensitive flag set through PatternBuilder"
+            })
+        } else {
+            None
+        }
+    }
+
+    /// `pattern` with redundant constructs rewritten, or `None` if it has none
+    ///
+    /// `a{1}` becomes `a`, a non-capturing group around a single atom (or
+    /// around a sequence with nothing quantifying the group) loses its
+    /// parentheses, and a class with one member like `[a]` or `[.]` becomes
+    /// that char. The result matches exactly what `pattern` does, with the
+    /// same capture groups. [`new`](Self::new) applies this itself before
+    /// picking a fast path; it's public for inspecting generated patterns.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// assert_eq!(Pattern::try_simplify("(?:hello)").as_deref(), Some("hello"));
+    /// assert_eq!(Pattern::try_simplify("(?:a)+[.]x{1}").as_deref(), Some(r"a+\.x"));
+    /// assert_eq!(Pattern::try_simplify("(?:ab)+"), None);
+    /// ```
+    pub fn try_simplify(pattern: &str) -> Option<String> {
+        parser::simplify::simplify(pattern)
+    }
+
+    /// Literals every match starts with one of, as used by the prefilter
+    ///
+    /// The engine scans for these before running the full matcher, so a text
+    /// that contains none of them cannot match. Empty when the pattern has no
+    /// prefilter (see [`has_prefilter`](Self::has_prefilter)); literal
+    /// extraction is conservative, and patterns with groups, inline flags or
+    /// leading classes get none.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"https?://\S+").unwrap();
+    /// assert_eq!(pattern.required_literals(), vec!["http"]);
+    /// assert!(Pattern::new(r"\d+").unwrap().required_literals().is_empty());
+    /// ```
+    pub fn required_literals(&self) -> Vec<String> {
+        self.prefilter
+            .as_ref()
+            .map(|(prefilter, _)| prefilter.needles())
+            .unwrap_or_default()
+    }
+
+    /// Check if searches scan for [`required_literals`](Self::required_literals)
+    /// before running the matcher
+    ///
+    /// A fast path, when the pattern has one, is tried ahead of the
+    /// prefilter; [`explain`](Self::explain) shows both.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// assert!(Pattern::new(r"hello \w+").unwrap().has_prefilter());
+    /// assert!(!Pattern::new(r"\w+ world").unwrap().has_prefilter());
+    /// ```
+    pub fn has_prefilter(&self) -> bool {
+        self.prefilter.is_some()
+    }
+
+    /// The source pattern string this pattern was compiled from
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(?i)\d+").unwrap();
+    /// assert_eq!(pattern.as_str(), r"(?i)\d+");
+    /// ```
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
     pub fn is_match(&self, text: &str) -> bool {
         // Fast path for common patterns (JIT-style)
         if let Some(ref fp) = self.fast_path {
@@ -335,6 +905,124 @@ impl Pattern {
         self.matcher.is_match(text)
     }
 
+    /// Check if the pattern matches the whole of `text`, not just part of it
+    ///
+    /// Same as wrapping the pattern in `^(?:...)$`, but alternations are
+    /// checked branch by branch, so a shorter branch listed first doesn't
+    /// hide a longer one that covers the text.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new("foo|foobar").unwrap();
+    /// assert!(pattern.is_full_match("foobar"));
+    /// assert!(!pattern.is_full_match("foobarbaz"));
+    /// assert!(pattern.is_match("foobarbaz"));
+    /// ```
+    pub fn is_full_match(&self, text: &str) -> bool {
+        Matcher::matches_entire(&self.matcher, text)
+    }
+
+    /// Check if a match begins at byte 0 of `text`
+    ///
+    /// Unlike [`is_match`](Self::is_match) this never reports a match
+    /// further along, which is what a hand-written lexer wants when asking
+    /// "does this token start here".
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let number = Pattern::new(r"\d+").unwrap();
+    /// assert!(number.is_match_at_start("42 + x"));
+    /// assert!(!number.is_match_at_start("x + 42"));
+    /// ```
+    pub fn is_match_at_start(&self, text: &str) -> bool {
+        self.match_at_start(text).is_some()
+    }
+
+    /// End offset of the match that begins at byte 0 of `text`, if any
+    ///
+    /// The match is the same one [`find`](Self::find) would report when it
+    /// starts at 0. Literals, quantified classes and sequences are tried at
+    /// position 0 only; other patterns fall back to a search.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let ident = Pattern::new(r"[a-z_]\w*").unwrap();
+    /// assert_eq!(ident.match_at_start("foo_1 = 2"), Some(5));
+    /// assert_eq!(ident.match_at_start(" foo"), None);
+    /// ```
+    pub fn match_at_start(&self, text: &str) -> Option<usize> {
+        match &self.matcher {
+            Matcher::Literal(lit) => text.starts_with(lit.as_str()).then_some(lit.len()),
+            Matcher::Quantified(qp) => qp.match_at(text),
+            Matcher::Sequence(seq) => seq.match_at(text),
+            _ => self
+                .find(text)
+                .and_then(|(start, end)| (start == 0).then_some(end)),
+        }
+    }
+
+    /// Number of capture groups, counting group 0 (the whole match)
+    ///
+    /// This is the length of every [`Captures`] the pattern produces, so
+    /// `captures_len() - 1` is the number of explicit groups; indices run
+    /// from 0 (the full match) to `captures_len() - 1`.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"(\w+)@(\w+)\.com").unwrap();
+    /// assert_eq!(pattern.captures_len(), 3);
+    /// assert_eq!(Pattern::new("abc").unwrap().captures_len(), 1);
+    /// ```
+    pub fn captures_len(&self) -> usize {
+        self.matcher.group_count() + 1
+    }
+
+    /// Longest possible match in bytes, when the pattern is a literal or an
+    /// alternation of literals
+    pub(crate) fn max_match_len(&self) -> Option<usize> {
+        match &self.matcher {
+            Matcher::Literal(literal) => Some(literal.len()),
+            Matcher::AnchoredLiteral { literal, .. } => Some(literal.len()),
+            Matcher::MultiLiteral { literals, .. } => literals.iter().map(String::len).max(),
+            _ => None,
+        }
+    }
+
+    /// The literals the pattern matches exactly, when it is a plain literal
+    /// or a case-sensitive alternation of non-empty literals
+    ///
+    /// An occurrence of any of them is a match, so no matcher needs to run.
+    pub(crate) fn literal_alternatives(&self) -> Option<Vec<String>> {
+        use optimization::fast_path::FastPath;
+        match (&self.fast_path, &self.matcher) {
+            // A registered matcher may not agree with the literal
+            (Some(FastPath::Custom(_)), _) => None,
+            (Some(FastPath::Literal(literal)), _) if !literal.is_empty() => {
+                Some(vec![literal.clone()])
+            }
+            (_, Matcher::Literal(literal)) if !literal.is_empty() => {
+                Some(vec![literal.to_string()])
+            }
+            (
+                _,
+                Matcher::MultiLiteral {
+                    literals,
+                    ascii_case_insensitive: false,
+                    ..
+                },
+            ) if literals.iter().all(|literal| !literal.is_empty()) => Some(literals.clone()),
+            _ => None,
+        }
+    }
+
     /// Match with prefilter using bounded verification strategy
     fn is_match_with_prefilter(
         &self,
@@ -348,8 +1036,11 @@ impl Pattern {
         let max_lookback = match literal_kind {
             literal::LiteralKind::Prefix => 10, // Prefix: small window (e.g., https?)
             literal::LiteralKind::Inner => 30,  // Inner: medium window (e.g., \w+@)
-            literal::LiteralKind::Suffix => 50, // Suffix: larger window
-            literal::LiteralKind::None => return self.matcher.is_match(text),
+            // Suffix literals end `$` sequences, which SuffixAnchored
+            // matches backward from the end of the text
+            literal::LiteralKind::Suffix | literal::LiteralKind::None => {
+                return self.matcher.is_match(text)
+            }
         };
 
         for candidate_pos in prefilter.candidates(bytes) {
@@ -384,7 +1075,42 @@ impl Pattern {
         self.matcher.find(text)
     }
 
+    /// Find the first match starting at or after `start`, with offsets into `text`
+    ///
+    /// Unlike searching `&text[start..]`, anchors and boundaries still see the
+    /// text before `start`, and a leading `\G` requires the match to begin
+    /// exactly at `start`. Returns `None` when `start` is past the end or not
+    /// on a char boundary.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let token = Pattern::new(r"\G\s*(\w+)").unwrap();
+    /// let text = "let x = 1";
+    /// let mut pos = 0;
+    /// let mut words = Vec::new();
+    /// while let Some((start, end)) = token.find_at(text, pos) {
+    ///     words.push(text[start..end].trim());
+    ///     pos = end;
+    /// }
+    /// // `=` can't start a token, so the scan stops there instead of skipping it
+    /// assert_eq!(words, vec!["let", "x"]);
+    /// assert_eq!(&text[pos..], " = 1");
+    /// ```
+    pub fn find_at(&self, text: &str, start: usize) -> Option<(usize, usize)> {
+        if !text.is_char_boundary(start) {
+            return None;
+        }
+        self.search_at(text, start)
+    }
+
     /// Find with prefilter using bounded verification strategy
+    ///
+    /// Candidates come in increasing order and no match starts more than the
+    /// lookback before its candidate, so the first candidate bounds where the
+    /// leftmost match can start; the matcher's own forward scan from there
+    /// finds it. Later candidates can't start an earlier match.
     fn find_with_prefilter(
         &self,
         text: &str,
@@ -392,92 +1118,242 @@ impl Pattern {
         literal_kind: literal::LiteralKind,
     ) -> Option<(usize, usize)> {
         let bytes = text.as_bytes();
-        let mut earliest_match: Option<(usize, usize)> = None;
 
         // Determine lookback window based on literal kind
         let max_lookback = match literal_kind {
             literal::LiteralKind::Prefix => 10,
             literal::LiteralKind::Inner => 30,
-            literal::LiteralKind::Suffix => 50,
-            literal::LiteralKind::None => return self.matcher.find(text),
-        };
-
-        // For each candidate position found by prefilter
-        for candidate_pos in prefilter.candidates(bytes) {
-            // If we already found a match before this candidate, return it
-            if let Some((start, _)) = earliest_match {
-                if start < candidate_pos {
-                    return earliest_match;
-                }
+            literal::LiteralKind::Suffix | literal::LiteralKind::None => {
+                return self.matcher.find(text)
             }
+        };
 
-            let lookback = candidate_pos.min(max_lookback);
-
-            for offset in 0..=lookback {
-                let start_pos = candidate_pos - offset;
-
-                // Try to find match from this position
-                if let Some((match_start, match_end)) =
-                    self.matcher.find(safe_slice(text, start_pos).unwrap_or(""))
-                {
-                    let abs_start = start_pos + match_start;
-                    let abs_end = start_pos + match_end;
-
-                    // Update earliest match if this is earlier
-                    if earliest_match.is_none() || abs_start < earliest_match.unwrap().0 {
-                        earliest_match = Some((abs_start, abs_end));
-                    }
-                    break;
-                }
-            }
+        let candidate_pos = prefilter.candidates(bytes).next()?;
+        let mut start_pos = candidate_pos.saturating_sub(max_lookback);
+        while !text.is_char_boundary(start_pos) {
+            start_pos -= 1;
         }
 
-        earliest_match
+        let (match_start, match_end) = self.matcher.find(&text[start_pos..])?;
+        Some((start_pos + match_start, start_pos + match_end))
     }
 
-    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
-        // Fast path for common patterns (JIT-style)
-        if let Some(ref fp) = self.fast_path {
-            return fp.find_all(text);
+    /// Find all non-overlapping matches, in order
+    ///
+    /// Each search resumes at the end of the previous match, so adjacent
+    /// matches are all found while overlapping ones are not; see
+    /// [`find_all_with`](Self::find_all_with) for the overlapping policy.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"\d+").unwrap();
+    /// assert_eq!(pattern.find_all("12a34"), vec![(0, 2), (3, 5)]);
+    /// ```
+    pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut out = Vec::new();
+        self.find_all_into(text, &mut out);
+        out
+    }
+
+    /// Find all matches under the given [`Overlap`] policy, in order of start
+    ///
+    /// [`Overlap::NonOverlapping`] gives the same matches as
+    /// [`find_all`](Self::find_all); [`Overlap::Overlapping`] searches again
+    /// from one char after the start of each match, like
+    /// [`captures_iter_overlapping`](Self::captures_iter_overlapping).
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::{Overlap, Pattern};
+    ///
+    /// let pairs = Pattern::new(r"\d\d").unwrap();
+    /// assert_eq!(pairs.find_all_with("1234", Overlap::NonOverlapping), vec![(0, 2), (2, 4)]);
+    /// assert_eq!(
+    ///     pairs.find_all_with("1234", Overlap::Overlapping),
+    ///     vec![(0, 2), (1, 3), (2, 4)]
+    /// );
+    /// ```
+    pub fn find_all_with(&self, text: &str, overlap: Overlap) -> Vec<(usize, usize)> {
+        match overlap {
+            Overlap::NonOverlapping => self.find_all(text),
+            Overlap::Overlapping => {
+                let mut matches = Vec::new();
+                let mut pos = 0;
+                while pos <= text.len() {
+                    let Some((start, end)) = self.search_at(text, pos) else {
+                        break;
+                    };
+                    matches.push((start, end));
+                    pos = next_char_boundary(text, start);
+                }
+                matches
+            }
+        }
+    }
+
+    /// Find all matches like [`find_all`](Self::find_all), into a buffer the
+    /// caller keeps
+    ///
+    /// `out` is cleared first, then filled with the `(start, end)` spans, so
+    /// one buffer can be reused across many texts without reallocating.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let numbers = Pattern::new(r"\d+").unwrap();
+    /// let mut spans = Vec::new();
+    /// for line in ["a 1 b 22", "333"] {
+    ///     numbers.find_all_into(line, &mut spans);
+    ///     assert_eq!(spans, numbers.find_all(line));
+    /// }
+    /// assert_eq!(spans, vec![(0, 3)]);
+    /// ```
+    pub fn find_all_into(&self, text: &str, out: &mut Vec<(usize, usize)>) {
+        out.clear();
+
+        // Anchored patterns match at most once per text (or per line)
+        if let Matcher::AnchoredLiteral { .. }
+        | Matcher::AnchoredGroup { .. }
+        | Matcher::AnchoredPattern { .. }
+        | Matcher::SuffixAnchored { .. }
+        | Matcher::LineAnchored { .. } = &self.matcher
+        {
+            out.extend(self.matcher.find_all(text));
+            return;
+        }
+
+        // Patterns that can match the empty string go through the iterator,
+        // which steps over empty matches; the scanners below expect non-empty ones
+        // (a sequence like `\b\d?` only matches empty next to a word char)
+        let nullable_sequence =
+            matches!(&self.matcher, Matcher::Sequence(seq) if seq.can_match_empty());
+        let continued = matches!(
+            &self.matcher,
+            Matcher::ContinueAnchored(_) | Matcher::FinalNewlineEnd(_)
+        );
+        if nullable_sequence || continued || self.is_match("") {
+            out.extend(self.find_iter(text).map(|m| (m.start(), m.end())));
+            return;
+        }
+
+        // Fast path for common patterns (JIT-style)
+        if let Some(ref fp) = self.fast_path {
+            fp.find_all_into(text, out);
+            return;
         }
 
         // OPTIMIZED: Fast path for Literal using memchr's find_iter
         match &self.matcher {
             Matcher::Literal(lit) => {
                 // Use memmem::find_iter for direct SIMD iteration
-                memmem::find_iter(text.as_bytes(), lit.as_bytes())
-                    .map(|pos| (pos, pos + lit.len()))
-                    .collect()
-            }
-            Matcher::MultiLiteral(ac) => {
-                // AhoCorasick already has find_iter
-                ac.find_iter(text)
-                    .map(|mat| (mat.start(), mat.end()))
-                    .collect()
+                out.extend(
+                    memmem::find_iter(text.as_bytes(), lit.as_bytes())
+                        .map(|pos| (pos, pos + lit.len())),
+                );
             }
+            Matcher::MultiLiteral { searcher, .. } => out.extend(searcher.find_iter(text)),
             Matcher::Sequence(seq) => {
                 // OPTIMIZED: Use specialized sequence iterator with cached Finder
-                seq.find_all(text)
+                seq.find_all_into(text, out);
             }
-            Matcher::Quantified(qp) => qp.find_all(text),
-            Matcher::AnchoredLiteral { .. }
-            | Matcher::AnchoredGroup { .. }
-            | Matcher::AnchoredPattern { .. } => self.matcher.find_all(text),
+            Matcher::Quantified(qp) => qp.find_all_into(text, out),
             _ => {
                 // Complex patterns: use general iterator
-                self.find_iter(text).map(|m| (m.start(), m.end())).collect()
+                out.extend(self.find_iter(text).map(|m| (m.start(), m.end())));
+            }
+        }
+    }
+
+    /// Count the matches in the text without collecting them
+    ///
+    /// Counts the same matches as [`find_iter`](Self::find_iter); literal,
+    /// literal-alternation, digit-run and word-run patterns are counted in a
+    /// single scan.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let numbers = Pattern::new(r"\d+").unwrap();
+    /// assert_eq!(numbers.count_matches("took 12ms, 3 retries, 404"), 3);
+    /// ```
+    pub fn count_matches(&self, text: &str) -> usize {
+        if let Some(count) = self.fast_path.as_ref().and_then(|fp| fp.count(text)) {
+            return count;
+        }
+
+        match &self.matcher {
+            Matcher::Literal(lit) if !lit.is_empty() => {
+                memmem::find_iter(text.as_bytes(), lit.as_bytes()).count()
             }
+            Matcher::DigitRun => optimization::fast_path::count_digit_runs(text),
+            Matcher::WordRun => optimization::fast_path::count_word_runs(text),
+            _ => self.find_iter(text).count(),
         }
     }
 
     /// Create an iterator over all matches
+    ///
+    /// Zero-width matches are reported once per position and never right
+    /// after the previous match, as in the `regex` crate:
+    ///
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new("a*").unwrap();
+    /// let spans: Vec<_> = pattern.find_iter("baaab").map(|m| (m.start(), m.end())).collect();
+    /// assert_eq!(spans, vec![(0, 0), (1, 4), (5, 5)]);
+    /// ```
     pub fn find_iter<'a>(&'a self, text: &'a str) -> FindIter<'a> {
+        let branches = match (&self.fast_path, &self.matcher) {
+            (None, Matcher::AlternationWithCaptures { branches, .. }) => {
+                Some(BranchSearch::new(branches))
+            }
+            _ => None,
+        };
         FindIter {
-            matcher: &self.matcher,
-            fast_path: &self.fast_path,
+            pattern: self,
             text,
-            pos: 0,
+            cursor: SearchCursor::new(),
+            branches,
+            back: text.len() + 1,
+            collected: None,
+        }
+    }
+
+    /// Iterate lazily over all matches as [`Match`] values
+    ///
+    /// The same iterator as [`find_iter`](Self::find_iter), so it composes
+    /// with any iterator adapter and stops searching when dropped.
+    ///
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"\d+").unwrap();
+    /// let mut numbers = Vec::new();
+    /// for m in pattern.matches("a1 b22 c333") {
+    ///     numbers.push((m.start(), m.as_str()));
+    /// }
+    /// assert_eq!(numbers, vec![(1, "1"), (4, "22"), (8, "333")]);
+    ///
+    /// let first_long = pattern.matches("a1 b22 c333").find(|m| m.len() > 1);
+    /// assert_eq!(first_long.map(|m| m.as_str()), Some("22"));
+    /// ```
+    pub fn matches<'a>(&'a self, text: &'a str) -> Matches<'a> {
+        self.find_iter(text)
+    }
+
+    /// Find ONE match starting the search at `pos`, with offsets into `text`
+    fn search_at(&self, text: &str, pos: usize) -> Option<(usize, usize)> {
+        // Use fast path if available - find_at() finds ONE match from position
+        if let Some(ref fast_path) = self.fast_path {
+            return fast_path.find_at(text, pos);
         }
+
+        self.matcher.find_from(text, pos)
     }
 
     /// Capture groups from the first match
@@ -498,57 +1374,108 @@ impl Pattern {
     /// }
     /// ```
     pub fn captures<'t>(&self, text: &'t str) -> Option<Captures<'t>> {
+        self.captures_reusing(text, &mut Vec::new())
+    }
+
+    /// [`captures`](Self::captures), with the group slots taken from `slots`
+    /// so its allocation is reused; `slots` is left as it was when there is
+    /// no match
+    fn captures_reusing<'t>(
+        &self,
+        text: &'t str,
+        slots: &mut Vec<Option<(usize, usize)>>,
+    ) -> Option<Captures<'t>> {
+        // Capture DFA: locate the match, then read its groups in one forward pass
+        // (falls back to backtracking when the DFA can't report exact group spans)
+        if let Some(optimization::fast_path::FastPath::CaptureDFA(dfa)) = &self.fast_path {
+            if dfa.has_exact_captures() {
+                return Self::captures_with_dfa(dfa, text, 0, slots);
+            }
+        }
+
+        if let Matcher::FinalNewlineEnd(_) = &self.matcher {
+            return self.captures_iter(text).next();
+        }
+
+        // \G: the first match counts only if it starts the text
+        if let Matcher::ContinueAnchored(inner) = &self.matcher {
+            let caps = inner.captures_reusing(text, slots)?;
+            if caps.pos(0).is_some_and(|(start, _)| start == 0) {
+                return Some(caps);
+            }
+            *slots = caps.into_positions();
+            return None;
+        }
+
+        // (?m) anchors: capture within the first line that matches
+        if let Matcher::LineAnchored {
+            line,
+            crlf,
+            terminator,
+            ..
+        } = &self.matcher
+        {
+            return line_segments(text, 0, *crlf, *terminator).find_map(
+                |(line_start, line_end)| {
+                    let line_caps = line.captures(&text[line_start..line_end])?;
+                    let (match_start, match_end) = line_caps.pos(0)?;
+                    let mut caps = Captures::reusing(
+                        text,
+                        (line_start + match_start, line_start + match_end),
+                        line_caps.len() - 1,
+                        slots,
+                    );
+                    for group in 1..line_caps.len() {
+                        if let Some((cap_start, cap_end)) = line_caps.pos(group) {
+                            caps.set(group, line_start + cap_start, line_start + cap_end);
+                        }
+                    }
+                    Some(caps)
+                },
+            );
+        }
+
         // Check if this is a PatternWithCaptures matcher
         if let Matcher::PatternWithCaptures {
             elements,
             total_groups,
         } = &self.matcher
         {
-            // Try matching with backtracking at any position
-            for start_pos in 0..=text.len() {
-                if let Some((end_pos, capture_list)) =
-                    Matcher::match_elements_with_backtrack_and_captures(text, start_pos, elements)
-                {
-                    if end_pos > start_pos || elements.is_empty() {
-                        // Create Captures with full match and capture groups
-                        let mut caps = Captures::new(text, (start_pos, end_pos), *total_groups);
-
-                        // Add each capture group
-                        for (group_num, cap_start, cap_end) in capture_list {
-                            caps.set(group_num, cap_start, cap_end);
-                        }
-
-                        return Some(caps);
-                    }
-                }
+            if Matcher::has_backreferences(elements) {
+                return Self::captures_with_backreferences(text, 0, elements, *total_groups, slots);
             }
-            None
-        } else if let Matcher::Capture(inner_matcher, group_index) = &self.matcher {
-            // Single capture group - get total groups from inner matcher
-            let total_groups =
-                if let Matcher::PatternWithCaptures { total_groups, .. } = **inner_matcher {
-                    total_groups
-                } else {
-                    *group_index // If inner is not PatternWithCaptures, just use group_index
-                };
 
-            if let Some((start, end)) = inner_matcher.find(text) {
-                let mut caps = Captures::new(text, (start, end), total_groups);
-
-                // Record the main capture
+            Self::captures_with_backtracking(text, 0, elements, *total_groups, slots)
+        } else if let Matcher::Capture(inner_matcher, group_index) = &self.matcher {
+            // Single capture group: the leftmost match gives the span, and the
+            // nested groups are those of the path that produced it
+            let (start, end) = inner_matcher.find(text)?;
+            let mut caps = Captures::reusing(text, (start, end), self.matcher.group_count(), slots);
+            // A repetition's group is its last iteration, set below if it ran
+            if !matches!(inner_matcher.as_ref(), Matcher::QuantifiedCapture(..)) {
                 caps.set(*group_index, start, end);
-
-                // Extract all nested captures recursively
-                let nested = inner_matcher.extract_nested_captures(text, start);
-                for (group_num, cap_start, cap_end) in nested {
-                    caps.set(group_num, cap_start, cap_end);
-                }
-
-                Some(caps)
-            } else {
-                None
             }
-        } else if let Matcher::AnchoredPattern { inner, start, end } = &self.matcher {
+            for (group_num, cap_start, cap_end) in self.matcher.captures_in_span(text, start, end) {
+                caps.set(group_num, cap_start, cap_end);
+            }
+            Some(caps)
+        } else if let Matcher::AlternationWithCaptures {
+            branches,
+            total_groups,
+        } = &self.matcher
+        {
+            let found = self.find(text)?;
+            Some(Self::alternation_captures(
+                text,
+                found,
+                branches,
+                *total_groups,
+                slots,
+            ))
+        } else if let Matcher::AnchoredPattern {
+            inner, start, end, ..
+        } = &self.matcher
+        {
             // Handle anchored patterns with captures
             // Delegate to inner matcher's captures logic, but with anchor constraints
             if let Matcher::PatternWithCaptures {
@@ -579,7 +1506,8 @@ impl Pattern {
                             && check_anchor(start_pos, end_pos)
                         {
                             // Create Captures with full match and capture groups
-                            let mut caps = Captures::new(text, (start_pos, end_pos), *total_groups);
+                            let mut caps =
+                                Captures::reusing(text, (start_pos, end_pos), *total_groups, slots);
 
                             // Add each capture group
                             for (group_num, cap_start, cap_end) in capture_list {
@@ -590,18 +1518,211 @@ impl Pattern {
                         }
                     }
                 }
+
+                // Leftmost-first backtracking commits to one alternation branch;
+                // without groups to report, the anchored find span is enough
+                if *total_groups == 0 {
+                    return self.find(text).map(|(match_start, match_end)| {
+                        Captures::reusing(text, (match_start, match_end), 0, slots)
+                    });
+                }
                 None
+            } else if let Matcher::Capture(_, group_index) = inner.as_ref() {
+                // Anchored single capture group: ^(foo|bar)$
+                let (match_start, match_end) = self.find(text)?;
+                let mut caps =
+                    Captures::reusing(text, (match_start, match_end), inner.group_count(), slots);
+                caps.set(*group_index, match_start, match_end);
+                for (group_num, cap_start, cap_end) in
+                    inner.captures_in_span(text, match_start, match_end)
+                {
+                    caps.set(group_num, cap_start, cap_end);
+                }
+                Some(caps)
             } else {
                 // Simple pattern without captures - just return full match with anchor check
                 self.find(text).map(|(match_start, match_end)| {
-                    Captures::new(text, (match_start, match_end), 0)
+                    Captures::reusing(text, (match_start, match_end), 0, slots)
                 })
             }
+        } else if let Matcher::CombinedWithLookaround { prefix, .. } = &self.matcher {
+            // foo(?=bar): the lookaround is zero-width, so the groups are the
+            // prefix's within the match
+            let (start, end) = self.find(text)?;
+            let mut caps = Captures::reusing(text, (start, end), self.matcher.group_count(), slots);
+            for (group_num, cap_start, cap_end) in prefix.captures_in_span(text, start, end) {
+                caps.set(group_num, cap_start, cap_end);
+            }
+            Some(caps)
         } else {
             // Simple pattern without explicit captures - just return full match
-            self.find(text)
-                .map(|(start, end)| Captures::new(text, (start, end), 0))
+            let found = self.find(text)?;
+            let mut caps = Captures::reusing(text, found, 0, slots);
+            if let Some(index) = self.top_level_branch(text, found) {
+                caps.set_branch(index);
+            }
+            Some(caps)
+        }
+    }
+
+    /// Index of the top-level alternation branch that produced the match
+    /// `start..end`, or None if the pattern is not an alternation
+    fn top_level_branch(&self, text: &str, (start, end): (usize, usize)) -> Option<usize> {
+        match &self.matcher {
+            Matcher::AlternationWithCaptures { branches, .. } => {
+                Matcher::winning_branch(branches, text, start)
+            }
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive,
+                ..
+            } => {
+                let matched = &text[start..end];
+                literals.iter().position(|lit| {
+                    if *ascii_case_insensitive {
+                        lit.eq_ignore_ascii_case(matched)
+                    } else {
+                        lit == matched
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Captures of a top-level alternation match like (a)|(b): the groups of
+    /// the branch that produced `start..end`, with the other branches' unset
+    fn alternation_captures<'t>(
+        text: &'t str,
+        (start, end): (usize, usize),
+        branches: &[Matcher],
+        total_groups: usize,
+        slots: &mut Vec<Option<(usize, usize)>>,
+    ) -> Captures<'t> {
+        let mut caps = Captures::reusing(text, (start, end), total_groups, slots);
+        if let Some(index) = Matcher::winning_branch(branches, text, start) {
+            caps.set_branch(index);
+            for (group_num, cap_start, cap_end) in
+                branches[index].captures_in_span(text, start, end)
+            {
+                caps.set(group_num, cap_start, cap_end);
+            }
+        }
+        caps
+    }
+
+    /// Captures for the first DFA match at or after `base`, with absolute offsets
+    fn captures_with_dfa<'t>(
+        dfa: &engine::capture_dfa::CaptureDFA,
+        text: &'t str,
+        base: usize,
+        slots: &mut Vec<Option<(usize, usize)>>,
+    ) -> Option<Captures<'t>> {
+        let remaining = safe_slice(text, base)?;
+        let (rel_start, rel_end) = dfa.find(remaining)?;
+        let (start, end) = (base + rel_start, base + rel_end);
+
+        // The DFA doesn't look outside the match, so it can run on `text`
+        // and write absolute offsets straight into the slots
+        let mut caps = Captures::reusing(text, (start, end), dfa.total_groups(), slots);
+        if !dfa.captures_into(text, start, end, caps.groups_mut()) {
+            *slots = caps.into_positions();
+            return None;
+        }
+        Some(caps)
+    }
+
+    /// Capture groups from the first match into a reusable buffer
+    ///
+    /// Like [`Pattern::captures`], but writes group byte ranges into `locs`
+    /// instead of returning a new `Captures`, so a loop over many inputs keeps
+    /// one allocation. Returns false (leaving `locs` empty) if there is no match.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::{CaptureLocations, Pattern};
+    ///
+    /// let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    /// let mut locs = CaptureLocations::new();
+    ///
+    /// for line in ["a=1", "none", "bb=22"] {
+    ///     if pattern.captures_read(&mut locs, line) {
+    ///         let (start, end) = locs.get(2).unwrap();
+    ///         println!("{}", &line[start..end]);
+    ///     }
+    /// }
+    /// ```
+    pub fn captures_read(&self, locs: &mut CaptureLocations, text: &str) -> bool {
+        match self.captures_reusing(text, &mut locs.positions) {
+            Some(caps) => {
+                locs.positions = caps.into_positions();
+                true
+            }
+            None => {
+                locs.clear();
+                false
+            }
+        }
+    }
+
+    /// Captures for the first backtracking match at or after `base`
+    ///
+    /// Starts are tried in order and the first that matches wins, even with
+    /// an empty match, so the span is the one `find` reports.
+    fn captures_with_backtracking<'t>(
+        text: &'t str,
+        base: usize,
+        elements: &[CompiledCaptureElement],
+        total_groups: usize,
+        slots: &mut Vec<Option<(usize, usize)>>,
+    ) -> Option<Captures<'t>> {
+        let start_filter = Matcher::capture_start_filter(elements);
+        let mut from = base;
+        while let Some(start_pos) = start_filter.next(text, from) {
+            from = start_pos + 1;
+            if !text.is_char_boundary(start_pos) {
+                continue;
+            }
+            if let Some((end_pos, capture_list)) =
+                Matcher::match_elements_with_backtrack_and_captures(text, start_pos, elements)
+            {
+                let mut caps = Captures::reusing(text, (start_pos, end_pos), total_groups, slots);
+                for (group_num, cap_start, cap_end) in capture_list {
+                    caps.set(group_num, cap_start, cap_end);
+                }
+                return Some(caps);
+            }
+        }
+        None
+    }
+
+    /// Captures for the first backreference-aware match at or after `base`
+    fn captures_with_backreferences<'t>(
+        text: &'t str,
+        base: usize,
+        elements: &[CompiledCaptureElement],
+        total_groups: usize,
+        slots: &mut Vec<Option<(usize, usize)>>,
+    ) -> Option<Captures<'t>> {
+        let remaining = safe_slice(text, base)?;
+        for (offset, _) in remaining.char_indices() {
+            let start_pos = base + offset;
+            if let Some((end_pos, groups)) =
+                Matcher::match_pattern_with_backreferences(text, start_pos, elements)
+            {
+                if end_pos > start_pos {
+                    let mut caps =
+                        Captures::reusing(text, (start_pos, end_pos), total_groups, slots);
+                    for (i, group) in groups.into_iter().enumerate() {
+                        if let Some((start, end)) = group {
+                            caps.set(i + 1, start, end);
+                        }
+                    }
+                    return Some(caps);
+                }
+            }
         }
+        None
     }
 
     /// Iterate over all captures in the text
@@ -621,13 +1742,75 @@ impl Pattern {
         CapturesIter {
             pattern: self,
             text,
-            pos: 0,
+            cursor: SearchCursor::new(),
+            overlap: Overlap::NonOverlapping,
+        }
+    }
+
+    /// Iterate over captures at every position a match can start, including
+    /// matches that overlap earlier ones
+    ///
+    /// Unlike [`captures_iter`](Self::captures_iter), each search resumes one
+    /// char after the start of the previous match rather than at its end, so
+    /// every start position yields the match found from there. Matches still
+    /// come in order of their start, and no two start at the same offset.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pairs = Pattern::new(r"(\d)(\d)").unwrap();
+    /// let found: Vec<_> = pairs
+    ///     .captures_iter_overlapping("1234")
+    ///     .map(|caps| caps.get(0).unwrap())
+    ///     .collect();
+    /// assert_eq!(found, vec!["12", "23", "34"]);
+    /// ```
+    pub fn captures_iter_overlapping<'r, 't>(&'r self, text: &'t str) -> CapturesIter<'r, 't> {
+        CapturesIter {
+            pattern: self,
+            text,
+            cursor: SearchCursor::new(),
+            overlap: Overlap::Overlapping,
         }
     }
 
+    /// Split text into tokens, one per successive non-overlapping match
+    ///
+    /// Each item pairs the index of the top-level alternation branch that
+    /// matched (see [`Captures::branch`]; always 0 when the pattern is not
+    /// an alternation) with the match's groups. Every search resumes where
+    /// the previous token ended, and text between matches is skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let lexer = Pattern::new(r"(\d+)|([a-z]+)|\s+").unwrap();
+    /// let tokens: Vec<_> = lexer
+    ///     .tokenize("let x 42")
+    ///     .map(|(kind, caps)| (kind, caps.pos(0).unwrap()))
+    ///     .collect();
+    /// assert_eq!(
+    ///     tokens,
+    ///     vec![(1, (0, 3)), (2, (3, 4)), (1, (4, 5)), (2, (5, 6)), (0, (6, 8))]
+    /// );
+    /// ```
+    pub fn tokenize<'r, 't>(
+        &'r self,
+        text: &'t str,
+    ) -> impl Iterator<Item = (usize, Captures<'t>)> + 'r
+    where
+        't: 'r,
+    {
+        self.captures_iter(text)
+            .map(|caps| (caps.branch().unwrap_or(0), caps))
+    }
+
     /// Replace the first match with a replacement string
     ///
-    /// Supports capture group references using $1, $2, etc.
+    /// Supports capture group references using $1, $2, etc.; see [`Captures::expand`]
+    /// for the full template syntax.
     ///
     /// # Example
     /// ```
@@ -666,32 +1849,7 @@ impl Pattern {
 
                 let mut result = String::new();
                 result.push_str(&text[..match_start]);
-
-                // Process replacement string with $1, $2, etc.
-                let mut chars = replacement.chars().peekable();
-                while let Some(ch) = chars.next() {
-                    if ch == '$' {
-                        // Check if next char is a digit
-                        if let Some(&next_ch) = chars.peek() {
-                            if next_ch.is_ascii_digit() {
-                                chars.next(); // consume the digit
-                                let group_num = next_ch.to_digit(10).unwrap() as usize;
-
-                                // Insert the captured group
-                                if let Some(group_text) = caps.get(group_num) {
-                                    result.push_str(group_text);
-                                }
-                            } else {
-                                result.push('$');
-                            }
-                        } else {
-                            result.push('$');
-                        }
-                    } else {
-                        result.push(ch);
-                    }
-                }
-
+                caps.expand(replacement, &mut result);
                 result.push_str(&text[match_end..]);
                 result
             } else {
@@ -703,7 +1861,16 @@ impl Pattern {
 
     /// Replace all matches with a replacement string
     ///
-    /// Supports capture group references using $1, $2, etc.
+    /// Supports capture group references using $1, $2, etc.; see [`Captures::expand`]
+    /// for the full template syntax. A group that did not participate in a
+    /// match is replaced with nothing, and so is a reference to a group the
+    /// pattern doesn't have; [`replace_all_strict`](Self::replace_all_strict)
+    /// rejects the latter.
+    ///
+    /// Empty matches insert the replacement between chars: every position
+    /// where the pattern matches empty gets one copy, and the text in between
+    /// is kept, so no char is dropped or repeated. An empty match right where
+    /// a non-empty one ended is skipped, as in [`find_all`](Self::find_all).
     ///
     /// # Example
     /// ```
@@ -712,6 +1879,9 @@ impl Pattern {
     /// let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
     /// let result = pattern.replace_all("a=1 b=2", "$1:[$2]");
     /// assert_eq!(result, "a:[1] b:[2]");
+    ///
+    /// let boundary = Pattern::new(r"\b").unwrap();
+    /// assert_eq!(boundary.replace_all("ab cd", "|"), "|ab| |cd|");
     /// ```
     pub fn replace_all(&self, text: &str, replacement: &str) -> String {
         // Check if replacement contains capture references like $1, $2
@@ -736,40 +1906,13 @@ impl Pattern {
         let mut last_end = 0;
 
         for caps in self.captures_iter(text) {
-            let _full_match = caps.get(0).unwrap();
             let match_start = caps.pos(0).unwrap().0;
             let match_end = caps.pos(0).unwrap().1;
 
             // Add text before this match
             result.push_str(&text[last_end..match_start]);
 
-            // Process replacement string with $1, $2, etc.
-            let mut chars = replacement.chars().peekable();
-            while let Some(ch) = chars.next() {
-                if ch == '$' {
-                    // Check if next char is a digit
-                    if let Some(&next_ch) = chars.peek() {
-                        if next_ch.is_ascii_digit() {
-                            chars.next(); // consume the digit
-                            let group_num = next_ch.to_digit(10).unwrap() as usize;
-
-                            // Insert the captured group
-                            if let Some(group_text) = caps.get(group_num) {
-                                result.push_str(group_text);
-                            }
-                            // If group doesn't exist, just skip (don't insert anything)
-                        } else {
-                            // $ not followed by digit, insert literal $
-                            result.push('$');
-                        }
-                    } else {
-                        // $ at end of string
-                        result.push('$');
-                    }
-                } else {
-                    result.push(ch);
-                }
-            }
+            caps.expand(replacement, &mut result);
 
             last_end = match_end;
         }
@@ -779,24 +1922,237 @@ impl Pattern {
         result
     }
 
-    /// Split text by matches of this pattern
+    /// Replace all matches, rejecting a template that references a group
+    /// the pattern doesn't have
+    ///
+    /// The template is checked before searching, so the error doesn't depend
+    /// on `text`. Groups that exist but did not participate in a match are
+    /// still replaced with nothing.
     ///
     /// # Example
     /// ```
     /// use rexile::Pattern;
     ///
-    /// let pattern = Pattern::new(r"\s+").unwrap();
-    /// let parts: Vec<_> = pattern.split("a  b   c").collect();
-    /// assert_eq!(parts, vec!["a", "b", "c"]);
+    /// let pattern = Pattern::new(r"(\d+)?(\w+)").unwrap();
+    /// assert_eq!(pattern.replace_all_strict("abc 12x", "<$1$2>").unwrap(), "<abc> <12x>");
+    ///
+    /// let err = pattern.replace_all_strict("abc", "$3").unwrap_err();
+    /// assert_eq!(err.groups, 2);
     /// ```
-    pub fn split<'r, 't>(&'r self, text: &'t str) -> SplitIter<'r, 't> {
-        SplitIter {
-            pattern: self,
+    pub fn replace_all_strict(
+        &self,
+        text: &str,
+        replacement: &str,
+    ) -> Result<String, UnknownGroup> {
+        advanced::captures::check_template(replacement, self.matcher.group_count())?;
+        Ok(self.replace_all(text, replacement))
+    }
+
+    /// Replace all matches, borrowing `text` unchanged when nothing matches
+    ///
+    /// Same replacements as [`replace_all`](Self::replace_all), but only
+    /// allocates once there is a match to substitute.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    /// use std::borrow::Cow;
+    ///
+    /// let pattern = Pattern::new(r"\d+").unwrap();
+    /// assert!(matches!(pattern.replace_all_cow("no digits", "#"), Cow::Borrowed(_)));
+    /// assert_eq!(pattern.replace_all_cow("a1b22", "#"), "a#b#");
+    /// ```
+    pub fn replace_all_cow<'t>(&self, text: &'t str, replacement: &str) -> Cow<'t, str> {
+        if self.find(text).is_none() {
+            return Cow::Borrowed(text);
+        }
+        Cow::Owned(self.replace_all(text, replacement))
+    }
+
+    /// Split text by matches of this pattern
+    ///
+    /// Pieces follow `str::split`: a match at the start or end of the text
+    /// yields an empty first or last piece, adjacent matches yield an empty
+    /// piece between them, and an empty text yields one empty piece.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let pattern = Pattern::new(r"\s+").unwrap();
+    /// let parts: Vec<_> = pattern.split("a  b   c").collect();
+    /// assert_eq!(parts, vec!["a", "b", "c"]);
+    ///
+    /// let comma = Pattern::new(",").unwrap();
+    /// let parts: Vec<_> = comma.split(",a,").collect();
+    /// assert_eq!(parts, vec!["", "a", ""]);
+    /// ```
+    pub fn split<'r, 't>(&'r self, text: &'t str) -> SplitIter<'r, 't> {
+        SplitIter {
+            pattern: self,
             text,
-            pos: 0,
+            cursor: SearchCursor::new(),
+            piece_start: 0,
+            allow_trailing_empty: true,
             finished: false,
         }
     }
+
+    /// Split text by matches of this pattern, treating them as terminators
+    ///
+    /// Same as [`split`](Self::split), except that an empty last piece is
+    /// skipped, like `str::split_terminator`: text ending in a match doesn't
+    /// yield a trailing `""`, and an empty text yields nothing.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let newline = Pattern::new(r"\r?\n").unwrap();
+    /// let lines: Vec<_> = newline.split_terminator("a\r\nb\n").collect();
+    /// assert_eq!(lines, vec!["a", "b"]);
+    /// ```
+    pub fn split_terminator<'r, 't>(&'r self, text: &'t str) -> SplitIter<'r, 't> {
+        SplitIter {
+            allow_trailing_empty: false,
+            ..self.split(text)
+        }
+    }
+}
+
+/// Builds a [`Pattern`] with flags set in code rather than inline
+///
+/// Each option is equivalent to the matching inline flag at the start of the
+/// pattern; inline flags stay enabled even if the builder leaves them off.
+///
+/// # Example
+///
+/// ```
+/// use rexile::PatternBuilder;
+///
+/// let pattern = PatternBuilder::new(r"(?m)^\w+$").crlf(true).build().unwrap();
+/// let lines: Vec<_> = pattern.find_iter("one\r\ntwo\r\n").map(|m| m.as_str()).collect();
+/// assert_eq!(lines, vec!["one", "two"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PatternBuilder {
+    pattern: String,
+    flags: Flags,
+}
+
+impl PatternBuilder {
+    /// Start building `pattern` with no flags set
+    pub fn new(pattern: &str) -> Self {
+        PatternBuilder {
+            pattern: pattern.to_string(),
+            flags: Flags::new(),
+        }
+    }
+
+    /// Case-insensitive matching, like `(?i)`
+    pub fn case_insensitive(&mut self, yes: bool) -> &mut Self {
+        self.flags.case_insensitive = yes;
+        self
+    }
+
+    /// `^` and `$` match at line boundaries, like `(?m)`
+    pub fn multi_line(&mut self, yes: bool) -> &mut Self {
+        self.flags.multiline = yes;
+        self
+    }
+
+    /// `.` matches line terminators too, like `(?s)`
+    pub fn dot_matches_new_line(&mut self, yes: bool) -> &mut Self {
+        self.flags.dot_matches_newline = yes;
+        self
+    }
+
+    /// CRLF mode, like `(?R)`: `.` excludes `\r` as well as `\n`, and
+    /// multi-line anchors treat `\r\n` as a single line terminator
+    pub fn crlf(&mut self, yes: bool) -> &mut Self {
+        self.flags.crlf = yes;
+        self
+    }
+
+    /// The byte that `.` excludes and that multi-line anchors break lines
+    /// on, `\n` by default; e.g. `b'\0'` for NUL-separated records
+    ///
+    /// Must be ASCII, or `build` fails. CRLF mode takes precedence.
+    pub fn line_terminator(&mut self, byte: u8) -> &mut Self {
+        self.flags.line_terminator = Some(byte);
+        self
+    }
+
+    /// Whether `\b` and `\B` treat non-ASCII alphanumerics as word chars
+    /// (the default); `false` is like `(?-u)` and only checks ASCII bytes
+    pub fn unicode_word_boundary(&mut self, yes: bool) -> &mut Self {
+        self.flags.ascii_word_boundary = !yes;
+        self
+    }
+
+    /// Anchored search: a match must start exactly where the search does,
+    /// as if the pattern began with `\G`
+    ///
+    /// [`find`](Pattern::find) and [`is_match`](Pattern::is_match) then
+    /// only try offset 0, [`find_at`](Pattern::find_at) only `start`, and
+    /// [`find_all`](Pattern::find_all) yields a run of back-to-back matches
+    /// from offset 0 that stops at the first gap. Unlike a leading `\G`,
+    /// this also anchors every branch of a top-level alternation.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::PatternBuilder;
+    ///
+    /// let token = PatternBuilder::new(r"\d+|[a-z]+").anchored(true).build().unwrap();
+    /// assert_eq!(token.find("abc 42"), Some((0, 3)));
+    /// assert_eq!(token.find(" 42"), None);
+    /// assert_eq!(token.find_at("abc 42", 4), Some((4, 6)));
+    /// assert_eq!(token.find_all("ab12 cd"), vec![(0, 2), (2, 4)]);
+    /// ```
+    pub fn anchored(&mut self, yes: bool) -> &mut Self {
+        self.flags.anchored = yes;
+        self
+    }
+
+    /// Compile the pattern with the configured flags
+    pub fn build(&self) -> Result<Pattern, PatternError> {
+        Pattern::with_flags(&self.pattern, self.flags)
+    }
+}
+
+/// Displays the source pattern string
+///
+/// `Debug` shows the compiled matcher tree; `Display` shows what was passed
+/// to `Pattern::new`.
+impl core::fmt::Display for Pattern {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Two patterns are equal when they have the same source string and the same
+/// effective flags (inline flags plus any set through [`PatternBuilder`])
+///
+/// Equality is by source, not by what the patterns match: `a|b` and `[ab]`
+/// are different patterns even though they accept the same strings.
+impl PartialEq for Pattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source && self.flags == other.flags
+    }
+}
+
+impl Eq for Pattern {}
+
+/// Hashes the source string and effective flags, consistent with `PartialEq`
+///
+/// Clippy's `mutable_key_type` lint may fire on `HashSet<Pattern>` because
+/// some matchers keep an internal state cache; that cache is never hashed or
+/// compared, so it is safe to allow.
+impl core::hash::Hash for Pattern {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.source.hash(state);
+        self.flags.hash(state);
+    }
 }
 
 /// A single match in the haystack.
@@ -837,7 +2193,7 @@ impl<'t> Match<'t> {
 
     /// Returns the range of byte offsets spanned by this match.
     #[inline]
-    pub fn range(&self) -> std::ops::Range<usize> {
+    pub fn range(&self) -> core::ops::Range<usize> {
         self.start..self.end
     }
 
@@ -855,170 +2211,394 @@ impl<'t> Match<'t> {
 }
 
 /// Iterator over pattern matches
+///
+/// Also iterates from the back: `next_back` yields the last match of the
+/// forward sequence that has not been returned yet. Forward and backward
+/// cursors never cross, so when they meet both ends return `None` and every
+/// match is yielded exactly once.
+///
+/// Which match is last depends on all those before it, so apart from a
+/// literal that can't overlap itself (found with a reverse search), the first
+/// `next_back` runs the forward search to the end and keeps the matches it
+/// has not returned yet; both ends then take from those.
 pub struct FindIter<'a> {
-    matcher: &'a Matcher,
-    fast_path: &'a Option<optimization::fast_path::FastPath>,
+    pattern: &'a Pattern,
     text: &'a str,
-    pos: usize,
+    cursor: SearchCursor,
+    /// An alternation's branch matches, kept from one step to the next
+    branches: Option<BranchSearch<'a>>,
+    /// Start of the last match returned by `next_back` (one past the text
+    /// initially); remaining matches must start before and end at or before it
+    back: usize,
+    /// The remaining forward matches, each with the cursor just past it,
+    /// once `next_back` has collected them
+    collected: Option<VecDeque<((usize, usize), SearchCursor)>>,
+}
+
+impl<'a> FindIter<'a> {
+    /// The text after the last match returned by `next`, or all of it
+    /// before the first one
+    ///
+    /// Once the iterator is done this is the trailing input no match
+    /// covered, which a tokenizer can report as unexpected.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let tokens = Pattern::new(r"\w+\s*").unwrap();
+    /// let mut iter = tokens.find_iter("let x = 1;");
+    /// assert_eq!(iter.rest(), "let x = 1;");
+    /// assert_eq!(iter.next().unwrap().as_str(), "let ");
+    /// assert_eq!(iter.rest(), "x = 1;");
+    ///
+    /// let words: Vec<_> = iter.by_ref().map(|m| m.as_str()).collect();
+    /// assert_eq!(words, vec!["x ", "1"]);
+    /// assert_eq!(iter.rest(), ";");
+    /// ```
+    pub fn rest(&self) -> &'a str {
+        &self.text[self.cursor.last_end.unwrap_or(0)..]
+    }
+
+    /// Next forward match from `cursor`, advancing it
+    fn next_from(&mut self, cursor: &mut SearchCursor) -> Option<(usize, usize)> {
+        while cursor.in_text(self.text) && cursor.pos < self.back {
+            let found = match &mut self.branches {
+                Some(branches) => branches.find_from(self.text, cursor.pos)?,
+                None => self.pattern.search_at(self.text, cursor.pos)?,
+            };
+            if !self.in_bounds(found) {
+                return None;
+            }
+            if cursor.accept(self.text, found) {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// Whether a match is still between the two cursors
+    fn in_bounds(&self, (start, end): (usize, usize)) -> bool {
+        start < self.back && end <= self.back
+    }
 }
 
 impl<'a> Iterator for FindIter<'a> {
     type Item = Match<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(collected) = &mut self.collected {
+            let ((start, end), cursor) = collected.pop_front()?;
+            self.cursor = cursor;
+            return Some(Match::new(self.text, start, end));
+        }
+
         // TRUE LAZY EVALUATION: Find one match at a time
-        if self.pos >= self.text.len() {
-            return None;
+        let mut cursor = self.cursor;
+        let found = self.next_from(&mut cursor);
+        match found {
+            Some((start, end)) => {
+                self.cursor = cursor;
+                Some(Match::new(self.text, start, end))
+            }
+            None => {
+                // No more matches before the back cursor
+                self.cursor.pos = self.back;
+                None
+            }
         }
+    }
+}
 
-        // Use fast path if available - find_at() finds ONE match from position
-        if let Some(ref fast_path) = self.fast_path {
-            if let Some((start, end)) = fast_path.find_at(self.text, self.pos) {
-                // Move position past this match
-                self.pos = end.max(self.pos + 1);
-                return Some(Match::new(self.text, start, end));
-            } else {
-                // No more matches
+impl<'a> DoubleEndedIterator for FindIter<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.collected.is_none() {
+            if !self.cursor.in_text(self.text) || self.cursor.pos >= self.back {
                 return None;
             }
+
+            // A literal that cannot overlap itself matches at every occurrence, so
+            // the rightmost occurrence is also the last forward match
+            if let Matcher::Literal(needle) = &self.pattern.matcher {
+                if !needle.is_empty() && !literal_overlaps_itself(needle) {
+                    let pos = self.cursor.pos;
+                    let window = &self.text.as_bytes()[pos..self.back.min(self.text.len())];
+                    return match memchr::memmem::rfind(window, needle.as_bytes()) {
+                        Some(rel_start) => {
+                            let start = pos + rel_start;
+                            self.back = start;
+                            Some(Match::new(self.text, start, start + needle.len()))
+                        }
+                        None => {
+                            self.back = pos;
+                            None
+                        }
+                    };
+                }
+            }
+
+            // General matchers: run the forward sequence to the end once
+            let mut cursor = self.cursor;
+            let mut collected = VecDeque::new();
+            while let Some(found) = self.next_from(&mut cursor) {
+                collected.push_back((found, cursor));
+            }
+            self.collected = Some(collected);
         }
 
-        // Fallback: normal matcher iteration
-        let remaining = &self.text[self.pos..];
-        if let Some((rel_start, rel_end)) = self.matcher.find(remaining) {
-            let abs_start = self.pos + rel_start;
-            let abs_end = self.pos + rel_end;
+        let ((start, end), _) = self.collected.as_mut()?.pop_back()?;
+        self.back = start;
+        Some(Match::new(self.text, start, end))
+    }
+}
 
-            // Move position past this match to avoid infinite loop
-            self.pos = abs_end.max(self.pos + 1);
+/// Iterator over [`Match`] values, returned by [`Pattern::matches`]
+pub type Matches<'a> = FindIter<'a>;
 
-            Some(Match::new(self.text, abs_start, abs_end))
-        } else {
-            None
-        }
+/// A pattern paired with a text, iterable (any number of times) over the
+/// matches of one in the other
+///
+/// # Example
+/// ```
+/// use rexile::{Pattern, PatternMatches};
+///
+/// let words = Pattern::new(r"\w+").unwrap();
+/// let matches = PatternMatches::new(&words, "one two three");
+/// assert_eq!((&matches).into_iter().count(), 3);
+///
+/// let longest = matches.into_iter().max_by_key(|m| m.len()).unwrap();
+/// assert_eq!(longest.as_str(), "three");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct PatternMatches<'a> {
+    pattern: &'a Pattern,
+    text: &'a str,
+}
+
+impl<'a> PatternMatches<'a> {
+    /// Pair `pattern` with the `text` to search
+    pub fn new(pattern: &'a Pattern, text: &'a str) -> Self {
+        PatternMatches { pattern, text }
+    }
+}
+
+impl<'a> IntoIterator for PatternMatches<'a> {
+    type Item = Match<'a>;
+    type IntoIter = Matches<'a>;
+
+    fn into_iter(self) -> Matches<'a> {
+        self.pattern.matches(self.text)
     }
 }
 
+impl<'a> IntoIterator for &PatternMatches<'a> {
+    type Item = Match<'a>;
+    type IntoIter = Matches<'a>;
+
+    fn into_iter(self) -> Matches<'a> {
+        self.pattern.matches(self.text)
+    }
+}
+
+/// Check if a literal has a proper prefix that is also a suffix ("aa", "abab")
+fn literal_overlaps_itself(needle: &str) -> bool {
+    let bytes = needle.as_bytes();
+    (1..bytes.len()).any(|k| bytes[..k] == bytes[bytes.len() - k..])
+}
+
+/// Whether successive matches may overlap, i.e. where the search for the
+/// next match resumes
+///
+/// See [`Pattern::find_all_with`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Overlap {
+    /// At the end of the match, so matches never overlap (as in `find_all`
+    /// and `find_iter`); an empty match right where the previous one ended
+    /// is skipped
+    NonOverlapping,
+    /// One char after the start of the match, so matches may overlap; each
+    /// start position reports at most one match
+    Overlapping,
+}
+
 /// Iterator over captures for each match
 pub struct CapturesIter<'r, 't> {
     pattern: &'r Pattern,
     text: &'t str,
-    pos: usize,
+    cursor: SearchCursor,
+    overlap: Overlap,
 }
 
 impl<'r, 't> Iterator for CapturesIter<'r, 't> {
     type Item = Captures<'t>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.pos >= self.text.len() {
-            return None;
+        while self.cursor.in_text(self.text) {
+            let caps = self.captures_at(self.cursor.pos)?;
+            let (start, end) = caps.pos(0)?;
+            match self.overlap {
+                Overlap::NonOverlapping => {
+                    if self.cursor.accept(self.text, (start, end)) {
+                        return Some(caps);
+                    }
+                }
+                Overlap::Overlapping => {
+                    self.cursor.pos = next_char_boundary(self.text, start);
+                    return Some(caps);
+                }
+            }
         }
+        None
+    }
+}
 
+impl<'r, 't> CapturesIter<'r, 't> {
+    /// Captures of the first match found from `pos`, with offsets into the text
+    fn captures_at(&self, pos: usize) -> Option<Captures<'t>> {
+        // Capture DFA: single forward pass per match, no per-position backtracking
+        if let Some(optimization::fast_path::FastPath::CaptureDFA(dfa)) = &self.pattern.fast_path {
+            if dfa.has_exact_captures() {
+                return Pattern::captures_with_dfa(dfa, self.text, pos, &mut Vec::new());
+            }
+        }
+
+        // `$` before a final `\n`: the leftmost captures over the text with
+        // and without it, as for find
+        if let Matcher::FinalNewlineEnd(inner) = &self.pattern.matcher {
+            let whole = inner.captures_iter(self.text).captures_at(pos);
+            let trimmed = self
+                .text
+                .strip_suffix('\n')
+                .filter(|trimmed| pos <= trimmed.len())
+                .and_then(|trimmed| inner.captures_iter(trimmed).captures_at(pos));
+            let start = |caps: &Captures<'t>| caps.pos(0).map_or(usize::MAX, |(start, _)| start);
+            return match (whole, trimmed) {
+                (Some(whole), Some(trimmed)) if start(&trimmed) < start(&whole) => Some(trimmed),
+                (whole, trimmed) => whole.or(trimmed),
+            };
+        }
+
+        // \G: the inner pattern's captures from `pos`, if they start there
+        if let Matcher::ContinueAnchored(inner) = &self.pattern.matcher {
+            return inner
+                .captures_iter(self.text)
+                .captures_at(pos)
+                .filter(|caps| caps.pos(0).is_some_and(|(start, _)| start == pos));
+        }
+
+        // (?m) anchors: locate the match with line context, then capture
+        // within the rest of its line
+        if let Matcher::LineAnchored {
+            line,
+            start,
+            crlf,
+            terminator,
+        } = &self.pattern.matcher
+        {
+            let (match_start, _) =
+                Matcher::line_anchored_find(line, self.text, pos, *start, *crlf, *terminator)?;
+            let line_end = self.text.as_bytes()[match_start..]
+                .iter()
+                .position(|&b| is_line_break(b, *crlf, *terminator))
+                .map_or(self.text.len(), |rel| match_start + rel);
+            let line_caps = line.captures(&self.text[match_start..line_end])?;
+            let (rel_start, rel_end) = line_caps.pos(0)?;
+            let (abs_start, abs_end) = (match_start + rel_start, match_start + rel_end);
+
+            let mut caps = Captures::new(self.text, (abs_start, abs_end), line_caps.len() - 1);
+            for group in 1..line_caps.len() {
+                if let Some((cap_start, cap_end)) = line_caps.pos(group) {
+                    caps.set(group, match_start + cap_start, match_start + cap_end);
+                }
+            }
+
+            return Some(caps);
+        }
         // Check if this is a PatternWithCaptures matcher
         if let Matcher::PatternWithCaptures {
             elements,
             total_groups,
         } = &self.pattern.matcher
         {
-            // Find next match starting from current position and extract capture positions
-            let remaining = &self.text[self.pos..];
-
-            // Iterate over char boundaries, not arbitrary byte positions
-            let char_indices: Vec<usize> = remaining.char_indices().map(|(i, _)| i).collect();
-            let search_positions: Vec<usize> = if char_indices.is_empty() {
-                vec![0]
-            } else {
-                char_indices
-                    .into_iter()
-                    .chain(std::iter::once(remaining.len()))
-                    .collect()
-            };
-
-            for &start_offset in &search_positions {
-                if start_offset >= remaining.len() {
-                    break;
-                }
-
-                let mut pos = start_offset;
-                let mut capture_positions: Vec<(usize, usize)> = Vec::new();
-                let mut all_matched = true;
-
-                for element in elements {
-                    let (matcher, group_num_opt) = match element {
-                        CompiledCaptureElement::Capture(m, num) => (m, Some(*num)),
-                        CompiledCaptureElement::NonCapture(m) => (m, None),
-                    };
-
-                    if let Some((rel_start, rel_end)) = matcher.find(&remaining[pos..]) {
-                        if rel_start != 0 {
-                            // Element must match at current position
-                            all_matched = false;
-                            break;
-                        }
-
-                        let abs_start = pos;
-                        let abs_end = pos + rel_end;
-
-                        // If this is a capture group, record its position
-                        if let Some(group_num) = group_num_opt {
-                            // Ensure we have enough space
-                            while capture_positions.len() < group_num {
-                                capture_positions.push((0, 0));
-                            }
-                            capture_positions[group_num - 1] = (abs_start, abs_end);
-                        }
-
-                        pos = abs_end;
-                    } else {
-                        all_matched = false;
-                        break;
-                    }
+            if Matcher::has_backreferences(elements) {
+                return Pattern::captures_with_backreferences(
+                    self.text,
+                    pos,
+                    elements,
+                    *total_groups,
+                    &mut Vec::new(),
+                );
+            }
+
+            Pattern::captures_with_backtracking(
+                self.text,
+                pos,
+                elements,
+                *total_groups,
+                &mut Vec::new(),
+            )
+        } else {
+            // Without groups the match is all there is to capture
+            if self.pattern.matcher.group_count() == 0 {
+                let found = self.pattern.search_at(self.text, pos)?;
+                let mut caps = Captures::new(self.text, found, 0);
+                if let Some(index) = self.pattern.top_level_branch(self.text, found) {
+                    caps.set_branch(index);
                 }
+                return Some(caps);
+            }
 
-                if all_matched {
-                    // Convert relative positions to absolute positions
-                    let abs_start = self.pos + start_offset;
-                    let abs_end = self.pos + pos;
-
-                    // Move position past this match
-                    self.pos = abs_end.max(self.pos + 1);
-
-                    // Create Captures with full match and capture groups
-                    let mut caps = Captures::new(self.text, (abs_start, abs_end), *total_groups);
+            // Alternations search with the whole text in view, so ^ in a
+            // branch keeps meaning the start of the text
+            if let Matcher::AlternationWithCaptures {
+                branches,
+                total_groups,
+            } = &self.pattern.matcher
+            {
+                let found = self.pattern.search_at(self.text, pos)?;
+                return Some(Pattern::alternation_captures(
+                    self.text,
+                    found,
+                    branches,
+                    *total_groups,
+                    &mut Vec::new(),
+                ));
+            }
 
-                    // Add each capture group using the set method
-                    for (i, &(start, end)) in capture_positions.iter().enumerate() {
-                        caps.set(i + 1, self.pos - pos + start, self.pos - pos + end);
-                    }
+            // Other matchers: capture within the remaining text, then shift to absolute offsets
+            let remaining = &self.text[pos..];
+            let rel_caps = self.pattern.captures(remaining)?;
+            let (rel_start, rel_end) = rel_caps.pos(0)?;
+            let abs_start = pos + rel_start;
+            let abs_end = pos + rel_end;
 
-                    return Some(caps);
+            let mut caps = Captures::new(self.text, (abs_start, abs_end), rel_caps.len() - 1);
+            for i in 1..rel_caps.len() {
+                if let Some((start, end)) = rel_caps.pos(i) {
+                    caps.set(i, pos + start, pos + end);
                 }
             }
-            None
-        } else {
-            // Simple pattern without explicit captures
-            let remaining = &self.text[self.pos..];
-            if let Some((rel_start, rel_end)) = self.pattern.matcher.find(remaining) {
-                let abs_start = self.pos + rel_start;
-                let abs_end = self.pos + rel_end;
-
-                // Move position past this match
-                self.pos = abs_end.max(self.pos + 1);
-
-                // Create captures for this match
-                Some(Captures::new(self.text, (abs_start, abs_end), 0))
-            } else {
-                None
+            if let Some(index) = rel_caps.branch() {
+                caps.set_branch(index);
             }
+
+            Some(caps)
         }
     }
 }
 
 /// Iterator over text split by pattern matches
+///
+/// Empty matches split too, so an empty pattern yields the text char by
+/// char, between an empty first and last piece (as `regex` does).
 pub struct SplitIter<'r, 't> {
     pattern: &'r Pattern,
     text: &'t str,
-    pos: usize,
+    cursor: SearchCursor,
+    /// Start of the piece after the last match
+    piece_start: usize,
+    /// Whether an empty piece after the last match is yielded
+    allow_trailing_empty: bool,
     finished: bool,
 }
 
@@ -1031,70 +2611,562 @@ impl<'r, 't> Iterator for SplitIter<'r, 't> {
         }
 
         // Find next match starting from current position
-        let remaining = &self.text[self.pos..];
-        if let Some((rel_start, rel_end)) = self.pattern.matcher.find(remaining) {
-            let abs_start = self.pos + rel_start;
-            let abs_end = self.pos + rel_end;
+        while self.cursor.in_text(self.text) {
+            let Some((start, end)) = self.pattern.search_at(self.text, self.cursor.pos) else {
+                break;
+            };
+            if self.cursor.accept(self.text, (start, end)) {
+                // Return text before the match
+                let piece = &self.text[self.piece_start..start];
+                self.piece_start = end;
+                return Some(piece);
+            }
+        }
 
-            // Return text before the match
-            let result = &self.text[self.pos..abs_start];
-            self.pos = abs_end;
+        // No more matches, return remaining text
+        self.finished = true;
+        let rest = &self.text[self.piece_start..];
+        if rest.is_empty() && !self.allow_trailing_empty {
+            return None;
+        }
+        Some(rest)
+    }
+}
 
-            Some(result)
-        } else {
-            // No more matches, return remaining text
-            self.finished = true;
-            Some(&self.text[self.pos..])
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PatternError {
+    ParseError {
+        message: String,
+        /// Byte offset into the pattern where parsing failed, when known
+        offset: Option<usize>,
+        /// The offending part of the pattern, starting at `offset`
+        fragment: Option<String>,
+    },
+    UnsupportedFeature(UnsupportedFeature),
+}
+
+/// Regex constructs that ReXile recognizes but does not implement
+///
+/// Returned inside [`PatternError::UnsupportedFeature`] so callers can decide
+/// whether to fall back to a full regex engine for a specific limitation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UnsupportedFeature {
+    /// Lookbehind whose inner pattern has no fixed length, e.g. `(?<=a+)`
+    VariableLengthLookbehind,
+    /// Unicode properties other than general categories, e.g. scripts: `\p{Greek}`
+    UnicodeProperty,
+    /// A quantifier applied to another quantifier: `a**`, `a++`, `a{2}{3}`
+    NestedQuantifier,
+    /// Named capture groups: `(?P<name>...)`, `(?<name>...)`
+    NamedGroup,
+    /// Atomic groups: `(?>...)`
+    AtomicGroup,
+    /// Any other unsupported construct, described in words
+    Other(String),
+}
+
+impl core::fmt::Display for UnsupportedFeature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            UnsupportedFeature::VariableLengthLookbehind => {
+                write!(f, "variable-length lookbehind")
+            }
+            UnsupportedFeature::UnicodeProperty => {
+                write!(f, "Unicode properties other than general categories")
+            }
+            UnsupportedFeature::NestedQuantifier => write!(f, "nested quantifiers"),
+            UnsupportedFeature::NamedGroup => write!(f, "named capture groups"),
+            UnsupportedFeature::AtomicGroup => write!(f, "atomic groups"),
+            UnsupportedFeature::Other(what) => write!(f, "{}", what),
+        }
+    }
+}
+
+/// Whether `b` ends a line: `terminator` (usually `\n`), or in CRLF mode
+/// `\n` and `\r`
+fn is_line_break(b: u8, crlf: bool, terminator: u8) -> bool {
+    if crlf {
+        b == b'\n' || b == b'\r'
+    } else {
+        b == terminator
+    }
+}
+
+/// Lines of `text` from `from` onwards as (start, end) byte ranges, excluding
+/// the terminator: `terminator` (usually `\n`), or in CRLF mode `\n`,
+/// `\r\n` and a lone `\r`
+///
+/// Text ending in a terminator yields a final empty line.
+fn line_segments(
+    text: &str,
+    from: usize,
+    crlf: bool,
+    terminator: u8,
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let bytes = text.as_bytes();
+    let mut next_start = Some(from);
+    core::iter::from_fn(move || {
+        let line_start = next_start?;
+        match bytes[line_start..]
+            .iter()
+            .position(|&b| is_line_break(b, crlf, terminator))
+        {
+            Some(rel) => {
+                let line_end = line_start + rel;
+                let terminator_len = if crlf
+                    && bytes[line_end] == b'\r'
+                    && bytes.get(line_end + 1) == Some(&b'\n')
+                {
+                    2
+                } else {
+                    1
+                };
+                next_start = Some(line_end + terminator_len);
+                Some((line_start, line_end))
+            }
+            None => {
+                next_start = None;
+                Some((line_start, bytes.len()))
+            }
+        }
+    })
+}
+
+/// Class every `.` becomes under `(?s)`: any character at all
+const DOTALL_CLASS: &str = "[\0-\u{10FFFF}]";
+
+/// Class every `.` becomes in CRLF mode: anything but `\r` and `\n`
+const CRLF_DOT_CLASS: &str = "[^\r\n]";
+
+/// Class every `.` becomes with a custom line terminator: anything but it
+fn terminator_dot_class(terminator: u8) -> String {
+    let terminator = char::from(terminator);
+    if terminator.is_ascii_punctuation() {
+        format!("[^\\{}]", terminator)
+    } else {
+        format!("[^{}]", terminator)
+    }
+}
+
+/// Rewrite each `.` outside brackets and escapes into the bracket class `class`
+fn replace_dots(pattern: &str, class: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                out.push(ch);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                out.push(ch);
+                // A leading `]` (after an optional `^`) is a literal member
+                if chars.peek() == Some(&'^') {
+                    out.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']') {
+                    out.push(chars.next().unwrap());
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(ch);
+            }
+            '.' if !in_class => out.push_str(class),
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// Rewrite each `\b`/`\B` outside brackets into its ASCII-only form, for `(?-u)`
+fn ascii_word_boundaries(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    let mut chars = pattern.chars().peekable();
+    let mut in_class = false;
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' => {
+                out.push(ch);
+                if let Some(escaped) = chars.next() {
+                    out.push(escaped);
+                    let is_boundary = matches!(escaped, 'b' | 'B') && !in_class;
+                    if is_boundary && !chars.clone().take(7).eq("{ascii}".chars()) {
+                        out.push_str("{ascii}");
+                    }
+                }
+            }
+            '[' if !in_class => {
+                in_class = true;
+                out.push(ch);
+                // A leading `]` (after an optional `^`) is a literal member
+                if chars.peek() == Some(&'^') {
+                    out.push(chars.next().unwrap());
+                }
+                if chars.peek() == Some(&']') {
+                    out.push(chars.next().unwrap());
+                }
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(ch);
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    out
+}
+
+/// Scan `pattern` for constructs that are known to be unsupported, so they fail
+/// with a specific reason instead of a generic parse error or a silent mismatch
+fn find_unsupported_feature(pattern: &str) -> Option<UnsupportedFeature> {
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    let mut in_class = false;
+    let mut after_quantifier = false;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if b == b'\\' {
+            if matches!(bytes.get(i + 1), Some(b'p') | Some(b'P')) {
+                // Malformed names are left to the parser to report
+                if let Ok((name, _)) = parser::unicode::parse_property_name(&pattern[i + 2..]) {
+                    if parser::unicode::UnicodeProperty::from_name(name).is_none() {
+                        return Some(UnsupportedFeature::UnicodeProperty);
+                    }
+                }
+            }
+            if !in_class
+                && bytes.get(i + 1) == Some(&b'R')
+                && matches!(
+                    bytes.get(i + 2),
+                    Some(b'*') | Some(b'+') | Some(b'?') | Some(b'{')
+                )
+            {
+                return Some(UnsupportedFeature::Other(
+                    "quantified line breaks (\\R)".to_string(),
+                ));
+            }
+            // \G only anchors the whole pattern, after any inline flags
+            if !in_class && bytes.get(i + 1) == Some(&b'G') {
+                let pattern_start = Flags::parse_from_pattern(pattern)
+                    .map_or(0, |(_, rest)| pattern.len() - rest.len());
+                if i != pattern_start {
+                    return Some(UnsupportedFeature::Other(
+                        "\\G anywhere but the start of the pattern".to_string(),
+                    ));
+                }
+            }
+            after_quantifier = false;
+            i += 2;
+            continue;
+        }
+
+        if in_class {
+            if b == b']' {
+                in_class = false;
+            }
+            i += 1;
+            continue;
+        }
+
+        match b {
+            b'[' => {
+                in_class = true;
+                after_quantifier = false;
+                i += 1;
+                // A leading `]` (after an optional `^`) is a literal member
+                if bytes.get(i) == Some(&b'^') {
+                    i += 1;
+                }
+                if bytes.get(i) == Some(&b']') {
+                    i += 1;
+                }
+                continue;
+            }
+            b'*' | b'+' | b'?' | b'{' => {
+                let len = if b == b'{' {
+                    match counted_repetition_len(&bytes[i..]) {
+                        Some(len) => len,
+                        // Not a repetition, `{` is a literal
+                        None => {
+                            after_quantifier = false;
+                            i += 1;
+                            continue;
+                        }
+                    }
+                } else {
+                    1
+                };
+                if after_quantifier {
+                    return Some(UnsupportedFeature::NestedQuantifier);
+                }
+                after_quantifier = true;
+                i += len;
+                // Lazy suffix belongs to this quantifier
+                if bytes.get(i) == Some(&b'?') {
+                    i += 1;
+                }
+                continue;
+            }
+            b'(' => {
+                let rest = &pattern[i..];
+                if rest.starts_with("(?P<")
+                    || (rest.starts_with("(?<")
+                        && !rest.starts_with("(?<=")
+                        && !rest.starts_with("(?<!"))
+                {
+                    return Some(UnsupportedFeature::NamedGroup);
+                }
+                if rest.starts_with("(?>") {
+                    return Some(UnsupportedFeature::AtomicGroup);
+                }
+                if rest.starts_with("(?#") {
+                    return Some(UnsupportedFeature::Other(
+                        "inline comments (?#...)".to_string(),
+                    ));
+                }
+            }
+            _ => {}
+        }
+
+        after_quantifier = false;
+        i += 1;
+    }
+
+    None
+}
+
+/// Reject malformed syntax that the parsers would otherwise fall back to
+/// matching literally: unclosed brackets, unbalanced parentheses, quantifiers
+/// with nothing to repeat and a trailing backslash. Group nesting is bounded
+/// here too, before any of the recursive parsers run
+fn check_syntax(pattern: &str) -> Result<(), PatternError> {
+    let bytes = pattern.as_bytes();
+    let mut i = 0;
+    let mut class_start = None;
+    let mut open_groups = Vec::new();
+    // Whether the previous token can be repeated
+    let mut has_atom = false;
+
+    while i < bytes.len() {
+        let b = bytes[i];
+
+        if b == b'\\' {
+            if i + 1 >= bytes.len() {
+                return Err(PatternError::parse_at(
+                    "Incomplete escape sequence",
+                    i,
+                    &pattern[i..],
+                ));
+            }
+            has_atom = true;
+            i += 2;
+            continue;
+        }
+
+        if class_start.is_some() {
+            if b == b']' {
+                class_start = None;
+                has_atom = true;
+            }
+            i += 1;
+            continue;
         }
+
+        match b {
+            b'[' => {
+                class_start = Some(i);
+                i += 1;
+                // A leading `]` (after an optional `^`) is a literal member
+                if bytes.get(i) == Some(&b'^') {
+                    i += 1;
+                }
+                if bytes.get(i) == Some(&b']') {
+                    i += 1;
+                }
+                continue;
+            }
+            b'(' => {
+                // Deeper nesting would exhaust the stack in the recursive parsers
+                if open_groups.len() >= MAX_RECURSION_DEPTH {
+                    return Err(PatternError::parse_at("Pattern too deeply nested", i, "("));
+                }
+                open_groups.push(i);
+                has_atom = false;
+                i += 1;
+                // Skip the group kind: (?:, (?=, (?!, (?<=, (?<!, (?i)
+                if bytes.get(i) == Some(&b'?') {
+                    i += 1;
+                    while i < bytes.len() && !matches!(bytes[i], b':' | b')' | b'(' | b'\\') {
+                        i += 1;
+                    }
+                    if bytes.get(i) == Some(&b':') {
+                        i += 1;
+                    }
+                }
+                continue;
+            }
+            b')' => {
+                if open_groups.pop().is_none() {
+                    return Err(PatternError::parse_at("Unmatched parenthesis", i, ")"));
+                }
+                has_atom = true;
+            }
+            b'|' => has_atom = false,
+            b'*' | b'+' | b'?' | b'{' => {
+                let len = if b == b'{' {
+                    match counted_repetition_len(&bytes[i..]) {
+                        Some(len) => len,
+                        // Not a repetition; left to the quantifier parser
+                        None => {
+                            has_atom = true;
+                            i += 1;
+                            continue;
+                        }
+                    }
+                } else {
+                    1
+                };
+                if !has_atom {
+                    return Err(PatternError::parse_at(
+                        "Quantifier without an expression to repeat",
+                        i,
+                        &pattern[i..],
+                    ));
+                }
+                if b == b'{' {
+                    // Reversed bounds like {3,2} can never match
+                    let body = &pattern[i + 1..i + len - 1];
+                    if let Some((min, max)) = body.split_once(',') {
+                        if let (Ok(min), Ok(max)) = (min.parse::<usize>(), max.parse::<usize>()) {
+                            if min > max {
+                                return Err(PatternError::parse_at(
+                                    "Invalid quantifier",
+                                    i,
+                                    &pattern[i..i + len],
+                                ));
+                            }
+                        }
+                    }
+                }
+                i += len;
+                // Lazy suffix belongs to this quantifier
+                if bytes.get(i) == Some(&b'?') {
+                    i += 1;
+                }
+                continue;
+            }
+            _ => has_atom = true,
+        }
+
+        i += 1;
+    }
+
+    if let Some(start) = class_start {
+        return Err(PatternError::parse_at(
+            "Unclosed character class",
+            start,
+            &pattern[start..],
+        ));
+    }
+    if let Some(&start) = open_groups.last() {
+        return Err(PatternError::parse_at("Unmatched parenthesis", start, "("));
     }
+    Ok(())
 }
 
-static CACHE: OnceLock<Mutex<HashMap<String, Pattern>>> = OnceLock::new();
-
-fn get_cache() -> &'static Mutex<HashMap<String, Pattern>> {
-    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+/// Length of a counted repetition `{n}`, `{n,}` or `{n,m}` at the start of
+/// `bytes`, or None if the braces don't form one
+fn counted_repetition_len(bytes: &[u8]) -> Option<usize> {
+    let close = bytes.iter().position(|&b| b == b'}')?;
+    let body = &bytes[1..close];
+    let mut parts = body.splitn(2, |&b| b == b',');
+    let min = parts.next()?;
+    if min.is_empty() || !min.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    match parts.next() {
+        Some(max) if !max.iter().all(u8::is_ascii_digit) => None,
+        _ => Some(close + 1),
+    }
 }
 
-pub fn get_pattern(pattern: &str) -> Result<Pattern, PatternError> {
-    let mut cache = get_cache().lock().unwrap();
-    if let Some(p) = cache.get(pattern) {
-        return Ok(p.clone());
+impl PatternError {
+    /// Parse error without a known position
+    fn parse(message: impl Into<String>) -> Self {
+        PatternError::ParseError {
+            message: message.into(),
+            offset: None,
+            fragment: None,
+        }
     }
-    let compiled = Pattern::new(pattern)?;
-    cache.insert(pattern.to_string(), compiled.clone());
-    Ok(compiled)
-}
 
-pub fn is_match(pattern: &str, text: &str) -> Result<bool, PatternError> {
-    Ok(get_pattern(pattern)?.is_match(text))
-}
+    /// Parse error at `offset` within the pattern text being parsed
+    fn parse_at(message: impl Into<String>, offset: usize, fragment: &str) -> Self {
+        PatternError::ParseError {
+            message: message.into(),
+            offset: Some(offset),
+            fragment: Some(fragment.to_string()),
+        }
+    }
 
-pub fn find(pattern: &str, text: &str) -> Result<Option<(usize, usize)>, PatternError> {
-    Ok(get_pattern(pattern)?.find(text))
-}
+    /// Rebase the offset of an error raised while parsing a sub-pattern that
+    /// starts at byte `base` of its parent
+    fn offset_by(self, base: usize) -> Self {
+        match self {
+            PatternError::ParseError {
+                message,
+                offset,
+                fragment,
+            } => PatternError::ParseError {
+                message,
+                offset: offset.map(|offset| offset + base),
+                fragment,
+            },
+            other => other,
+        }
+    }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum PatternError {
-    ParseError(String),
-    UnsupportedFeature(String),
+    /// Byte offset into the pattern where parsing failed, when known
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            PatternError::ParseError { offset, .. } => *offset,
+            PatternError::UnsupportedFeature(_) => None,
+        }
+    }
 }
 
-impl std::fmt::Display for PatternError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for PatternError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            PatternError::ParseError(msg) => write!(f, "Parse error: {}", msg),
-            PatternError::UnsupportedFeature(msg) => write!(f, "Unsupported: {}", msg),
+            PatternError::ParseError {
+                message,
+                offset: Some(offset),
+                ..
+            } => write!(f, "Parse error at byte {}: {}", offset, message),
+            PatternError::ParseError { message, .. } => write!(f, "Parse error: {}", message),
+            PatternError::UnsupportedFeature(feature) => write!(f, "Unsupported: {}", feature),
         }
     }
 }
 
+#[cfg(feature = "std")]
 impl std::error::Error for PatternError {}
 
 #[derive(Debug, Clone, PartialEq)]
 enum Ast {
     Literal(String),
-    Dot,    // Matches any character except newline
-    DotAll, // Matches any character INCLUDING newline (for (?s) flag)
+    Dot, // Matches any character except newline
     Alternation(Vec<String>),
     Anchored {
         literal: String,
@@ -1114,7 +3186,6 @@ enum Ast {
     CharClass(CharClass),
     Quantified(QuantifiedPattern),
     Sequence(Sequence),
-    SequenceWithFlags(Sequence, Flags), // Sequence with flags applied
     Group(Group),
     Boundary(BoundaryType),   // Phase 6: Word boundary support
     Lookaround(Lookaround),   // Phase 7: Lookahead/lookbehind
@@ -1363,21 +3434,57 @@ fn parse_pattern_with_groups(pattern: &str) -> Result<Ast, PatternError> {
         }
     }
 
-    Err(PatternError::ParseError(
-        "Complex group pattern not fully supported".to_string(),
+    Err(PatternError::parse(
+        "Complex group pattern not fully supported",
     ))
 }
 
+/// Parse the top-level branches of an alternation
+///
+/// Branches that are all plain literals go to the multi-literal searcher;
+/// otherwise each branch is parsed as a pattern of its own (`\d+|0x[0-9a-f]+`)
+/// and tried leftmost-first.
+fn parse_alternation(parts: Vec<String>, depth: usize) -> Result<Ast, PatternError> {
+    let mut branches = Vec::with_capacity(parts.len());
+    let mut offset = 0;
+    for part in &parts {
+        let branch = parse_pattern_with_depth(part, depth + 1).map_err(|e| e.offset_by(offset))?;
+        branches.push(branch);
+        offset += part.len() + 1; // Skip the '|' separator
+    }
+
+    if let Some(literals) = branches.iter().map(literal_from_ast).collect() {
+        return Ok(Ast::Alternation(literals));
+    }
+    Ok(Ast::AlternationWithCaptures {
+        branches,
+        total_groups: 0,
+    })
+}
+
 fn parse_pattern(pattern: &str) -> Result<Ast, PatternError> {
     parse_pattern_with_depth(pattern, 0)
 }
 
+/// Error for a malformed `{...}` repetition, pointing at its opening brace
+fn invalid_quantifier_error(pattern: &str) -> PatternError {
+    match pattern.find('{') {
+        Some(open) => {
+            let close = pattern[open..]
+                .find('}')
+                .map_or(pattern.len(), |i| open + i + 1);
+            PatternError::parse_at("Invalid quantifier", open, &pattern[open..close])
+        }
+        None => PatternError::parse("Invalid quantifier"),
+    }
+}
+
 const MAX_RECURSION_DEPTH: usize = 100;
 
 fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternError> {
     if depth > MAX_RECURSION_DEPTH {
-        return Err(PatternError::ParseError(
-            "Pattern too complex: recursion depth exceeded".to_string(),
+        return Err(PatternError::parse(
+            "Pattern too complex: recursion depth exceeded",
         ));
     }
 
@@ -1418,7 +3525,8 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
                 // If inner contains captures, let parse_pattern_with_captures handle it
                 if !contains_unescaped_paren(inner) || inner.starts_with("(?") {
                     // Simple capture with no nesting
-                    let inner_ast = parse_pattern_with_depth(inner, depth + 1)?;
+                    let inner_ast =
+                        parse_pattern_with_depth(inner, depth + 1).map_err(|e| e.offset_by(1))?;
                     return Ok(Ast::Capture(Box::new(inner_ast), 1)); // Group 1
                 }
                 // Else: fall through to parse_pattern_with_captures below
@@ -1428,28 +3536,11 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
 
     // Phase 8.1: Check for patterns with embedded captures: Hello (\w+), (\w+)=(\d+)
     // Phase 8.2: Also handles non-capturing groups: (?:Hello) (\w+)
+    // Also quantified groups like (test)?, (?:a|b+)*, whatever their content
     // But skip patterns starting with anchors - they need special handling below
-    // Also skip quantified groups like (test)?, (foo)+, (bar)* - they're handled as quantified patterns
-    let is_quantified_group = pattern.starts_with('(')
-        && if let Some(close_idx) = find_matching_paren(pattern, 0) {
-            close_idx == pattern.len() - 2
-                && (pattern.ends_with('?') || pattern.ends_with('*') || pattern.ends_with('+'))
-        } else {
-            false
-        };
-
-    let is_bounded_quantified_group = pattern.starts_with('(')
-        && if let Some(close_idx) = find_matching_paren(pattern, 0) {
-            close_idx < pattern.len() - 1 && pattern[close_idx + 1..].starts_with('{')
-        } else {
-            false
-        };
-
     if contains_unescaped_paren(pattern)
         && !pattern.starts_with('^')
         && !pattern.ends_with('$')
-        && !is_quantified_group
-        && !is_bounded_quantified_group
         && !pattern.contains("(?=")
         && !pattern.contains("(?!")
         && !pattern.contains("(?<=")
@@ -1470,34 +3561,36 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
         }
     }
 
+    // Check for alternation (|) first: ^a|b$ anchors each branch separately
+    if let Some(parts) = split_by_alternation(pattern) {
+        return parse_alternation(parts, depth);
+    }
+
     // Check for anchors (before sequences)
     let has_start_anchor = pattern.starts_with('^');
-    let has_end_anchor = pattern.ends_with('$');
+    let has_end_anchor = ends_with_end_anchor(pattern);
 
     if has_start_anchor || has_end_anchor {
         // Strip anchors properly - don't fall back to original pattern
-        let mut literal = pattern;
+        let mut inner = pattern;
         if has_start_anchor {
-            literal = literal.strip_prefix('^').unwrap();
+            inner = inner.strip_prefix('^').unwrap();
         }
         if has_end_anchor {
-            literal = literal.strip_suffix('$').unwrap();
+            inner = inner.strip_suffix('$').unwrap();
         }
 
-        // Don't treat anchored patterns as sequences
-        return Ok(Ast::Anchored {
-            literal: literal.to_string(),
+        // The middle is a full sub-pattern (^\d+$, ^foo\d*$); plain text
+        // still compiles to an anchored literal
+        let inner_ast = parse_pattern_with_depth(inner, depth + 1)
+            .map_err(|e| e.offset_by(usize::from(has_start_anchor)))?;
+        return Ok(Ast::AnchoredPattern {
+            inner: Box::new(inner_ast),
             start: has_start_anchor,
             end: has_end_anchor,
         });
     }
 
-    // Check for alternation (|)
-    if pattern.contains('|') && !pattern.contains('[') {
-        let parts: Vec<String> = pattern.split('|').map(|s| s.to_string()).collect();
-        return Ok(Ast::Alternation(parts));
-    }
-
     // Check for sequence pattern (most complex)
     if is_sequence_pattern(pattern) {
         match parse_sequence(pattern) {
@@ -1526,6 +3619,13 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
                     if let Some(ch) = seq.to_char() {
                         return Ok(Ast::Literal(ch.to_string()));
                     }
+                    // \R can span two chars, so it's a one-element sequence
+                    if seq == parser::escape::EscapeSequence::LineBreak {
+                        use crate::parser::sequence::{Sequence, SequenceElement};
+                        return Ok(Ast::Sequence(Sequence::new(vec![
+                            SequenceElement::LineBreak,
+                        ])));
+                    }
                 }
                 // Otherwise, check for quantifier after escape
                 let remaining = &pattern[bytes_consumed..];
@@ -1540,7 +3640,10 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
                     }
                 }
             }
-            Err(e) => return Err(PatternError::ParseError(e)),
+            Err(e) => {
+                let escape_len = pattern.chars().take(2).map(char::len_utf8).sum();
+                return Err(PatternError::parse_at(e, 0, &pattern[..escape_len]));
+            }
         }
     }
 
@@ -1556,7 +3659,7 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
             Ok(qp) => return Ok(Ast::Quantified(qp)),
             Err(_) => {
                 if pattern.contains('{') {
-                    return Err(PatternError::ParseError("Invalid quantifier".to_string()));
+                    return Err(invalid_quantifier_error(pattern));
                 }
                 // Fall through to other parsers for non-brace suffixes.
             }
@@ -1564,7 +3667,7 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
     }
 
     if pattern.contains('{') {
-        return Err(PatternError::ParseError("Invalid quantifier".to_string()));
+        return Err(invalid_quantifier_error(pattern));
     }
 
     // Check for character class [...]
@@ -1573,13 +3676,16 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
         if end_idx == pattern.len() - 1 {
             // Pure character class pattern: [a-z]
             let class_content = &pattern[1..end_idx];
-            let char_class = CharClass::parse(class_content).map_err(PatternError::ParseError)?;
+            let char_class = CharClass::parse(class_content)
+                .map_err(|e| PatternError::parse_at(e, 0, pattern))?;
             return Ok(Ast::CharClass(char_class));
         }
         // Character class with quantifier is handled above
     } else if pattern.starts_with('[') {
-        return Err(PatternError::ParseError(
-            "Unclosed character class".to_string(),
+        return Err(PatternError::parse_at(
+            "Unclosed character class",
+            0,
+            pattern,
         ));
     }
 
@@ -1610,21 +3716,9 @@ fn parse_pattern_with_depth(pattern: &str, depth: usize) -> Result<Ast, PatternE
 }
 
 /// Parse pattern with flags applied
-/// This handles (?i) case-insensitive, (?m) multiline, (?s) dotall flags
+/// (?i) wraps the result in CaseInsensitive; (?s) dots are already rewritten
+/// to a match-all class by the caller
 fn parse_pattern_with_flags(pattern: &str, flags: &Flags) -> Result<Ast, PatternError> {
-    // If dotall flag is set, we need to handle . differently
-    // If case_insensitive is set, wrap result in CaseInsensitive
-
-    if flags.dot_matches_newline {
-        // Parse the pattern with dot matching newlines
-        let ast = parse_pattern_dotall(pattern, flags)?;
-        if flags.case_insensitive {
-            return Ok(Ast::CaseInsensitive(Box::new(ast)));
-        }
-        return Ok(ast);
-    }
-
-    // Parse normally
     let ast = parse_pattern(pattern)?;
     if flags.case_insensitive {
         return Ok(Ast::CaseInsensitive(Box::new(ast)));
@@ -1632,50 +3726,6 @@ fn parse_pattern_with_flags(pattern: &str, flags: &Flags) -> Result<Ast, Pattern
     Ok(ast)
 }
 
-/// Parse pattern with DOTALL mode: . matches newlines
-fn parse_pattern_dotall(pattern: &str, flags: &Flags) -> Result<Ast, PatternError> {
-    if pattern.is_empty() {
-        return Ok(Ast::Literal(String::new()));
-    }
-
-    // Check for single dot wildcard
-    if pattern == "." {
-        return Ok(Ast::DotAll);
-    }
-
-    // Check if pattern contains dots - needs sequence parsing with DotAll
-    if pattern.contains('.') {
-        // Check if this is a sequence pattern
-        if is_sequence_pattern(pattern) {
-            // Parse the sequence and apply DOTALL flag
-            match parse_sequence(pattern) {
-                Ok(seq) => return Ok(Ast::SequenceWithFlags(seq, *flags)),
-                Err(_) => {
-                    // Fall through to other parsers
-                }
-            }
-        }
-
-        // Pattern like "a.c" needs to be parsed as sequence with dot wildcard
-        use crate::parser::sequence::{Sequence, SequenceElement};
-        let mut elements = Vec::new();
-
-        for ch in pattern.chars() {
-            if ch == '.' {
-                // Use DotAll element (will be handled by SequenceWithFlags)
-                elements.push(SequenceElement::Dot);
-            } else {
-                elements.push(SequenceElement::Char(ch));
-            }
-        }
-
-        return Ok(Ast::SequenceWithFlags(Sequence::new(elements), *flags));
-    }
-
-    // For non-dot patterns, delegate to normal parsing
-    parse_pattern(pattern)
-}
-
 /// Parse patterns with captures and flags
 fn parse_pattern_with_captures_with_flags(
     pattern: &str,
@@ -1689,15 +3739,53 @@ fn parse_pattern_with_captures_with_flags(
         return Ok(Ast::CaseInsensitive(Box::new(ast)));
     }
 
-    // If DOTALL flag is set and the pattern has sequences, we need special handling
-    // For now, return as-is - full support requires more work
     Ok(ast)
 }
 
+/// A literal together with its substring searcher, built once when the
+/// pattern compiles rather than on every search
+#[derive(Clone)]
+struct LiteralMatcher {
+    literal: String,
+    finder: memmem::Finder<'static>,
+}
+
+impl LiteralMatcher {
+    fn new(literal: String) -> Self {
+        let finder = memmem::Finder::new(literal.as_bytes()).into_owned();
+        LiteralMatcher { literal, finder }
+    }
+
+    /// Start of the first occurrence in `text`
+    fn find(&self, text: &str) -> Option<usize> {
+        self.finder.find(text.as_bytes())
+    }
+}
+
+impl core::ops::Deref for LiteralMatcher {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.literal
+    }
+}
+
+impl core::fmt::Debug for LiteralMatcher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.literal.fmt(f)
+    }
+}
+
 #[derive(Debug, Clone)]
 enum Matcher {
-    Literal(String),
-    MultiLiteral(AhoCorasick),
+    Literal(LiteralMatcher),
+    /// Literal alternation; under (?i), `searcher` folds ASCII case itself
+    MultiLiteral {
+        searcher: LiteralSearcher,
+        /// The alternatives, for whole-text checks
+        literals: Vec<String>,
+        ascii_case_insensitive: bool,
+    },
     AnchoredLiteral {
         literal: String,
         start: bool,
@@ -1712,11 +3800,34 @@ enum Matcher {
         inner: Box<Matcher>,
         start: bool,
         end: bool,
+        /// `$` only: the pattern read backward with its groups ignored, to
+        /// find where the match starts in one pass
+        reverse: Option<engine::lazy_dfa::LazyDFA>,
+    },
+    /// `$`-anchored sequence: `suffix` must end the text, and `reverse` reads
+    /// the rest backward from there to find the leftmost start
+    SuffixAnchored {
+        suffix: String,
+        reverse: engine::lazy_dfa::LazyDFA,
     },
+    /// (?m) anchors: `line` is anchored to the whole text and runs on each
+    /// line, split at `terminator` (or at `\r`/`\n` in CRLF mode)
+    LineAnchored {
+        line: Box<Pattern>,
+        start: bool,
+        crlf: bool,
+        terminator: u8,
+    },
+    /// `\G` at the start: `inner` only counts when its match begins where
+    /// the search does
+    ContinueAnchored(Box<Pattern>),
+    /// `$` outside multi-line mode: `inner` (compiled with a strict `$`)
+    /// runs on the text and on the text without its final `\n`, and the
+    /// leftmost match wins
+    FinalNewlineEnd(Box<Pattern>),
     CharClass(CharClass),
     Quantified(QuantifiedPattern),
     Sequence(Sequence),
-    SequenceWithFlags(Sequence, Flags), // Sequence with flags (e.g., DOTALL)
     Group(Group),
     DigitRun,                                  // Specialized fast path for \d+ pattern
     WordRun,                                   // Specialized fast path for \w+ pattern
@@ -1756,11 +3867,70 @@ enum CompiledCaptureElement {
     NonCapture(Matcher),     // Compiled matcher (non-capturing)
 }
 
+/// What a match of a capture element list starts with, to jump straight to
+/// the positions worth trying instead of every offset
+enum StartFilter<'m> {
+    /// Nothing known: every position is a candidate
+    Any,
+    /// The first element is this (non-empty) literal
+    Literal(&'m str),
+    /// The first char is one of the first `n` of these ASCII bytes
+    Bytes([u8; 3], usize),
+}
+
+impl StartFilter<'_> {
+    /// First candidate start at or after `from`
+    fn next(&self, text: &str, from: usize) -> Option<usize> {
+        let rest = text.as_bytes().get(from..)?;
+        let found = match *self {
+            StartFilter::Any => Some(0),
+            StartFilter::Literal(lit) => memmem::find(rest, lit.as_bytes()),
+            StartFilter::Bytes([a, _, _], 1) => memchr::memchr(a, rest),
+            StartFilter::Bytes([a, b, _], 2) => memchr::memchr2(a, b, rest),
+            StartFilter::Bytes([a, b, c], _) => memchr::memchr3(a, b, c, rest),
+        };
+        found.map(|at| from + at)
+    }
+}
+
 impl Matcher {
+    /// Variant name, as reported by `Pattern::explain`
+    fn name(&self) -> &'static str {
+        match self {
+            Matcher::Literal(_) => "Literal",
+            Matcher::MultiLiteral { .. } => "MultiLiteral",
+            Matcher::AnchoredLiteral { .. } => "AnchoredLiteral",
+            Matcher::AnchoredGroup { .. } => "AnchoredGroup",
+            Matcher::AnchoredPattern { .. } => "AnchoredPattern",
+            Matcher::SuffixAnchored { .. } => "SuffixAnchored",
+            Matcher::LineAnchored { .. } => "LineAnchored",
+            Matcher::ContinueAnchored(_) => "ContinueAnchored",
+            Matcher::FinalNewlineEnd(_) => "FinalNewlineEnd",
+            Matcher::CharClass(_) => "CharClass",
+            Matcher::Quantified(_) => "Quantified",
+            Matcher::Sequence(_) => "Sequence",
+            Matcher::Group(_) => "Group",
+            Matcher::DigitRun => "DigitRun",
+            Matcher::WordRun => "WordRun",
+            Matcher::Boundary(_) => "Boundary",
+            Matcher::Lookaround(..) => "Lookaround",
+            Matcher::Capture(..) => "Capture",
+            Matcher::QuantifiedCapture(..) => "QuantifiedCapture",
+            Matcher::CombinedWithLookaround { .. } => "CombinedWithLookaround",
+            Matcher::LookbehindWithSuffix { .. } => "LookbehindWithSuffix",
+            Matcher::PatternWithCaptures { .. } => "PatternWithCaptures",
+            Matcher::AlternationWithCaptures { .. } => "AlternationWithCaptures",
+            Matcher::Backreference(_) => "Backreference",
+            Matcher::DFA(_) => "DFA",
+            Matcher::LazyDFA(_) => "LazyDFA",
+            Matcher::CaseInsensitive(_) => "CaseInsensitive",
+        }
+    }
+
     fn is_match(&self, text: &str) -> bool {
         match self {
-            Matcher::Literal(lit) => memmem::find(text.as_bytes(), lit.as_bytes()).is_some(),
-            Matcher::MultiLiteral(ac) => ac.is_match(text),
+            Matcher::Literal(lit) => lit.find(text).is_some(),
+            Matcher::MultiLiteral { searcher, .. } => searcher.is_match(text),
             Matcher::AnchoredLiteral {
                 literal,
                 start,
@@ -1775,11 +3945,8 @@ impl Matcher {
                 // Check if group matches with anchor constraints
                 match (start, end) {
                     (true, true) => {
-                        // Must match entire text
-                        group
-                            .match_at(text, 0)
-                            .map(|len| len == text.len())
-                            .unwrap_or(false)
+                        // Must match entire text (any branch may cover it)
+                        group.matches_to_end(text, 0)
                     }
                     (true, false) => {
                         // Must match at start
@@ -1787,58 +3954,53 @@ impl Matcher {
                     }
                     (false, true) => {
                         // Must match at end
-                        if let Some((_start_pos, end_pos)) = group.find(text) {
-                            end_pos == text.len()
-                        } else {
-                            false
-                        }
+                        group.find_at_end(text).is_some()
                     }
                     _ => unreachable!(),
                 }
             }
-            Matcher::AnchoredPattern { inner, start, end } => {
+            Matcher::AnchoredPattern {
+                inner,
+                start,
+                end,
+                reverse,
+            } => {
                 // Check if inner pattern matches with anchor constraints
-                match (start, end) {
-                    (true, true) => {
-                        // Must match entire text
-                        if let Some((match_start, match_end)) = inner.find(text) {
-                            match_start == 0 && match_end == text.len()
-                        } else {
-                            false
-                        }
-                    }
-                    (true, false) => {
-                        // Must match at start
-                        if let Some((match_start, _)) = inner.find(text) {
-                            match_start == 0
-                        } else {
-                            false
-                        }
-                    }
-                    (false, true) => {
-                        // Must match at end
-                        if let Some((_, match_end)) = inner.find(text) {
-                            match_end == text.len()
-                        } else {
-                            false
-                        }
-                    }
-                    _ => unreachable!(),
-                }
+                Self::anchored_find(inner, reverse.as_ref(), text, *start, *end).is_some()
+            }
+            Matcher::SuffixAnchored { suffix, reverse } => {
+                Self::suffix_anchored_find(suffix, reverse, text).is_some()
+            }
+            Matcher::LineAnchored {
+                line,
+                start,
+                crlf,
+                terminator,
+            } => Self::line_anchored_find(line, text, 0, *start, *crlf, *terminator).is_some(),
+            Matcher::ContinueAnchored(inner) => {
+                inner.find(text).is_some_and(|(start, _)| start == 0)
             }
+            Matcher::FinalNewlineEnd(inner) => Self::final_newline_find(inner, text, 0).is_some(),
             Matcher::CharClass(cc) => {
                 // OPTIMIZED: Use SIMD-friendly find_first for ASCII text
                 cc.find_first(text).is_some()
             }
             Matcher::Quantified(qp) => {
                 if let crate::parser::quantifier::QuantifiedElement::CharClass(cc) = &qp.element {
-                    if let Some(bitmap) = cc.get_ascii_bitmap() {
+                    // Byte scan only sees ASCII, so skip it for classes reaching beyond
+                    let bitmap = cc
+                        .get_ascii_bitmap()
+                        .filter(|_| !cc.has_non_ascii_members());
+                    if let Some(bitmap) = bitmap {
                         let negated = cc.negated;
                         let min = qp.quantifier.min_matches();
                         let bytes = text.as_bytes();
 
-                        if min <= 1 {
-                            // For +, *, ?, {0,N}, {1,N} - just find one matching byte
+                        if min == 0 {
+                            // Matches empty, even with no matching byte
+                            return true;
+                        } else if min == 1 {
+                            // For + and {1,N} - just find one matching byte
                             for &byte in bytes {
                                 if byte < 128 {
                                     let idx = byte as usize;
@@ -1878,13 +4040,8 @@ impl Matcher {
             Matcher::WordRun => Self::word_run_is_match(text), // NEW: Specialized word fast path
             Matcher::Boundary(boundary_type) => boundary_type.find_first(text).is_some(),
             Matcher::Lookaround(lookaround, inner_matcher) => {
-                // Lookaround assertions are zero-width, check if they match at any position
-                for pos in 0..=text.len() {
-                    if lookaround.matches_at(text, pos, inner_matcher) {
-                        return true;
-                    }
-                }
-                false
+                // Lookaround assertions are zero-width: any position will do
+                lookaround.find_from(text, 0, inner_matcher).is_some()
             }
             Matcher::Capture(inner_matcher, _group_index) => {
                 // Capture groups don't affect matching, just check inner pattern
@@ -1894,32 +4051,10 @@ impl Matcher {
                 // Quantified capture - match inner pattern with quantifier semantics
                 Self::quantified_is_match(text, inner_matcher, quantifier)
             }
-            Matcher::CombinedWithLookaround {
-                prefix,
-                lookaround,
-                lookaround_matcher,
-            } => {
-                // Need to find where prefix matches, then check lookaround at that position
-                if let Some((_start, end)) = prefix.find(text) {
-                    // Check if lookaround succeeds at the end position of the prefix match
-                    lookaround.matches_at(text, end, lookaround_matcher)
-                } else {
-                    false
-                }
-            }
-            Matcher::LookbehindWithSuffix {
-                lookbehind,
-                lookbehind_matcher,
-                suffix,
-            } => {
-                // Match suffix anywhere in text, then check if lookbehind matches at that position
-                // Try to find suffix match
-                if let Some((start, _end)) = suffix.find(text) {
-                    // Check if lookbehind succeeds at the start position of the suffix match
-                    lookbehind.matches_at(text, start, lookbehind_matcher)
-                } else {
-                    false
-                }
+            Matcher::CombinedWithLookaround { .. } | Matcher::LookbehindWithSuffix { .. } => {
+                // The first prefix (or suffix) match may fail the assertion, like
+                // an empty a* at 0 in a*(?=b), while a later one passes it
+                self.find(text).is_some()
             }
             Matcher::PatternWithCaptures { .. } => {
                 // OPTIMIZATION: Delegate to find() for simplicity
@@ -1936,13 +4071,8 @@ impl Matcher {
                 dfa.is_match(text)
             }
             Matcher::LazyDFA(lazy_dfa) => {
-                // Lazy DFA is mutable, clone for is_match
-                let mut dfa = lazy_dfa.clone();
-                dfa.find(text).is_some()
-            }
-            Matcher::SequenceWithFlags(seq, flags) => {
-                // Sequence matching with flags (e.g., DOTALL)
-                seq.is_match_with_flags(text, flags)
+                // Lazy DFA - states built on demand
+                lazy_dfa.find(text).is_some()
             }
             Matcher::AlternationWithCaptures { branches, .. } => {
                 // Try each branch - return true if ANY branch matches
@@ -1996,7 +4126,7 @@ impl Matcher {
                     }
                 }
                 // Fast path: alternation of literals
-                if let Matcher::MultiLiteral(ac) = inner.as_ref() {
+                if let Matcher::MultiLiteral { searcher, .. } = inner.as_ref() {
                     let bytes = text.as_bytes();
                     let len = bytes.len();
                     if len <= 256 {
@@ -2011,8 +4141,8 @@ impl Matcher {
                             buf[i] = if b >= b'A' && b <= b'Z' { b + 32 } else { b };
                         }
                         if all_ascii {
-                            let lower = unsafe { std::str::from_utf8_unchecked(&buf[..len]) };
-                            return ac.is_match(lower);
+                            let lower = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
+                            return searcher.is_match(lower);
                         }
                     }
                 }
@@ -2031,7 +4161,7 @@ impl Matcher {
                         buf[i] = if b >= b'A' && b <= b'Z' { b + 32 } else { b };
                     }
                     if all_ascii {
-                        let lower = unsafe { std::str::from_utf8_unchecked(&buf[..len]) };
+                        let lower = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
                         return inner.is_match(lower);
                     }
                 }
@@ -2041,172 +4171,361 @@ impl Matcher {
         }
     }
 
-    /// Recursively extract all nested captures from a matched pattern
-    /// Returns Vec<(group_num, start, end)> for all capture groups found
-    fn extract_nested_captures(&self, text: &str, start_pos: usize) -> Vec<(usize, usize, usize)> {
-        let mut captures = Vec::new();
+    /// Leftmost match of any of `branches`, for a single search; see
+    /// [`BranchSearch`] for the tie-breaking
+    fn leftmost_branch_find(
+        branches: &[Matcher],
+        text: &str,
+        from: usize,
+    ) -> Option<(usize, usize)> {
+        BranchSearch::new(branches).find_from(text, from)
+    }
+
+    /// Index of the first branch matching at `start_pos`: the one a
+    /// leftmost-first search commits to when its match starts there
+    fn winning_branch(branches: &[Matcher], text: &str, start_pos: usize) -> Option<usize> {
+        branches
+            .iter()
+            .position(|branch| matches!(branch.find_from(text, start_pos), Some((start, _)) if start == start_pos))
+    }
+
+    /// First match at or after `from`, with offsets into `text`
+    ///
+    /// Anchors, boundaries and lookbehinds look at the text before `from`, so
+    /// `\bcat` doesn't match right after an earlier "cat". Searching the
+    /// rest of the text is enough for everything else but a `^` anchor,
+    /// which can only match when the search starts at 0.
+    fn find_from(&self, text: &str, from: usize) -> Option<(usize, usize)> {
+        match self {
+            Matcher::LineAnchored {
+                line,
+                start,
+                crlf,
+                terminator,
+            } => Self::line_anchored_find(line, text, from, *start, *crlf, *terminator),
+            Matcher::ContinueAnchored(inner) => Self::continue_anchored_find(inner, text, from),
+            Matcher::FinalNewlineEnd(inner) => Self::final_newline_find(inner, text, from),
+            Matcher::AnchoredLiteral { .. }
+            | Matcher::AnchoredGroup { .. }
+            | Matcher::AnchoredPattern { .. } => {
+                self.find(text).filter(|&(start, _)| start >= from)
+            }
+            Matcher::AlternationWithCaptures { branches, .. } => {
+                Self::leftmost_branch_find(branches, text, from)
+            }
+            Matcher::Capture(inner, _) => inner.find_from(text, from),
+            Matcher::PatternWithCaptures { elements, .. } if elements.len() == 1 => {
+                match &elements[0] {
+                    CompiledCaptureElement::Capture(m, _)
+                    | CompiledCaptureElement::NonCapture(m) => m.find_from(text, from),
+                }
+            }
+            Matcher::Boundary(boundary) => boundary.find_from(text, from).map(|at| (at, at)),
+            Matcher::Sequence(seq) if seq.has_boundary() => seq.find_at(text, from),
+            Matcher::Lookaround(lookaround, inner) => {
+                lookaround.find_from(text, from, inner).map(|at| (at, at))
+            }
+            Matcher::LookbehindWithSuffix {
+                lookbehind,
+                lookbehind_matcher,
+                suffix,
+            } => Self::lookbehind_suffix_find(lookbehind, lookbehind_matcher, suffix, text, from),
+            _ => {
+                if from > 0 && self.is_start_anchored() {
+                    return None;
+                }
+                let (start, end) = self.find(safe_slice(text, from)?)?;
+                Some((from + start, from + end))
+            }
+        }
+    }
+
+    /// Whether every match must start at a `^` anchor
+    fn is_start_anchored(&self) -> bool {
+        match self {
+            Matcher::AnchoredLiteral { start, .. }
+            | Matcher::AnchoredGroup { start, .. }
+            | Matcher::AnchoredPattern { start, .. } => *start,
+            Matcher::PatternWithCaptures { elements, .. } => {
+                elements.first().is_some_and(|element| match element {
+                    CompiledCaptureElement::Capture(m, _)
+                    | CompiledCaptureElement::NonCapture(m) => m.is_start_anchored(),
+                })
+            }
+            Matcher::Capture(inner, _) => inner.is_start_anchored(),
+            _ => false,
+        }
+    }
+
+    /// Groups set by a match of `self` spanning exactly `start..end`
+    ///
+    /// Alternatives and quantifiers are retried in leftmost-first order until
+    /// one path ends at `end`, so nested groups agree with the reported span.
+    fn captures_in_span(&self, text: &str, start: usize, end: usize) -> Vec<(usize, usize, usize)> {
+        let mut found = Vec::new();
+        Self::match_matcher_then(self, text, start, &mut Vec::new(), &mut |reached, caps| {
+            if reached != end {
+                return false;
+            }
+            found = core::mem::take(caps);
+            true
+        });
+        found
+    }
+
+    /// Match pattern with backreferences, tracking captures as we go
+    /// Returns Some((end_pos, groups)) if match succeeds, None otherwise
+    ///
+    /// Elements are matched left to right with the capture state threaded
+    /// through, backtracking over the lengths of quantified elements, so `\N`
+    /// always compares against what group N captured on the current path.
+    fn match_pattern_with_backreferences(
+        text: &str,
+        start_pos: usize,
+        elements: &[CompiledCaptureElement],
+    ) -> Option<(usize, Vec<Option<(usize, usize)>>)> {
+        let num_groups = elements
+            .iter()
+            .map(|element| match element {
+                CompiledCaptureElement::Capture(_, num) => *num,
+                CompiledCaptureElement::NonCapture(_) => 0,
+            })
+            .max()
+            .unwrap_or(0);
+        let mut groups = vec![None; num_groups];
+        let end_pos = Self::match_backref_elements(text, start_pos, elements, &mut groups)?;
+        Some((end_pos, groups))
+    }
+
+    fn match_backref_elements(
+        text: &str,
+        pos: usize,
+        elements: &[CompiledCaptureElement],
+        groups: &mut Vec<Option<(usize, usize)>>,
+    ) -> Option<usize> {
+        let Some((first, rest)) = elements.split_first() else {
+            return Some(pos);
+        };
+        let (matcher, group_num) = match first {
+            CompiledCaptureElement::Capture(m, num) => (m, Some(*num)),
+            CompiledCaptureElement::NonCapture(m) => (m, None),
+        };
+
+        if let Matcher::Backreference(ref_num) = matcher {
+            // A group that hasn't participated in the match can't be referenced
+            let (cap_start, cap_end) = (*groups.get(ref_num.checked_sub(1)?)?)?;
+            let captured_text = &text[cap_start..cap_end];
+            if !safe_slice(text, pos)?.starts_with(captured_text) {
+                return None;
+            }
+            return Self::match_backref_elements(text, pos + captured_text.len(), rest, groups);
+        }
+
+        for end_pos in Self::candidate_ends(matcher, text, pos) {
+            let saved = group_num.map(|num| groups[num - 1]);
+            if let Some(num) = group_num {
+                groups[num - 1] = Some((pos, end_pos));
+            }
+            if let Some(final_pos) = Self::match_backref_elements(text, end_pos, rest, groups) {
+                return Some(final_pos);
+            }
+            if let (Some(num), Some(previous)) = (group_num, saved) {
+                groups[num - 1] = previous;
+            }
+        }
+        None
+    }
+
+    /// End positions `matcher` can reach from `pos`, in preference order
+    /// (longest first for greedy elements, shortest first for lazy ones)
+    fn candidate_ends(matcher: &Matcher, text: &str, pos: usize) -> Vec<usize> {
+        let Some(remaining) = safe_slice(text, pos) else {
+            return Vec::new();
+        };
+
+        let run = Self::unwrap_single_element(matcher);
+        let is_run = matches!(
+            run,
+            Matcher::Quantified(_) | Matcher::WordRun | Matcher::DigitRun
+        );
+        if !is_run && !Self::contains_quantified(matcher) {
+            // Fixed element: only its own match at this position
+            return match matcher.find(remaining) {
+                Some((0, rel_end)) => vec![pos + rel_end],
+                _ => Vec::new(),
+            };
+        }
+
+        // A run can only shrink from its greedy match, so that bounds the search
+        let limit = if is_run {
+            match run.find(remaining) {
+                Some((0, rel_end)) => rel_end,
+                _ => 0,
+            }
+        } else {
+            remaining.len()
+        };
+
+        Self::backtracking_lengths(
+            &remaining[..limit],
+            Self::prefers_lazy_backtracking(matcher),
+        )
+        .into_iter()
+        .filter(|&len| Self::matches_entire(matcher, &remaining[..len]))
+        .map(|len| pos + len)
+        .collect()
+    }
+
+    /// Look through capture wrappers around a single element
+    fn unwrap_single_element(matcher: &Matcher) -> &Matcher {
+        match matcher {
+            Matcher::Capture(inner, _) => Self::unwrap_single_element(inner),
+            Matcher::PatternWithCaptures { elements, .. } if elements.len() == 1 => {
+                match &elements[0] {
+                    CompiledCaptureElement::Capture(m, _)
+                    | CompiledCaptureElement::NonCapture(m) => Self::unwrap_single_element(m),
+                }
+            }
+            _ => matcher,
+        }
+    }
+
+    /// Highest capture group number in this matcher tree (0 without groups)
+    fn group_count(&self) -> usize {
+        match self {
+            Matcher::Capture(inner, group_index) => (*group_index).max(inner.group_count()),
+            Matcher::QuantifiedCapture(inner, _)
+            | Matcher::CaseInsensitive(inner)
+            | Matcher::AnchoredPattern { inner, .. }
+            | Matcher::Lookaround(_, inner) => inner.group_count(),
+            Matcher::LineAnchored { line, .. } => line.matcher.group_count(),
+            Matcher::ContinueAnchored(inner) => inner.matcher.group_count(),
+            Matcher::FinalNewlineEnd(inner) => inner.matcher.group_count(),
+            Matcher::PatternWithCaptures {
+                elements,
+                total_groups,
+            } => elements
+                .iter()
+                .map(|element| match element {
+                    CompiledCaptureElement::Capture(m, group_index) => {
+                        (*group_index).max(m.group_count())
+                    }
+                    CompiledCaptureElement::NonCapture(m) => m.group_count(),
+                })
+                .fold(*total_groups, usize::max),
+            Matcher::AlternationWithCaptures {
+                branches,
+                total_groups,
+            } => branches
+                .iter()
+                .map(Matcher::group_count)
+                .fold(*total_groups, usize::max),
+            Matcher::CombinedWithLookaround {
+                prefix,
+                lookaround_matcher,
+                ..
+            } => prefix.group_count().max(lookaround_matcher.group_count()),
+            Matcher::LookbehindWithSuffix {
+                lookbehind_matcher,
+                suffix,
+                ..
+            } => lookbehind_matcher.group_count().max(suffix.group_count()),
+            _ => 0,
+        }
+    }
+
+    /// Where matches of an element list can start, from its first element
+    fn capture_start_filter(elements: &[CompiledCaptureElement]) -> StartFilter<'_> {
+        match elements.first() {
+            Some(CompiledCaptureElement::Capture(first, _))
+            | Some(CompiledCaptureElement::NonCapture(first)) => Self::start_filter(first),
+            None => StartFilter::Any,
+        }
+    }
+
+    /// Where matches of `self` can start: at its leading literal, or at one
+    /// of up to three ASCII bytes its first char can be
+    fn start_filter(&self) -> StartFilter<'_> {
+        use parser::sequence::SequenceElement;
 
         match self {
+            Matcher::Capture(inner, _) => return inner.start_filter(),
             Matcher::PatternWithCaptures { elements, .. } => {
-                let mut pos = start_pos;
-
-                for element in elements {
-                    match element {
-                        CompiledCaptureElement::Capture(inner_matcher, group_num) => {
-                            if let Some((rel_start, rel_end)) =
-                                inner_matcher.find(safe_slice(text, pos).unwrap_or(""))
-                            {
-                                if rel_start == 0 {
-                                    let abs_start = pos;
-                                    let abs_end = pos + rel_end;
-
-                                    // Record this capture
-                                    captures.push((*group_num, abs_start, abs_end));
-
-                                    // Recursively extract nested captures
-                                    let nested =
-                                        inner_matcher.extract_nested_captures(text, abs_start);
-                                    captures.extend(nested);
-
-                                    pos = abs_end;
-                                } else {
-                                    break;
-                                }
-                            } else {
-                                break;
-                            }
-                        }
-                        CompiledCaptureElement::NonCapture(inner_matcher) => {
-                            if let Some((rel_start, rel_end)) =
-                                inner_matcher.find(safe_slice(text, pos).unwrap_or(""))
-                            {
-                                if rel_start == 0 {
-                                    let abs_start = pos;
-
-                                    // Even for non-capturing, extract nested captures
-                                    let nested =
-                                        inner_matcher.extract_nested_captures(text, abs_start);
-                                    captures.extend(nested);
-
-                                    pos += rel_end;
-                                } else {
-                                    break;
-                                }
-                            } else {
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
-            Matcher::Capture(inner_matcher, group_num) => {
-                // This is a single capture - record it and check for nested
-                if let Some((rel_start, rel_end)) =
-                    inner_matcher.find(safe_slice(text, start_pos).unwrap_or(""))
-                {
-                    let abs_start = start_pos + rel_start;
-                    let abs_end = start_pos + rel_end;
-
-                    // Record this capture
-                    captures.push((*group_num, abs_start, abs_end));
-
-                    // Recursively extract nested captures
-                    let nested = inner_matcher.extract_nested_captures(text, abs_start);
-                    captures.extend(nested);
-                }
+                return Self::capture_start_filter(elements)
             }
-            Matcher::AlternationWithCaptures { branches, .. } => {
-                // Try each branch to find which one matched
-                for branch in branches {
-                    if let Some((rel_start, _rel_end)) =
-                        branch.find(safe_slice(text, start_pos).unwrap_or(""))
-                    {
-                        if rel_start == 0 {
-                            let abs_start = start_pos;
-                            // Extract captures from the matched branch
-                            let nested = branch.extract_nested_captures(text, abs_start);
-                            captures.extend(nested);
-                            break; // Only one branch can match
-                        }
+            Matcher::Literal(lit) if !lit.is_empty() => return StartFilter::Literal(lit),
+            Matcher::Sequence(seq) => {
+                if let Some(SequenceElement::Literal(lit)) = seq.elements.first() {
+                    if !lit.is_empty() {
+                        return StartFilter::Literal(lit);
                     }
                 }
             }
-            _ => {
-                // Other matchers don't have nested captures
-            }
+            _ => {}
         }
 
-        captures
+        let Some(mut first_bytes) = self.first_bytes() else {
+            return StartFilter::Any;
+        };
+        first_bytes.sort_unstable();
+        first_bytes.dedup();
+        match first_bytes[..] {
+            [a] => StartFilter::Bytes([a; 3], 1),
+            [a, b] => StartFilter::Bytes([a, b, b], 2),
+            [a, b, c] => StartFilter::Bytes([a, b, c], 3),
+            _ => StartFilter::Any,
+        }
     }
 
-    /// Match pattern with backreferences, tracking captures as we go
-    /// Returns Some(end_pos) if match succeeds, None otherwise
-    fn match_pattern_with_backreferences(
-        text: &str,
-        start_pos: usize,
-        elements: &[CompiledCaptureElement],
-    ) -> Option<usize> {
-        let mut pos = start_pos;
-        let mut capture_positions: Vec<(usize, usize)> = Vec::new();
-
-        for element in elements {
-            match element {
-                CompiledCaptureElement::Capture(m, num) => {
-                    if let Some((rel_start, rel_end)) = m.find(safe_slice(text, pos).unwrap_or(""))
-                    {
-                        if rel_start != 0 {
-                            return None; // Must match at current position
-                        }
+    /// The bytes every match of `self` starts with one of, when all of them
+    /// are ASCII
+    fn first_bytes(&self) -> Option<Vec<u8>> {
+        use parser::sequence::SequenceElement;
 
-                        let abs_start = pos;
-                        let abs_end = pos + rel_end;
+        let ascii_class = |cc: &CharClass| {
+            (!cc.negated && !cc.has_non_ascii_members())
+                .then(|| (0..128u8).filter(|&b| cc.matches(b as char)).collect())
+        };
+        let first_ascii = |lit: &str| lit.bytes().next().filter(u8::is_ascii);
 
-                        // Record capture position
-                        while capture_positions.len() < *num {
-                            capture_positions.push((0, 0));
-                        }
-                        capture_positions[*num - 1] = (abs_start, abs_end);
-                        pos = abs_end;
-                    } else {
-                        return None;
-                    }
-                }
-                CompiledCaptureElement::NonCapture(m) => {
-                    // Check if this is a Backreference
-                    if let Matcher::Backreference(ref_num) = m {
-                        // Get the captured text for this backreference
-                        if *ref_num > 0 && *ref_num <= capture_positions.len() {
-                            let (cap_start, cap_end) = capture_positions[*ref_num - 1];
-                            let captured_text = &text[cap_start..cap_end];
-
-                            // Check if remaining text starts with the captured text
-                            if text[pos..].starts_with(captured_text) {
-                                pos += captured_text.len();
-                            } else {
-                                return None;
-                            }
-                        } else {
-                            // Invalid backreference or not captured yet
-                            return None;
-                        }
-                    } else {
-                        // Normal non-capture element
-                        if let Some((rel_start, rel_end)) =
-                            m.find(safe_slice(text, pos).unwrap_or(""))
-                        {
-                            if rel_start != 0 {
-                                return None;
-                            }
-                            pos += rel_end;
-                        } else {
-                            return None;
-                        }
-                    }
+        match self {
+            Matcher::Capture(inner, _) => inner.first_bytes(),
+            Matcher::PatternWithCaptures { elements, .. } => match elements.first()? {
+                CompiledCaptureElement::Capture(first, _)
+                | CompiledCaptureElement::NonCapture(first) => first.first_bytes(),
+            },
+            Matcher::Literal(lit) => first_ascii(lit).map(|b| vec![b]),
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive: false,
+                ..
+            } => literals.iter().map(|lit| first_ascii(lit)).collect(),
+            Matcher::CharClass(cc) => ascii_class(cc),
+            Matcher::Sequence(seq) => match seq.elements.first()? {
+                SequenceElement::Char(ch) => ch.is_ascii().then(|| vec![*ch as u8]),
+                SequenceElement::Literal(lit) => first_ascii(lit).map(|b| vec![b]),
+                SequenceElement::CharClass(cc) => ascii_class(cc),
+                _ => None,
+            },
+            Matcher::AlternationWithCaptures { branches, .. } => {
+                let mut bytes = Vec::new();
+                for branch in branches {
+                    bytes.extend(branch.first_bytes()?);
                 }
+                Some(bytes)
             }
+            _ => None,
         }
+    }
 
-        Some(pos)
+    /// Check whether an element list refers back to a capture group
+    fn has_backreferences(elements: &[CompiledCaptureElement]) -> bool {
+        elements.iter().any(|elem| {
+            matches!(
+                elem,
+                CompiledCaptureElement::NonCapture(Matcher::Backreference(_))
+            )
+        })
     }
 
     /// Specialized fast path for \d+ pattern
@@ -2250,43 +4569,96 @@ impl Matcher {
         inner_matcher: &Matcher,
         quantifier: &parser::quantifier::Quantifier,
     ) -> Option<(usize, usize)> {
+        let mut found = None;
+        Self::quantified_scan(text, inner_matcher, quantifier, |start, end| {
+            found = Some((start, end));
+            false
+        });
+        found
+    }
+
+    /// Single left-to-right scan for quantified capture matches
+    ///
+    /// Candidate starts come from the leftmost inner match, and the lookahead
+    /// match computed while extending a repetition is reused as the next
+    /// candidate, so the text is not rescanned from every position.
+    /// `on_match` returns false to stop the scan.
+    fn quantified_scan(
+        text: &str,
+        inner_matcher: &Matcher,
+        quantifier: &parser::quantifier::Quantifier,
+        mut on_match: impl FnMut(usize, usize) -> bool,
+    ) {
         let (min, max) = quantifier_bounds(quantifier);
 
-        // Special case: empty text can match if min is 0
-        if text.is_empty() {
-            return if min == 0 { Some((0, 0)) } else { None };
-        }
+        // Leftmost inner match at or after `from` (absolute positions)
+        let next_inner = |from: usize| -> Option<(usize, usize)> {
+            let (rel_start, rel_end) = inner_matcher.find(safe_slice(text, from)?)?;
+            Some((from + rel_start, from + rel_end))
+        };
 
-        // Try to match at each position in text
-        for start_pos in 0..text.len() {
-            let mut pos = start_pos;
-            let mut count = 0;
+        let mut search_pos = 0;
+        // Cached lookahead: (searched_from, leftmost inner match from there)
+        let mut lookahead: Option<(usize, Option<(usize, usize)>)> = None;
 
-            // Match inner pattern as many times as possible (greedy)
-            while count < max && pos < text.len() {
-                if let Some((rel_start, rel_end)) =
-                    inner_matcher.find(safe_slice(text, pos).unwrap_or(""))
+        while search_pos <= text.len() {
+            let candidate = match lookahead {
+                Some((from, cand))
+                    if from <= search_pos && cand.map_or(true, |(s, _)| s >= search_pos) =>
                 {
-                    // Must match at current position
-                    if rel_start != 0 {
-                        break;
+                    cand
+                }
+                _ => next_inner(search_pos),
+            };
+
+            let start = if min == 0 {
+                search_pos
+            } else {
+                match candidate {
+                    Some((s, _)) => s,
+                    None => return,
+                }
+            };
+
+            // Match inner pattern as many times as possible (greedy), or
+            // only as many as required (lazy)
+            let mut pos = start;
+            let mut count = 0;
+            let mut current = candidate;
+            let wanted = if quantifier.is_lazy() { min } else { max };
+            while count < wanted {
+                match current {
+                    Some((s, e)) if s == pos && e > s => {
+                        pos = e;
+                        count += 1;
+                        current = next_inner(pos);
                     }
-                    if rel_end == 0 {
-                        break; // Avoid infinite loops on zero-width matches
+                    // An empty iteration can repeat as often as still required
+                    Some((s, _)) if s == pos => {
+                        count = count.max(min);
+                        break;
                     }
-                    pos += rel_end;
-                    count += 1;
-                } else {
-                    break;
+                    _ => break,
                 }
             }
+            lookahead = Some((pos, current));
 
             if count >= min {
-                return Some((start_pos, pos));
+                if !on_match(start, pos) {
+                    return;
+                }
+                if pos > start {
+                    search_pos = pos;
+                    continue;
+                }
             }
-        }
 
-        None
+            // Failed (or empty) match: advance one char past the start
+            match safe_slice(text, start).and_then(|rest| rest.chars().next()) {
+                Some(ch) => search_pos = start + ch.len_utf8(),
+                None => return,
+            }
+        }
     }
 
     /// Check if a matcher contains a quantified pattern that can match variable lengths
@@ -2294,6 +4666,9 @@ impl Matcher {
     fn contains_quantified(matcher: &Matcher) -> bool {
         match matcher {
             Matcher::Quantified(_) | Matcher::QuantifiedCapture(_, _) => true,
+            // Runs, and the DFAs compiled from quantified sub-patterns
+            Matcher::WordRun | Matcher::DigitRun | Matcher::DFA(_) | Matcher::LazyDFA(_) => true,
+            Matcher::Sequence(seq) => seq.quantifiers().next().is_some(),
             Matcher::Capture(inner, _) => Self::contains_quantified(inner),
             Matcher::PatternWithCaptures { elements, .. } => {
                 // Check if this is a simple sequence with quantified elements
@@ -2320,6 +4695,7 @@ impl Matcher {
         match matcher {
             Matcher::Quantified(qp) => qp.quantifier.is_lazy(),
             Matcher::QuantifiedCapture(_, quantifier) => quantifier.is_lazy(),
+            Matcher::Sequence(seq) => seq.quantifiers().next().is_some_and(|q| q.is_lazy()),
             Matcher::Capture(inner, _) => Self::prefers_lazy_backtracking(inner),
             Matcher::PatternWithCaptures { elements, .. } => {
                 elements.iter().any(|elem| match elem {
@@ -2375,7 +4751,7 @@ impl Matcher {
                     .char_indices()
                     .map(|(idx, _)| idx)
                     .filter(|&idx| idx > pos)
-                    .chain(std::iter::once(text.len()))
+                    .chain(core::iter::once(text.len()))
                 {
                     if try_piece(next_pos) {
                         return true;
@@ -2386,7 +4762,7 @@ impl Matcher {
                     .char_indices()
                     .map(|(idx, _)| idx)
                     .filter(|&idx| idx > pos)
-                    .chain(std::iter::once(text.len()))
+                    .chain(core::iter::once(text.len()))
                     .collect();
                 for next_pos in boundaries.into_iter().rev() {
                     if try_piece(next_pos) {
@@ -2406,7 +4782,7 @@ impl Matcher {
         let mut lengths: Vec<usize> = text
             .char_indices()
             .map(|(idx, _)| idx)
-            .chain(std::iter::once(text.len()))
+            .chain(core::iter::once(text.len()))
             .collect();
 
         if !prefers_lazy {
@@ -2416,6 +4792,183 @@ impl Matcher {
         lengths
     }
 
+    /// Find for `FinalNewlineEnd`: the leftmost of `inner`'s matches from
+    /// `pos` in the text and in the text before its final `\n`, preferring
+    /// the one over the whole text when they start together
+    fn final_newline_find(inner: &Pattern, text: &str, pos: usize) -> Option<(usize, usize)> {
+        let whole = inner.search_at(text, pos);
+        let trimmed = text
+            .strip_suffix('\n')
+            .filter(|trimmed| pos <= trimmed.len())
+            .and_then(|trimmed| inner.search_at(trimmed, pos));
+        match (whole, trimmed) {
+            (Some(whole), Some(trimmed)) if trimmed.0 < whole.0 => Some(trimmed),
+            (whole, trimmed) => whole.or(trimmed),
+        }
+    }
+
+    /// Find for `ContinueAnchored`: `inner`'s leftmost match from `pos`,
+    /// kept only if it starts right there
+    fn continue_anchored_find(inner: &Pattern, text: &str, pos: usize) -> Option<(usize, usize)> {
+        // At the start of the text there's no context to look back on, so
+        // a plain start-of-text check avoids scanning the rest
+        if pos == 0 {
+            return inner.match_at_start(text).map(|end| (0, end));
+        }
+        inner
+            .search_at(text, pos)
+            .filter(|&(start, _)| start == pos)
+    }
+
+    /// Find for `LookbehindWithSuffix`: the first `suffix` match at or after
+    /// `from` that the lookbehind accepts, looking back past `from` if needed
+    fn lookbehind_suffix_find(
+        lookbehind: &Lookaround,
+        lookbehind_matcher: &Matcher,
+        suffix: &Matcher,
+        text: &str,
+        from: usize,
+    ) -> Option<(usize, usize)> {
+        let mut search_pos = from;
+        while search_pos <= text.len() {
+            let (rel_start, rel_end) = suffix.find(&text[search_pos..])?;
+            let abs_start = search_pos + rel_start;
+
+            // Check if lookbehind succeeds at the start of the suffix match
+            if lookbehind.matches_at(text, abs_start, lookbehind_matcher) {
+                return Some((abs_start, search_pos + rel_end));
+            }
+
+            // Move search position past this match to try next one
+            search_pos = next_char_boundary(text, abs_start);
+        }
+        None
+    }
+
+    /// Find for `LineAnchored`: the first match at or after `from` whose
+    /// anchors sit at line boundaries
+    ///
+    /// Each line (without its terminator) is matched on its own, so matches
+    /// never span a line break.
+    fn line_anchored_find(
+        line: &Pattern,
+        text: &str,
+        from: usize,
+        start: bool,
+        crlf: bool,
+        terminator: u8,
+    ) -> Option<(usize, usize)> {
+        let bytes = text.as_bytes();
+        let mut from = from;
+        // Between the `\r` and `\n` of a CRLF pair is not a line position
+        if crlf && from > 0 && bytes.get(from) == Some(&b'\n') && bytes[from - 1] == b'\r' {
+            from += 1;
+        }
+        let at_line_start = from == 0 || is_line_break(bytes[from - 1], crlf, terminator);
+
+        for (i, (line_start, line_end)) in line_segments(text, from, crlf, terminator).enumerate() {
+            // A start anchor can't match in the middle of the first line
+            if start && i == 0 && !at_line_start {
+                continue;
+            }
+            if let Some((match_start, match_end)) = line.find(&text[line_start..line_end]) {
+                return Some((line_start + match_start, line_start + match_end));
+            }
+        }
+        None
+    }
+
+    /// Find for `AnchoredPattern`
+    ///
+    /// Alternations are checked branch by branch: the leftmost-first branch
+    /// may stop short of an end anchor that a later branch satisfies
+    /// (`^(a|ab)$` on "ab"), or a later start position may be the only one
+    /// reaching the end (`(ab|b)$` on "abab").
+    fn anchored_find(
+        inner: &Matcher,
+        reverse: Option<&engine::lazy_dfa::LazyDFA>,
+        text: &str,
+        start: bool,
+        end: bool,
+    ) -> Option<(usize, usize)> {
+        // `$` alone, read backward: the leftmost start comes out in one pass
+        if let (false, true, Some(reverse)) = (start, end, reverse) {
+            return reverse
+                .rfind_start(text)
+                .map(|match_start| (match_start, text.len()));
+        }
+
+        let branches = match Self::unwrap_single_element(inner) {
+            Matcher::AlternationWithCaptures { branches, .. } => Some(branches),
+            _ => None,
+        };
+
+        match (start, end, branches) {
+            (true, false, _) => match inner {
+                // Anchored DFAs only try position 0
+                Matcher::DFA(dfa) => dfa.find_anchored(text),
+                Matcher::LazyDFA(lazy_dfa) => lazy_dfa.find_anchored(text),
+                // Must match at start
+                _ => inner
+                    .find(text)
+                    .filter(|&(match_start, _)| match_start == 0),
+            },
+            (true, true, Some(branches)) => branches
+                .iter()
+                .any(|branch| Self::matches_entire(branch, text))
+                .then_some((0, text.len())),
+            (true, true, None) => {
+                // Must match entire text: only ever tried from position 0
+                let entire = match inner {
+                    Matcher::LazyDFA(lazy_dfa) => lazy_dfa.matches_entire(text),
+                    _ => Self::matches_entire(inner, text),
+                };
+                entire.then_some((0, text.len()))
+            }
+            (false, true, branches) => {
+                // Must match at end. No match starts before the leftmost one
+                // found from a position, so only those starts are tried, each
+                // with the text before it in view: `\bx$` can't start after
+                // an "x" in "xabcx". The preferred match may still stop short
+                // of the end, as a lazy (?:ab)+? does, or a later branch may
+                // be the one reaching it, as in `(ab|b)$` on "abab"
+                let searched = Self::unwrap_single_element(inner);
+                let mut from = 0;
+                loop {
+                    let (start, end) = searched.find_from(text, from)?;
+                    let rest = &text[start..];
+                    let reaches_end = end == text.len()
+                        || match branches {
+                            Some(branches) => branches
+                                .iter()
+                                .any(|branch| Self::matches_entire(branch, rest)),
+                            None => Self::matches_entire(inner, rest),
+                        };
+                    if reaches_end {
+                        return Some((start, text.len()));
+                    }
+                    if start == text.len() {
+                        return None;
+                    }
+                    from = next_char_boundary(text, start);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Match of a `$`-anchored sequence: the suffix literal can only sit at
+    /// the very end, and the part before it is verified backward
+    fn suffix_anchored_find(
+        suffix: &str,
+        reverse: &engine::lazy_dfa::LazyDFA,
+        text: &str,
+    ) -> Option<(usize, usize)> {
+        let body = text.strip_suffix(suffix)?;
+        let start = reverse.rfind_start(body)?;
+        Some((start, text.len()))
+    }
+
     fn matches_entire(matcher: &Matcher, text: &str) -> bool {
         match matcher {
             Matcher::Quantified(qp) => {
@@ -2434,9 +4987,24 @@ impl Matcher {
             Matcher::QuantifiedCapture(inner_matcher, quantifier) => {
                 Self::matches_quantified_capture_entire(text, inner_matcher, quantifier)
             }
-            Matcher::Sequence(seq) => seq.match_at(text).is_some_and(|len| len == text.len()),
+            Matcher::Sequence(seq) => seq.matches_entire(text),
             Matcher::Group(group) => group.match_at(text, 0).is_some_and(|len| len == text.len()),
             Matcher::Capture(inner, _) => Self::matches_entire(inner, text),
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive,
+                ..
+            } => literals.iter().any(|literal| {
+                if *ascii_case_insensitive {
+                    literal.eq_ignore_ascii_case(text)
+                } else {
+                    literal == text
+                }
+            }),
+            Matcher::AlternationWithCaptures { branches, .. } => branches
+                .iter()
+                .any(|branch| Self::matches_entire(branch, text)),
+            Matcher::CaseInsensitive(inner) => Self::matches_entire(inner, &text.to_lowercase()),
             Matcher::PatternWithCaptures { elements, .. } => {
                 if let [element] = elements.as_slice() {
                     match element {
@@ -2444,8 +5012,9 @@ impl Matcher {
                         | CompiledCaptureElement::NonCapture(m) => Self::matches_entire(m, text),
                     }
                 } else {
-                    Self::match_elements_with_backtrack(text, 0, elements)
-                        .is_some_and(|end_pos| end_pos == text.len())
+                    Self::match_elements_then(text, 0, elements, &mut Vec::new(), &mut |end, _| {
+                        end == text.len()
+                    })
                 }
             }
             _ => matcher
@@ -2461,236 +5030,326 @@ impl Matcher {
         start_pos: usize,
         elements: &[CompiledCaptureElement],
     ) -> Option<(usize, Vec<(usize, usize, usize)>)> {
-        // Base case: no more elements
-        if elements.is_empty() {
-            return Some((start_pos, Vec::new()));
-        }
-
-        // Get first element
-        let first_element = &elements[0];
-
-        // Check if this element contains a quantified pattern that needs backtracking
-        let needs_backtracking = if elements.len() <= 1 {
-            false
-        } else {
-            match first_element {
-                CompiledCaptureElement::Capture(m, _) | CompiledCaptureElement::NonCapture(m) => {
-                    Self::contains_quantified(m)
-                }
-            }
-        };
-
-        if needs_backtracking {
-            // Backtracking needed
-            let remaining_text = safe_slice(text, start_pos).unwrap_or("");
-            let prefers_lazy = match first_element {
-                CompiledCaptureElement::Capture(m, _) | CompiledCaptureElement::NonCapture(m) => {
-                    Self::prefers_lazy_backtracking(m)
-                }
-            };
-
-            let try_match = |try_len: usize| {
-                let next_pos = start_pos + try_len;
-
-                if let Some((final_pos, mut remaining_caps)) =
-                    Self::match_elements_with_backtrack_and_captures(text, next_pos, &elements[1..])
-                {
-                    if try_len == 0 {
-                        match first_element {
-                            CompiledCaptureElement::Capture(m, num) => {
-                                if Self::matches_entire(m, "") {
-                                    let mut caps = vec![(*num, start_pos, start_pos)];
-                                    caps.append(&mut remaining_caps);
-                                    return Some((final_pos, caps));
-                                }
-                            }
-                            CompiledCaptureElement::NonCapture(m) => {
-                                if Self::matches_entire(m, "") {
-                                    return Some((final_pos, remaining_caps));
-                                }
-                            }
-                        }
-                    } else {
-                        let substring = safe_slice_range(text, start_pos, next_pos).unwrap_or("");
-
-                        match first_element {
-                            CompiledCaptureElement::Capture(m, num) => {
-                                if Self::matches_entire(m, substring) {
-                                    let mut caps = vec![(*num, start_pos, next_pos)];
-                                    caps.append(&mut remaining_caps);
-                                    return Some((final_pos, caps));
-                                }
-                            }
-                            CompiledCaptureElement::NonCapture(m) => {
-                                if Self::matches_entire(m, substring) {
-                                    let nested_caps = m.extract_nested_captures(text, start_pos);
-                                    let mut all_caps = nested_caps;
-                                    all_caps.extend(remaining_caps);
-                                    return Some((final_pos, all_caps));
-                                }
-                            }
-                        }
-                    }
-                }
-
-                None
-            };
-
-            for try_len in Self::backtracking_lengths(remaining_text, prefers_lazy) {
-                if let Some(result) = try_match(try_len) {
-                    return Some(result);
-                }
-            }
-
-            None
-        } else {
-            // No backtracking needed
-            match first_element {
-                CompiledCaptureElement::Capture(m, num) => {
-                    if let Some((rel_start, rel_end)) =
-                        m.find(safe_slice(text, start_pos).unwrap_or(""))
-                    {
-                        if rel_start == 0 {
-                            let next_pos = start_pos + rel_end;
-                            if let Some((final_pos, mut remaining_caps)) =
-                                Self::match_elements_with_backtrack_and_captures(
-                                    text,
-                                    next_pos,
-                                    &elements[1..],
-                                )
-                            {
-                                let mut caps = vec![(*num, start_pos, next_pos)];
-                                caps.append(&mut remaining_caps);
-                                return Some((final_pos, caps));
-                            }
-                        }
-                    }
-                    None
-                }
-                CompiledCaptureElement::NonCapture(m) => {
-                    if let Some((rel_start, rel_end)) =
-                        m.find(safe_slice(text, start_pos).unwrap_or(""))
-                    {
-                        if rel_start == 0 {
-                            let next_pos = start_pos + rel_end;
-                            if let Some((final_pos, remaining_caps)) =
-                                Self::match_elements_with_backtrack_and_captures(
-                                    text,
-                                    next_pos,
-                                    &elements[1..],
-                                )
-                            {
-                                // Extract nested captures
-                                let nested_caps = m.extract_nested_captures(text, start_pos);
-                                let mut all_caps = nested_caps;
-                                all_caps.extend(remaining_caps);
-                                return Some((final_pos, all_caps));
-                            }
-                        }
-                    }
-                    None
-                }
-            }
-        }
+        let mut found = None;
+        Self::match_elements_then(
+            text,
+            start_pos,
+            elements,
+            &mut Vec::new(),
+            &mut |end, caps| {
+                found = Some((end, core::mem::take(caps)));
+                true
+            },
+        );
+        found
     }
 
     /// Try to match sequence of elements with backtracking support
-    /// Returns (start, end) if successful
+    /// Returns the end position if successful
     fn match_elements_with_backtrack(
         text: &str,
         start_pos: usize,
         elements: &[CompiledCaptureElement],
     ) -> Option<usize> {
-        // Base case: no more elements
-        if elements.is_empty() {
-            return Some(start_pos);
-        }
+        let mut found = None;
+        Self::match_elements_then(text, start_pos, elements, &mut Vec::new(), &mut |end, _| {
+            found = Some(end);
+            true
+        });
+        found
+    }
 
-        // Get first element
-        let first_element = &elements[0];
-        let first_matcher = match first_element {
-            CompiledCaptureElement::Capture(m, _) => m,
-            CompiledCaptureElement::NonCapture(m) => m,
+    /// Match `elements` at `pos`, offering each end they can reach to `accept`
+    ///
+    /// Ends come in leftmost-first order: alternatives as written, quantifiers
+    /// by preferred length. `caps` holds the groups set on the current path;
+    /// the search stops at the first end `accept` takes, and leaves `caps` as
+    /// it found it otherwise.
+    fn match_elements_then(
+        text: &str,
+        pos: usize,
+        elements: &[CompiledCaptureElement],
+        caps: &mut Vec<(usize, usize, usize)>,
+        accept: &mut dyn FnMut(usize, &mut Vec<(usize, usize, usize)>) -> bool,
+    ) -> bool {
+        let Some((first, rest)) = elements.split_first() else {
+            return accept(pos, caps);
         };
-
-        // Check if this element contains a quantified pattern that needs backtracking
-        // This includes: Quantified, QuantifiedCapture, and Captures containing quantified patterns
-        let needs_backtracking = if elements.len() <= 1 {
-            false // No backtracking needed if this is the last element
-        } else {
-            // Check if first_matcher contains a quantified pattern
-            Self::contains_quantified(first_matcher)
+        let mut then = |end: usize, caps: &mut Vec<(usize, usize, usize)>| {
+            Self::match_elements_then(text, end, rest, caps, accept)
         };
+        match first {
+            CompiledCaptureElement::Capture(m, num) => {
+                Self::match_group_then(m, *num, text, pos, caps, &mut then)
+            }
+            CompiledCaptureElement::NonCapture(m) => {
+                Self::match_matcher_then(m, text, pos, caps, &mut then)
+            }
+        }
+    }
 
-        if needs_backtracking {
-            // Quantified element followed by more elements - need backtracking.
-            let remaining_text = safe_slice(text, start_pos).unwrap_or("");
-            let prefers_lazy = Self::prefers_lazy_backtracking(first_matcher);
-
-            let try_match = |try_len: usize| {
-                let next_pos = start_pos + try_len;
+    /// [`Matcher::match_matcher_then`] for `inner` wrapped in group `num`
+    fn match_group_then(
+        inner: &Matcher,
+        num: usize,
+        text: &str,
+        pos: usize,
+        caps: &mut Vec<(usize, usize, usize)>,
+        accept: &mut dyn FnMut(usize, &mut Vec<(usize, usize, usize)>) -> bool,
+    ) -> bool {
+        if matches!(inner, Matcher::QuantifiedCapture(..)) {
+            return Self::match_repetition_then(inner, Some(num), text, pos, caps, accept);
+        }
+        Self::match_matcher_then(inner, text, pos, caps, &mut |end, caps| {
+            caps.push((num, pos, end));
+            if accept(end, caps) {
+                return true;
+            }
+            caps.pop();
+            false
+        })
+    }
 
-                if let Some(final_pos) =
-                    Self::match_elements_with_backtrack(text, next_pos, &elements[1..])
-                {
-                    let substring = safe_slice_range(text, start_pos, next_pos).unwrap_or("");
+    /// Match `m` at `pos`, offering each end it can reach to `accept`
+    ///
+    /// Grouping matchers are walked so their groups land in `caps`; any other
+    /// matcher offers its preferred end, plus every other length it can match
+    /// entirely when it is quantified.
+    fn match_matcher_then(
+        m: &Matcher,
+        text: &str,
+        pos: usize,
+        caps: &mut Vec<(usize, usize, usize)>,
+        accept: &mut dyn FnMut(usize, &mut Vec<(usize, usize, usize)>) -> bool,
+    ) -> bool {
+        let rest = safe_slice(text, pos).unwrap_or("");
+        match m {
+            Matcher::Capture(inner, num) => {
+                Self::match_group_then(inner, *num, text, pos, caps, accept)
+            }
+            Matcher::PatternWithCaptures { elements, .. } => {
+                Self::match_elements_then(text, pos, elements, caps, accept)
+            }
+            Matcher::AlternationWithCaptures { branches, .. } => branches
+                .iter()
+                .any(|branch| Self::match_matcher_then(branch, text, pos, caps, accept)),
+            Matcher::MultiLiteral {
+                literals,
+                ascii_case_insensitive,
+                ..
+            } => literals.iter().any(|literal| {
+                rest.get(..literal.len()).is_some_and(|prefix| {
+                    if *ascii_case_insensitive {
+                        prefix.eq_ignore_ascii_case(literal)
+                    } else {
+                        prefix == literal
+                    }
+                }) && accept(pos + literal.len(), caps)
+            }),
+            Matcher::QuantifiedCapture(body, _) if body.group_count() > 0 => {
+                Self::match_repetition_then(m, None, text, pos, caps, accept)
+            }
+            _ => Self::match_ends_then(m, text, pos, caps, accept),
+        }
+    }
 
-                    if try_len == 0 {
-                        if Self::matches_entire(first_matcher, "") {
-                            return Some(final_pos);
+    /// Match the repetition `m` at `pos` like any other matcher, then set
+    /// the groups from its iterations: `group` (the group wrapping
+    /// the repetition, if any) gets the last iteration's span, and groups
+    /// inside `body` keep what they captured in the last iteration that set
+    /// them
+    ///
+    /// A repetition that ran zero times, like `(a)?` skipped, sets nothing;
+    /// an empty match only counts as an iteration if `body` can match empty.
+    fn match_repetition_then(
+        m: &Matcher,
+        group: Option<usize>,
+        text: &str,
+        pos: usize,
+        caps: &mut Vec<(usize, usize, usize)>,
+        accept: &mut dyn FnMut(usize, &mut Vec<(usize, usize, usize)>) -> bool,
+    ) -> bool {
+        let Matcher::QuantifiedCapture(body, quantifier) = m else {
+            return Self::match_ends_then(m, text, pos, caps, accept);
+        };
+        let max = quantifier.max_matches();
+        Self::match_ends_then(m, text, pos, caps, &mut |end, caps| {
+            let mark = caps.len();
+            if end > pos {
+                match Self::repetition_spans(body, max, text, pos, end) {
+                    Some(spans) => {
+                        for &(iter_start, iter_end) in &spans {
+                            if body.group_count() > 0 {
+                                Self::match_matcher_then(
+                                    body,
+                                    text,
+                                    iter_start,
+                                    caps,
+                                    &mut |reached, _| reached == iter_end,
+                                );
+                            }
+                        }
+                        if let (Some(num), Some(&(last_start, _))) = (group, spans.last()) {
+                            caps.push((num, last_start, end));
+                        }
+                    }
+                    // Iterations the greedy walk can't find: the whole span
+                    None => {
+                        if let Some(num) = group {
+                            caps.push((num, pos, end));
                         }
-                    } else if Self::matches_entire(first_matcher, substring) {
-                        return Some(final_pos);
                     }
                 }
-
-                None
-            };
-
-            for try_len in Self::backtracking_lengths(remaining_text, prefers_lazy) {
-                if let Some(result) = try_match(try_len) {
-                    return Some(result);
+            } else if Self::match_matcher_then(body, text, pos, caps, &mut |reached, _| {
+                reached == pos
+            }) {
+                // Matching nothing still takes one empty iteration when the
+                // body allows it: (a*)* sets group 1 on "", (a|b)* doesn't
+                if let Some(num) = group {
+                    caps.push((num, pos, pos));
                 }
             }
+            if accept(end, caps) {
+                return true;
+            }
+            caps.truncate(mark);
+            false
+        })
+    }
 
-            None
-        } else {
-            // Non-quantified element or last element - match normally
+    /// Split `pos..end`, matched by at most `max` repetitions of `body`, into
+    /// iterations
+    ///
+    /// Each iteration is `body`'s preferred match where it fits, else the
+    /// whole remainder if `body` matches it. When that walk gets stuck, as
+    /// `(a|ab)+` does on `aba`, short spans are searched for any split.
+    fn repetition_spans(
+        body: &Matcher,
+        max: usize,
+        text: &str,
+        pos: usize,
+        end: usize,
+    ) -> Option<Vec<(usize, usize)>> {
+        let mut spans = Vec::new();
+        let mut iter_start = pos;
+        while iter_start < end {
+            let last_allowed = spans.len() + 1 >= max;
+            let len = match body.find(safe_slice(text, iter_start)?) {
+                Some((0, len)) if len > 0 && iter_start + len <= end && !last_allowed => len,
+                _ if Self::matches_entire(body, text.get(iter_start..end)?) => end - iter_start,
+                _ if end - pos <= MAX_REPETITION_SPLIT_LEN => {
+                    return Self::split_repetition(body, max, text, pos, end)
+                }
+                _ => return None,
+            };
+            spans.push((iter_start, iter_start + len));
+            iter_start += len;
+        }
+        Some(spans)
+    }
 
-            // Special case: if first element is AlternationWithCaptures, try all branches
-            if let Matcher::AlternationWithCaptures { branches, .. } = first_matcher {
-                // Try each branch - return first one that leads to complete match
-                for branch in branches {
-                    if let Some((rel_start, rel_end)) =
-                        branch.find(safe_slice(text, start_pos).unwrap_or(""))
+    /// Search for at most `max` iterations of `body` covering `pos..end`,
+    /// longest iteration first
+    fn split_repetition(
+        body: &Matcher,
+        max: usize,
+        text: &str,
+        pos: usize,
+        end: usize,
+    ) -> Option<Vec<(usize, usize)>> {
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        // Offsets no split of the rest starts from; only tracked without a
+        // count limit, since otherwise it depends on the iterations left
+        let mut failed = vec![false; end - pos];
+        let mut start = pos;
+        // Next iteration end to try from `start`, counting down
+        let mut candidate = end;
+        loop {
+            if start == end {
+                return Some(spans);
+            }
+            let mut advanced = false;
+            if spans.len() < max && !failed[start - pos] {
+                while candidate > start {
+                    let iter_end = candidate;
+                    candidate -= 1;
+                    if text.is_char_boundary(iter_end)
+                        && Self::matches_entire(body, &text[start..iter_end])
                     {
-                        if rel_start == 0 {
-                            let next_pos = start_pos + rel_end;
-                            // Try to match remaining elements with this branch
-                            if let Some(final_pos) =
-                                Self::match_elements_with_backtrack(text, next_pos, &elements[1..])
-                            {
-                                return Some(final_pos);
-                            }
-                            // This branch didn't lead to complete match, try next branch
-                        }
+                        spans.push((start, iter_end));
+                        start = iter_end;
+                        candidate = end;
+                        advanced = true;
+                        break;
                     }
                 }
-                return None;
             }
+            if !advanced {
+                if max == usize::MAX {
+                    failed[start - pos] = true;
+                }
+                let (prev_start, prev_end) = spans.pop()?;
+                start = prev_start;
+                candidate = prev_end - 1;
+            }
+        }
+    }
 
-            // Regular case: non-alternation element
-            if let Some((rel_start, rel_end)) =
-                first_matcher.find(safe_slice(text, start_pos).unwrap_or(""))
-            {
-                if rel_start == 0 {
-                    let next_pos = start_pos + rel_end;
-                    // Match remaining elements
-                    return Self::match_elements_with_backtrack(text, next_pos, &elements[1..]);
+    /// [`match_matcher_then`](Self::match_matcher_then) for a matcher whose
+    /// groups (if any) aren't walked: its preferred end, then every other
+    /// length it can match entirely when it is quantified
+    fn match_ends_then(
+        m: &Matcher,
+        text: &str,
+        pos: usize,
+        caps: &mut Vec<(usize, usize, usize)>,
+        accept: &mut dyn FnMut(usize, &mut Vec<(usize, usize, usize)>) -> bool,
+    ) -> bool {
+        // ^ only holds at the start of the whole text, not of `rest`
+        if pos > 0 && m.is_start_anchored() {
+            return false;
+        }
+        let rest = safe_slice(text, pos).unwrap_or("");
+        let preferred = match m.find(rest) {
+            Some((0, end)) => Some(end),
+            _ => None,
+        };
+        if preferred.is_some_and(|end| accept(pos + end, caps)) {
+            return true;
+        }
+        if !Self::contains_quantified(m) {
+            return false;
+        }
+        Self::quantified_lengths(m, rest)
+            .into_iter()
+            .filter(|&len| Some(len) != preferred)
+            .any(|len| accept(pos + len, caps))
+    }
+
+    /// Lengths a quantified matcher can match at the start of `rest`,
+    /// preferred first
+    fn quantified_lengths(m: &Matcher, rest: &str) -> Vec<usize> {
+        if let Matcher::Quantified(qp) = m {
+            // Only prefixes of the run of matching chars qualify
+            let mut ends = vec![0];
+            for (count, (idx, ch)) in rest.char_indices().enumerate() {
+                if count >= qp.quantifier.max_matches() || !qp.element.matches(ch) {
+                    break;
                 }
+                ends.push(idx + ch.len_utf8());
             }
-            None
+            let mut lengths: Vec<usize> =
+                ends.into_iter().skip(qp.quantifier.min_matches()).collect();
+            if !qp.quantifier.is_lazy() {
+                lengths.reverse();
+            }
+            return lengths;
         }
+
+        Self::backtracking_lengths(rest, Self::prefers_lazy_backtracking(m))
+            .into_iter()
+            .filter(|&len| Self::matches_entire(m, &rest[..len]))
+            .collect()
     }
 
     /// Find all quantified capture matches
@@ -2700,35 +5359,20 @@ impl Matcher {
         quantifier: &parser::quantifier::Quantifier,
     ) -> Vec<(usize, usize)> {
         let mut matches = Vec::new();
-        let mut search_pos = 0;
-
-        while search_pos < text.len() {
-            if let Some((start, end)) =
-                Self::quantified_find(&text[search_pos..], inner_matcher, quantifier)
-            {
-                matches.push((search_pos + start, search_pos + end));
-                search_pos += start + 1; // Avoid overlapping
-                if start == end {
-                    search_pos += 1; // Avoid infinite loop on zero-width match
-                }
-            } else {
-                break;
-            }
-        }
-
+        Self::quantified_scan(text, inner_matcher, quantifier, |start, end| {
+            matches.push((start, end));
+            true
+        });
         matches
     }
 
     fn find(&self, text: &str) -> Option<(usize, usize)> {
         match self {
             Matcher::Literal(lit) => {
-                let pos = memmem::find(text.as_bytes(), lit.as_bytes())?;
+                let pos = lit.find(text)?;
                 Some((pos, pos + lit.len()))
             }
-            Matcher::MultiLiteral(ac) => {
-                let mat = ac.find(text)?;
-                Some((mat.start(), mat.end()))
-            }
+            Matcher::MultiLiteral { searcher, .. } => searcher.find(text),
             Matcher::AnchoredLiteral {
                 literal,
                 start,
@@ -2744,14 +5388,8 @@ impl Matcher {
             Matcher::AnchoredGroup { group, start, end } => {
                 match (start, end) {
                     (true, true) => {
-                        // Must match entire text
-                        group.match_at(text, 0).and_then(|len| {
-                            if len == text.len() {
-                                Some((0, len))
-                            } else {
-                                None
-                            }
-                        })
+                        // Must match entire text (any branch may cover it)
+                        group.matches_to_end(text, 0).then_some((0, text.len()))
                     }
                     (true, false) => {
                         // Must match at start
@@ -2759,52 +5397,28 @@ impl Matcher {
                     }
                     (false, true) => {
                         // Must match at end
-                        group.find(text).and_then(|(start_pos, end_pos)| {
-                            if end_pos == text.len() {
-                                Some((start_pos, end_pos))
-                            } else {
-                                None
-                            }
-                        })
+                        group.find_at_end(text)
                     }
                     _ => unreachable!(),
                 }
             }
-            Matcher::AnchoredPattern { inner, start, end } => {
-                match (start, end) {
-                    (true, true) => {
-                        // Must match entire text: match at position 0 and cover full text
-                        inner.find(text).and_then(|(match_start, match_end)| {
-                            if match_start == 0 && match_end == text.len() {
-                                Some((0, text.len()))
-                            } else {
-                                None
-                            }
-                        })
-                    }
-                    (true, false) => {
-                        // Must match at start
-                        inner.find(text).and_then(|(match_start, match_end)| {
-                            if match_start == 0 {
-                                Some((0, match_end))
-                            } else {
-                                None
-                            }
-                        })
-                    }
-                    (false, true) => {
-                        // Must match at end
-                        inner.find(text).and_then(|(match_start, match_end)| {
-                            if match_end == text.len() {
-                                Some((match_start, match_end))
-                            } else {
-                                None
-                            }
-                        })
-                    }
-                    _ => unreachable!(),
-                }
+            Matcher::AnchoredPattern {
+                inner,
+                start,
+                end,
+                reverse,
+            } => Self::anchored_find(inner, reverse.as_ref(), text, *start, *end),
+            Matcher::SuffixAnchored { suffix, reverse } => {
+                Self::suffix_anchored_find(suffix, reverse, text)
             }
+            Matcher::LineAnchored {
+                line,
+                start,
+                crlf,
+                terminator,
+            } => Self::line_anchored_find(line, text, 0, *start, *crlf, *terminator),
+            Matcher::ContinueAnchored(inner) => Self::continue_anchored_find(inner, text, 0),
+            Matcher::FinalNewlineEnd(inner) => Self::final_newline_find(inner, text, 0),
             Matcher::CharClass(cc) => {
                 // Find first character matching the class
                 for (idx, ch) in text.char_indices() {
@@ -2825,12 +5439,8 @@ impl Matcher {
             }
             Matcher::Lookaround(lookaround, inner_matcher) => {
                 // Find first position where lookaround succeeds
-                for pos in 0..=text.len() {
-                    if lookaround.matches_at(text, pos, inner_matcher) {
-                        return Some((pos, pos)); // Zero-width match
-                    }
-                }
-                None
+                let pos = lookaround.find_from(text, 0, inner_matcher)?;
+                Some((pos, pos)) // Zero-width match
             }
             Matcher::Capture(inner_matcher, _group_index) => {
                 // Capture groups don't affect position, use inner matcher
@@ -2847,7 +5457,7 @@ impl Matcher {
             } => {
                 // Find first position where prefix matches AND lookaround succeeds
                 let mut search_pos = 0;
-                while search_pos < text.len() {
+                while search_pos <= text.len() {
                     let remaining = &text[search_pos..];
                     if let Some((rel_start, rel_end)) = prefix.find(remaining) {
                         let abs_start = search_pos + rel_start;
@@ -2859,7 +5469,7 @@ impl Matcher {
                         }
 
                         // Move search position past this match to try next one
-                        search_pos = abs_start + 1;
+                        search_pos = next_char_boundary(text, abs_start);
                     } else {
                         break;
                     }
@@ -2870,28 +5480,7 @@ impl Matcher {
                 lookbehind,
                 lookbehind_matcher,
                 suffix,
-            } => {
-                // Find first position where suffix matches AND lookbehind succeeds before it
-                let mut search_pos = 0;
-                while search_pos < text.len() {
-                    let remaining = &text[search_pos..];
-                    if let Some((rel_start, rel_end)) = suffix.find(remaining) {
-                        let abs_start = search_pos + rel_start;
-                        let abs_end = search_pos + rel_end;
-
-                        // Check if lookbehind succeeds at the start of the suffix match
-                        if lookbehind.matches_at(text, abs_start, lookbehind_matcher) {
-                            return Some((abs_start, abs_end));
-                        }
-
-                        // Move search position past this match to try next one
-                        search_pos = abs_start + 1;
-                    } else {
-                        break;
-                    }
-                }
-                None
-            }
+            } => Self::lookbehind_suffix_find(lookbehind, lookbehind_matcher, suffix, text, 0),
             Matcher::PatternWithCaptures { elements, .. } => {
                 // Special case: single element can match anywhere
                 if elements.len() == 1 {
@@ -2903,20 +5492,13 @@ impl Matcher {
                 }
 
                 // Check if pattern contains backreferences
-                let has_backrefs = elements.iter().any(|elem| {
-                    matches!(
-                        elem,
-                        CompiledCaptureElement::NonCapture(Matcher::Backreference(_))
-                    )
-                });
-
-                if has_backrefs {
+                if Self::has_backreferences(elements) {
                     // Use backreference-aware matching
-                    for start_pos in 0..=text.len() {
-                        if let Some(end_pos) =
+                    for (start_pos, _) in text.char_indices() {
+                        if let Some((end_pos, _)) =
                             Self::match_pattern_with_backreferences(text, start_pos, elements)
                         {
-                            if end_pos > start_pos || elements.is_empty() {
+                            if end_pos > start_pos {
                                 return Some((start_pos, end_pos));
                             }
                         }
@@ -2929,15 +5511,19 @@ impl Matcher {
                     return dfa.find(text);
                 }
 
-                // Fallback: Linear scan through all positions
-                for start_pos in 0..=text.len() {
-                    if let Some(end_pos) =
-                        Self::match_elements_with_backtrack(text, start_pos, elements)
-                    {
-                        if end_pos > start_pos || elements.is_empty() {
+                // Fallback: try each position a match can start at; the
+                // leftmost one wins even when it is empty, as for (a)*(b)?
+                let start_filter = Self::capture_start_filter(elements);
+                let mut from = 0;
+                while let Some(start_pos) = start_filter.next(text, from) {
+                    if text.is_char_boundary(start_pos) {
+                        if let Some(end_pos) =
+                            Self::match_elements_with_backtrack(text, start_pos, elements)
+                        {
                             return Some((start_pos, end_pos));
                         }
                     }
+                    from = start_pos + 1;
                 }
 
                 None
@@ -2951,27 +5537,11 @@ impl Matcher {
                 dfa.find(text)
             }
             Matcher::LazyDFA(lazy_dfa) => {
-                // Lazy DFA requires mutable access, clone it
-                let mut dfa = lazy_dfa.clone();
-                dfa.find(text)
-            }
-            Matcher::SequenceWithFlags(seq, flags) => {
-                // Find with flags (e.g., DOTALL mode where . matches newlines)
-                seq.find_with_flags(text, flags)
+                // Lazy DFA find - states built on demand
+                lazy_dfa.find(text)
             }
             Matcher::AlternationWithCaptures { branches, .. } => {
-                // Try each branch in order (leftmost-first), return first match
-                let mut best_match: Option<(usize, usize)> = None;
-
-                for branch in branches {
-                    if let Some((start, end)) = branch.find(text) {
-                        // Keep the leftmost (earliest starting) match
-                        if best_match.is_none() || start < best_match.unwrap().0 {
-                            best_match = Some((start, end));
-                        }
-                    }
-                }
-                best_match
+                Self::leftmost_branch_find(branches, text, 0)
             }
             Matcher::CaseInsensitive(inner) => {
                 let bytes = text.as_bytes();
@@ -2988,12 +5558,16 @@ impl Matcher {
                         buf[i] = if b >= b'A' && b <= b'Z' { b + 32 } else { b };
                     }
                     if all_ascii {
-                        let lower = unsafe { std::str::from_utf8_unchecked(&buf[..len]) };
+                        let lower = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
                         return inner.find(lower);
                     }
                 }
-                let lower_text = text.to_lowercase();
-                inner.find(&lower_text)
+                if text.is_ascii() {
+                    return inner.find(&text.to_ascii_lowercase());
+                }
+                let lowered = Lowercased::new(text);
+                let span = inner.find(&lowered.lower)?;
+                Some(lowered.span(text, span))
             }
         }
     }
@@ -3060,17 +5634,12 @@ impl Matcher {
 
     fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
         match self {
-            Matcher::Literal(lit) => {
-                let finder = memmem::Finder::new(lit.as_bytes());
-                finder
-                    .find_iter(text.as_bytes())
-                    .map(|pos| (pos, pos + lit.len()))
-                    .collect()
-            }
-            Matcher::MultiLiteral(ac) => ac
-                .find_iter(text)
-                .map(|mat| (mat.start(), mat.end()))
+            Matcher::Literal(lit) => lit
+                .finder
+                .find_iter(text.as_bytes())
+                .map(|pos| (pos, pos + lit.len()))
                 .collect(),
+            Matcher::MultiLiteral { searcher, .. } => searcher.find_iter(text).collect(),
             Matcher::AnchoredLiteral { .. } => {
                 if let Some(m) = self.find(text) {
                     vec![m]
@@ -3086,7 +5655,7 @@ impl Matcher {
                     vec![]
                 }
             }
-            Matcher::AnchoredPattern { .. } => {
+            Matcher::AnchoredPattern { .. } | Matcher::SuffixAnchored { .. } => {
                 // Anchored patterns can only match once
                 if let Some(m) = self.find(text) {
                     vec![m]
@@ -3094,6 +5663,58 @@ impl Matcher {
                     vec![]
                 }
             }
+            Matcher::LineAnchored {
+                line,
+                start,
+                crlf,
+                terminator,
+            } => {
+                // At most one match per line, in line order
+                let mut matches = Vec::new();
+                let mut pos = 0;
+                while pos <= text.len() {
+                    match Self::line_anchored_find(line, text, pos, *start, *crlf, *terminator) {
+                        Some((match_start, match_end)) => {
+                            matches.push((match_start, match_end));
+                            pos = if match_end > match_start {
+                                match_end
+                            } else {
+                                match_end
+                                    + text[match_end..].chars().next().map_or(1, char::len_utf8)
+                            };
+                        }
+                        None => break,
+                    }
+                }
+                matches
+            }
+            Matcher::ContinueAnchored(inner) => {
+                // Each match has to start where the search resumed
+                let mut matches = Vec::new();
+                let mut cursor = SearchCursor::new();
+                while cursor.in_text(text) {
+                    let Some(found) = Self::continue_anchored_find(inner, text, cursor.pos) else {
+                        break;
+                    };
+                    if cursor.accept(text, found) {
+                        matches.push(found);
+                    }
+                }
+                matches
+            }
+            Matcher::FinalNewlineEnd(inner) => {
+                let mut matches = Vec::new();
+                let mut cursor = SearchCursor::new();
+                while cursor.in_text(text) {
+                    let Some(found) = Self::final_newline_find(inner, text, cursor.pos) else {
+                        break;
+                    };
+                    if cursor.accept(text, found) {
+                        matches.push(found);
+                    }
+                }
+                matches
+            }
             Matcher::CharClass(cc) => {
                 // Find all characters matching the class
                 text.char_indices()
@@ -3116,10 +5737,13 @@ impl Matcher {
             }
             Matcher::Lookaround(lookaround, inner_matcher) => {
                 // Find all positions where lookaround succeeds
-                (0..=text.len())
-                    .filter(|&pos| lookaround.matches_at(text, pos, inner_matcher))
-                    .map(|pos| (pos, pos)) // Zero-width matches
-                    .collect()
+                let mut positions = Vec::new();
+                let mut from = 0;
+                while let Some(pos) = lookaround.find_from(text, from, inner_matcher) {
+                    positions.push((pos, pos)); // Zero-width matches
+                    from = pos + 1;
+                }
+                positions
             }
             Matcher::Capture(inner_matcher, _group_index) => {
                 // Capture groups don't affect find_all, use inner matcher
@@ -3138,7 +5762,7 @@ impl Matcher {
                 let mut matches = Vec::new();
                 let mut search_pos = 0;
 
-                while search_pos < text.len() {
+                while search_pos <= text.len() {
                     let remaining = &text[search_pos..];
                     if let Some((rel_start, rel_end)) = prefix.find(remaining) {
                         let abs_start = search_pos + rel_start;
@@ -3150,7 +5774,7 @@ impl Matcher {
                         }
 
                         // Move search position past the start of this match
-                        search_pos = abs_start + 1;
+                        search_pos = next_char_boundary(text, abs_start);
                     } else {
                         break;
                     }
@@ -3167,7 +5791,7 @@ impl Matcher {
                 let mut matches = Vec::new();
                 let mut search_pos = 0;
 
-                while search_pos < text.len() {
+                while search_pos <= text.len() {
                     let remaining = &text[search_pos..];
                     if let Some((rel_start, rel_end)) = suffix.find(remaining) {
                         let abs_start = search_pos + rel_start;
@@ -3179,7 +5803,7 @@ impl Matcher {
                         }
 
                         // Move search position past the start of this match
-                        search_pos = abs_start + 1;
+                        search_pos = next_char_boundary(text, abs_start);
                     } else {
                         break;
                     }
@@ -3192,6 +5816,16 @@ impl Matcher {
                 let mut matches = Vec::new();
                 let mut start_pos = 0;
 
+                if Self::has_backreferences(elements) {
+                    while let Some((start, end)) =
+                        safe_slice(text, start_pos).and_then(|rest| self.find(rest))
+                    {
+                        matches.push((start_pos + start, start_pos + end));
+                        start_pos += end; // Backreference matches are never empty
+                    }
+                    return matches;
+                }
+
                 while start_pos < text.len() {
                     let mut pos = start_pos;
                     let mut all_matched = true;
@@ -3219,9 +5853,10 @@ impl Matcher {
 
                     if all_matched {
                         matches.push((start_pos, pos));
-                        start_pos = pos.max(start_pos + 1); // Move past this match
+                        start_pos = pos.max(next_char_boundary(text, start_pos));
+                    // Move past this match
                     } else {
-                        start_pos += 1;
+                        start_pos = next_char_boundary(text, start_pos);
                     }
                 }
 
@@ -3232,53 +5867,20 @@ impl Matcher {
                 vec![]
             }
             Matcher::DFA(dfa) => {
-                // DFA find_all - multiple matches
-                let mut matches = Vec::new();
-                let mut search_start = 0;
-
-                while search_start < text.len() {
-                    if let Some((start, end)) = dfa.find(&text[search_start..]) {
-                        let abs_start = search_start + start;
-                        let abs_end = search_start + end;
-                        matches.push((abs_start, abs_end));
-                        search_start = abs_end.max(abs_start + 1);
-                    } else {
-                        break;
-                    }
-                }
-
-                matches
+                // DFA find_all - single pass, restarting after each match
+                dfa.find_iter(text).collect()
             }
             Matcher::LazyDFA(lazy_dfa) => {
-                // Lazy DFA find_all
-                let mut dfa = lazy_dfa.clone();
-                let mut matches = Vec::new();
-                let mut search_start = 0;
-
-                while search_start < text.len() {
-                    if let Some((start, end)) = dfa.find(&text[search_start..]) {
-                        let abs_start = search_start + start;
-                        let abs_end = search_start + end;
-                        matches.push((abs_start, abs_end));
-                        search_start = abs_end.max(abs_start + 1);
-                    } else {
-                        break;
-                    }
-                }
-
-                matches
-            }
-            Matcher::SequenceWithFlags(seq, flags) => {
-                // Find all with flags
+                // Lazy DFA find_all - the state cache is reused across searches
                 let mut matches = Vec::new();
                 let mut search_start = 0;
 
                 while search_start < text.len() {
-                    if let Some((start, end)) = seq.find_with_flags(&text[search_start..], flags) {
+                    if let Some((start, end)) = lazy_dfa.find(&text[search_start..]) {
                         let abs_start = search_start + start;
                         let abs_end = search_start + end;
                         matches.push((abs_start, abs_end));
-                        search_start = abs_end.max(abs_start + 1);
+                        search_start = abs_end.max(next_char_boundary(text, abs_start));
                     } else {
                         break;
                     }
@@ -3290,25 +5892,16 @@ impl Matcher {
                 // Find all matches from any branch
                 let mut matches = Vec::new();
                 let mut search_start = 0;
+                let mut search = BranchSearch::new(branches);
 
                 while search_start < text.len() {
-                    // Try each branch and find the leftmost match
-                    let mut best_match: Option<(usize, usize)> = None;
-
-                    for branch in branches {
-                        if let Some((start, end)) = branch.find(&text[search_start..]) {
-                            let abs_start = search_start + start;
-                            let abs_end = search_start + end;
-
-                            if best_match.is_none() || abs_start < best_match.unwrap().0 {
-                                best_match = Some((abs_start, abs_end));
-                            }
-                        }
-                    }
+                    // Same tie-breaking as `find`: the first branch at the
+                    // leftmost start wins
+                    let best_match = search.find_from(text, search_start);
 
                     if let Some((start, end)) = best_match {
                         matches.push((start, end));
-                        search_start = end.max(start + 1);
+                        search_start = end.max(next_char_boundary(text, start));
                     } else {
                         break;
                     }
@@ -3331,12 +5924,23 @@ impl Matcher {
                         buf[i] = if b >= b'A' && b <= b'Z' { b + 32 } else { b };
                     }
                     if all_ascii {
-                        let lower = unsafe { std::str::from_utf8_unchecked(&buf[..len]) };
+                        let lower = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
                         return inner.find_all(lower);
                     }
                 }
-                let lower_text = text.to_lowercase();
-                inner.find_all(&lower_text)
+                if text.is_ascii() {
+                    return inner.find_all(&text.to_ascii_lowercase());
+                }
+                let lowered = Lowercased::new(text);
+                let mut matches: Vec<(usize, usize)> = inner
+                    .find_all(&lowered.lower)
+                    .into_iter()
+                    .map(|span| lowered.span(text, span))
+                    .collect();
+                // Empty matches inside one char's lowercase form land on the
+                // same offset
+                matches.dedup();
+                matches
             }
         }
     }
@@ -3422,22 +6026,24 @@ impl Matcher {
 
 fn compile_ast(ast: &Ast) -> Result<Matcher, PatternError> {
     match ast {
-        Ast::Literal(lit) => Ok(Matcher::Literal(lit.clone())),
+        Ast::Literal(lit) => Ok(Matcher::Literal(LiteralMatcher::new(lit.clone()))),
         Ast::Dot => {
-            // Dot matches any character except newline
-            // Parse as [^\n] character class
+            // Dot matches any character except newline: [^\n]
             use crate::parser::charclass::CharClass;
-            let char_class = CharClass::parse(r"^\n")
-                .map_err(|e| PatternError::ParseError(format!("Dot charclass: {}", e)))?;
+            let mut char_class = CharClass::new();
+            char_class.add_char('\n');
+            char_class.negate();
+            char_class.finalize();
             Ok(Matcher::CharClass(char_class))
         }
         Ast::Alternation(parts) => {
-            use aho_corasick::MatchKind;
-            let ac = AhoCorasick::builder()
-                .match_kind(MatchKind::LeftmostFirst)
-                .build(parts)
-                .map_err(|e| PatternError::ParseError(format!("Aho-Corasick: {}", e)))?;
-            Ok(Matcher::MultiLiteral(ac))
+            let searcher = LiteralSearcher::new(parts, false)
+                .map_err(|e| PatternError::parse(format!("Multi-literal: {}", e)))?;
+            Ok(Matcher::MultiLiteral {
+                searcher,
+                literals: parts.clone(),
+                ascii_case_insensitive: false,
+            })
         }
         Ast::Anchored {
             literal,
@@ -3462,11 +6068,81 @@ fn compile_ast(ast: &Ast) -> Result<Matcher, PatternError> {
                 });
             }
 
+            // `^` sequence: a DFA that only runs from the start of the text
+            if let (true, false, Ast::Sequence(seq)) = (*start, *end, inner.as_ref()) {
+                let dfa = engine::dfa::DFA::try_compile(seq)
+                    .map(Matcher::DFA)
+                    .or_else(|| {
+                        engine::lazy_dfa::LazyDFA::try_compile_anchored(seq).map(Matcher::LazyDFA)
+                    });
+                if let Some(dfa) = dfa {
+                    return Ok(Matcher::AnchoredPattern {
+                        inner: Box::new(dfa),
+                        start: true,
+                        end: false,
+                        reverse: None,
+                    });
+                }
+            }
+
+            // `^...$` sequence: one pass over the text decides it, stopping
+            // as soon as no match can continue
+            if let (true, true, Ast::Sequence(seq)) = (*start, *end, inner.as_ref()) {
+                if let Some(dfa) = engine::lazy_dfa::LazyDFA::try_compile_entire(seq) {
+                    return Ok(Matcher::AnchoredPattern {
+                        inner: Box::new(Matcher::LazyDFA(dfa)),
+                        start: true,
+                        end: true,
+                        reverse: None,
+                    });
+                }
+            }
+
+            // `$` sequence: the trailing literal can only end the text, and
+            // the rest is read backward from it
+            let end_elements = match (*start, *end) {
+                (false, true) => sequence_elements(inner, false),
+                _ => None,
+            };
+            if let Some(elements) = end_elements {
+                let body_len = elements
+                    .iter()
+                    .rposition(|elem| {
+                        !matches!(
+                            elem,
+                            parser::sequence::SequenceElement::Char(_)
+                                | parser::sequence::SequenceElement::Literal(_)
+                        )
+                    })
+                    .map_or(0, |last| last + 1);
+                let (body, tail) = elements.split_at(body_len);
+                if let Some(reverse) = engine::lazy_dfa::LazyDFA::try_compile_reverse(body) {
+                    let suffix = tail
+                        .iter()
+                        .map(|elem| match elem {
+                            parser::sequence::SequenceElement::Char(ch) => ch.to_string(),
+                            parser::sequence::SequenceElement::Literal(lit) => lit.clone(),
+                            _ => unreachable!(),
+                        })
+                        .collect();
+                    return Ok(Matcher::SuffixAnchored { suffix, reverse });
+                }
+            }
+
+            // With groups to report the match still starts where the pattern
+            // read backward without them says
+            let reverse = match (*start, *end) {
+                (false, true) => sequence_elements(inner, true)
+                    .and_then(|elements| engine::lazy_dfa::LazyDFA::try_compile_reverse(&elements)),
+                _ => None,
+            };
+
             let inner_matcher = compile_ast(inner)?;
             Ok(Matcher::AnchoredPattern {
                 inner: Box::new(inner_matcher),
                 start: *start,
                 end: *end,
+                reverse,
             })
         }
         Ast::CharClass(cc) => Ok(Matcher::CharClass(cc.clone())),
@@ -3488,24 +6164,26 @@ fn compile_ast(ast: &Ast) -> Result<Matcher, PatternError> {
             Ok(Matcher::Quantified(qp.clone()))
         }
         Ast::Sequence(seq) => {
-            // Lazy DFA is experimental and currently slower - disabled for now
-            // TODO: Optimize LazyDFA implementation
-            // if let Some(lazy_dfa) = engine::lazy_dfa::LazyDFA::try_compile(seq) {
-            //     return Ok(Matcher::LazyDFA(lazy_dfa));
-            // }
-
             // Try to compile to DFA for better performance
             if let Some(dfa) = engine::dfa::DFA::try_compile(seq) {
                 return Ok(Matcher::DFA(dfa));
             }
+            // Counted repetitions without a literal prefix: lazy DFA
+            if let Some(lazy_dfa) = engine::lazy_dfa::LazyDFA::try_compile(seq) {
+                return Ok(Matcher::LazyDFA(lazy_dfa));
+            }
             // Fallback to regular sequence matcher
             Ok(Matcher::Sequence(seq.clone()))
         }
         Ast::Group(group) => Ok(Matcher::Group(group.clone())),
         Ast::Boundary(boundary_type) => Ok(Matcher::Boundary(*boundary_type)),
         Ast::Lookaround(lookaround) => {
-            // Compile the inner pattern of the lookaround
-            let inner_matcher = compile_ast(&lookaround.pattern)?;
+            // Compile the inner pattern of the lookaround; a plain literal
+            // stays a literal so candidates come from memmem
+            let inner_matcher = match literal_from_ast(&lookaround.pattern) {
+                Some(lit) => Matcher::Literal(LiteralMatcher::new(lit)),
+                None => compile_ast(&lookaround.pattern)?,
+            };
             Ok(Matcher::Lookaround(
                 Box::new(lookaround.clone()),
                 Box::new(inner_matcher),
@@ -3584,21 +6262,20 @@ fn compile_ast(ast: &Ast) -> Result<Matcher, PatternError> {
             })
         }
         Ast::Backreference(group_num) => Ok(Matcher::Backreference(*group_num)),
-        Ast::DotAll => {
-            // DotAll matches ANY character including newline
-            // Create a character class that matches everything
-            use crate::parser::charclass::CharClass;
-            // Use empty negated class which matches everything
-            let mut char_class = CharClass::new();
-            char_class.add_range('\0', char::MAX); // Match all unicode
-            char_class.finalize();
-            Ok(Matcher::CharClass(char_class))
-        }
-        Ast::SequenceWithFlags(seq, flags) => {
-            // Compile sequence with flag awareness
-            Ok(Matcher::SequenceWithFlags(seq.clone(), *flags))
-        }
         Ast::CaseInsensitive(inner) => {
+            // ASCII literal alternations fold case inside the searcher itself,
+            // so the text never has to be lowercased
+            if let Ast::Alternation(parts) = inner.as_ref() {
+                if parts.iter().all(|p| p.is_ascii()) {
+                    let searcher = LiteralSearcher::new(parts, true)
+                        .map_err(|e| PatternError::parse(format!("Multi-literal: {}", e)))?;
+                    return Ok(Matcher::MultiLiteral {
+                        searcher,
+                        literals: parts.clone(),
+                        ascii_case_insensitive: true,
+                    });
+                }
+            }
             // Lowercase the pattern before compiling
             let lowercased = lowercase_ast(inner);
             let inner_matcher = compile_ast(&lowercased)?;
@@ -3607,6 +6284,154 @@ fn compile_ast(ast: &Ast) -> Result<Matcher, PatternError> {
     }
 }
 
+/// `ast` as the elements of one sequence, when it holds nothing else a
+/// sequence can't; captures make it `None` unless `drop_groups`, which
+/// keeps what they match without the group
+fn sequence_elements(
+    ast: &Ast,
+    drop_groups: bool,
+) -> Option<Vec<parser::sequence::SequenceElement>> {
+    use parser::quantifier::QuantifiedElement;
+    use parser::sequence::SequenceElement;
+
+    match ast {
+        Ast::Sequence(seq) => Some(seq.elements.clone()),
+        Ast::Literal(lit) => Some(vec![SequenceElement::Literal(lit.clone())]),
+        Ast::Quantified(q) => Some(vec![match &q.element {
+            QuantifiedElement::Char(ch) => {
+                SequenceElement::QuantifiedChar(*ch, q.quantifier.clone())
+            }
+            QuantifiedElement::CharClass(cc) => {
+                SequenceElement::QuantifiedCharClass(cc.clone(), q.quantifier.clone())
+            }
+        }]),
+        Ast::Group(group) if !group.capturing || drop_groups => {
+            Some(vec![SequenceElement::Group(Group {
+                capturing: false,
+                ..group.clone()
+            })])
+        }
+        Ast::Capture(inner, _) if drop_groups => sequence_elements(inner, drop_groups),
+        Ast::QuantifiedCapture(inner, quantifier) => Some(vec![SequenceElement::QuantifiedGroup(
+            sequence_group(inner, drop_groups)?,
+            quantifier.clone(),
+        )]),
+        // Non-capturing groups next to other elements: x(?:ab)+
+        Ast::PatternWithCaptures { elements, .. } => {
+            let mut flat = Vec::new();
+            for element in elements {
+                match element {
+                    CaptureElement::NonCapture(ast) => {
+                        flat.extend(sequence_elements(ast, drop_groups)?)
+                    }
+                    CaptureElement::Capture(ast, _) if drop_groups => {
+                        flat.extend(sequence_elements(ast, drop_groups)?)
+                    }
+                    CaptureElement::Capture(..) => return None,
+                }
+            }
+            Some(flat)
+        }
+        _ => None,
+    }
+}
+
+/// `ast` as a non-capturing group of sequences, for repeating it
+fn sequence_group(ast: &Ast, drop_groups: bool) -> Option<Group> {
+    use parser::group::GroupContent;
+    use parser::sequence::SequenceElement;
+
+    let content = match ast {
+        Ast::Alternation(literals) => GroupContent::ParsedAlternation(
+            literals
+                .iter()
+                .map(|lit| Sequence::new(vec![SequenceElement::Literal(lit.clone())]))
+                .collect(),
+        ),
+        Ast::AlternationWithCaptures { branches, .. } => GroupContent::ParsedAlternation(
+            branches
+                .iter()
+                .map(|branch| sequence_elements(branch, drop_groups).map(Sequence::new))
+                .collect::<Option<_>>()?,
+        ),
+        _ => GroupContent::Sequence(Sequence::new(sequence_elements(ast, drop_groups)?)),
+    };
+    Some(Group::new_non_capturing(content))
+}
+
+/// Most chars a match of `ast` can span, or `None` when unbounded (or too
+/// irregular to tell)
+fn max_char_len(ast: &Ast) -> Option<usize> {
+    match ast {
+        Ast::Literal(lit) | Ast::Anchored { literal: lit, .. } => Some(lit.chars().count()),
+        Ast::Dot | Ast::CharClass(_) => Some(1),
+        Ast::Boundary(_) => Some(0),
+        Ast::Alternation(branches) => branches.iter().map(|b| b.chars().count()).max(),
+        Ast::Quantified(qp) => bounded_repeat(1, &qp.quantifier),
+        Ast::Sequence(seq) => sequence_max_char_len(seq),
+        Ast::Group(group) => group_max_char_len(group),
+        Ast::Capture(inner, _) | Ast::CaseInsensitive(inner) => max_char_len(inner),
+        Ast::QuantifiedCapture(inner, quantifier) => {
+            bounded_repeat(max_char_len(inner)?, quantifier)
+        }
+        _ => None,
+    }
+}
+
+/// `len` repeated as often as `quantifier` allows, if that is bounded
+fn bounded_repeat(len: usize, quantifier: &parser::quantifier::Quantifier) -> Option<usize> {
+    match quantifier.max_matches() {
+        usize::MAX => None,
+        max => len.checked_mul(max),
+    }
+}
+
+fn sequence_max_char_len(seq: &Sequence) -> Option<usize> {
+    use parser::sequence::SequenceElement;
+
+    seq.elements.iter().try_fold(0usize, |total, element| {
+        let len = match element {
+            SequenceElement::Char(_) | SequenceElement::Dot | SequenceElement::CharClass(_) => 1,
+            SequenceElement::QuantifiedChar(_, q) | SequenceElement::QuantifiedCharClass(_, q) => {
+                bounded_repeat(1, q)?
+            }
+            SequenceElement::Literal(lit) => lit.chars().count(),
+            SequenceElement::Group(group) => group_max_char_len(group)?,
+            SequenceElement::QuantifiedGroup(group, q) => {
+                bounded_repeat(group_max_char_len(group)?, q)?
+            }
+            SequenceElement::Boundary(_) => 0,
+            // \r\n
+            SequenceElement::LineBreak => 2,
+        };
+        total.checked_add(len)
+    })
+}
+
+fn group_max_char_len(group: &Group) -> Option<usize> {
+    let branch_max = |source: &String| {
+        parse_pattern(source)
+            .ok()
+            .and_then(|ast| max_char_len(&ast))
+    };
+    let len = match &group.content {
+        parser::group::GroupContent::Single(source) => branch_max(source)?,
+        parser::group::GroupContent::Alternation(branches) => branches
+            .iter()
+            .map(branch_max)
+            .try_fold(0, |max, len| len.map(|len| max.max(len)))?,
+        parser::group::GroupContent::Sequence(seq) => sequence_max_char_len(seq)?,
+        parser::group::GroupContent::ParsedAlternation(seqs) => seqs
+            .iter()
+            .map(sequence_max_char_len)
+            .try_fold(0, |max, len| len.map(|len| max.max(len)))?,
+    };
+    match &group.quantifier {
+        Some(quantifier) => bounded_repeat(len, quantifier),
+        None => Some(len),
+    }
+}
+
 fn literal_from_ast(ast: &Ast) -> Option<String> {
     match ast {
         Ast::Literal(lit) => Some(lit.clone()),
@@ -3738,28 +6563,6 @@ fn lowercase_ast(ast: &Ast) -> Ast {
             start: *start,
             end: *end,
         },
-        Ast::SequenceWithFlags(seq, flags) => {
-            let mut new_seq = seq.clone();
-            new_seq.elements = new_seq
-                .elements
-                .into_iter()
-                .map(|elem| match elem {
-                    parser::sequence::SequenceElement::Literal(s) => {
-                        parser::sequence::SequenceElement::Literal(s.to_lowercase())
-                    }
-                    parser::sequence::SequenceElement::Char(c) => {
-                        let lowered: String = c.to_lowercase().collect();
-                        if lowered.len() == 1 {
-                            parser::sequence::SequenceElement::Char(lowered.chars().next().unwrap())
-                        } else {
-                            parser::sequence::SequenceElement::Literal(lowered)
-                        }
-                    }
-                    other => other,
-                })
-                .collect();
-            Ast::SequenceWithFlags(new_seq, *flags)
-        }
         Ast::CaseInsensitive(inner) => {
             // Already case-insensitive, just lowercase inner
             Ast::CaseInsensitive(Box::new(lowercase_ast(inner)))
@@ -3817,13 +6620,35 @@ fn quantifier_bounds(q: &parser::quantifier::Quantifier) -> (usize, usize) {
 /// Check if CharClass matches \d pattern (only [0-9])
 fn is_digit_charclass(cc: &CharClass) -> bool {
     // Check if ranges contain exactly [0-9] and no other chars
-    cc.ranges.len() == 1 && cc.ranges[0] == ('0', '9') && cc.chars.is_empty() && !cc.negated
+    cc.ranges.len() == 1
+        && cc.ranges[0] == ('0', '9')
+        && cc.chars.is_empty()
+        && !cc.negated
+        && cc.properties.is_empty()
 }
 
 /// Check if CharClass matches \w pattern ([a-zA-Z0-9_])
+/// Fast path for a compiled run of word chars or of another ASCII-only
+/// class, however the class was spelled: `[a-zA-Z0-9_]+`, `[\w.-]+`
+fn class_run_fast_path(matcher: &Matcher) -> Option<optimization::fast_path::FastPath> {
+    use optimization::fast_path::FastPath;
+    match matcher {
+        Matcher::WordRun => Some(FastPath::WordRun),
+        Matcher::DigitRun => Some(FastPath::DigitRun),
+        Matcher::Quantified(qp) => match (&qp.quantifier, &qp.element) {
+            (
+                parser::quantifier::Quantifier::OneOrMore,
+                parser::quantifier::QuantifiedElement::CharClass(cc),
+            ) => cc.ascii_only_bitmap().map(FastPath::AsciiClassRun),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 fn is_word_charclass(cc: &CharClass) -> bool {
     // Check if ranges contain [a-z], [A-Z], [0-9] and chars contain '_'
-    if cc.negated || cc.ranges.len() != 3 {
+    if cc.negated || cc.ranges.len() != 3 || !cc.properties.is_empty() {
         return false;
     }
 
@@ -3855,9 +6680,7 @@ fn parse_lookaround(pattern: &str, depth: usize) -> Result<Ast, PatternError> {
     } else if pattern.starts_with("(?<!") {
         LookaroundType::NegativeLookbehind
     } else {
-        return Err(PatternError::ParseError(
-            "Invalid lookaround syntax".to_string(),
-        ));
+        return Err(PatternError::parse("Invalid lookaround syntax"));
     };
 
     // Find the matching closing parenthesis
@@ -3869,13 +6692,15 @@ fn parse_lookaround(pattern: &str, depth: usize) -> Result<Ast, PatternError> {
 
     if let Some(close_idx) = find_matching_paren(pattern, 0) {
         let inner = &pattern[prefix_len..close_idx];
-        let inner_ast = parse_pattern_with_depth(inner, depth + 1)?;
+        let inner_ast =
+            parse_pattern_with_depth(inner, depth + 1).map_err(|e| e.offset_by(prefix_len))?;
 
         // Check if there's a suffix after the lookaround
         if close_idx != pattern.len() - 1 {
             // This is a lookaround with suffix
             let suffix = &pattern[close_idx + 1..];
-            let suffix_ast = parse_pattern_with_depth(suffix, depth + 1)?;
+            let suffix_ast = parse_pattern_with_depth(suffix, depth + 1)
+                .map_err(|e| e.offset_by(close_idx + 1))?;
 
             // For lookbehind: (?<=foo)bar - match bar only if preceded by foo
             // For lookahead: (?=foo)bar - doesn't make semantic sense
@@ -3883,22 +6708,29 @@ fn parse_lookaround(pattern: &str, depth: usize) -> Result<Ast, PatternError> {
                 lookaround_type,
                 LookaroundType::PositiveLookbehind | LookaroundType::NegativeLookbehind
             ) {
-                let lookbehind = Lookaround::new(lookaround_type, inner_ast);
+                let lookbehind = Lookaround::new(lookaround_type, inner_ast)?;
                 return Ok(Ast::LookbehindWithSuffix {
                     lookbehind,
                     suffix: Box::new(suffix_ast),
                 });
             } else {
-                return Err(PatternError::ParseError(
-                    "Lookahead cannot have suffix pattern after it".to_string(),
+                return Err(PatternError::parse_at(
+                    "Lookahead cannot have suffix pattern after it",
+                    close_idx + 1,
+                    suffix,
                 ));
             }
         }
 
-        Ok(Ast::Lookaround(Lookaround::new(lookaround_type, inner_ast)))
+        Ok(Ast::Lookaround(Lookaround::new(
+            lookaround_type,
+            inner_ast,
+        )?))
     } else {
-        Err(PatternError::ParseError(
-            "Unmatched parenthesis in lookaround".to_string(),
+        Err(PatternError::parse_at(
+            "Unmatched parenthesis in lookaround",
+            0,
+            &pattern[..prefix_len],
         ))
     }
 }
@@ -3936,67 +6768,93 @@ fn parse_combined_with_lookaround(pattern: &str, depth: usize) -> Result<Ast, Pa
             let prefix_len = lookaround_start.len();
             if let Some(close_idx) = find_matching_paren(lookaround_part, 0) {
                 if close_idx != lookaround_part.len() - 1 {
-                    return Err(PatternError::ParseError(
-                        "Extra characters after lookaround".to_string(),
+                    return Err(PatternError::parse_at(
+                        "Extra characters after lookaround",
+                        pos + close_idx + 1,
+                        &lookaround_part[close_idx + 1..],
                     ));
                 }
 
                 let inner = &lookaround_part[prefix_len..close_idx];
-                let inner_ast = parse_pattern_with_depth(inner, depth + 1)?;
+                let inner_ast = parse_pattern_with_depth(inner, depth + 1)
+                    .map_err(|e| e.offset_by(pos + prefix_len))?;
 
-                let lookaround = Lookaround::new(lookaround_type, inner_ast);
+                let lookaround = Lookaround::new(lookaround_type, inner_ast)?;
 
                 return Ok(Ast::CombinedWithLookaround {
                     prefix: Box::new(prefix_ast),
                     lookaround,
                 });
             } else {
-                return Err(PatternError::ParseError(
-                    "Unmatched parenthesis in lookaround".to_string(),
+                return Err(PatternError::parse_at(
+                    "Unmatched parenthesis in lookaround",
+                    pos,
+                    lookaround_start,
                 ));
             }
         }
     }
 
-    Err(PatternError::ParseError(
-        "No lookaround found in pattern".to_string(),
-    ))
+    Err(PatternError::parse("No lookaround found in pattern"))
 }
 
-/// Find the index of the matching closing parenthesis
-/// Returns None if no match found
-/// Check if a pattern contains unescaped parentheses (not \( or \) and not inside [...])
-fn contains_unescaped_paren(pattern: &str) -> bool {
+/// Byte offsets of the parentheses that are pattern syntax: not escaped
+/// (`\(`) and not members of a bracket class (`[()]`)
+fn unescaped_parens(pattern: &str) -> impl Iterator<Item = usize> + '_ {
     let bytes = pattern.as_bytes();
     let mut i = 0;
-    while i < bytes.len() {
-        if bytes[i] == b'\\' && i + 1 < bytes.len() {
-            i += 2; // Skip escaped character
-        } else if bytes[i] == b'[' {
-            // Skip character class to avoid counting parens inside it
-            i += 1;
-            if i < bytes.len() && bytes[i] == b'^' {
-                i += 1;
-            }
-            while i < bytes.len() {
-                if bytes[i] == b'\\' {
-                    i += 2;
-                } else if bytes[i] == b']' {
+    core::iter::from_fn(move || {
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\\' => i += 2, // Skip escaped character
+                b'[' => {
+                    // Skip character class to avoid counting parens inside it
                     i += 1;
-                    break;
-                } else {
+                    if bytes.get(i) == Some(&b'^') {
+                        i += 1;
+                    }
+                    while i < bytes.len() {
+                        match bytes[i] {
+                            b'\\' => i += 2,
+                            b']' => {
+                                i += 1;
+                                break;
+                            }
+                            _ => i += 1,
+                        }
+                    }
+                }
+                b'(' | b')' => {
                     i += 1;
+                    return Some(i - 1);
                 }
+                _ => i += 1,
             }
-        } else if bytes[i] == b'(' || bytes[i] == b')' {
-            return true;
-        } else {
-            i += 1;
         }
-    }
-    false
+        None
+    })
+}
+
+/// Check if a pattern contains unescaped parentheses (not \( or \) and not inside [...])
+fn contains_unescaped_paren(pattern: &str) -> bool {
+    unescaped_parens(pattern).next().is_some()
+}
+
+/// Check if a pattern opens a capturing group: an unescaped `(` outside
+/// brackets that isn't `(?...)`
+fn contains_capture_group(pattern: &str) -> bool {
+    let bytes = pattern.as_bytes();
+    unescaped_parens(pattern).any(|i| bytes[i] == b'(' && bytes.get(i + 1) != Some(&b'?'))
 }
 
+/// Check if a pattern opens a group starting with `opener`, e.g. `(?:`,
+/// ignoring escaped parens and parens inside brackets
+fn contains_group_opener(pattern: &str, opener: &str) -> bool {
+    unescaped_parens(pattern).any(|i| pattern[i..].starts_with(opener))
+}
+
+/// Find the index of the matching closing parenthesis
+/// Returns None if no match found
 fn find_matching_paren(pattern: &str, start: usize) -> Option<usize> {
     let bytes = pattern.as_bytes();
     if start >= bytes.len() || bytes[start] != b'(' {
@@ -4051,10 +6909,100 @@ fn find_matching_paren(pattern: &str, start: usize) -> Option<usize> {
 /// Returns an AST that represents a sequence with captures
 fn parse_pattern_with_captures(pattern: &str) -> Result<Ast, PatternError> {
     let mut group_counter = 1;
-    let (ast, _total_groups) = parse_pattern_with_captures_inner(pattern, &mut group_counter)?;
+    let (ast, _total_groups) = parse_pattern_with_captures_inner(pattern, &mut group_counter, 0)?;
     Ok(ast)
 }
 
+/// Byte offsets of the `$` anchors and `\z`/`\Z` escapes in `pattern`,
+/// outside classes
+fn end_anchor_offsets(pattern: &str) -> Vec<usize> {
+    let bytes = pattern.as_bytes();
+    let mut offsets = Vec::new();
+    let mut in_class = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => {
+                if !in_class && matches!(bytes.get(i + 1), Some(b'z') | Some(b'Z')) {
+                    offsets.push(i);
+                }
+                i += 2;
+                continue;
+            }
+            b'[' if !in_class => {
+                in_class = true;
+                i += 1;
+                // A leading `]` (after an optional `^`) is a literal member
+                if bytes.get(i) == Some(&b'^') {
+                    i += 1;
+                }
+                if bytes.get(i) == Some(&b']') {
+                    i += 1;
+                }
+                continue;
+            }
+            b']' if in_class => in_class = false,
+            b'$' if !in_class => offsets.push(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    offsets
+}
+
+/// Whether `pattern` has a `$` anchor anywhere outside a class
+fn contains_end_anchor(pattern: &str) -> bool {
+    end_anchor_offsets(pattern)
+        .iter()
+        .any(|&at| pattern.as_bytes()[at] == b'$')
+}
+
+/// `pattern` with `\z` and `\Z` written as `$`, and whether that `$` is the
+/// strict `\z`; `None` when there are neither
+///
+/// `$` can only mean one of the two in a pattern, so `\z` can't be mixed
+/// with `$` or `\Z`.
+fn rewrite_end_of_text_escapes(pattern: &str) -> Result<Option<(String, bool)>, PatternError> {
+    let offsets = end_anchor_offsets(pattern);
+    let escapes: Vec<usize> = offsets
+        .iter()
+        .copied()
+        .filter(|&at| pattern.as_bytes()[at] == b'\\')
+        .collect();
+    if escapes.is_empty() {
+        return Ok(None);
+    }
+    let strict = escapes.iter().any(|&at| pattern.as_bytes()[at + 1] == b'z');
+    if strict
+        && offsets.len()
+            > escapes
+                .iter()
+                .filter(|&&at| pattern.as_bytes()[at + 1] == b'z')
+                .count()
+    {
+        return Err(PatternError::UnsupportedFeature(UnsupportedFeature::Other(
+            "\\z together with $ or \\Z".to_string(),
+        )));
+    }
+    let mut rewritten = String::with_capacity(pattern.len());
+    let mut from = 0;
+    for at in escapes {
+        rewritten.push_str(&pattern[from..at]);
+        rewritten.push('$');
+        from = at + 2;
+    }
+    rewritten.push_str(&pattern[from..]);
+    Ok(Some((rewritten, strict)))
+}
+
+/// Whether `pattern` ends with a `$` anchor rather than an escaped `\$`
+fn ends_with_end_anchor(pattern: &str) -> bool {
+    match pattern.strip_suffix('$') {
+        Some(body) => body.bytes().rev().take_while(|&b| b == b'\\').count() % 2 == 0,
+        None => false,
+    }
+}
+
 /// Split pattern by top-level '|' characters (not inside groups)
 /// Returns None if no top-level alternation found
 fn split_by_alternation(pattern: &str) -> Option<Vec<String>> {
@@ -4123,21 +7071,87 @@ fn split_by_alternation(pattern: &str) -> Option<Vec<String>> {
     }
 }
 
+/// Parse the quantifier (if any) that follows a group closing before `pos`
+///
+/// Handles `*`, `+`, `?` and their lazy forms as well as `{n}`, `{n,}` and
+/// `{n,m}`. Returns the quantifier and the position just past it.
+fn parse_group_quantifier(
+    pattern: &str,
+    pos: usize,
+) -> Result<(Option<parser::quantifier::Quantifier>, usize), PatternError> {
+    use parser::quantifier::Quantifier;
+
+    let remaining = &pattern[pos..];
+    let lazy = remaining
+        .get(1..)
+        .is_some_and(|after| after.starts_with('?'));
+    let quantifier = match remaining.chars().next() {
+        Some('*') if lazy => (Quantifier::ZeroOrMoreLazy, 2),
+        Some('*') => (Quantifier::ZeroOrMore, 1),
+        Some('+') if lazy => (Quantifier::OneOrMoreLazy, 2),
+        Some('+') => (Quantifier::OneOrMore, 1),
+        Some('?') if lazy => (Quantifier::ZeroOrOneLazy, 2),
+        Some('?') => (Quantifier::ZeroOrOne, 1),
+        Some('{') => {
+            let Some(close) = remaining.find('}') else {
+                return Ok((None, pos));
+            };
+            let inner = &remaining[1..close];
+            if inner.is_empty() || !inner.bytes().all(|b| b.is_ascii_digit() || b == b',') {
+                // Not a repetition, e.g. a literal `{`
+                return Ok((None, pos));
+            }
+            let invalid =
+                || PatternError::parse_at("Invalid quantifier", pos, &remaining[..=close]);
+            let quantifier = match inner.split_once(',') {
+                None => Quantifier::Exactly(inner.parse().map_err(|_| invalid())?),
+                Some((min, "")) => Quantifier::AtLeast(min.parse().map_err(|_| invalid())?),
+                Some((min, max)) => {
+                    let min: usize = min.parse().map_err(|_| invalid())?;
+                    let max: usize = max.parse().map_err(|_| invalid())?;
+                    if min > max {
+                        return Err(invalid());
+                    }
+                    Quantifier::Between(min, max)
+                }
+            };
+            if remaining[close + 1..].starts_with('?') {
+                return Err(PatternError::parse_at(
+                    "Lazy bounded quantifiers not yet supported",
+                    pos,
+                    &remaining[..close + 2],
+                ));
+            }
+            (quantifier, close + 1)
+        }
+        _ => return Ok((None, pos)),
+    };
+    Ok((Some(quantifier.0), pos + quantifier.1))
+}
+
 /// Inner recursive parser that tracks group numbers across nested captures
 fn parse_pattern_with_captures_inner(
     pattern: &str,
     group_counter: &mut usize,
+    depth: usize,
 ) -> Result<(Ast, usize), PatternError> {
+    if depth > MAX_RECURSION_DEPTH {
+        return Err(PatternError::parse("Pattern too deeply nested"));
+    }
+
     // FIRST: Check if this pattern contains top-level alternation
     if let Some(branches) = split_by_alternation(pattern) {
         // This is an alternation pattern like (a)|(b) or foo|bar
         let _start_group = *group_counter;
         let mut parsed_branches = Vec::new();
 
+        let mut branch_offset = 0;
         for branch in branches {
             // Parse each branch independently
-            let (branch_ast, _) = parse_pattern_with_captures_inner(&branch, group_counter)?;
+            let (branch_ast, _) = parse_pattern_with_captures_inner(&branch, group_counter, depth)
+                .map_err(|e| e.offset_by(branch_offset))?;
             parsed_branches.push(branch_ast);
+            branch_offset += branch.len() + 1; // Skip the '|' separator
         }
 
         let total_groups = *group_counter - 1;
@@ -4211,48 +7225,12 @@ fn parse_pattern_with_captures_inner(
             if let Some(close_idx) = find_matching_paren(pattern, pos) {
                 // Parse the content as a non-capturing group (recursive)
                 let inner = &pattern[pos + 3..close_idx]; // Skip "(?:"
-                let (inner_ast, _) = parse_pattern_with_captures_inner(inner, group_counter)?;
+                let (inner_ast, _) =
+                    parse_pattern_with_captures_inner(inner, group_counter, depth + 1)
+                        .map_err(|e| e.offset_by(pos + 3))?;
 
                 // Check for quantifier after the non-capturing group (same as capturing groups)
-                let mut after_group = close_idx + 1;
-                let mut quantifier: Option<parser::quantifier::Quantifier> = None;
-
-                if after_group < pattern.len() {
-                    let remaining = &pattern[after_group..];
-                    let chars: Vec<char> = remaining.chars().take(2).collect();
-                    if !chars.is_empty() {
-                        let first = chars[0];
-                        let has_lazy = chars.len() > 1 && chars[1] == '?';
-
-                        match first {
-                            '*' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrMoreLazy);
-                                after_group += 2;
-                            }
-                            '*' => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrMore);
-                                after_group += 1;
-                            }
-                            '+' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::OneOrMoreLazy);
-                                after_group += 2;
-                            }
-                            '+' => {
-                                quantifier = Some(parser::quantifier::Quantifier::OneOrMore);
-                                after_group += 1;
-                            }
-                            '?' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrOneLazy);
-                                after_group += 2;
-                            }
-                            '?' => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrOne);
-                                after_group += 1;
-                            }
-                            _ => {}
-                        }
-                    }
-                }
+                let (quantifier, after_group) = parse_group_quantifier(pattern, close_idx + 1)?;
 
                 // Build the non-capture element with optional quantifier
                 if let Some(q) = quantifier {
@@ -4266,9 +7244,7 @@ fn parse_pattern_with_captures_inner(
                 }
                 pos = after_group;
             } else {
-                return Err(PatternError::ParseError(
-                    "Unmatched parenthesis".to_string(),
-                ));
+                return Err(PatternError::parse_at("Unmatched parenthesis", pos, "("));
             }
         } else if pattern[pos..].starts_with('(') && !pattern[pos..].starts_with("(?") {
             // Found a capture group
@@ -4278,48 +7254,12 @@ fn parse_pattern_with_captures_inner(
 
                 // Parse the content of the capture (recursive, may have nested captures)
                 let inner = &pattern[pos + 1..close_idx];
-                let (inner_ast, _) = parse_pattern_with_captures_inner(inner, group_counter)?;
+                let (inner_ast, _) =
+                    parse_pattern_with_captures_inner(inner, group_counter, depth + 1)
+                        .map_err(|e| e.offset_by(pos + 1))?;
 
                 // Check for quantifier after the group
-                let mut after_group = close_idx + 1;
-                let mut quantifier: Option<parser::quantifier::Quantifier> = None;
-
-                if after_group < pattern.len() {
-                    let remaining = &pattern[after_group..];
-                    let chars: Vec<char> = remaining.chars().take(2).collect();
-                    if !chars.is_empty() {
-                        let first = chars[0];
-                        let has_lazy = chars.len() > 1 && chars[1] == '?';
-
-                        match first {
-                            '*' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrMoreLazy);
-                                after_group += 2;
-                            }
-                            '*' => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrMore);
-                                after_group += 1;
-                            }
-                            '+' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::OneOrMoreLazy);
-                                after_group += 2;
-                            }
-                            '+' => {
-                                quantifier = Some(parser::quantifier::Quantifier::OneOrMore);
-                                after_group += 1;
-                            }
-                            '?' if has_lazy => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrOneLazy);
-                                after_group += 2;
-                            }
-                            '?' => {
-                                quantifier = Some(parser::quantifier::Quantifier::ZeroOrOne);
-                                after_group += 1;
-                            }
-                            _ => {}
-                        }
-                    }
-                }
+                let (quantifier, after_group) = parse_group_quantifier(pattern, close_idx + 1)?;
 
                 // Build the capture AST with optional quantifier
                 if let Some(q) = quantifier {
@@ -4332,18 +7272,24 @@ fn parse_pattern_with_captures_inner(
                 }
                 pos = after_group;
             } else {
-                return Err(PatternError::ParseError(
-                    "Unmatched parenthesis".to_string(),
-                ));
+                return Err(PatternError::parse_at("Unmatched parenthesis", pos, "("));
             }
         } else {
             // Check for backreference \1, \2, etc. AT CURRENT POSITION
             if pattern[pos..].starts_with('\\') && pos + 1 < pattern.len() {
-                let next_char = pattern.chars().nth(pos + 1);
+                let next_char = pattern[pos + 1..].chars().next();
                 if let Some(ch) = next_char {
                     if ch.is_ascii_digit() {
                         // This is a backreference like \1
                         let digit = ch.to_digit(10).unwrap() as usize;
+                        // Only groups already opened can be referred back to
+                        if digit >= *group_counter {
+                            return Err(PatternError::parse_at(
+                                format!("Backreference to undefined group {}", digit),
+                                pos,
+                                &pattern[pos..pos + 2],
+                            ));
+                        }
                         elements.push(CaptureElement::NonCapture(Ast::Backreference(digit)));
                         pos += 2; // Skip \1
                         continue;
@@ -4390,10 +7336,11 @@ fn parse_pattern_with_captures_inner(
             let mut search_pos = pos;
             let mut next_backref = pattern.len();
 
-            while search_pos < pattern.len() {
-                if pattern[search_pos..].starts_with('\\') && search_pos + 1 < pattern.len() {
-                    let next_ch = pattern.chars().nth(search_pos + 1);
-                    if next_ch.map(|c| c.is_ascii_digit()).unwrap_or(false) {
+            // Scan bytes: `\\` and digits are ASCII, so they never sit inside a char
+            let bytes = pattern.as_bytes();
+            while search_pos < bytes.len() {
+                if bytes[search_pos] == b'\\' && search_pos + 1 < bytes.len() {
+                    if bytes[search_pos + 1].is_ascii_digit() {
                         next_backref = search_pos;
                         break;
                     }
@@ -4415,7 +7362,7 @@ fn parse_pattern_with_captures_inner(
                     Ast::Literal(String::new())
                 } else {
                     // Use basic parsing for non-capture segments
-                    parse_pattern(segment)?
+                    parse_pattern(segment).map_err(|e| e.offset_by(pos))?
                 };
 
                 elements.push(CaptureElement::NonCapture(segment_ast));
@@ -4479,6 +7426,352 @@ mod tests {
         assert!(!p.is_match("hello world"));
     }
 
+    #[test]
+    fn as_str_returns_source() {
+        for source in ["hello", "^(foo|bar)$", r"(\w+)@(\w+)\.com", "(?i)abc", ""] {
+            assert_eq!(Pattern::new(source).unwrap().as_str(), source);
+        }
+    }
+
+    #[test]
+    fn display_shows_source() {
+        let p = Pattern::new(r"\d+").unwrap();
+        assert_eq!(format!("{}", p), r"\d+");
+        assert_eq!(p.to_string(), p.as_str());
+        assert_ne!(format!("{:?}", p), p.to_string());
+    }
+
+    #[test]
+    // The lazy DFA's state cache is interior-mutable, but it isn't hashed
+    #[allow(clippy::mutable_key_type)]
+    fn eq_and_hash_use_source_and_flags() {
+        use std::collections::HashSet;
+
+        assert_eq!(Pattern::new(r"\d+").unwrap(), Pattern::new(r"\d+").unwrap());
+        // Same language, different source
+        assert_ne!(Pattern::new("a|b").unwrap(), Pattern::new("[ab]").unwrap());
+        // Same source, different builder flags
+        let folded = PatternBuilder::new("abc")
+            .case_insensitive(true)
+            .build()
+            .unwrap();
+        assert_ne!(folded, Pattern::new("abc").unwrap());
+
+        let set: HashSet<Pattern> = ["foo", "bar", "foo"]
+            .iter()
+            .map(|source| Pattern::new(source).unwrap())
+            .chain(core::iter::once(folded))
+            .collect();
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&Pattern::new("bar").unwrap()));
+    }
+
+    #[test]
+    fn explain_reports_strategy() {
+        let digits = Pattern::new(r"\d+").unwrap().explain();
+        assert!(digits.contains("fast path: DigitRun"), "{}", digits);
+
+        let literal = Pattern::new(r"foo\d+bar").unwrap().explain();
+        assert!(literal.contains("fast path: none"), "{}", literal);
+        assert!(
+            literal.contains("prefilter: SingleString (Prefix)"),
+            "{}",
+            literal
+        );
+        assert!(literal.contains("matcher: Sequence"), "{}", literal);
+        assert_eq!(literal.lines().count(), 3);
+
+        let folded = Pattern::new(r"(?i)foo\d+bar").unwrap().explain();
+        assert!(folded.contains("prefilter: none"), "{}", folded);
+        assert!(
+            folded.ends_with("\nprefilter disabled: case-insensitive flag set"),
+            "{}",
+            folded
+        );
+    }
+
+    #[test]
+    fn optimizations_disabled_reason_names_the_flag() {
+        let reason = |pattern: &str| {
+            Pattern::new(pattern)
+                .unwrap()
+                .optimizations_disabled_reason()
+        };
+        assert_eq!(reason(r"\d+"), None);
+        assert_eq!(
+            reason(r"(?m)^\d+"),
+            Some("fast path and prefilter disabled: multi-line flag set")
+        );
+        assert_eq!(
+            reason(r"(?s)a.b"),
+            Some("fast path and prefilter disabled: dot-matches-newline flag set")
+        );
+        assert_eq!(
+            reason(r"(?R)a.b"),
+            Some("fast path disabled: CRLF flag set")
+        );
+
+        let built =
+            |builder: &mut PatternBuilder| builder.build().unwrap().optimizations_disabled_reason();
+        assert_eq!(
+            built(PatternBuilder::new("word").case_insensitive(true)),
+            Some("fast path and prefilter disabled: case-insensitive flag set through PatternBuilder")
+        );
+        assert_eq!(
+            built(PatternBuilder::new("(?i)word").case_insensitive(true)),
+            Some("prefilter disabled: case-insensitive flag set")
+        );
+        assert_eq!(
+            built(PatternBuilder::new("a.b").line_terminator(0)),
+            Some("fast path disabled: custom line terminator set")
+        );
+    }
+
+    #[test]
+    fn start_anchored_sequences_use_anchored_dfa() {
+        for pattern in [r"^\d{4}-\d{2}-\d{2}", r"^\d+\.\d+", r"^[a-z]+?\d"] {
+            let re = Pattern::new(pattern).unwrap();
+            match &re.matcher {
+                Matcher::AnchoredPattern { inner, .. } => assert_eq!(inner.name(), "LazyDFA"),
+                other => panic!("{pattern:?} compiled to {}", other.name()),
+            }
+        }
+
+        let date = Pattern::new(r"^\d{4}-\d{2}-\d{2}").unwrap();
+        assert_eq!(date.find("2024-01-15 ok"), Some((0, 10)));
+        assert_eq!(date.find("on 2024-01-15"), None);
+        assert_eq!(date.find_all("2024-01-15 2024-01-16"), vec![(0, 10)]);
+    }
+
+    #[test]
+    fn bounded_field_sequences_use_lazy_dfa() {
+        let log = "INFO 200 ab3f GET\nWARN 404 c0de miss\n";
+        let fields = Pattern::new(r"[A-Z]{2,4} [0-9]{2,4} [a-f0-9]{2,4}").unwrap();
+        assert_eq!(fields.matcher.name(), "LazyDFA");
+        assert_eq!(fields.find_all(log), vec![(0, 13), (18, 31)]);
+
+        // Past the unrolling cap the sequence matcher takes over
+        let wide = Pattern::new(r"[a-z]{2,400}-\d{2}").unwrap();
+        assert_eq!(wide.matcher.name(), "Sequence");
+        assert_eq!(wide.find("x abc-12"), Some((2, 8)));
+    }
+
+    #[test]
+    fn captures_read_reuses_its_buffer() {
+        let mut locs = CaptureLocations::new();
+        for (source, text, group) in [
+            // Capture DFA, then backtracking
+            (r"(\w+)=(\d+)", "key=123", Some((4, 7))),
+            (r"(a|b)(\d+)", "b123", Some((1, 4))),
+        ] {
+            let pattern = Pattern::new(source).unwrap();
+            assert!(pattern.captures_read(&mut locs, text));
+            let buffer = locs.positions.as_ptr();
+            for _ in 0..3 {
+                assert!(pattern.captures_read(&mut locs, text));
+                assert_eq!(locs.positions.as_ptr(), buffer, "{source:?}");
+                assert_eq!(locs.get(2), group, "{source:?}");
+                assert_eq!(
+                    locs.positions,
+                    pattern.captures(text).unwrap().into_positions()
+                );
+            }
+        }
+
+        let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+        assert!(!pattern.captures_read(&mut locs, "none"));
+        assert!(locs.is_empty());
+    }
+
+    /// The matcher behind a `$`-anchored pattern, whose `$` also holds
+    /// before a final newline
+    fn strict_end_matcher(re: &Pattern) -> &Matcher {
+        match &re.matcher {
+            Matcher::FinalNewlineEnd(inner) => &inner.matcher,
+            other => other,
+        }
+    }
+
+    #[test]
+    fn fully_anchored_sequences_match_whole_text() {
+        for pattern in [r"^\d{3}-\d{4}$", r"^[a-z]+\d$", r"^a.*?b$"] {
+            let re = Pattern::new(pattern).unwrap();
+            match strict_end_matcher(&re) {
+                Matcher::AnchoredPattern {
+                    inner,
+                    start: true,
+                    end: true,
+                    ..
+                } => assert_eq!(inner.name(), "LazyDFA"),
+                other => panic!("{pattern:?} compiled to {}", other.name()),
+            }
+        }
+
+        let long_miss = "x".repeat(1 << 20);
+        for (pattern, hit) in [(r"^\d{3}$", "123"), (r"^\d{3}-\d{4}$", "555-1234")] {
+            let re = Pattern::new(pattern).unwrap();
+            assert!(re.is_match(hit), "{pattern:?}");
+            assert!(!re.is_match(&format!("{hit}0")), "{pattern:?}");
+            assert!(!re.is_match(&long_miss), "{pattern:?}");
+            assert_eq!(re.find(hit), Some((0, hit.len())), "{pattern:?}");
+        }
+        // Laziness can't stop a match short of the end anchor
+        assert_eq!(Pattern::new(r"^a.*?b$").unwrap().find("abab"), Some((0, 4)));
+    }
+
+    #[test]
+    fn capture_patterns_jump_to_start_candidates() {
+        let filter = |pattern: &str| match &Pattern::new(pattern).unwrap().matcher {
+            Matcher::PatternWithCaptures { elements, .. } => {
+                match Matcher::capture_start_filter(elements) {
+                    StartFilter::Any => "any".to_string(),
+                    StartFilter::Literal(lit) => format!("literal {lit}"),
+                    StartFilter::Bytes(bytes, n) => {
+                        format!("bytes {}", String::from_utf8_lossy(&bytes[..n]))
+                    }
+                }
+            }
+            other => panic!("{pattern:?} compiled to {}", other.name()),
+        };
+        assert_eq!(filter(r"foo(\d+)"), "bytes f");
+        assert_eq!(filter(r"(foo)(\d+)"), "bytes f");
+        assert_eq!(filter(r"(?:foo|bar)(\d+)"), "bytes bf");
+        assert_eq!(filter(r"[xy]=(\d+)"), "bytes xy");
+        assert_eq!(filter(r"(\w+)=(\d+)"), "any");
+        assert_eq!(filter(r"(?:é|e)(\d+)"), "any");
+
+        let sparse = format!("{}foo42 {}foo7", "x".repeat(10_000), "y".repeat(10_000));
+        let p = Pattern::new(r"(?:foo|bar)(\d+)").unwrap();
+        assert_eq!(
+            p.find_all(&sparse),
+            vec![(10_000, 10_005), (20_006, 20_010)]
+        );
+        let caps: Vec<_> = p.captures_iter(&sparse).map(|c| c.pos(1)).collect();
+        assert_eq!(caps, vec![Some((10_003, 10_005)), Some((20_009, 20_010))]);
+    }
+
+    #[test]
+    fn end_anchored_sequences_match_backward() {
+        for pattern in [r"\w+\.rs$", r"\d+$", r"[a-z]+\d{2}$"] {
+            let re = Pattern::new(pattern).unwrap();
+            assert_eq!(
+                strict_end_matcher(&re).name(),
+                "SuffixAnchored",
+                "{pattern:?}"
+            );
+        }
+
+        let sources = Pattern::new(r"\w+\.rs$").unwrap();
+        assert_eq!(sources.find("a.rs b.rs"), Some((5, 9)));
+        assert_eq!(sources.find("a.rs b.rsx"), None);
+        assert_eq!(
+            Pattern::new(r"\d+$").unwrap().find_all("1 22 333"),
+            vec![(5, 8)]
+        );
+    }
+
+    #[test]
+    fn parse_error_reports_byte_offset() {
+        let err = Pattern::new("(?i)[abc").unwrap_err();
+        assert_eq!(err.offset(), Some(4));
+        assert_eq!(
+            err.to_string(),
+            "Parse error at byte 4: Unclosed character class"
+        );
+
+        let err = Pattern::new("x(a)(b").unwrap_err();
+        assert_eq!(
+            err,
+            PatternError::ParseError {
+                message: "Unmatched parenthesis".to_string(),
+                offset: Some(4),
+                fragment: Some("(".to_string()),
+            }
+        );
+
+        let err = Pattern::new(r"\d{3,2}").unwrap_err();
+        assert_eq!(err.offset(), Some(2));
+        assert!(matches!(
+            err,
+            PatternError::ParseError { fragment: Some(ref f), .. } if f == "{3,2}"
+        ));
+
+        // Offsets are rebased through capture groups
+        assert_eq!(Pattern::new("(a)[z-a]").unwrap_err().offset(), Some(3));
+    }
+
+    #[test]
+    fn malformed_patterns_are_not_literals() {
+        let error_at = |p: &str| match Pattern::new(p) {
+            Err(PatternError::ParseError {
+                message, offset, ..
+            }) => (message, offset),
+            other => panic!("{:?} should fail to parse, got {:?}", p, other.map(|_| ())),
+        };
+
+        assert_eq!(
+            error_at("[abc"),
+            ("Unclosed character class".to_string(), Some(0))
+        );
+        assert_eq!(error_at("a{").1, Some(1));
+        assert_eq!(
+            error_at("*foo"),
+            (
+                "Quantifier without an expression to repeat".to_string(),
+                Some(0)
+            )
+        );
+        assert_eq!(error_at("(|+a)").1, Some(2));
+        assert_eq!(
+            error_at("ab)c"),
+            ("Unmatched parenthesis".to_string(), Some(2))
+        );
+        assert_eq!(error_at(r"ab\").1, Some(2));
+
+        // Still valid: a literal `]`, empty alternatives, quantified assertions
+        for ok in ["a]b", "a||b", "()", r"\b*", "[)]", r"\(", "(?:a|b)+?"] {
+            assert!(Pattern::new(ok).is_ok(), "{:?} should compile", ok);
+        }
+    }
+
+    #[test]
+    fn unsupported_features_are_structured() {
+        let unsupported = |p: &str| match Pattern::new(p) {
+            Err(PatternError::UnsupportedFeature(feature)) => feature,
+            other => panic!("{:?} should be unsupported, got {:?}", p, other.map(|_| ())),
+        };
+        assert_eq!(
+            unsupported(r"\p{Greek}+"),
+            UnsupportedFeature::UnicodeProperty
+        );
+        assert_eq!(
+            unsupported(r"[\P{Han}]"),
+            UnsupportedFeature::UnicodeProperty
+        );
+        assert_eq!(unsupported("a**"), UnsupportedFeature::NestedQuantifier);
+        assert_eq!(unsupported("a++"), UnsupportedFeature::NestedQuantifier);
+        assert_eq!(unsupported("a{2}{3}"), UnsupportedFeature::NestedQuantifier);
+        assert_eq!(
+            unsupported("(?P<year>\\d+)"),
+            UnsupportedFeature::NamedGroup
+        );
+        assert_eq!(unsupported("(?<year>\\d+)"), UnsupportedFeature::NamedGroup);
+        assert_eq!(unsupported("(?>ab|a)c"), UnsupportedFeature::AtomicGroup);
+        assert!(matches!(
+            unsupported("(?#note)a"),
+            UnsupportedFeature::Other(_)
+        ));
+        assert_eq!(
+            Pattern::new("a++").unwrap_err().to_string(),
+            "Unsupported: nested quantifiers"
+        );
+
+        // Lazy quantifiers and escaped metacharacters are fine
+        for ok in ["a+?b", "a{2,3}?b", r"\*+", "[*+]+", r"\\p", "(?<=a)b"] {
+            assert!(Pattern::new(ok).is_ok(), "{:?} should compile", ok);
+        }
+    }
+
     #[test]
     fn find_test() {
         let p = Pattern::new("world").unwrap();
@@ -4486,8 +7779,150 @@ mod tests {
     }
 
     #[test]
+    fn find_iter_from_both_ends() {
+        let spans = |it: &mut FindIter<'_>, back: bool| {
+            let m = if back { it.next_back() } else { it.next() };
+            m.map(|m| (m.start(), m.end()))
+        };
+
+        let p = Pattern::new(r"\d+").unwrap();
+        let mut it = p.find_iter("1 22 333 4444");
+        assert_eq!(spans(&mut it, true), Some((9, 13)));
+        assert_eq!(spans(&mut it, false), Some((0, 1)));
+        assert_eq!(spans(&mut it, true), Some((5, 8)));
+        assert_eq!(spans(&mut it, false), Some((2, 4)));
+        // Cursors have met
+        assert_eq!(spans(&mut it, false), None);
+        assert_eq!(spans(&mut it, true), None);
+
+        // Collected once, then taken from both ends
+        let p = Pattern::new(r"\d+|x").unwrap();
+        let mut it = p.find_iter("1 x 22 333");
+        assert_eq!(spans(&mut it, true), Some((7, 10)));
+        assert_eq!(spans(&mut it, false), Some((0, 1)));
+        assert_eq!(it.rest(), " x 22 333");
+        assert_eq!(spans(&mut it, true), Some((4, 6)));
+        assert_eq!(spans(&mut it, false), Some((2, 3)));
+        assert_eq!(it.rest(), " 22 333");
+        assert_eq!(spans(&mut it, false), None);
+        assert_eq!(spans(&mut it, true), None);
+
+        // Literal path via rfind
+        let p = Pattern::new("ab").unwrap();
+        let mut it = p.find_iter("ab ab ab");
+        assert_eq!(spans(&mut it, true), Some((6, 8)));
+        assert_eq!(spans(&mut it, false), Some((0, 2)));
+        assert_eq!(spans(&mut it, true), Some((3, 5)));
+        assert_eq!(spans(&mut it, true), None);
+
+        // Self-overlapping literal follows the forward sequence
+        let p = Pattern::new("aa").unwrap();
+        let rev: Vec<_> = p.find_iter("aaaaa").rev().map(|m| m.start()).collect();
+        assert_eq!(rev, vec![2, 0]);
+    }
+
+    #[test]
+    fn find_iter_rest_is_text_after_last_forward_match() {
+        let p = Pattern::new(r"\d+").unwrap();
+        let mut it = p.find_iter("1 22 ?!");
+        assert_eq!(it.rest(), "1 22 ?!");
+        it.next();
+        assert_eq!(it.rest(), " 22 ?!");
+        assert_eq!(it.by_ref().count(), 1);
+        assert_eq!(it.rest(), " ?!");
+
+        // An empty match ends where it starts
+        let p = Pattern::new("x*").unwrap();
+        let mut it = p.find_iter("xxab");
+        assert_eq!(it.next().map(|m| m.as_str()), Some("xx"));
+        assert_eq!(it.next().map(|m| m.range()), Some(3..3));
+        assert_eq!(it.rest(), "b");
+
+        // Matches taken from the back are still part of the rest
+        let p = Pattern::new("ab").unwrap();
+        let mut it = p.find_iter("ab ab");
+        it.next_back();
+        assert_eq!(it.rest(), "ab ab");
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
     fn cached() {
         assert!(is_match("test", "this is a test").unwrap());
+        // Hits share the compiled pattern instead of copying it
+        let first = get_pattern(r"cached\d+").unwrap();
+        let second = get_pattern(r"cached\d+").unwrap();
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn lazy_patterns_compile_once() {
+        static DIGITS: LazyPattern = LazyPattern::new(r"\d+");
+        assert!(core::ptr::eq(DIGITS.get(), DIGITS.get()));
+        assert_eq!(DIGITS.find("ab12"), Some((2, 4)));
+
+        // Each call site keeps its own pattern
+        let site = || lazy_pattern!(r"x+");
+        assert!(core::ptr::eq(site(), site()));
+
+        static BROKEN: LazyPattern = LazyPattern::new("(");
+        assert!(BROKEN.try_get().is_err());
+        assert_eq!(BROKEN.as_str(), "(");
+    }
+
+    #[test]
+    fn quantified_capture_find_all_is_non_overlapping() {
+        let p = Pattern::new("(ab)+").unwrap();
+        assert_eq!(p.find_all("ababxab"), vec![(0, 4), (5, 7)]);
+        let p = Pattern::new("(a)+").unwrap();
+        assert_eq!(p.find_all("aaba"), vec![(0, 2), (3, 4)]);
+    }
+
+    #[test]
+    fn case_insensitive_alternation_keeps_aho_corasick() {
+        let p = Pattern::new("(?im)foo|bar").unwrap();
+        assert!(matches!(p.matcher, Matcher::MultiLiteral { .. }));
+        assert_eq!(p.find_all("FOO x bAr"), vec![(0, 3), (6, 9)]);
+    }
+
+    #[test]
+    fn literal_alternations_agree_with_and_without_fast_path() {
+        let texts = ["xab ab aab", "a", "b", "", "XaB Ab"];
+        for pattern in ["a|ab", "ab|a", "(?i)a|ab", "(?i)ab|a", "(?i)(ab|a)"] {
+            let fast = Pattern::new(pattern).unwrap();
+            assert!(
+                fast.fast_path.is_some(),
+                "{pattern:?} should take the fast path"
+            );
+            let mut slow = fast.clone();
+            slow.fast_path = None;
+            for text in texts {
+                assert_eq!(fast.is_match(text), slow.is_match(text));
+                assert_eq!(fast.find(text), slow.find(text), "{pattern:?} on {text:?}");
+                assert_eq!(fast.find_all(text), slow.find_all(text));
+                assert_eq!(fast.count_matches(text), slow.count_matches(text));
+            }
+        }
+        let first = Pattern::new("a|ab").unwrap();
+        assert_eq!(first.find_all("xab ab"), vec![(1, 2), (4, 5)]);
+        let longer = Pattern::new("ab|a").unwrap();
+        assert_eq!(longer.find_all("xab ab"), vec![(1, 3), (4, 6)]);
+    }
+
+    #[test]
+    fn find_all_into_reuses_buffer() {
+        let text = "id=12 key: 7 \"quoted\" foo bar ab1 ab22";
+        let mut out = vec![(99, 99)];
+        // Literal, fast path, multi-literal, sequence, quantified, anchored
+        // and empty-matching patterns all clear the buffer before filling it
+        for pattern in [
+            "foo", r"\d+", "foo|bar", r"ab\d+", "[a-z]+", r"^id", r"\d*", r"(\w+)=",
+        ] {
+            let p = Pattern::new(pattern).unwrap();
+            p.find_all_into(text, &mut out);
+            assert_eq!(out, p.find_all(text), "pattern {pattern:?}");
+        }
     }
 }
 
@@ -1,8 +1,10 @@
-use aho_corasick::AhoCorasick;
+use crate::optimization::multi_literal::LiteralSearcher;
+use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 /// Specialized fast path implementations for common patterns
 /// These bypass the general matching machinery for maximum speed
 use memchr::{memchr, memchr_iter, memmem};
-use std::sync::Arc;
 
 /// Fast path for literal strings (no special chars)
 /// Uses memchr for first byte + slice compare to avoid Finder construction overhead
@@ -215,8 +217,8 @@ pub fn find_literal_dot_star_literal_all(
     prefix: &str,
     suffix: &str,
     lazy: bool,
-) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+    results: &mut Vec<(usize, usize)>,
+) {
     let mut pos = 0;
 
     while pos <= text.len() {
@@ -229,8 +231,6 @@ pub fn find_literal_dot_star_literal_all(
             break;
         }
     }
-
-    results
 }
 
 #[inline]
@@ -311,6 +311,112 @@ pub fn find_word_run(text: &str) -> Option<(usize, usize)> {
     None
 }
 
+/// Whether ASCII byte `b` is in `set`, a 128-bit class bitmap; bytes of
+/// non-ASCII chars never are
+#[inline(always)]
+fn in_ascii_set(set: &[u64; 2], b: u8) -> bool {
+    b < 128 && set[(b >> 6) as usize] & (1u64 << (b & 63)) != 0
+}
+
+/// Fast path for a run of an ASCII-only class, like `[\w.-]+`, from `start_pos`
+///
+/// The same tight byte loop as [`find_word_run_at`], with membership read
+/// from the class bitmap instead of a fixed test.
+#[inline]
+pub fn find_ascii_run_at(text: &str, set: &[u64; 2], start_pos: usize) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let start = start_pos
+        + bytes
+            .get(start_pos..)?
+            .iter()
+            .position(|&b| in_ascii_set(set, b))?;
+    let end = bytes[start..]
+        .iter()
+        .position(|&b| !in_ascii_set(set, b))
+        .map_or(bytes.len(), |len| start + len);
+    Some((start, end))
+}
+
+/// Fast path for find_all: runs of an ASCII-only class
+#[inline]
+pub fn find_ascii_run_all(text: &str, set: &[u64; 2], results: &mut Vec<(usize, usize)>) {
+    let mut pos = 0;
+    while let Some((start, end)) = find_ascii_run_at(text, set, pos) {
+        results.push((start, end));
+        pos = end;
+    }
+}
+
+/// Count runs of an ASCII-only class without recording their positions
+#[inline]
+pub fn count_ascii_runs(text: &str, set: &[u64; 2]) -> usize {
+    count_runs(text.as_bytes(), |b| in_ascii_set(set, b))
+}
+
+/// End of a number starting at `start`: an optional sign, a digit run, and
+/// (when `fraction`) a `.` taken only if a digit follows it
+#[inline(always)]
+fn signed_number_end(bytes: &[u8], start: usize, plus: bool, fraction: bool) -> Option<usize> {
+    let mut i = start;
+    if bytes[i] == b'-' || (plus && bytes[i] == b'+') {
+        i += 1;
+    }
+    if !bytes.get(i)?.is_ascii_digit() {
+        return None;
+    }
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+    }
+    if fraction && bytes.get(i) == Some(&b'.') && bytes.get(i + 1).is_some_and(u8::is_ascii_digit) {
+        i += 2;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+    }
+    Some(i)
+}
+
+/// Fast path for `-?\d+(\.\d+)?` and its variants, from `start_pos`
+///
+/// Sign, integer digits and fraction are scanned in one byte loop. A sign
+/// not followed by a digit is skipped, so the leftmost match starts at the
+/// first digit or at a sign right before one.
+#[inline]
+pub fn find_signed_number_at(
+    text: &str,
+    plus: bool,
+    fraction: bool,
+    start_pos: usize,
+) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut i = start_pos;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b.is_ascii_digit() || b == b'-' || (plus && b == b'+') {
+            if let Some(end) = signed_number_end(bytes, i, plus, fraction) {
+                return Some((i, end));
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Fast path for find_all: signed integers and decimals
+#[inline]
+pub fn find_signed_number_all(
+    text: &str,
+    plus: bool,
+    fraction: bool,
+    results: &mut Vec<(usize, usize)>,
+) {
+    let mut pos = 0;
+    while let Some((start, end)) = find_signed_number_at(text, plus, fraction, pos) {
+        results.push((start, end));
+        pos = end;
+    }
+}
+
 /// Fast path for quoted strings: "[^"]+"
 #[inline]
 pub fn find_quoted_string(text: &str) -> Option<(usize, usize)> {
@@ -336,8 +442,7 @@ pub fn find_quoted_string(text: &str) -> Option<(usize, usize)> {
 
 /// Fast path for find_all: literal strings
 #[inline]
-pub fn find_literal_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_literal_all(text: &str, literal: &str, results: &mut Vec<(usize, usize)>) {
     let len = literal.len();
 
     if len >= 3 {
@@ -358,8 +463,6 @@ pub fn find_literal_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
             pos = abs_pos + len;
         }
     }
-
-    results
 }
 
 /// Fast path for find_all: case-insensitive literal
@@ -367,17 +470,17 @@ pub fn find_literal_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
 pub fn find_literal_case_insensitive_all(
     text: &str,
     literal_lowercase: &str,
-) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+    results: &mut Vec<(usize, usize)>,
+) {
     let needle = literal_lowercase.as_bytes();
     let haystack = text.as_bytes();
     let needle_len = needle.len();
 
     if needle_len == 0 {
-        return results;
+        return;
     }
     if needle_len > haystack.len() {
-        return results;
+        return;
     }
 
     // For single byte - use memchr2 or memchr_iter
@@ -396,7 +499,7 @@ pub fn find_literal_case_insensitive_all(
                 results.push((pos, pos + 1));
             }
         }
-        return results;
+        return;
     }
 
     // Multi-byte - use memchr2 in a loop with optimized comparison
@@ -426,14 +529,29 @@ pub fn find_literal_case_insensitive_all(
             break;
         }
     }
+}
 
-    results
+/// Find case-insensitive literal starting from position (for lazy iteration)
+#[inline]
+pub fn find_literal_case_insensitive_at(
+    text: &str,
+    literal_lowercase: &str,
+    start_pos: usize,
+) -> Option<(usize, usize)> {
+    if start_pos >= text.len() {
+        return None;
+    }
+    find_literal_case_insensitive(&text[start_pos..], literal_lowercase)
+        .map(|(rel_start, rel_end)| (start_pos + rel_start, start_pos + rel_end))
 }
 
 /// Fast path for find_all: literal + whitespace
 #[inline]
-pub fn find_literal_plus_whitespace_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_literal_plus_whitespace_all(
+    text: &str,
+    literal: &str,
+    results: &mut Vec<(usize, usize)>,
+) {
     let finder = memmem::Finder::new(literal.as_bytes());
 
     for pos in finder.find_iter(text.as_bytes()) {
@@ -457,14 +575,11 @@ pub fn find_literal_plus_whitespace_all(text: &str, literal: &str) -> Vec<(usize
             results.push((pos, after + matched));
         }
     }
-
-    results
 }
 
 /// Fast path for find_all: digit runs
 #[inline]
-pub fn find_digit_run_all(text: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_digit_run_all(text: &str, results: &mut Vec<(usize, usize)>) {
     let bytes = text.as_bytes();
     let mut i = 0;
 
@@ -479,14 +594,106 @@ pub fn find_digit_run_all(text: &str) -> Vec<(usize, usize)> {
             i += 1;
         }
     }
+}
+
+/// One piece of a [`FastPath::DigitFields`] pattern
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DigitField {
+    /// `\d{min,max}`; `max` is `usize::MAX` when unbounded
+    Digits { min: usize, max: usize },
+    /// A literal ASCII char other than a digit
+    Byte(u8),
+}
+
+/// End of the digit fields matched at `start`, taking as many digits as
+/// each run allows
+#[inline]
+fn match_digit_fields(bytes: &[u8], fields: &[DigitField], start: usize) -> Option<usize> {
+    let mut pos = start;
+    for field in fields {
+        match *field {
+            DigitField::Byte(b) => {
+                if bytes.get(pos) != Some(&b) {
+                    return None;
+                }
+                pos += 1;
+            }
+            DigitField::Digits { min, max } => {
+                let run = bytes[pos..]
+                    .iter()
+                    .take(max)
+                    .take_while(|b| b.is_ascii_digit())
+                    .count();
+                if run < min {
+                    return None;
+                }
+                pos += run;
+            }
+        }
+    }
+    Some(pos)
+}
 
-    results
+/// Fast path for counted digit fields like `\d{4}-\d{2}-\d{2}`, from `start_pos`
+pub fn find_digit_fields_at(
+    text: &str,
+    fields: &[DigitField],
+    start_pos: usize,
+) -> Option<(usize, usize)> {
+    let bytes = text.as_bytes();
+    let mut pos = start_pos;
+    while pos < bytes.len() {
+        // Jump to the next byte the first field can start with
+        let next = match fields.first()? {
+            DigitField::Digits { .. } => bytes[pos..].iter().position(u8::is_ascii_digit),
+            DigitField::Byte(b) => memchr(*b, &bytes[pos..]),
+        };
+        let start = pos + next?;
+        if let Some(end) = match_digit_fields(bytes, fields, start) {
+            return Some((start, end));
+        }
+        pos = start + 1;
+    }
+    None
+}
+
+/// Fast path for counted digit fields - all matches
+pub fn find_digit_fields_all(text: &str, fields: &[DigitField], results: &mut Vec<(usize, usize)>) {
+    let mut pos = 0;
+    while let Some((start, end)) = find_digit_fields_at(text, fields, pos) {
+        results.push((start, end));
+        pos = end;
+    }
+}
+
+/// Count digit runs without recording their positions
+#[inline]
+pub fn count_digit_runs(text: &str) -> usize {
+    count_runs(text.as_bytes(), |b| b.is_ascii_digit())
+}
+
+/// Count word runs without recording their positions
+#[inline]
+pub fn count_word_runs(text: &str) -> usize {
+    count_runs(text.as_bytes(), |b| b.is_ascii_alphanumeric() || b == b'_')
+}
+
+/// Count maximal runs of bytes satisfying `in_run`: one per run start
+#[inline(always)]
+fn count_runs(bytes: &[u8], in_run: impl Fn(u8) -> bool) -> usize {
+    let mut count = 0;
+    let mut prev_in_run = false;
+    for &b in bytes {
+        let cur = in_run(b);
+        count += (cur && !prev_in_run) as usize;
+        prev_in_run = cur;
+    }
+    count
 }
 
 /// Fast path for find_all: word runs
 #[inline]
-pub fn find_word_run_all(text: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_word_run_all(text: &str, results: &mut Vec<(usize, usize)>) {
     let bytes = text.as_bytes();
     let mut i = 0;
 
@@ -507,8 +714,6 @@ pub fn find_word_run_all(text: &str) -> Vec<(usize, usize)> {
             i += 1;
         }
     }
-
-    results
 }
 
 /// Fast path for: identifier pattern [a-zA-Z_]\w*
@@ -541,8 +746,7 @@ pub fn find_identifier_run(text: &str) -> Option<(usize, usize)> {
 
 /// Fast path for find_all: identifier pattern [a-zA-Z_]\w*
 #[inline]
-pub fn find_identifier_run_all(text: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_identifier_run_all(text: &str, results: &mut Vec<(usize, usize)>) {
     let bytes = text.as_bytes();
     let mut i = 0;
 
@@ -566,14 +770,11 @@ pub fn find_identifier_run_all(text: &str) -> Vec<(usize, usize)> {
             i += 1;
         }
     }
-
-    results
 }
 
 /// Fast path for find_all: quoted strings
 #[inline]
-pub fn find_quoted_string_all(text: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_quoted_string_all(text: &str, results: &mut Vec<(usize, usize)>) {
     let bytes = text.as_bytes();
     let mut pos = 0;
 
@@ -599,8 +800,6 @@ pub fn find_quoted_string_all(text: &str) -> Vec<(usize, usize)> {
             break;
         }
     }
-
-    results
 }
 
 /// Fast path for: literal + whitespace + quoted string (rule\s+"[^"]+")
@@ -644,8 +843,7 @@ pub fn find_literal_ws_quoted(text: &str, literal: &str) -> Option<(usize, usize
 
 /// Fast path for find_all: literal + whitespace + quoted string
 #[inline]
-pub fn find_literal_ws_quoted_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_literal_ws_quoted_all(text: &str, literal: &str, results: &mut Vec<(usize, usize)>) {
     let finder = memmem::Finder::new(literal.as_bytes());
 
     for pos in finder.find_iter(text.as_bytes()) {
@@ -678,8 +876,6 @@ pub fn find_literal_ws_quoted_all(text: &str, literal: &str) -> Vec<(usize, usiz
             }
         }
     }
-
-    results
 }
 
 /// Fast path for: literal + whitespace + digits (salience\s+\d+)
@@ -720,8 +916,7 @@ pub fn find_literal_ws_digits(text: &str, literal: &str) -> Option<(usize, usize
 
 /// Fast path for find_all: literal + whitespace + digits
 #[inline]
-pub fn find_literal_ws_digits_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_literal_ws_digits_all(text: &str, literal: &str, results: &mut Vec<(usize, usize)>) {
     let finder = memmem::Finder::new(literal.as_bytes());
 
     for pos in finder.find_iter(text.as_bytes()) {
@@ -752,8 +947,15 @@ pub fn find_literal_ws_digits_all(text: &str, literal: &str) -> Vec<(usize, usiz
             results.push((pos, after + digit_end));
         }
     }
+}
 
-    results
+/// Byte length of the run of `\s` chars at the start of `text`
+#[inline]
+fn whitespace_run_len(text: &str) -> usize {
+    text.chars()
+        .take_while(|ch| ch.is_whitespace())
+        .map(char::len_utf8)
+        .sum()
 }
 
 /// Fast path for: word + optional ws + >= + optional ws + digits (\w+\s*>=\s*\d+)
@@ -777,20 +979,14 @@ pub fn find_word_compare_digit(text: &str) -> Option<(usize, usize)> {
             }
 
             // Skip optional whitespace
-            while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\n') {
-                i += 1;
-            }
+            i += whitespace_run_len(&text[i..]);
 
             // Check for >=
             if i + 1 < bytes.len() && bytes[i] == b'>' && bytes[i + 1] == b'=' {
                 i += 2;
 
                 // Skip optional whitespace
-                while i < bytes.len()
-                    && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\n')
-                {
-                    i += 1;
-                }
+                i += whitespace_run_len(&text[i..]);
 
                 // Check for digit
                 if i < bytes.len() && bytes[i].is_ascii_digit() {
@@ -812,8 +1008,7 @@ pub fn find_word_compare_digit(text: &str) -> Option<(usize, usize)> {
 
 /// Fast path for find_all: word + optional ws + >= + optional ws + digits
 #[inline]
-pub fn find_word_compare_digit_all(text: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_word_compare_digit_all(text: &str, results: &mut Vec<(usize, usize)>) {
     let bytes = text.as_bytes();
     let mut i = 0;
 
@@ -835,20 +1030,14 @@ pub fn find_word_compare_digit_all(text: &str) -> Vec<(usize, usize)> {
             let _saved_i = i; // Save position before checking pattern
 
             // Skip optional whitespace
-            while i < bytes.len() && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\n') {
-                i += 1;
-            }
+            i += whitespace_run_len(&text[i..]);
 
             // Check for >=
             if i + 1 < bytes.len() && bytes[i] == b'>' && bytes[i + 1] == b'=' {
                 i += 2;
 
                 // Skip optional whitespace
-                while i < bytes.len()
-                    && (bytes[i] == b' ' || bytes[i] == b'\t' || bytes[i] == b'\n')
-                {
-                    i += 1;
-                }
+                i += whitespace_run_len(&text[i..]);
 
                 // Check for digit
                 if i < bytes.len() && bytes[i].is_ascii_digit() {
@@ -866,21 +1055,23 @@ pub fn find_word_compare_digit_all(text: &str) -> Vec<(usize, usize)> {
             i += 1;
         }
     }
-
-    results
 }
 
 /// Fast path for: alternation of literals (word1|word2|word3)
 #[inline]
-pub fn find_alternation(ac: &AhoCorasick, text: &str) -> Option<(usize, usize)> {
-    ac.find(text).map(|m| (m.start(), m.end()))
+pub fn find_alternation(searcher: &LiteralSearcher, text: &str) -> Option<(usize, usize)> {
+    searcher.find(text)
 }
 
 /// Fast path for find_all: alternation of literals
-/// Uses pre-built aho-corasick automaton for O(n + z) performance
+/// Uses the pre-built searcher (an aho-corasick automaton by default)
 #[inline]
-pub fn find_alternation_all(ac: &AhoCorasick, text: &str) -> Vec<(usize, usize)> {
-    ac.find_iter(text).map(|m| (m.start(), m.end())).collect()
+pub fn find_alternation_all(
+    searcher: &LiteralSearcher,
+    text: &str,
+    results: &mut Vec<(usize, usize)>,
+) {
+    results.extend(searcher.find_iter(text));
 }
 
 /// Strip simple capture groups for fast path detection
@@ -944,7 +1135,142 @@ fn is_plain_literal_fragment(fragment: &str) -> bool {
     ])
 }
 
+/// User-supplied matcher for one exact pattern: the first match in `text`
+pub type CustomMatcher = fn(&str) -> Option<(usize, usize)>;
+
+#[cfg(feature = "std")]
+static CUSTOM_FAST_PATHS: std::sync::OnceLock<
+    std::sync::RwLock<std::collections::HashMap<&'static str, CustomMatcher>>,
+> = std::sync::OnceLock::new();
+
+/// Register `matcher` as the fast path for patterns spelled exactly `pattern`
+///
+/// Replaces any matcher registered earlier for the same string.
+#[cfg(feature = "std")]
+pub fn register_fast_path(pattern: &'static str, matcher: CustomMatcher) {
+    CUSTOM_FAST_PATHS
+        .get_or_init(Default::default)
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .insert(pattern, matcher);
+}
+
+/// The fast path registered for exactly `pattern`, if any
+pub fn registered_fast_path(pattern: &str) -> Option<FastPath> {
+    #[cfg(feature = "std")]
+    {
+        let registry = CUSTOM_FAST_PATHS.get()?;
+        let registry = registry
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        registry
+            .get(pattern)
+            .map(|&matcher| FastPath::Custom(matcher))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        let _ = pattern;
+        None
+    }
+}
+
+/// Detect an optionally signed integer or decimal: `-?\d+`, `[+-]?\d+`,
+/// either followed by `(\.\d+)?` or `(?:\.\d+)?`
+///
+/// Matched against the pattern as written, since stripping the fraction's
+/// group would leave its `?` applying to the digits alone.
+pub fn detect_signed_number(pattern: &str) -> Option<FastPath> {
+    let (plus, rest) = if let Some(rest) = pattern.strip_prefix("-?") {
+        (false, rest)
+    } else if let Some(rest) = pattern
+        .strip_prefix("[+-]?")
+        .or_else(|| pattern.strip_prefix("[-+]?"))
+    {
+        (true, rest)
+    } else {
+        return None;
+    };
+    let fraction = match rest.strip_prefix(r"\d+")? {
+        "" => false,
+        r"(\.\d+)?" | r"(?:\.\d+)?" => true,
+        _ => return None,
+    };
+    Some(FastPath::SignedNumber { plus, fraction })
+}
+
+/// Detect fixed-layout digit fields: counted digit runs like `\d{4}` or
+/// `\d{2,4}` between literal non-digit ASCII chars, as in `\d{4}-\d{2}-\d{2}`
+///
+/// Every run must be followed by a literal or the end, and none may be
+/// empty, so the greedy scan never has to give digits back.
+pub fn detect_digit_fields(pattern: &str) -> Option<Vec<DigitField>> {
+    let bytes = pattern.as_bytes();
+    let mut fields = Vec::new();
+    let mut counted = false;
+    let mut i = 0;
+    while i < bytes.len() {
+        let field = match bytes[i] {
+            b'\\' if bytes.get(i + 1) == Some(&b'd') => {
+                i += 2;
+                let (min, max) = match bytes.get(i) {
+                    Some(b'+') => {
+                        i += 1;
+                        (1, usize::MAX)
+                    }
+                    Some(b'{') => {
+                        let len = bytes[i..].iter().position(|&b| b == b'}')?;
+                        let (min, max) = parse_count(&pattern[i + 1..i + len])?;
+                        i += len + 1;
+                        counted = true;
+                        (min, max)
+                    }
+                    _ => (1, 1),
+                };
+                if min == 0 || min > max || matches!(fields.last(), Some(DigitField::Digits { .. }))
+                {
+                    return None;
+                }
+                DigitField::Digits { min, max }
+            }
+            // Escaped punctuation stands for itself
+            b'\\' => {
+                let b = *bytes.get(i + 1)?;
+                if !b.is_ascii_punctuation() {
+                    return None;
+                }
+                i += 2;
+                DigitField::Byte(b)
+            }
+            b if b.is_ascii() && !b.is_ascii_digit() && !br"[](){}*+?|.^$".contains(&b) => {
+                i += 1;
+                DigitField::Byte(b)
+            }
+            _ => return None,
+        };
+        fields.push(field);
+    }
+    // Anything else is left to the matchers that already handle it
+    counted.then_some(fields)
+}
+
+/// Bounds of a `{n}`, `{n,}` or `{n,m}` count, given the text between the braces
+fn parse_count(count: &str) -> Option<(usize, usize)> {
+    match count.split_once(',') {
+        None => {
+            let n = count.parse().ok()?;
+            Some((n, n))
+        }
+        Some((min, "")) => Some((min.parse().ok()?, usize::MAX)),
+        Some((min, max)) => Some((min.parse().ok()?, max.parse().ok()?)),
+    }
+}
+
 pub fn detect_fast_path(pattern: &str) -> Option<FastPath> {
+    // Matchers registered for this exact pattern come before the built-in ones
+    if let Some(custom) = registered_fast_path(pattern) {
+        return Some(custom);
+    }
+
     // Don't use fast path for anchored patterns - they need special handling
     if pattern.starts_with('^') || pattern.ends_with('$') {
         return None;
@@ -960,22 +1286,22 @@ pub fn detect_fast_path(pattern: &str) -> Option<FastPath> {
             '\\', '[', ']', '(', ')', '*', '+', '?', '{', '}', '.', '^', '$',
         ]) {
             // Check for alternation: (?i)get|post
-            if normalized.contains('|') {
+            // The searcher only folds ASCII case, so non-ASCII branches fall through
+            if normalized.contains('|') && normalized.is_ascii() {
                 let alternatives: Vec<String> =
                     normalized.split('|').map(|s| s.to_string()).collect();
                 if alternatives.iter().all(|alt| !alt.is_empty()) {
-                    // Build case-insensitive aho-corasick automaton
-                    if let Ok(ac) = AhoCorasick::builder()
-                        .match_kind(aho_corasick::MatchKind::LeftmostFirst)
-                        .ascii_case_insensitive(true)
-                        .build(&alternatives)
-                    {
-                        return Some(FastPath::Alternation(Arc::new(ac)));
+                    // Build case-insensitive searcher
+                    if let Ok(searcher) = LiteralSearcher::new(&alternatives, true) {
+                        return Some(FastPath::Alternation(Arc::new(searcher)));
                     }
                 }
-            } else {
-                // Simple case-insensitive literal
-                return Some(FastPath::LiteralCaseInsensitive(normalized.to_lowercase()));
+            } else if !normalized.is_empty() && normalized.is_ascii() {
+                // Simple case-insensitive literal (ASCII only - the scan folds bytes,
+                // so non-ASCII literals fall back to the full matcher)
+                return Some(FastPath::CaseInsensitiveLiteral(
+                    normalized.to_ascii_lowercase(),
+                ));
             }
         }
 
@@ -994,16 +1320,30 @@ pub fn detect_fast_path(pattern: &str) -> Option<FastPath> {
         });
     }
 
-    // Check for simple literal
-    if !normalized.contains(['\\', '[', ']', '(', ')', '*', '+', '?', '{', '}', '|', '.']) {
+    // Check for simple literal (the empty pattern matches between chars,
+    // which only the general iterator steps over)
+    if !normalized.is_empty()
+        && !normalized.contains([
+            '\\', '[', ']', '(', ')', '*', '+', '?', '{', '}', '|', '.', '^', '$',
+        ])
+    {
         return Some(FastPath::Literal(normalized.to_string()));
     }
 
+    if let Some(number) = detect_signed_number(pattern) {
+        return Some(number);
+    }
+
     // Check for digit run
     if normalized == r"\d+" {
         return Some(FastPath::DigitRun);
     }
 
+    // Check for counted digit fields: \d{4}, \d{4}-\d{2}-\d{2}
+    if let Some(fields) = detect_digit_fields(&normalized) {
+        return Some(FastPath::DigitFields(fields));
+    }
+
     // Check for word run
     if normalized == r"\w+" {
         return Some(FastPath::WordRun);
@@ -1069,18 +1409,16 @@ pub fn detect_fast_path(pattern: &str) -> Option<FastPath> {
 
     // Check for simple alternation of literals: word1|word2|word3
     if normalized.contains('|')
-        && !pattern.contains(['\\', '[', ']', '(', ')', '*', '+', '?', '{', '}', '.'])
+        && !pattern.contains([
+            '\\', '[', ']', '(', ')', '*', '+', '?', '{', '}', '.', '^', '$',
+        ])
     {
         let alternatives: Vec<String> = normalized.split('|').map(|s| s.to_string()).collect();
         // Only use fast path if all alternatives are simple literals
         if alternatives.iter().all(|alt| !alt.is_empty()) {
-            // Pre-build the aho-corasick automaton once during pattern detection
-            // Use fastest configuration for small pattern sets
-            if let Ok(ac) = AhoCorasick::builder()
-                .match_kind(aho_corasick::MatchKind::LeftmostFirst)
-                .build(&alternatives)
-            {
-                return Some(FastPath::Alternation(Arc::new(ac)));
+            // Pre-build the searcher once during pattern detection
+            if let Ok(searcher) = LiteralSearcher::new(&alternatives, false) {
+                return Some(FastPath::Alternation(Arc::new(searcher)));
             }
         }
     }
@@ -1223,28 +1561,9 @@ pub fn find_literal_plus_whitespace_at(
     if start_pos >= text.len() {
         return None;
     }
-    let search_text = &text[start_pos..];
-    let finder = memmem::Finder::new(literal.as_bytes());
-    let lit_pos = finder.find(search_text.as_bytes())?;
-    let abs_pos = start_pos + lit_pos;
-    let after = abs_pos + literal.len();
-    if after >= text.len() {
-        return None;
-    }
-    let rest = &text[after..];
-    let mut matched = 0;
-    for ch in rest.chars() {
-        if ch.is_whitespace() {
-            matched += ch.len_utf8();
-        } else {
-            break;
-        }
-    }
-    if matched > 0 {
-        Some((abs_pos, after + matched))
-    } else {
-        None
-    }
+    // Occurrences without whitespace after them don't end the search
+    find_literal_plus_whitespace(&text[start_pos..], literal)
+        .map(|(start, end)| (start_pos + start, start_pos + end))
 }
 
 #[inline]
@@ -1298,8 +1617,7 @@ pub fn find_literal_ws_word(text: &str, literal: &str) -> Option<(usize, usize)>
 
 /// Fast path for find_all: literal + whitespace + word
 #[inline]
-pub fn find_literal_ws_word_all(text: &str, literal: &str) -> Vec<(usize, usize)> {
-    let mut results = Vec::new();
+pub fn find_literal_ws_word_all(text: &str, literal: &str, results: &mut Vec<(usize, usize)>) {
     let finder = memmem::Finder::new(literal.as_bytes());
 
     for pos in finder.find_iter(text.as_bytes()) {
@@ -1343,8 +1661,6 @@ pub fn find_literal_ws_word_all(text: &str, literal: &str) -> Vec<(usize, usize)
             results.push((pos, word_start + word_len));
         }
     }
-
-    results
 }
 
 /// Find literal + whitespace + word starting from position
@@ -1365,7 +1681,7 @@ pub fn find_literal_ws_word_at(
 #[derive(Clone)]
 pub enum FastPath {
     Literal(String),
-    LiteralCaseInsensitive(String), // (?i)literal - case-insensitive literal
+    CaseInsensitiveLiteral(String), // (?i)literal - ASCII literal, stored lowercased
     LiteralPlusWhitespace(String),
     LiteralWhitespaceQuoted(String), // rule\s+"[^"]+"
     LiteralWhitespaceDigits(String), // salience\s+\d+
@@ -1375,20 +1691,27 @@ pub enum FastPath {
         suffix: String,
         lazy: bool,
     },
-    WordCompareDigit,              // \w+\s*>=\s*\d+
-    Alternation(Arc<AhoCorasick>), // Pre-built automaton for word1|word2|word3
+    WordCompareDigit,                  // \w+\s*>=\s*\d+
+    Alternation(Arc<LiteralSearcher>), // Pre-built searcher for word1|word2|word3
     DigitRun,
+    DigitFields(Vec<DigitField>), // \d{4}-\d{2}-\d{2} - counted digit runs between literals
     WordRun,
+    AsciiClassRun([u64; 2]), // [\w.-]+ and other runs of an ASCII-only class
+    SignedNumber {
+        plus: bool,     // [+-]? rather than -?
+        fraction: bool, // (\.\d+)? follows the digits
+    },
     IdentifierRun, // [a-zA-Z_]\w* - identifier pattern
     QuotedString,
     CaptureDFA(Arc<crate::engine::capture_dfa::CaptureDFA>), // DFA for patterns with captures
+    Custom(CustomMatcher), // Registered with Pattern::register_fast_path
 }
 
-impl std::fmt::Debug for FastPath {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for FastPath {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             FastPath::Literal(s) => write!(f, "Literal({:?})", s),
-            FastPath::LiteralCaseInsensitive(s) => write!(f, "LiteralCaseInsensitive({:?})", s),
+            FastPath::CaseInsensitiveLiteral(s) => write!(f, "CaseInsensitiveLiteral({:?})", s),
             FastPath::LiteralPlusWhitespace(s) => write!(f, "LiteralPlusWhitespace({:?})", s),
             FastPath::LiteralWhitespaceQuoted(s) => write!(f, "LiteralWhitespaceQuoted({:?})", s),
             FastPath::LiteralWhitespaceDigits(s) => write!(f, "LiteralWhitespaceDigits({:?})", s),
@@ -1403,22 +1726,52 @@ impl std::fmt::Debug for FastPath {
                 prefix, suffix, lazy
             ),
             FastPath::WordCompareDigit => write!(f, "WordCompareDigit"),
-            FastPath::Alternation(_) => write!(f, "Alternation(<AhoCorasick>)"),
+            FastPath::Alternation(_) => write!(f, "Alternation(<LiteralSearcher>)"),
             FastPath::DigitRun => write!(f, "DigitRun"),
+            FastPath::DigitFields(fields) => write!(f, "DigitFields({:?})", fields),
             FastPath::WordRun => write!(f, "WordRun"),
+            FastPath::AsciiClassRun(_) => write!(f, "AsciiClassRun"),
+            FastPath::SignedNumber { plus, fraction } => {
+                write!(f, "SignedNumber(plus={}, fraction={})", plus, fraction)
+            }
             FastPath::IdentifierRun => write!(f, "IdentifierRun"),
             FastPath::QuotedString => write!(f, "QuotedString"),
             FastPath::CaptureDFA(_) => write!(f, "CaptureDFA"),
+            FastPath::Custom(_) => write!(f, "Custom"),
         }
     }
 }
 
 impl FastPath {
+    /// Variant name, as reported by `Pattern::explain`
+    pub fn name(&self) -> &'static str {
+        match self {
+            FastPath::Literal(_) => "Literal",
+            FastPath::CaseInsensitiveLiteral(_) => "CaseInsensitiveLiteral",
+            FastPath::LiteralPlusWhitespace(_) => "LiteralPlusWhitespace",
+            FastPath::LiteralWhitespaceQuoted(_) => "LiteralWhitespaceQuoted",
+            FastPath::LiteralWhitespaceDigits(_) => "LiteralWhitespaceDigits",
+            FastPath::LiteralWhitespaceWord(_) => "LiteralWhitespaceWord",
+            FastPath::LiteralDotStarLiteral { .. } => "LiteralDotStarLiteral",
+            FastPath::WordCompareDigit => "WordCompareDigit",
+            FastPath::Alternation(_) => "Alternation",
+            FastPath::DigitRun => "DigitRun",
+            FastPath::DigitFields(_) => "DigitFields",
+            FastPath::WordRun => "WordRun",
+            FastPath::AsciiClassRun(_) => "AsciiClassRun",
+            FastPath::SignedNumber { .. } => "SignedNumber",
+            FastPath::IdentifierRun => "IdentifierRun",
+            FastPath::QuotedString => "QuotedString",
+            FastPath::CaptureDFA(_) => "CaptureDFA",
+            FastPath::Custom(_) => "Custom",
+        }
+    }
+
     #[inline]
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
         match self {
             FastPath::Literal(s) => find_literal(text, s),
-            FastPath::LiteralCaseInsensitive(s) => find_literal_case_insensitive(text, s),
+            FastPath::CaseInsensitiveLiteral(s) => find_literal_case_insensitive(text, s),
             FastPath::LiteralPlusWhitespace(s) => find_literal_plus_whitespace(text, s),
             FastPath::LiteralWhitespaceQuoted(s) => find_literal_ws_quoted(text, s),
             FastPath::LiteralWhitespaceDigits(s) => find_literal_ws_digits(text, s),
@@ -1429,38 +1782,60 @@ impl FastPath {
                 lazy,
             } => find_literal_dot_star_literal(text, prefix, suffix, *lazy),
             FastPath::WordCompareDigit => find_word_compare_digit(text),
-            FastPath::Alternation(ac) => find_alternation(ac, text),
+            FastPath::Alternation(searcher) => find_alternation(searcher, text),
             FastPath::DigitRun => find_digit_run(text),
+            FastPath::DigitFields(fields) => find_digit_fields_at(text, fields, 0),
             FastPath::WordRun => find_word_run(text),
+            FastPath::AsciiClassRun(set) => find_ascii_run_at(text, set, 0),
+            FastPath::SignedNumber { plus, fraction } => {
+                find_signed_number_at(text, *plus, *fraction, 0)
+            }
             FastPath::IdentifierRun => find_identifier_run(text),
             FastPath::QuotedString => find_quoted_string(text),
             FastPath::CaptureDFA(dfa) => dfa.find(text),
+            FastPath::Custom(matcher) => matcher(text),
         }
     }
 
     #[inline]
     pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut results = Vec::new();
+        self.find_all_into(text, &mut results);
+        results
+    }
+
+    /// Append every match to `results`, like [`find_all`](Self::find_all)
+    #[inline]
+    pub fn find_all_into(&self, text: &str, results: &mut Vec<(usize, usize)>) {
         match self {
-            FastPath::Literal(s) => find_literal_all(text, s),
-            FastPath::LiteralCaseInsensitive(s) => find_literal_case_insensitive_all(text, s),
-            FastPath::LiteralPlusWhitespace(s) => find_literal_plus_whitespace_all(text, s),
-            FastPath::LiteralWhitespaceQuoted(s) => find_literal_ws_quoted_all(text, s),
-            FastPath::LiteralWhitespaceDigits(s) => find_literal_ws_digits_all(text, s),
-            FastPath::LiteralWhitespaceWord(s) => find_literal_ws_word_all(text, s),
+            FastPath::Literal(s) => find_literal_all(text, s, results),
+            FastPath::CaseInsensitiveLiteral(s) => {
+                find_literal_case_insensitive_all(text, s, results)
+            }
+            FastPath::LiteralPlusWhitespace(s) => {
+                find_literal_plus_whitespace_all(text, s, results)
+            }
+            FastPath::LiteralWhitespaceQuoted(s) => find_literal_ws_quoted_all(text, s, results),
+            FastPath::LiteralWhitespaceDigits(s) => find_literal_ws_digits_all(text, s, results),
+            FastPath::LiteralWhitespaceWord(s) => find_literal_ws_word_all(text, s, results),
             FastPath::LiteralDotStarLiteral {
                 prefix,
                 suffix,
                 lazy,
-            } => find_literal_dot_star_literal_all(text, prefix, suffix, *lazy),
-            FastPath::WordCompareDigit => find_word_compare_digit_all(text),
-            FastPath::Alternation(ac) => find_alternation_all(ac, text),
-            FastPath::DigitRun => find_digit_run_all(text),
-            FastPath::WordRun => find_word_run_all(text),
-            FastPath::IdentifierRun => find_identifier_run_all(text),
-            FastPath::QuotedString => find_quoted_string_all(text),
+            } => find_literal_dot_star_literal_all(text, prefix, suffix, *lazy, results),
+            FastPath::WordCompareDigit => find_word_compare_digit_all(text, results),
+            FastPath::Alternation(searcher) => find_alternation_all(searcher, text, results),
+            FastPath::DigitRun => find_digit_run_all(text, results),
+            FastPath::DigitFields(fields) => find_digit_fields_all(text, fields, results),
+            FastPath::WordRun => find_word_run_all(text, results),
+            FastPath::AsciiClassRun(set) => find_ascii_run_all(text, set, results),
+            FastPath::SignedNumber { plus, fraction } => {
+                find_signed_number_all(text, *plus, *fraction, results)
+            }
+            FastPath::IdentifierRun => find_identifier_run_all(text, results),
+            FastPath::QuotedString => find_quoted_string_all(text, results),
             FastPath::CaptureDFA(dfa) => {
                 // For DFA, iterate using find_at
-                let mut results = Vec::new();
                 let mut pos = 0;
                 while pos < text.len() {
                     if let Some((start, end)) = dfa.find(&text[pos..]) {
@@ -1472,8 +1847,37 @@ impl FastPath {
                         break;
                     }
                 }
-                results
             }
+            FastPath::Custom(_) => {
+                // Resume after each match; an empty one steps over a char
+                let mut pos = 0;
+                while let Some((start, end)) = self.find_at(text, pos) {
+                    results.push((start, end));
+                    pos = if end > start {
+                        end
+                    } else {
+                        match text[end..].chars().next() {
+                            Some(ch) => end + ch.len_utf8(),
+                            None => break,
+                        }
+                    };
+                }
+            }
+        }
+    }
+
+    /// Count matches in a single scan, for the fast paths that have one
+    ///
+    /// Returns `None` when the caller should count through `find_at` instead.
+    #[inline]
+    pub fn count(&self, text: &str) -> Option<usize> {
+        match self {
+            FastPath::Literal(s) => Some(memmem::find_iter(text.as_bytes(), s.as_bytes()).count()),
+            FastPath::Alternation(searcher) => Some(searcher.find_iter(text).count()),
+            FastPath::DigitRun => Some(count_digit_runs(text)),
+            FastPath::WordRun => Some(count_word_runs(text)),
+            FastPath::AsciiClassRun(set) => Some(count_ascii_runs(text, set)),
+            _ => None,
         }
     }
 
@@ -1482,6 +1886,9 @@ impl FastPath {
     pub fn find_at(&self, text: &str, start_pos: usize) -> Option<(usize, usize)> {
         match self {
             FastPath::Literal(s) => find_literal_at(text, s, start_pos),
+            FastPath::CaseInsensitiveLiteral(s) => {
+                find_literal_case_insensitive_at(text, s, start_pos)
+            }
             FastPath::LiteralPlusWhitespace(s) => {
                 find_literal_plus_whitespace_at(text, s, start_pos)
             }
@@ -1492,7 +1899,12 @@ impl FastPath {
                 lazy,
             } => find_literal_dot_star_literal_at(text, prefix, suffix, *lazy, start_pos),
             FastPath::DigitRun => find_digit_run_at(text, start_pos),
+            FastPath::DigitFields(fields) => find_digit_fields_at(text, fields, start_pos),
             FastPath::WordRun => find_word_run_at(text, start_pos),
+            FastPath::AsciiClassRun(set) => find_ascii_run_at(text, set, start_pos),
+            FastPath::SignedNumber { plus, fraction } => {
+                find_signed_number_at(text, *plus, *fraction, start_pos)
+            }
             FastPath::IdentifierRun => find_identifier_run_at(text, start_pos),
             FastPath::QuotedString => find_quoted_string_at(text, start_pos),
             // For complex patterns, use find() on remaining text
@@ -1542,4 +1954,123 @@ mod tests {
         let fp = FastPath::Literal("hello".to_string());
         assert_eq!(fp.find(text), Some((14, 19)));
     }
+
+    #[test]
+    fn test_case_insensitive_literal_fast_path() {
+        assert!(matches!(
+            detect_fast_path("(?i)error"),
+            Some(FastPath::CaseInsensitiveLiteral(ref s)) if s == "error"
+        ));
+        // Non-ASCII literals can't be folded bytewise
+        assert!(detect_fast_path("(?i)café").is_none());
+
+        let fp = FastPath::CaseInsensitiveLiteral("error".to_string());
+        let text = "ERROR: x, Error: y, error: z";
+        assert_eq!(fp.find(text), Some((0, 5)));
+        assert_eq!(fp.find_all(text), vec![(0, 5), (10, 15), (20, 25)]);
+        assert_eq!(fp.find_at(text, 1), Some((10, 15)));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_registered_fast_path() {
+        // Every 'x' as a match, plus an empty match before each 'y'
+        fn x_or_before_y(text: &str) -> Option<(usize, usize)> {
+            let at = text.find(['x', 'y'])?;
+            let len = usize::from(text.as_bytes()[at] == b'x');
+            Some((at, at + len))
+        }
+
+        let is_custom = |pattern| matches!(detect_fast_path(pattern), Some(FastPath::Custom(_)));
+        assert!(!is_custom("registered-xy"));
+        register_fast_path("registered-xy", x_or_before_y);
+        assert!(is_custom("registered-xy"));
+        // Only the exact string is looked up
+        assert!(!is_custom("registered-xy "));
+
+        let fp = FastPath::Custom(x_or_before_y);
+
+        let text = "axéyx";
+        assert_eq!(fp.find(text), Some((1, 2)));
+        assert_eq!(fp.find_at(text, 2), Some((4, 4)));
+        assert_eq!(fp.find_all(text), vec![(1, 2), (4, 4), (5, 6)]);
+    }
+
+    #[test]
+    fn test_signed_number() {
+        assert!(matches!(
+            detect_fast_path(r"-?\d+(\.\d+)?"),
+            Some(FastPath::SignedNumber {
+                plus: false,
+                fraction: true
+            })
+        ));
+        assert!(matches!(
+            detect_fast_path(r"[+-]?\d+"),
+            Some(FastPath::SignedNumber {
+                plus: true,
+                fraction: false
+            })
+        ));
+        // An optional fraction is not the same as lazy digits
+        assert!(detect_signed_number(r"-?\d+\.\d+?").is_none());
+
+        let fp = FastPath::SignedNumber {
+            plus: false,
+            fraction: true,
+        };
+        let text = "a -x 1.5. --2 3.x";
+        assert_eq!(fp.find(text), Some((5, 8)));
+        assert_eq!(fp.find_at(text, 6), Some((7, 8)));
+        assert_eq!(fp.find_all(text), vec![(5, 8), (11, 13), (14, 15)]);
+    }
+
+    #[test]
+    fn test_digit_fields() {
+        assert_eq!(
+            detect_digit_fields(r"\d{4}-\d{2}-\d{2}"),
+            Some(vec![
+                DigitField::Digits { min: 4, max: 4 },
+                DigitField::Byte(b'-'),
+                DigitField::Digits { min: 2, max: 2 },
+                DigitField::Byte(b'-'),
+                DigitField::Digits { min: 2, max: 2 },
+            ])
+        );
+        assert!(matches!(
+            detect_fast_path(r"\d{2,4}"),
+            Some(FastPath::DigitFields(_))
+        ));
+        // Adjacent runs, empty runs and uncounted digits stay with the matcher
+        for pattern in [r"\d{2}\d", r"\d{0,2}x", r"\d+-\d+", r"\d{2}?", r"1\d{2}"] {
+            assert!(detect_digit_fields(pattern).is_none(), "{pattern:?}");
+        }
+
+        let fp = FastPath::DigitFields(detect_digit_fields(r"\d{4}-\d{2}").unwrap());
+        let text = "12345-67 2024-01-2";
+        assert_eq!(fp.find(text), Some((1, 8)));
+        assert_eq!(fp.find_at(text, 2), Some((9, 16)));
+        assert_eq!(fp.find_all(text), vec![(1, 8), (9, 16)]);
+
+        let fp = FastPath::DigitFields(detect_digit_fields(r"\d{2,3}").unwrap());
+        assert_eq!(fp.find_all("1 12345 67"), vec![(2, 5), (5, 7), (8, 10)]);
+    }
+
+    #[test]
+    fn test_ascii_class_run() {
+        let cc = crate::parser::charclass::CharClass::parse(r"\w.-").unwrap();
+        let set = cc.ascii_only_bitmap().unwrap();
+        let fp = FastPath::AsciiClassRun(set);
+        let text = "é a.b-c é_1";
+        assert_eq!(fp.find(text), Some((3, 8)));
+        assert_eq!(fp.find_at(text, 4), Some((4, 8)));
+        assert_eq!(fp.find_all(text), vec![(3, 8), (11, 13)]);
+        assert_eq!(fp.count(text), Some(2));
+
+        // Classes with non-ASCII members or negation need the full matcher
+        for class in ["a-zé", "^a-z", r"\p{L}"] {
+            let cc = crate::parser::charclass::CharClass::parse(class).unwrap();
+            assert!(cc.ascii_only_bitmap().is_none(), "{class:?}");
+        }
+    }
 }
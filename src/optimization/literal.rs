@@ -1,3 +1,7 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
 /// Literal extraction for prefilter optimization
 ///
 /// Extracts literal strings from patterns to use as fast prefilters
@@ -90,6 +94,12 @@ pub fn extract_from_pattern(pattern: &str) -> LiteralSet {
         }
     }
 
+    // An alternation without a literal in every branch: a prefix or inner
+    // anchor taken from one branch isn't required by the others
+    if pattern.contains('|') {
+        return LiteralSet::empty();
+    }
+
     // Quick check for prefix literals
     if let Some(prefix) = extract_simple_prefix(pattern) {
         if prefix.len() >= 3 {
@@ -124,11 +134,18 @@ fn extract_simple_prefix(pattern: &str) -> Option<String> {
 
     while let Some(&ch) = chars.peek() {
         match ch {
-            '.' | '*' | '+' | '?' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '^' | '$' => break,
+            // The char before `*`, `?` or `{` may repeat zero times
+            '*' | '?' | '{' => {
+                prefix.pop();
+                break;
+            }
+            '.' | '+' | '(' | ')' | '[' | ']' | '}' | '|' | '^' | '$' => break,
             '\\' => {
                 chars.next();
                 if let Some(next) = chars.peek() {
-                    if !matches!(next, 'd' | 'w' | 's' | 'D' | 'W' | 'S' | 'b' | 'B') {
+                    // Only escaped punctuation is literal; letters are classes
+                    // (\d, \p{L}), boundaries or control chars
+                    if !next.is_ascii_alphanumeric() {
                         prefix.push(*next);
                         chars.next();
                     } else {
@@ -208,27 +225,14 @@ fn extract_alternation_literals(pattern: &str) -> Option<Vec<Literal>> {
         return None;
     }
 
-    // Extract literal prefix from each branch
+    // Extract literal prefix from each branch; a branch without one could
+    // match anywhere, so no candidate set covers the alternation
     let mut literals = Vec::new();
     for branch in branches {
-        if let Some(prefix) = extract_simple_prefix(branch) {
-            if prefix.len() >= 2 {
-                // At least 2 chars to be useful
-                literals.push(Literal {
-                    text: prefix,
-                    is_exact: false,
-                });
-            }
-        } else if !branch.is_empty() {
-            // If no prefix, but branch is not empty, try first char
-            let first_char: String = branch.chars().take(1).collect();
-            if !first_char.is_empty() {
-                literals.push(Literal {
-                    text: first_char,
-                    is_exact: false,
-                });
-            }
-        }
+        literals.push(Literal {
+            text: extract_simple_prefix(branch)?,
+            is_exact: false,
+        });
     }
 
     if literals.is_empty() {
@@ -303,6 +307,14 @@ mod tests {
         assert_eq!(lits.literals[1].text, "https");
         assert_eq!(lits.kind, LiteralKind::Prefix);
 
+        // Optional or repeated chars end the prefix before them
+        assert_eq!(extract_from_pattern("colou?r").literals[0].text, "colo");
+        assert_eq!(extract_from_pattern(r"lib\.?rs").literals[0].text, "lib");
+
+        // Every alternation branch needs a literal prefix
+        assert_eq!(extract_from_pattern(r"a\d|x\d").literals.len(), 2);
+        assert!(extract_from_pattern(r"foo|\d+").is_empty());
+
         // Inner anchor
         let lits = extract_from_pattern(r"\w+@\w+\.\w+");
         assert_eq!(lits.literals.len(), 1);
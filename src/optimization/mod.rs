@@ -4,12 +4,15 @@
 //! - Fast path detection and execution (10 specialized patterns)
 //! - Prefilter optimization for quick candidate detection
 //! - Literal extraction for prefix/suffix optimization
+//! - Multi-literal search for alternations, with or without aho-corasick
 
 pub mod fast_path;
 pub mod literal;
+pub mod multi_literal;
 pub mod prefilter;
 
 // Re-export commonly used types
 pub use fast_path::FastPath;
 pub use literal::{Literal, LiteralKind};
+pub use multi_literal::LiteralSearcher;
 pub use prefilter::Prefilter;
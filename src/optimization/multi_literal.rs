@@ -0,0 +1,238 @@
+//! Multi-literal search for alternations like `foo|bar|baz`
+//!
+//! [`LiteralSearcher`] finds the leftmost-first occurrence of any of a set of
+//! literals: the earliest start wins, and at the same start the literal listed
+//! first. With the `aho-corasick` feature (on by default) it runs a single
+//! automaton; without it each literal gets its own `memmem` finder and the
+//! earliest hit is picked, which is slower for many literals but needs no
+//! extra dependency.
+
+use alloc::string::String;
+#[cfg(not(feature = "aho-corasick"))]
+use alloc::vec::Vec;
+#[cfg(not(feature = "aho-corasick"))]
+use memchr::{memchr2, memmem};
+
+/// Searches for any of several literals at once
+#[derive(Clone)]
+pub struct LiteralSearcher {
+    #[cfg(feature = "aho-corasick")]
+    ac: aho_corasick::AhoCorasick,
+    #[cfg(not(feature = "aho-corasick"))]
+    finders: Vec<memmem::Finder<'static>>,
+    /// ASCII case folding, for `(?i)` alternations
+    #[cfg(not(feature = "aho-corasick"))]
+    ascii_case_insensitive: bool,
+}
+
+impl core::fmt::Debug for LiteralSearcher {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LiteralSearcher").finish_non_exhaustive()
+    }
+}
+
+impl LiteralSearcher {
+    /// Build a searcher for `literals`, folding ASCII case if asked
+    ///
+    /// Fails only when the automaton can't be built (e.g. it would be too
+    /// large); the message says why.
+    pub fn new<S: AsRef<str>>(
+        literals: &[S],
+        ascii_case_insensitive: bool,
+    ) -> Result<Self, String> {
+        #[cfg(feature = "aho-corasick")]
+        {
+            use alloc::string::ToString;
+
+            let ac = aho_corasick::AhoCorasick::builder()
+                .match_kind(aho_corasick::MatchKind::LeftmostFirst)
+                .ascii_case_insensitive(ascii_case_insensitive)
+                .build(literals.iter().map(|lit| lit.as_ref()))
+                .map_err(|e| e.to_string())?;
+            Ok(LiteralSearcher { ac })
+        }
+        #[cfg(not(feature = "aho-corasick"))]
+        {
+            let finders = literals
+                .iter()
+                .map(|lit| {
+                    let lit = lit.as_ref();
+                    if ascii_case_insensitive {
+                        memmem::Finder::new(&lit.to_ascii_lowercase()).into_owned()
+                    } else {
+                        memmem::Finder::new(lit).into_owned()
+                    }
+                })
+                .collect();
+            Ok(LiteralSearcher {
+                finders,
+                ascii_case_insensitive,
+            })
+        }
+    }
+
+    /// Leftmost-first match as `(start, end)`
+    pub fn find(&self, text: &str) -> Option<(usize, usize)> {
+        self.find_at(text.as_bytes(), 0)
+    }
+
+    /// Whether any literal occurs in `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+
+    /// All non-overlapping leftmost-first matches
+    pub fn find_iter<'s, 't>(&'s self, text: &'t str) -> LiteralMatches<'s, 't> {
+        LiteralMatches {
+            searcher: self,
+            haystack: text.as_bytes(),
+            pos: 0,
+            #[cfg(not(feature = "aho-corasick"))]
+            next: (0..self.finders.len())
+                .map(|i| self.find_literal(i, text.as_bytes(), 0))
+                .collect(),
+        }
+    }
+
+    /// Leftmost-first match starting at or after `pos`
+    pub fn find_at(&self, haystack: &[u8], pos: usize) -> Option<(usize, usize)> {
+        self.find_indexed_at(haystack, pos)
+            .map(|(_, start, end)| (start, end))
+    }
+
+    /// Like [`find_at`](Self::find_at), also giving the index of the literal
+    /// that matched as `(index, start, end)`
+    #[cfg(feature = "aho-corasick")]
+    pub fn find_indexed_at(&self, haystack: &[u8], pos: usize) -> Option<(usize, usize, usize)> {
+        if pos > haystack.len() {
+            return None;
+        }
+        let input = aho_corasick::Input::new(haystack).span(pos..haystack.len());
+        self.ac
+            .find(input)
+            .map(|m| (m.pattern().as_usize(), m.start(), m.end()))
+    }
+
+    /// Like [`find_at`](Self::find_at), also giving the index of the literal
+    /// that matched as `(index, start, end)`
+    #[cfg(not(feature = "aho-corasick"))]
+    pub fn find_indexed_at(&self, haystack: &[u8], pos: usize) -> Option<(usize, usize, usize)> {
+        (0..self.finders.len())
+            .filter_map(|i| {
+                self.find_literal(i, haystack, pos)
+                    .map(|(start, end)| (i, start, end))
+            })
+            .min_by_key(|&(_, start, _)| start)
+    }
+
+    /// Next occurrence of literal `i` at or after `pos`
+    #[cfg(not(feature = "aho-corasick"))]
+    fn find_literal(&self, i: usize, haystack: &[u8], pos: usize) -> Option<(usize, usize)> {
+        let needle = self.finders[i].needle();
+        let rest = haystack.get(pos..)?;
+        if !self.ascii_case_insensitive {
+            let start = pos + self.finders[i].find(rest)?;
+            return Some((start, start + needle.len()));
+        }
+
+        // memmem can't fold case: jump between either case of the first
+        // byte and compare the rest ignoring ASCII case
+        let Some(&first) = needle.first() else {
+            return Some((pos, pos));
+        };
+        let mut from = 0;
+        while let Some(found) = memchr2(first, first.to_ascii_uppercase(), &rest[from..]) {
+            let start = from + found;
+            if rest[start..]
+                .get(..needle.len())
+                .is_some_and(|window| window.eq_ignore_ascii_case(needle))
+            {
+                return Some((pos + start, pos + start + needle.len()));
+            }
+            from = start + 1;
+        }
+        None
+    }
+}
+
+/// Iterator over [`LiteralSearcher`] matches
+///
+/// Without aho-corasick each literal's next occurrence is remembered, so a
+/// literal is only searched again once the scan has passed its last hit.
+pub struct LiteralMatches<'s, 't> {
+    searcher: &'s LiteralSearcher,
+    haystack: &'t [u8],
+    pos: usize,
+    /// Per literal: first occurrence at or after some earlier `pos`; `None`
+    /// once it no longer occurs
+    #[cfg(not(feature = "aho-corasick"))]
+    next: Vec<Option<(usize, usize)>>,
+}
+
+impl<'s, 't> Iterator for LiteralMatches<'s, 't> {
+    type Item = (usize, usize);
+
+    #[cfg(feature = "aho-corasick")]
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.pos > self.haystack.len() {
+            return None;
+        }
+        let (start, end) = self.searcher.find_at(self.haystack, self.pos)?;
+        self.pos = if end > start { end } else { end + 1 };
+        Some((start, end))
+    }
+
+    #[cfg(not(feature = "aho-corasick"))]
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.pos > self.haystack.len() {
+            return None;
+        }
+        let mut best: Option<(usize, usize)> = None;
+        for i in 0..self.next.len() {
+            // Refresh hits the scan has moved past
+            if self.next[i].is_some_and(|(start, _)| start < self.pos) {
+                self.next[i] = self.searcher.find_literal(i, self.haystack, self.pos);
+            }
+            if let Some(hit) = self.next[i] {
+                if best.map_or(true, |(start, _)| hit.0 < start) {
+                    best = Some(hit);
+                }
+            }
+        }
+        let (start, end) = best?;
+        self.pos = if end > start { end } else { end + 1 };
+        Some((start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_leftmost_first() {
+        let searcher = LiteralSearcher::new(&["ab", "abcd", "bc"], false).unwrap();
+        // Earliest start wins; at the same start the first-listed literal
+        assert_eq!(searcher.find("xabcd"), Some((1, 3)));
+        assert_eq!(searcher.find("xbcd"), Some((1, 3)));
+        assert_eq!(searcher.find("xyz"), None);
+        assert_eq!(searcher.find_at(b"ab ab", 1), Some((3, 5)));
+        assert_eq!(searcher.find_indexed_at(b"xbcd", 0), Some((2, 1, 3)));
+    }
+
+    #[test]
+    fn test_find_iter() {
+        let searcher = LiteralSearcher::new(&["foo", "bar", "oba"], false).unwrap();
+        let matches: Vec<_> = searcher.find_iter("foobar obar foo").collect();
+        assert_eq!(matches, vec![(0, 3), (3, 6), (7, 10), (12, 15)]);
+    }
+
+    #[test]
+    fn test_ascii_case_insensitive() {
+        let searcher = LiteralSearcher::new(&["get", "post"], true).unwrap();
+        assert!(searcher.is_match("a POST request"));
+        let matches: Vec<_> = searcher.find_iter("Get gEt PoSt").collect();
+        assert_eq!(matches, vec![(0, 3), (4, 7), (8, 12)]);
+    }
+}
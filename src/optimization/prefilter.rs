@@ -1,9 +1,13 @@
 use crate::optimization::literal::LiteralSet;
+use crate::optimization::multi_literal::LiteralSearcher;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 /// Prefilter using extracted literals for fast candidate finding
 ///
 /// A prefilter quickly finds candidate match positions using literal search,
 /// then the full regex engine verifies each candidate.
-use memchr::{memchr, memmem};
+use memchr::{memchr, memchr2, memchr3, memmem};
 
 /// A prefilter that uses literal search to find candidates
 pub struct Prefilter {
@@ -15,9 +19,15 @@ enum PrefilterStrategy {
     SingleByte(u8),
     /// Single string search using memmem
     SingleString(memmem::Finder<'static>),
-    /// Multiple string search using aho-corasick
+    /// Up to three distinct first bytes found with memchr, each hit checked
+    /// against the literals
+    FirstBytes {
+        bytes: Vec<u8>,
+        patterns: Vec<String>,
+    },
+    /// Multiple string search with a [`LiteralSearcher`]
     MultiString {
-        searcher: aho_corasick::AhoCorasick,
+        searcher: LiteralSearcher,
         patterns: Vec<String>,
     },
     /// No prefilter available
@@ -25,8 +35,8 @@ enum PrefilterStrategy {
 }
 
 // Manual Debug implementation
-impl std::fmt::Debug for Prefilter {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for Prefilter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match &self.strategy {
             PrefilterStrategy::SingleByte(b) => f
                 .debug_struct("Prefilter")
@@ -37,6 +47,12 @@ impl std::fmt::Debug for Prefilter {
                 .debug_struct("Prefilter")
                 .field("strategy", &"SingleString")
                 .finish(),
+            PrefilterStrategy::FirstBytes { bytes, patterns } => f
+                .debug_struct("Prefilter")
+                .field("strategy", &"FirstBytes")
+                .field("bytes", bytes)
+                .field("patterns", patterns)
+                .finish(),
             PrefilterStrategy::MultiString { patterns, .. } => f
                 .debug_struct("Prefilter")
                 .field("strategy", &"MultiString")
@@ -66,19 +82,18 @@ impl Clone for Prefilter {
                     strategy: PrefilterStrategy::SingleString(new_finder),
                 }
             }
-            PrefilterStrategy::MultiString { patterns, .. } => {
-                // Rebuild the aho-corasick automaton
-                let searcher = aho_corasick::AhoCorasick::builder()
-                    .match_kind(aho_corasick::MatchKind::LeftmostLongest)
-                    .build(patterns)
-                    .expect("Failed to rebuild aho-corasick in clone");
-                Prefilter {
-                    strategy: PrefilterStrategy::MultiString {
-                        searcher,
-                        patterns: patterns.clone(),
-                    },
-                }
-            }
+            PrefilterStrategy::FirstBytes { bytes, patterns } => Prefilter {
+                strategy: PrefilterStrategy::FirstBytes {
+                    bytes: bytes.clone(),
+                    patterns: patterns.clone(),
+                },
+            },
+            PrefilterStrategy::MultiString { searcher, patterns } => Prefilter {
+                strategy: PrefilterStrategy::MultiString {
+                    searcher: searcher.clone(),
+                    patterns: patterns.clone(),
+                },
+            },
             PrefilterStrategy::None => Prefilter {
                 strategy: PrefilterStrategy::None,
             },
@@ -131,7 +146,30 @@ impl Prefilter {
             }
         }
 
-        // Multiple literals - use aho-corasick if count is reasonable
+        // Few distinct first bytes (`error|warning|fatal` -> [ewf]): memchr skips
+        // the text in between, and only its hits are checked against the literals
+        let mut first_bytes: Vec<u8> = literals
+            .literals
+            .iter()
+            .filter_map(|lit| lit.text.as_bytes().first().copied())
+            .collect();
+        first_bytes.sort_unstable();
+        first_bytes.dedup();
+        let all_non_empty = literals.literals.iter().all(|lit| !lit.text.is_empty());
+        if all_non_empty && first_bytes.len() <= 3 {
+            return Prefilter {
+                strategy: PrefilterStrategy::FirstBytes {
+                    bytes: first_bytes,
+                    patterns: literals
+                        .literals
+                        .iter()
+                        .map(|lit| lit.text.clone())
+                        .collect(),
+                },
+            };
+        }
+
+        // Multiple literals - use a multi-literal searcher if count is reasonable
         if literals.literals.len() <= 100 {
             let patterns: Vec<String> = literals
                 .literals
@@ -139,10 +177,7 @@ impl Prefilter {
                 .map(|lit| lit.text.clone())
                 .collect();
 
-            if let Ok(searcher) = aho_corasick::AhoCorasick::builder()
-                .match_kind(aho_corasick::MatchKind::LeftmostLongest)
-                .build(&patterns)
-            {
+            if let Ok(searcher) = LiteralSearcher::new(&patterns, false) {
                 return Prefilter {
                     strategy: PrefilterStrategy::MultiString { searcher, patterns },
                 };
@@ -160,6 +195,31 @@ impl Prefilter {
         !matches!(self.strategy, PrefilterStrategy::None)
     }
 
+    /// Search strategy name: "SingleByte", "SingleString", "FirstBytes",
+    /// "MultiString" or "None"
+    pub fn strategy_name(&self) -> &'static str {
+        match &self.strategy {
+            PrefilterStrategy::SingleByte(_) => "SingleByte",
+            PrefilterStrategy::SingleString(_) => "SingleString",
+            PrefilterStrategy::FirstBytes { .. } => "FirstBytes",
+            PrefilterStrategy::MultiString { .. } => "MultiString",
+            PrefilterStrategy::None => "None",
+        }
+    }
+
+    /// The literals searched for: every candidate starts with one of them
+    pub fn needles(&self) -> Vec<String> {
+        match &self.strategy {
+            PrefilterStrategy::SingleByte(byte) => vec![char::from(*byte).into()],
+            PrefilterStrategy::SingleString(finder) => {
+                vec![String::from_utf8_lossy(finder.needle()).into_owned()]
+            }
+            PrefilterStrategy::FirstBytes { patterns, .. }
+            | PrefilterStrategy::MultiString { patterns, .. } => patterns.clone(),
+            PrefilterStrategy::None => Vec::new(),
+        }
+    }
+
     /// Find the next candidate position starting from `from`
     /// Returns the position where a candidate starts, or None if no more candidates
     pub fn find_candidate(&self, haystack: &[u8], from: usize) -> Option<usize> {
@@ -176,15 +236,40 @@ impl Prefilter {
                 finder.find(&haystack[from..]).map(|pos| from + pos)
             }
 
+            PrefilterStrategy::FirstBytes { bytes, patterns } => {
+                let mut pos = from;
+                while pos < haystack.len() {
+                    let rest = &haystack[pos..];
+                    let found = match bytes.as_slice() {
+                        [a] => memchr(*a, rest),
+                        [a, b] => memchr2(*a, *b, rest),
+                        [a, b, c] => memchr3(*a, *b, *c, rest),
+                        _ => unreachable!("FirstBytes holds one to three bytes"),
+                    };
+                    let hit = pos + found?;
+                    if patterns
+                        .iter()
+                        .any(|pattern| haystack[hit..].starts_with(pattern.as_bytes()))
+                    {
+                        return Some(hit);
+                    }
+                    pos = hit + 1;
+                }
+                None
+            }
+
             PrefilterStrategy::MultiString { searcher, .. } => {
-                searcher.find(&haystack[from..]).map(|m| from + m.start())
+                searcher.find_at(haystack, from).map(|(start, _)| start)
             }
 
             PrefilterStrategy::None => Some(from),
         }
     }
 
-    /// Iterate over all candidate positions
+    /// Iterate over all candidate positions, in strictly increasing order
+    ///
+    /// Each search resumes just past the previous candidate, so this holds
+    /// for every strategy.
     pub fn candidates<'a>(&'a self, haystack: &'a [u8]) -> CandidateIter<'a> {
         CandidateIter {
             prefilter: self,
@@ -271,4 +356,24 @@ mod tests {
         let candidates: Vec<usize> = prefilter.candidates(haystack).collect();
         assert_eq!(candidates, vec![0, 4, 12]);
     }
+
+    #[test]
+    fn test_first_byte_prefilter() {
+        let mut literals = LiteralSet::empty();
+        for text in ["error", "warning", "fatal", "exit"] {
+            literals.literals.push(Literal {
+                text: text.to_string(),
+                is_exact: true,
+            });
+        }
+        literals.kind = LiteralKind::Prefix;
+
+        let prefilter = Prefilter::from_literals(&literals);
+        assert_eq!(prefilter.strategy_name(), "FirstBytes");
+
+        // 'e', 'w' and 'f' hits that don't start a literal are skipped
+        let haystack = b"few errors were fatal; exit";
+        let candidates: Vec<usize> = prefilter.candidates(haystack).collect();
+        assert_eq!(candidates, vec![4, 16, 23]);
+    }
 }
@@ -0,0 +1,120 @@
+//! `find_all` split across threads for large inputs (feature = "parallel")
+//!
+//! The text is cut into one chunk per thread and each chunk collects the
+//! matches that start inside it. A chunk's search runs on past its end, so
+//! a match straddling the cut is found whole by the chunk it starts in, as
+//! if the chunks overlapped by the longest match. Stitching the chunks back
+//! together resumes from the previous chunk's last match: once that search
+//! lands on one of the chunk's own search positions, the rest of the chunk
+//! is taken as is, so the result is exactly what [`Pattern::find_all`] gives.
+
+use crate::{Matcher, Pattern};
+use alloc::vec::Vec;
+use rayon::prelude::*;
+
+/// Smallest chunk worth handing to another thread
+const MIN_CHUNK_LEN: usize = 64 * 1024;
+
+impl Pattern {
+    /// Find all matches like [`find_all`](Self::find_all), splitting large
+    /// texts across the rayon thread pool
+    ///
+    /// Only patterns whose matches don't depend on the text around them
+    /// split: literals, literal alternations, char classes and their runs
+    /// (`\d+`, `\w+`, `[a-f0-9]{2,}`). Anything else, and any text too short
+    /// to be worth splitting, runs [`find_all`](Self::find_all) on the
+    /// calling thread.
+    ///
+    /// # Example
+    /// ```
+    /// use rexile::Pattern;
+    ///
+    /// let numbers = Pattern::new(r"\d+").unwrap();
+    /// let text = "id 42, took 1337ms; ".repeat(50_000);
+    /// assert_eq!(numbers.find_all_parallel(&text), numbers.find_all(&text));
+    /// ```
+    pub fn find_all_parallel(&self, text: &str) -> Vec<(usize, usize)> {
+        let chunk_len = (text.len() / rayon::current_num_threads()).max(MIN_CHUNK_LEN);
+        if text.len() <= chunk_len || !self.splits_into_chunks() {
+            return self.find_all(text);
+        }
+
+        // Chunk starts, on char boundaries
+        let mut bounds: Vec<usize> = (0..text.len())
+            .step_by(chunk_len)
+            .map(|at| (0..=at).rev().find(|&i| text.is_char_boundary(i)).unwrap())
+            .collect();
+        bounds.dedup();
+        bounds.push(text.len());
+
+        let chunks: Vec<Vec<(usize, usize)>> = bounds
+            .par_windows(2)
+            .map(|window| self.chunk_matches(text, window[0], window[1]))
+            .collect();
+
+        let mut out = Vec::new();
+        let mut pos = 0;
+        for (chunk, window) in chunks.iter().zip(bounds.windows(2)) {
+            let (chunk_start, chunk_end) = (window[0], window[1]);
+            loop {
+                if pos >= chunk_end {
+                    break;
+                }
+                // In step with the chunk's own search: the rest is the same
+                let synced = if pos == chunk_start {
+                    Some(0)
+                } else {
+                    chunk
+                        .iter()
+                        .position(|&(_, end)| end == pos)
+                        .map(|index| index + 1)
+                };
+                if let Some(from) = synced {
+                    out.extend_from_slice(&chunk[from..]);
+                    pos = out.last().map_or(pos, |&(_, end)| end.max(pos));
+                    break;
+                }
+                match self.search_at(text, pos) {
+                    None => return out,
+                    // Belongs to a later chunk
+                    Some((start, _)) if start >= chunk_end => break,
+                    Some(found) => {
+                        out.push(found);
+                        pos = found.1;
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Whether every match is found the same from any search position before
+    /// it, and none is empty
+    fn splits_into_chunks(&self) -> bool {
+        let context_free = match &self.matcher {
+            Matcher::Literal(lit) => !lit.is_empty(),
+            Matcher::MultiLiteral { .. }
+            | Matcher::CharClass(_)
+            | Matcher::Quantified(_)
+            | Matcher::DigitRun
+            | Matcher::WordRun => true,
+            _ => false,
+        };
+        context_free && !self.is_match("")
+    }
+
+    /// Matches that start in `start..end`, searching on past `end` so the
+    /// last one is whole
+    fn chunk_matches(&self, text: &str, start: usize, end: usize) -> Vec<(usize, usize)> {
+        let mut matches = Vec::new();
+        let mut pos = start;
+        while let Some(found) = self.search_at(text, pos) {
+            if found.0 >= end {
+                break;
+            }
+            matches.push(found);
+            pos = found.1;
+        }
+        matches
+    }
+}
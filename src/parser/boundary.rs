@@ -3,6 +3,13 @@
 //! Supports:
 //! - \b - word boundary (transition between \w and \W)
 //! - \B - non-word boundary (NOT at word boundary)
+//! - \b{ascii}, \B{ascii} - the same, with only [a-zA-Z0-9_] as word chars
+//!
+//! `\b`/`\B` are Unicode-aware: any alphanumeric scalar value counts as a
+//! word char. The ASCII forms only look at bytes, which is cheaper; `(?-u)`
+//! makes every boundary in a pattern ASCII-only.
+
+use alloc::vec::Vec;
 
 /// Word boundary type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -11,6 +18,10 @@ pub enum BoundaryType {
     Word,
     /// \B - non-word boundary
     NonWord,
+    /// \b{ascii} - word boundary over ASCII word chars only
+    AsciiWord,
+    /// \B{ascii} - non-word boundary over ASCII word chars only
+    AsciiNonWord,
 }
 
 impl BoundaryType {
@@ -21,8 +32,29 @@ impl BoundaryType {
     /// - End of text preceded by word char
     /// - Between word char and non-word char
     /// - Between non-word char and word char
+    ///
+    /// Word chars are Unicode alphanumerics and `_`. Positions inside a
+    /// multi-byte char are never boundaries.
     #[inline]
     pub fn is_at_boundary(text: &str, pos: usize) -> bool {
+        if !text.is_char_boundary(pos) {
+            return false;
+        }
+
+        // Check characters before and after position
+        let before_is_word = text[..pos]
+            .chars()
+            .next_back()
+            .is_some_and(Self::is_word_char);
+        let after_is_word = text[pos..].chars().next().is_some_and(Self::is_word_char);
+
+        // Boundary = transition between word/non-word
+        before_is_word != after_is_word
+    }
+
+    /// Check if position is at a word boundary, with only ASCII word chars
+    #[inline]
+    pub fn is_at_ascii_boundary(text: &str, pos: usize) -> bool {
         let bytes = text.as_bytes();
 
         // Check characters before and after position
@@ -45,10 +77,19 @@ impl BoundaryType {
     /// Check if this boundary type matches at position
     #[inline]
     pub fn matches_at(&self, text: &str, pos: usize) -> bool {
-        let is_boundary = Self::is_at_boundary(text, pos);
         match self {
-            BoundaryType::Word => is_boundary,
-            BoundaryType::NonWord => !is_boundary,
+            BoundaryType::Word => Self::is_at_boundary(text, pos),
+            BoundaryType::NonWord => text.is_char_boundary(pos) && !Self::is_at_boundary(text, pos),
+            BoundaryType::AsciiWord => Self::is_at_ascii_boundary(text, pos),
+            BoundaryType::AsciiNonWord => !Self::is_at_ascii_boundary(text, pos),
+        }
+    }
+
+    /// The ASCII-only form of this boundary
+    pub fn to_ascii(self) -> Self {
+        match self {
+            BoundaryType::Word | BoundaryType::AsciiWord => BoundaryType::AsciiWord,
+            BoundaryType::NonWord | BoundaryType::AsciiNonWord => BoundaryType::AsciiNonWord,
         }
     }
 
@@ -58,13 +99,24 @@ impl BoundaryType {
         b.is_ascii_lowercase() || b.is_ascii_uppercase() || b.is_ascii_digit() || b == b'_'
     }
 
+    /// Check if char is a Unicode word character (alphanumeric or `_`)
+    #[inline(always)]
+    fn is_word_char(ch: char) -> bool {
+        if ch.is_ascii() {
+            Self::is_word_byte(ch as u8)
+        } else {
+            ch.is_alphanumeric()
+        }
+    }
+
     /// Find first position that matches this boundary in text
     pub fn find_first(&self, text: &str) -> Option<usize> {
         let bytes = text.as_bytes();
 
-        // Empty text has no boundaries or non-boundaries
+        // Empty text is a single position with no word char on either
+        // side: a non-boundary
         if bytes.is_empty() {
-            return None;
+            return self.matches_at(text, 0).then_some(0);
         }
 
         // Check position 0 (start of text)
@@ -87,13 +139,21 @@ impl BoundaryType {
         None
     }
 
+    /// Find the first position at or after `pos` that matches, judging the
+    /// chars on both sides in the whole text
+    pub fn find_from(&self, text: &str, pos: usize) -> Option<usize> {
+        (pos..=text.len()).find(|&i| text.is_char_boundary(i) && self.matches_at(text, i))
+    }
+
     /// Find all positions that match this boundary in text
     pub fn find_all(&self, text: &str) -> Vec<usize> {
         let bytes = text.as_bytes();
         let mut positions = Vec::new();
 
-        // Empty text has no boundaries or non-boundaries
+        // Empty text is a single position with no word char on either
+        // side: a non-boundary
         if bytes.is_empty() {
+            positions.extend(self.matches_at(text, 0).then_some(0));
             return positions;
         }
 
@@ -152,6 +212,14 @@ mod tests {
         assert!(BoundaryType::NonWord.matches_at(text, 7)); // "w|orld"
     }
 
+    #[test]
+    fn test_empty_text_is_a_non_boundary() {
+        assert_eq!(BoundaryType::NonWord.find_first(""), Some(0));
+        assert_eq!(BoundaryType::NonWord.find_all(""), vec![0]);
+        assert_eq!(BoundaryType::Word.find_first(""), None);
+        assert!(BoundaryType::Word.find_all("").is_empty());
+    }
+
     #[test]
     fn test_find_all_boundaries() {
         let text = "hello world";
@@ -181,4 +249,29 @@ mod tests {
         assert!(!BoundaryType::Word.matches_at(" hello", 0));
         assert!(BoundaryType::Word.matches_at(" hello", 1));
     }
+
+    #[test]
+    fn test_unicode_word_boundary() {
+        let text = "x αβγ y";
+
+        assert!(BoundaryType::Word.matches_at(text, 2)); // " |αβγ"
+        assert!(BoundaryType::Word.matches_at(text, 8)); // "αβγ| "
+        assert!(BoundaryType::NonWord.matches_at(text, 4)); // "α|β"
+        assert_eq!(BoundaryType::Word.find_all(text), vec![0, 1, 2, 8, 9, 10]);
+
+        // Inside a multi-byte char is no position at all
+        assert!(!BoundaryType::Word.matches_at(text, 3));
+        assert!(!BoundaryType::NonWord.matches_at(text, 3));
+    }
+
+    #[test]
+    fn test_ascii_word_boundary() {
+        let text = "éfoo";
+
+        // é is a word char in Unicode mode, so "foo" is inside a word
+        assert!(!BoundaryType::Word.matches_at(text, 2));
+        assert!(BoundaryType::AsciiWord.matches_at(text, 2));
+        assert!(BoundaryType::AsciiNonWord.matches_at(text, 0));
+        assert_eq!(BoundaryType::Word.to_ascii(), BoundaryType::AsciiWord);
+    }
 }
@@ -1,6 +1,17 @@
 //! Character class matching implementation
 //!
-//! Supports: [abc], [a-z], [0-9], [^abc] (negation), [A-Za-z0-9_]
+//! Supports: [abc], [a-z], [0-9], [^abc] (negation), [A-Za-z0-9_], [\p{L}\d],
+//! and set operations like [a-z&&[^aeiou]] (see [`class_set`](super::class_set))
+
+use crate::parser::class_set::parse_set_operations;
+use crate::parser::escape::{
+    HORIZONTAL_SPACE_CHARS, HORIZONTAL_SPACE_RANGES, VERTICAL_SPACE_CHARS, VERTICAL_SPACE_RANGES,
+    WHITESPACE_CHARS, WHITESPACE_RANGES,
+};
+use crate::parser::unicode::{parse_property_name, UnicodeProperty};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// Represents a character class pattern like [a-z] or [^0-9]
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -11,6 +22,8 @@ pub struct CharClass {
     pub(crate) ranges: Vec<(char, char)>, // Made pub(crate) for optimization checks
     /// If true, matches anything NOT in chars/ranges
     pub(crate) negated: bool, // Made pub(crate) for optimization checks
+    /// Unicode general-category properties (e.g. `\p{L}` from [\p{L}_])
+    pub(crate) properties: Vec<UnicodeProperty>,
     /// ASCII fast path: bitmap for ASCII characters (0-127)
     ascii_bitmap: Option<[u64; 2]>, // 128 bits = 2 x u64
 }
@@ -22,6 +35,7 @@ impl CharClass {
             chars: Vec::new(),
             ranges: Vec::new(),
             negated: false,
+            properties: Vec::new(),
             ascii_bitmap: None,
         }
     }
@@ -38,6 +52,12 @@ impl CharClass {
         self.ascii_bitmap = None; // Invalidate bitmap
     }
 
+    /// Add a Unicode property to the class
+    pub fn add_property(&mut self, property: UnicodeProperty) {
+        self.properties.push(property);
+        self.ascii_bitmap = None; // Invalidate bitmap
+    }
+
     /// Negate this character class
     pub fn negate(&mut self) {
         self.negated = !self.negated;
@@ -53,9 +73,14 @@ impl CharClass {
         if pattern.is_empty() {
             return Err("Empty character class".to_string());
         }
+        // Nested classes and `&&`/`--`, as in `a-z&&[^aeiou]`
+        if let Some(class) = parse_set_operations(pattern)? {
+            return Ok(class);
+        }
 
         let mut chars = Vec::new();
         let mut ranges = Vec::new();
+        let mut properties = Vec::new();
         let negated = pattern.starts_with('^');
         let pattern = if negated { &pattern[1..] } else { pattern };
 
@@ -68,13 +93,9 @@ impl CharClass {
                 let escape_char = pattern_chars[i + 1];
                 match escape_char {
                     's' => {
-                        // Whitespace: space, tab, newline, carriage return, form feed, vertical tab
-                        chars.push(' ');
-                        chars.push('\t');
-                        chars.push('\n');
-                        chars.push('\r');
-                        chars.push('\x0C'); // form feed
-                        chars.push('\x0B'); // vertical tab
+                        // Whitespace, the same set as a bare \s
+                        chars.extend_from_slice(WHITESPACE_CHARS);
+                        ranges.extend_from_slice(WHITESPACE_RANGES);
                         i += 2;
                     }
                     'd' => {
@@ -90,6 +111,31 @@ impl CharClass {
                         chars.push('_');
                         i += 2;
                     }
+                    'h' => {
+                        // Horizontal whitespace
+                        chars.extend_from_slice(HORIZONTAL_SPACE_CHARS);
+                        ranges.extend_from_slice(HORIZONTAL_SPACE_RANGES);
+                        i += 2;
+                    }
+                    'v' => {
+                        // Vertical whitespace
+                        chars.extend_from_slice(VERTICAL_SPACE_CHARS);
+                        ranges.extend_from_slice(VERTICAL_SPACE_RANGES);
+                        i += 2;
+                    }
+                    'p' | 'P' => {
+                        // Unicode property: \pL, \p{Lu}, \P{N}
+                        let rest: String = pattern_chars[i + 2..].iter().collect();
+                        let (name, consumed) = parse_property_name(&rest)?;
+                        let property = UnicodeProperty::from_name(name)
+                            .ok_or_else(|| format!("Unknown Unicode property: {}", name))?;
+                        properties.push(if escape_char == 'P' {
+                            property.complement()
+                        } else {
+                            property
+                        });
+                        i += 2 + rest[..consumed].chars().count();
+                    }
                     _ => {
                         // Other escapes like \., \-, etc. - treat as literal
                         chars.push(escape_char);
@@ -121,6 +167,7 @@ impl CharClass {
             chars,
             ranges,
             negated,
+            properties,
             ascii_bitmap: None,
         };
 
@@ -156,6 +203,16 @@ impl CharClass {
             }
         }
 
+        // Set bits for ASCII members of Unicode properties
+        if !self.properties.is_empty() {
+            for ch in (0u8..128).map(char::from) {
+                if self.properties.iter().any(|p| p.matches(ch)) {
+                    let idx = ch as usize;
+                    bitmap[idx / 64] |= 1u64 << (idx % 64);
+                }
+            }
+        }
+
         self.ascii_bitmap = Some(bitmap);
     }
 
@@ -165,6 +222,17 @@ impl CharClass {
         self.ascii_bitmap.as_ref()
     }
 
+    /// The ASCII bitmap, if it is the whole class: no negation, properties
+    /// or non-ASCII members, so a byte scan over UTF-8 text can't split a char
+    pub(crate) fn ascii_only_bitmap(&self) -> Option<[u64; 2]> {
+        let ascii_members = self.chars.iter().all(char::is_ascii)
+            && self.ranges.iter().all(|&(_, end)| end.is_ascii());
+        if self.negated || !self.properties.is_empty() || !ascii_members {
+            return None;
+        }
+        self.ascii_bitmap
+    }
+
     /// Check if an ASCII byte matches using pre-extracted bitmap (no function call overhead)
     #[inline(always)]
     pub fn matches_byte_bitmap(bitmap: &[u64; 2], negated: bool, byte: u8) -> bool {
@@ -209,6 +277,11 @@ impl CharClass {
             }
         }
 
+        // Check Unicode properties
+        if !matched {
+            matched = self.properties.iter().any(|p| p.matches(ch));
+        }
+
         // Apply negation
         if self.negated {
             !matched
@@ -217,9 +290,34 @@ impl CharClass {
         }
     }
 
+    /// Check if any member lies outside ASCII, so byte-level scans aren't enough
+    pub fn has_non_ascii_members(&self) -> bool {
+        !self.properties.is_empty()
+            || self.chars.iter().any(|ch| !ch.is_ascii())
+            || self.ranges.iter().any(|&(_, end)| !end.is_ascii())
+    }
+
+    /// Check if the only non-ASCII members (before negation) are the `\s`
+    /// ones, as in `\s`, `\S` or `[\s,]`
+    pub fn non_ascii_members_are_whitespace(&self) -> bool {
+        let non_ascii_chars = || self.chars.iter().filter(|ch| !ch.is_ascii());
+        let non_ascii_ranges = self.ranges.iter().filter(|&&(_, end)| !end.is_ascii());
+        self.properties.is_empty()
+            && non_ascii_chars().all(|ch| WHITESPACE_CHARS.contains(ch))
+            && WHITESPACE_CHARS
+                .iter()
+                .filter(|ch| !ch.is_ascii())
+                .all(|ch| self.chars.contains(ch))
+            && non_ascii_ranges.eq(WHITESPACE_RANGES.iter())
+    }
+
     /// Check if this is a dot wildcard class [^\n] (matches any char except newline)
     pub fn is_dot_class(&self) -> bool {
-        self.negated && self.chars.len() == 1 && self.chars[0] == '\n' && self.ranges.is_empty()
+        self.negated
+            && self.chars.len() == 1
+            && self.chars[0] == '\n'
+            && self.ranges.is_empty()
+            && self.properties.is_empty()
     }
 
     /// Check if this is a digit-only character class [0-9]
@@ -228,6 +326,7 @@ impl CharClass {
             && self.chars.is_empty()
             && self.ranges.len() == 1
             && self.ranges[0] == ('0', '9')
+            && self.properties.is_empty()
     }
 
     /// Check if this is a word character class [a-zA-Z0-9_]
@@ -248,16 +347,16 @@ impl CharClass {
             && has_underscore
             && self.ranges.len() == 3
             && self.chars.len() == 1
+            && self.properties.is_empty()
     }
 
     /// Check if this is a whitespace-only character class \s
     pub fn is_whitespace_class(&self) -> bool {
         !self.negated
-            && self.chars.contains(&' ')
-            && self.chars.contains(&'\t')
-            && self.chars.contains(&'\n')
-            && self.chars.contains(&'\r')
-            && self.ranges.is_empty()
+            && self.chars.len() == WHITESPACE_CHARS.len()
+            && WHITESPACE_CHARS.iter().all(|ch| self.chars.contains(ch))
+            && self.ranges == WHITESPACE_RANGES
+            && self.properties.is_empty()
     }
 
     /// Check if this character class overlaps with another
@@ -268,6 +367,10 @@ impl CharClass {
         if self.negated || other.negated {
             return true;
         }
+        // Properties span too many characters to compare cheaply
+        if !self.properties.is_empty() || !other.properties.is_empty() {
+            return true;
+        }
 
         // Check if any range in self overlaps with any range in other
         for &(s1, e1) in &self.ranges {
@@ -415,4 +518,47 @@ mod tests {
         assert!(cc.matches('a'));
         assert!(cc.matches('z'));
     }
+
+    #[test]
+    fn test_unicode_properties() {
+        let cc = CharClass::parse(r"\p{L}\d").unwrap();
+        assert!(cc.matches('a'));
+        assert!(cc.matches('É'));
+        assert!(cc.matches('β'));
+        assert!(cc.matches('7'));
+        assert!(!cc.matches('٣'));
+        assert!(!cc.matches('-'));
+
+        let cc = CharClass::parse(r"^\pN").unwrap();
+        assert!(cc.matches('x'));
+        assert!(!cc.matches('4'));
+        assert!(!cc.matches('٣'));
+
+        let cc = CharClass::parse(r"\P{L}").unwrap();
+        assert!(cc.matches(' '));
+        assert!(!cc.matches('ж'));
+
+        assert!(CharClass::parse(r"\p{Greek}").is_err());
+        assert!(CharClass::parse(r"\p{L").is_err());
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let consonants = CharClass::parse("a-z&&[^aeiou]").unwrap();
+        assert!(consonants.matches('b'));
+        assert!(consonants.matches('z'));
+        assert!(!consonants.matches('e'));
+        assert!(!consonants.matches('B'));
+
+        let nonzero = CharClass::parse("0-9--[0]").unwrap();
+        assert!(nonzero.matches('1'));
+        assert!(!nonzero.matches('0'));
+
+        let others = CharClass::parse("^a-z&&[^aeiou]").unwrap();
+        assert!(others.matches('a'));
+        assert!(others.matches('ж'));
+        assert!(!others.matches('x'));
+
+        assert!(CharClass::parse("a&&[b").is_err());
+    }
 }
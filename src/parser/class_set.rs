@@ -0,0 +1,447 @@
+//! Character class set operations: nested classes, `&&` and `--`
+//!
+//! `[a-z&&[^aeiou]]` is the intersection of its operands (the consonants),
+//! `[a-z--aeiou]` their difference, and a nested class on its own such as
+//! `[a[bc]]` joins the union. Operators are left-associative with equal
+//! precedence, and an empty operand is the empty set, as in the `regex` crate.
+//!
+//! These classes are evaluated to the code point ranges they cover and then
+//! handled like any plain class.
+
+use crate::parser::charclass::CharClass;
+use crate::parser::escape::parse_escape;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Sorted, non-overlapping, non-adjacent code point ranges
+type RangeSet = Vec<(u32, u32)>;
+
+const MAX_CHAR: u32 = char::MAX as u32;
+
+/// Rewrite every class in `pattern` that uses set operations or nested
+/// classes into the plain class it denotes
+///
+/// Returns `None` when there is nothing to rewrite. Errors carry the byte
+/// offset of the class they were found in.
+pub(crate) fn expand_set_operations(pattern: &str) -> Result<Option<String>, (usize, String)> {
+    let mut out = String::new();
+    let mut copied = 0;
+    let mut pos = 0;
+    while let Some(ch) = pattern[pos..].chars().next() {
+        match ch {
+            '\\' => {
+                pos += 1;
+                pos += pattern[pos..].chars().next().map_or(0, char::len_utf8);
+            }
+            '[' => {
+                let mut parser = SetParser::new(pattern, pos);
+                match parser.class() {
+                    Ok(members) if parser.extended => {
+                        out.push_str(&pattern[copied..pos]);
+                        push_class(&mut out, &members);
+                        copied = parser.pos;
+                        pos = parser.pos;
+                    }
+                    Err(err) if parser.extended => return Err(err),
+                    // A plain class: skip it the way the class parser does
+                    _ => pos = plain_class_end(pattern, pos),
+                }
+            }
+            _ => pos += ch.len_utf8(),
+        }
+    }
+    if copied == 0 {
+        return Ok(None);
+    }
+    out.push_str(&pattern[copied..]);
+    Ok(Some(out))
+}
+
+/// Parse the inside of a class (no brackets) if it uses set operations
+pub(crate) fn parse_set_operations(content: &str) -> Result<Option<CharClass>, String> {
+    let bracketed = format!("[{}]", content);
+    let mut parser = SetParser::new(&bracketed, 0);
+    match parser.class() {
+        Ok(members) if parser.extended && parser.pos == bracketed.len() => {
+            Ok(Some(class_from_ranges(&members)))
+        }
+        Err((_, message)) if parser.extended => Err(message),
+        _ => Ok(None),
+    }
+}
+
+/// Byte offset just past a plain class starting at `start`: the first
+/// unescaped `]`, not counting one right after `[` or `[^`
+fn plain_class_end(pattern: &str, start: usize) -> usize {
+    let bytes = pattern.as_bytes();
+    let mut i = start + 1;
+    if bytes.get(i) == Some(&b'^') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b']') {
+        i += 1;
+    }
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b']' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+struct SetParser<'p> {
+    pattern: &'p str,
+    pos: usize,
+    /// Whether an operator or nested class was seen
+    extended: bool,
+}
+
+impl<'p> SetParser<'p> {
+    fn new(pattern: &'p str, pos: usize) -> Self {
+        SetParser {
+            pattern,
+            pos,
+            extended: false,
+        }
+    }
+
+    fn rest(&self) -> &'p str {
+        &self.pattern[self.pos..]
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let found = self.rest().starts_with(token);
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    /// Parse a class starting at its `[`
+    fn class(&mut self) -> Result<RangeSet, (usize, String)> {
+        let open = self.pos;
+        self.pos += 1;
+        let negated = self.eat("^");
+        let mut members = self.operand(true)?;
+        loop {
+            if self.eat("&&") {
+                self.extended = true;
+                members = intersect(&members, &self.operand(false)?);
+            } else if self.eat("--") {
+                self.extended = true;
+                members = intersect(&members, &complement(&self.operand(false)?));
+            } else if self.eat("]") {
+                break;
+            } else {
+                return Err((open, "Unclosed character class".to_string()));
+            }
+        }
+        Ok(if negated {
+            complement(&members)
+        } else {
+            members
+        })
+    }
+
+    /// Union of the items up to the next operator or the closing `]`
+    fn operand(&mut self, at_open: bool) -> Result<RangeSet, (usize, String)> {
+        let mut members = Vec::new();
+        // A `]` right after `[` or `[^` is a member, not the end
+        let mut first = at_open;
+        while let Some(ch) = self.rest().chars().next() {
+            if (ch == ']' && !first)
+                || self.rest().starts_with("&&")
+                || self.rest().starts_with("--")
+            {
+                break;
+            }
+            first = false;
+
+            if ch == '[' {
+                let nested = self.class()?;
+                self.extended = true;
+                members.extend(nested);
+                continue;
+            }
+
+            let start = self.pos;
+            let (set, single) = self.atom()?;
+            // A range like a-z; a `-` before `]` or another `-` is not one
+            let range_end = self.rest().strip_prefix('-').and_then(|after| {
+                (!after.is_empty() && !after.starts_with(']') && !after.starts_with('-'))
+                    .then_some(())
+            });
+            match (single, range_end) {
+                (Some(lo), Some(())) => {
+                    self.pos += 1;
+                    let hi = match self.atom()? {
+                        (_, Some(hi)) => hi,
+                        _ => return Err((start, "Invalid range".to_string())),
+                    };
+                    if lo > hi {
+                        return Err((start, format!("Invalid range: {}-{}", lo, hi)));
+                    }
+                    members.push((lo as u32, hi as u32));
+                }
+                _ => members.extend(set),
+            }
+        }
+        Ok(normalize(members))
+    }
+
+    /// One char or escape; the char itself when it stands for a single one
+    fn atom(&mut self) -> Result<(RangeSet, Option<char>), (usize, String)> {
+        let start = self.pos;
+        let ch = self
+            .rest()
+            .chars()
+            .next()
+            .ok_or((start, "Unclosed character class".to_string()))?;
+        if ch != '\\' {
+            self.pos += ch.len_utf8();
+            let code = ch as u32;
+            return Ok((vec![(code, code)], Some(ch)));
+        }
+
+        let escaped = self.rest()[1..]
+            .chars()
+            .next()
+            .ok_or((start, "Incomplete escape sequence".to_string()))?;
+        if escaped.is_ascii_punctuation() {
+            // \- \& \] and the like stand for themselves
+            self.pos += 1 + escaped.len_utf8();
+            let code = escaped as u32;
+            return Ok((vec![(code, code)], Some(escaped)));
+        }
+        let (seq, consumed) = parse_escape(self.rest()).map_err(|e| (start, e))?;
+        self.pos += consumed;
+        if let Some(ch) = seq.to_char() {
+            let code = ch as u32;
+            return Ok((vec![(code, code)], Some(ch)));
+        }
+        match seq.to_char_class() {
+            Some(class) => Ok((class_members(&class), None)),
+            None => Err((
+                start,
+                format!(
+                    "Escape not allowed in a character class: {}",
+                    &self.pattern[start..self.pos]
+                ),
+            )),
+        }
+    }
+}
+
+/// Code points matched by a plain class
+fn class_members(class: &CharClass) -> RangeSet {
+    let members = normalize(
+        class
+            .chars
+            .iter()
+            .map(|&ch| (ch as u32, ch as u32))
+            .chain(class.ranges.iter().map(|&(lo, hi)| (lo as u32, hi as u32)))
+            .chain(
+                class
+                    .properties
+                    .iter()
+                    .flat_map(|property| property.ranges()),
+            )
+            .collect(),
+    );
+    if class.negated {
+        complement(&members)
+    } else {
+        members
+    }
+}
+
+fn normalize(mut ranges: RangeSet) -> RangeSet {
+    ranges.sort_unstable();
+    let mut merged: RangeSet = Vec::with_capacity(ranges.len());
+    for (lo, hi) in ranges {
+        match merged.last_mut() {
+            Some(last) if lo <= last.1.saturating_add(1) => last.1 = last.1.max(hi),
+            _ => merged.push((lo, hi)),
+        }
+    }
+    merged
+}
+
+fn complement(ranges: &[(u32, u32)]) -> RangeSet {
+    let mut gaps = Vec::new();
+    let mut next = 0;
+    for &(lo, hi) in ranges {
+        if lo > next {
+            gaps.push((next, lo - 1));
+        }
+        next = hi + 1;
+    }
+    if next <= MAX_CHAR {
+        gaps.push((next, MAX_CHAR));
+    }
+    gaps
+}
+
+fn intersect(a: &[(u32, u32)], b: &[(u32, u32)]) -> RangeSet {
+    let mut common = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        let lo = a[i].0.max(b[j].0);
+        let hi = a[i].1.min(b[j].1);
+        if lo <= hi {
+            common.push((lo, hi));
+        }
+        if a[i].1 < b[j].1 {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    common
+}
+
+/// Ranges as chars, leaving out the surrogate code points
+fn char_ranges(ranges: &[(u32, u32)]) -> Vec<(char, char)> {
+    const SURROGATES: (u32, u32) = (0xD800, 0xDFFF);
+    let mut chars = Vec::new();
+    for &(lo, hi) in ranges {
+        for (lo, hi) in [
+            (lo, hi.min(SURROGATES.0 - 1)),
+            (lo.max(SURROGATES.1 + 1), hi),
+        ] {
+            if let (Some(lo), Some(hi)) = (char::from_u32(lo), char::from_u32(hi)) {
+                if lo <= hi {
+                    chars.push((lo, hi));
+                }
+            }
+        }
+    }
+    chars
+}
+
+/// A set covering the top of the code point range is shorter negated
+fn negated_form(members: &[(u32, u32)]) -> (bool, RangeSet) {
+    match members.last() {
+        Some(&(_, hi)) if hi == MAX_CHAR => (true, complement(members)),
+        _ => (false, members.to_vec()),
+    }
+}
+
+fn class_from_ranges(members: &[(u32, u32)]) -> CharClass {
+    let (negated, ranges) = negated_form(members);
+    let mut class = CharClass::new();
+    for (lo, hi) in char_ranges(&ranges) {
+        if lo == hi {
+            class.add_char(lo);
+        } else {
+            class.add_range(lo, hi);
+        }
+    }
+    if negated {
+        class.negate();
+    }
+    class.finalize();
+    class
+}
+
+/// Write `members` as a plain bracketed class
+fn push_class(out: &mut String, members: &[(u32, u32)]) {
+    let (negated, ranges) = negated_form(members);
+    let ranges = char_ranges(&ranges);
+    if ranges.is_empty() {
+        // Every char, or none at all
+        out.push_str(if negated {
+            "[\0-\u{10FFFF}]"
+        } else {
+            "[^\0-\u{10FFFF}]"
+        });
+        return;
+    }
+    out.push('[');
+    if negated {
+        out.push('^');
+    }
+    for (lo, hi) in ranges {
+        push_class_char(out, lo);
+        if hi > lo {
+            out.push('-');
+            push_class_char(out, hi);
+        }
+    }
+    out.push(']');
+}
+
+fn push_class_char(out: &mut String, ch: char) {
+    if matches!(ch, '\\' | ']' | '[' | '^' | '-' | '&') {
+        out.push('\\');
+    }
+    out.push(ch);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand(pattern: &str) -> String {
+        expand_set_operations(pattern).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_intersection_and_difference() {
+        assert_eq!(expand("[a-z&&[^aeiou]]"), "[b-df-hj-np-tv-z]");
+        assert_eq!(expand("[a-z--[aeiou]]"), "[b-df-hj-np-tv-z]");
+        assert_eq!(expand("[a-z--aeiou]"), "[b-df-hj-np-tv-z]");
+        assert_eq!(expand("[0-9&&[^0]]"), "[1-9]");
+        assert_eq!(expand(r"x[\w&&\D]+y"), r"x[A-Z_a-z]+y");
+        assert_eq!(expand("[a-c&&b-d--c]"), "[b]");
+    }
+
+    #[test]
+    fn test_nested_union_and_negation() {
+        assert_eq!(expand("[a[bc]]"), "[a-c]");
+        assert_eq!(expand("[^a-z&&[^aeiou]]"), "[^b-df-hj-np-tv-z]");
+        assert_eq!(expand(r"[\]&&\]]"), r"[\]]");
+    }
+
+    #[test]
+    fn test_empty_operands() {
+        assert_eq!(expand("[a&&b]"), "[^\0-\u{10FFFF}]");
+        assert_eq!(expand("[&&a]"), "[^\0-\u{10FFFF}]");
+        assert_eq!(expand("[+--]"), "[+]");
+        assert_eq!(expand("[^a&&b]"), "[\0-\u{10FFFF}]");
+    }
+
+    #[test]
+    fn test_plain_classes_are_left_alone() {
+        for pattern in [
+            "[a-z]+",
+            "[]a]",
+            "[^]a]",
+            r"\[a&&b\]",
+            "[[]",
+            "a[-]b",
+            "[a-]",
+        ] {
+            assert_eq!(expand_set_operations(pattern), Ok(None), "{pattern:?}");
+        }
+    }
+
+    #[test]
+    fn test_malformed_set_operations() {
+        assert!(expand_set_operations("[a-z&&[^aeiou]").is_err());
+        assert!(expand_set_operations("[a&&[z-a]]").is_err());
+    }
+
+    #[test]
+    fn test_properties_in_operands() {
+        let class = parse_set_operations(r"\p{L}&&[^a-z]").unwrap().unwrap();
+        assert!(class.matches('É'));
+        assert!(class.matches('Q'));
+        assert!(!class.matches('q'));
+        assert!(!class.matches('1'));
+    }
+}
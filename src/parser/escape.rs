@@ -4,9 +4,14 @@ use crate::parser::boundary::BoundaryType;
 /// Supports:
 /// - Character classes: \d, \w, \s, \D, \W, \S
 /// - Special chars: \n, \t, \r
-/// - Word boundaries: \b, \B
+/// - Horizontal/vertical whitespace: \h, \v; any line break: \R
+/// - Word boundaries: \b, \B; ASCII-only \b{ascii}, \B{ascii}
+/// - Unicode general categories: \pL, \p{Lu}, \P{N}
 /// - Literal escapes: \., \*, \\, \+, \?, \[, \], \(, \), \|, \^, \$
 use crate::parser::charclass::CharClass;
+use crate::parser::unicode::{parse_property_name, UnicodeProperty};
+use alloc::format;
+use alloc::string::{String, ToString};
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum EscapeSequence {
@@ -18,20 +23,32 @@ pub enum EscapeSequence {
     Word,
     /// \W - non-word characters [^a-zA-Z0-9_]
     NonWord,
-    /// \s - whitespace [ \t\n\r]
+    /// \s - Unicode whitespace, the chars `char::is_whitespace` accepts
     Whitespace,
-    /// \S - non-whitespace [^ \t\n\r]
+    /// \S - non-whitespace, the complement of \s
     NonWhitespace,
+    /// \h - horizontal whitespace [ \t\u{A0}...]
+    HorizontalSpace,
+    /// \v - vertical whitespace [\n\x0B\x0C\r\u{85}\u{2028}\u{2029}]
+    VerticalSpace,
+    /// \R - any line break, \r\n counts as one
+    LineBreak,
     /// \b - word boundary
     WordBoundary,
     /// \B - non-word boundary
     NonWordBoundary,
+    /// \b{ascii} - word boundary, ASCII word chars only
+    AsciiWordBoundary,
+    /// \B{ascii} - non-word boundary, ASCII word chars only
+    AsciiNonWordBoundary,
     /// \n - newline
     Newline,
     /// \t - tab
     Tab,
     /// \r - carriage return
     CarriageReturn,
+    /// \p{..} or \P{..} - Unicode general category (already complemented for \P)
+    Property(UnicodeProperty),
     /// \. or \* or \\ etc - literal character
     Literal(char),
 }
@@ -77,27 +94,55 @@ impl EscapeSequence {
                 Some(cc)
             }
             EscapeSequence::Whitespace => {
-                // \s = [ \t\n\r]
+                // \s = [\t\n\x0B\x0C\r \u{85}\u{A0}...]
                 let mut cc = CharClass::new();
-                cc.add_char(' ');
-                cc.add_char('\t');
-                cc.add_char('\n');
-                cc.add_char('\r');
+                WHITESPACE_CHARS.iter().for_each(|&ch| cc.add_char(ch));
+                WHITESPACE_RANGES
+                    .iter()
+                    .for_each(|&(start, end)| cc.add_range(start, end));
                 cc.finalize();
                 Some(cc)
             }
             EscapeSequence::NonWhitespace => {
-                // \S = [^ \t\n\r]
+                // \S = [^\t\n\x0B\x0C\r \u{85}\u{A0}...]
                 let mut cc = CharClass::new();
-                cc.add_char(' ');
-                cc.add_char('\t');
-                cc.add_char('\n');
-                cc.add_char('\r');
+                WHITESPACE_CHARS.iter().for_each(|&ch| cc.add_char(ch));
+                WHITESPACE_RANGES
+                    .iter()
+                    .for_each(|&(start, end)| cc.add_range(start, end));
                 cc.negate();
                 cc.finalize();
                 Some(cc)
             }
-            _ => None, // Literals and boundaries don't convert to CharClass
+            EscapeSequence::HorizontalSpace => {
+                // \h = [\t \u{A0}\u{1680}\u{2000}-\u{200A}\u{202F}\u{205F}\u{3000}]
+                let mut cc = CharClass::new();
+                HORIZONTAL_SPACE_CHARS
+                    .iter()
+                    .for_each(|&ch| cc.add_char(ch));
+                HORIZONTAL_SPACE_RANGES
+                    .iter()
+                    .for_each(|&(start, end)| cc.add_range(start, end));
+                cc.finalize();
+                Some(cc)
+            }
+            EscapeSequence::VerticalSpace => {
+                // \v = [\n\x0B\x0C\r\u{85}\u{2028}\u{2029}]
+                let mut cc = CharClass::new();
+                VERTICAL_SPACE_CHARS.iter().for_each(|&ch| cc.add_char(ch));
+                VERTICAL_SPACE_RANGES
+                    .iter()
+                    .for_each(|&(start, end)| cc.add_range(start, end));
+                cc.finalize();
+                Some(cc)
+            }
+            EscapeSequence::Property(property) => {
+                let mut cc = CharClass::new();
+                cc.add_property(*property);
+                cc.finalize();
+                Some(cc)
+            }
+            _ => None, // Literals, boundaries and \R don't convert to CharClass
         }
     }
 
@@ -106,6 +151,8 @@ impl EscapeSequence {
         match self {
             EscapeSequence::WordBoundary => Some(BoundaryType::Word),
             EscapeSequence::NonWordBoundary => Some(BoundaryType::NonWord),
+            EscapeSequence::AsciiWordBoundary => Some(BoundaryType::AsciiWord),
+            EscapeSequence::AsciiNonWordBoundary => Some(BoundaryType::AsciiNonWord),
             _ => None,
         }
     }
@@ -137,7 +184,7 @@ pub fn parse_escape(pattern: &str) -> Result<(EscapeSequence, usize), String> {
     }
 
     let escape_char = bytes[1] as char;
-    let bytes_consumed = 2; // \d, \w, etc are always 2 bytes in ASCII
+    let mut bytes_consumed = 2; // \d, \w, etc are always 2 bytes in ASCII
 
     let seq = match escape_char {
         'd' => EscapeSequence::Digit,
@@ -146,11 +193,33 @@ pub fn parse_escape(pattern: &str) -> Result<(EscapeSequence, usize), String> {
         'W' => EscapeSequence::NonWord,
         's' => EscapeSequence::Whitespace,
         'S' => EscapeSequence::NonWhitespace,
+        'b' | 'B' if pattern[2..].starts_with("{ascii}") => {
+            bytes_consumed += "{ascii}".len();
+            if escape_char == 'b' {
+                EscapeSequence::AsciiWordBoundary
+            } else {
+                EscapeSequence::AsciiNonWordBoundary
+            }
+        }
         'b' => EscapeSequence::WordBoundary,    // NEW: \b
         'B' => EscapeSequence::NonWordBoundary, // NEW: \B
         'n' => EscapeSequence::Newline,
         't' => EscapeSequence::Tab,
         'r' => EscapeSequence::CarriageReturn,
+        'h' => EscapeSequence::HorizontalSpace,
+        'v' => EscapeSequence::VerticalSpace,
+        'R' => EscapeSequence::LineBreak,
+        'p' | 'P' => {
+            let (name, name_len) = parse_property_name(&pattern[2..])?;
+            let property = UnicodeProperty::from_name(name)
+                .ok_or_else(|| format!("Unknown Unicode property: {}", name))?;
+            bytes_consumed += name_len;
+            EscapeSequence::Property(if escape_char == 'P' {
+                property.complement()
+            } else {
+                property
+            })
+        }
         // Literal escapes for regex metacharacters
         '.' | '*' | '+' | '?' | '[' | ']' | '(' | ')' | '|' | '^' | '$' | '{' | '}' | '\\' => {
             EscapeSequence::Literal(escape_char)
@@ -161,6 +230,25 @@ pub fn parse_escape(pattern: &str) -> Result<(EscapeSequence, usize), String> {
     Ok((seq, bytes_consumed))
 }
 
+/// Single-char members of \s: Unicode White_Space, the same set as
+/// `char::is_whitespace`, which the fast-path scanners use instead
+pub(crate) const WHITESPACE_CHARS: &[char] = &[
+    '\t', '\n', '\x0B', '\x0C', '\r', ' ', '\u{85}', '\u{A0}', '\u{1680}', '\u{2028}', '\u{2029}',
+    '\u{202F}', '\u{205F}', '\u{3000}',
+];
+/// Range members of \s
+pub(crate) const WHITESPACE_RANGES: &[(char, char)] = &[('\u{2000}', '\u{200A}')];
+/// Single-char members of \h
+pub(crate) const HORIZONTAL_SPACE_CHARS: &[char] = &[
+    '\t', ' ', '\u{A0}', '\u{1680}', '\u{202F}', '\u{205F}', '\u{3000}',
+];
+/// Range members of \h
+pub(crate) const HORIZONTAL_SPACE_RANGES: &[(char, char)] = &[('\u{2000}', '\u{200A}')];
+/// Single-char members of \v
+pub(crate) const VERTICAL_SPACE_CHARS: &[char] = &['\u{85}'];
+/// Range members of \v: \n \x0B \x0C \r and the Unicode line/paragraph separators
+pub(crate) const VERTICAL_SPACE_RANGES: &[(char, char)] = &[('\n', '\r'), ('\u{2028}', '\u{2029}')];
+
 /// Check if a pattern starts with an escape sequence (OPTIMIZED)
 #[inline(always)]
 pub fn starts_with_escape(pattern: &str) -> bool {
@@ -221,6 +309,22 @@ mod tests {
         assert!(!cc.matches('a'));
     }
 
+    #[test]
+    fn test_whitespace_is_char_is_whitespace() {
+        // The fast-path scanners use char::is_whitespace, the classes these
+        // constants; they must be the same set
+        let (seq, _) = parse_escape("\\s").unwrap();
+        let cc = seq.to_char_class().unwrap();
+        assert!(cc.is_whitespace_class());
+        let bracketed = CharClass::parse("\\s").unwrap();
+        assert!(bracketed.is_whitespace_class());
+
+        for ch in (0..=0x10FFFF).filter_map(char::from_u32) {
+            assert_eq!(cc.matches(ch), ch.is_whitespace(), "{:?}", ch);
+            assert_eq!(bracketed.matches(ch), ch.is_whitespace(), "{:?}", ch);
+        }
+    }
+
     #[test]
     fn test_parse_non_whitespace() {
         let (seq, _) = parse_escape("\\S").unwrap();
@@ -243,6 +347,11 @@ mod tests {
         assert_eq!(seq, EscapeSequence::NonWordBoundary);
         assert_eq!(len, 2);
         assert!(seq.to_boundary().is_some());
+
+        let (seq, len) = parse_escape("\\b{ascii}foo").unwrap();
+        assert_eq!(seq, EscapeSequence::AsciiWordBoundary);
+        assert_eq!(len, 9);
+        assert_eq!(seq.to_boundary(), Some(BoundaryType::AsciiWord));
     }
 
     #[test]
@@ -273,6 +382,41 @@ mod tests {
         assert_eq!(seq, EscapeSequence::Literal('+'));
     }
 
+    #[test]
+    fn test_parse_unicode_property() {
+        let (seq, len) = parse_escape("\\p{Lu}+").unwrap();
+        assert_eq!(len, 6);
+        let cc = seq.to_char_class().unwrap();
+        assert!(cc.matches('Ä'));
+        assert!(!cc.matches('ä'));
+
+        let (seq, len) = parse_escape("\\PN").unwrap();
+        assert_eq!(len, 3);
+        let cc = seq.to_char_class().unwrap();
+        assert!(cc.matches('a'));
+        assert!(!cc.matches('5'));
+
+        assert!(parse_escape("\\p{Greek}").is_err());
+        assert!(parse_escape("\\p").is_err());
+    }
+
+    #[test]
+    fn test_parse_line_escapes() {
+        let (seq, _) = parse_escape("\\h").unwrap();
+        let cc = seq.to_char_class().unwrap();
+        assert!(cc.matches('\t') && cc.matches('\u{3000}'));
+        assert!(!cc.matches('\n'));
+
+        let (seq, _) = parse_escape("\\v").unwrap();
+        let cc = seq.to_char_class().unwrap();
+        assert!(cc.matches('\n') && cc.matches('\x0C') && cc.matches('\u{2029}'));
+        assert!(!cc.matches(' '));
+
+        let (seq, len) = parse_escape("\\R").unwrap();
+        assert_eq!((seq.clone(), len), (EscapeSequence::LineBreak, 2));
+        assert!(seq.to_char_class().is_none());
+    }
+
     #[test]
     fn test_unknown_escape() {
         let result = parse_escape("\\x");
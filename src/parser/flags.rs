@@ -6,11 +6,13 @@
 //! - `(?i)` - Case-insensitive matching
 //! - `(?m)` - Multi-line mode: ^ and $ match line boundaries
 //! - `(?s)` - Single-line/DOTALL mode: . matches newlines
+//! - `(?R)` - CRLF mode: `\r` is a line terminator too, for . and (?m) anchors
+//! - `(?-u)` - ASCII word boundaries: \b and \B only count [a-zA-Z0-9_] as word chars
 //!
 //! Flags can be combined: `(?ims)` enables all three flags.
 
 /// Regex flags that modify matching behavior
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
 pub struct Flags {
     /// Case-insensitive matching (`(?i)`)
     pub case_insensitive: bool,
@@ -18,6 +20,17 @@ pub struct Flags {
     pub multiline: bool,
     /// Single-line/DOTALL mode (`(?s)`): . matches newlines
     pub dot_matches_newline: bool,
+    /// CRLF mode (`(?R)`): . excludes `\r` as well as `\n`, and (?m) anchors
+    /// treat `\r\n`, `\n` and `\r` as line terminators
+    pub crlf: bool,
+    /// ASCII word boundaries (`(?-u)`): \b and \B ignore non-ASCII word chars
+    pub ascii_word_boundary: bool,
+    /// Byte that ends a line for `.` and (?m) anchors instead of `\n`;
+    /// CRLF mode takes precedence. Only settable through `PatternBuilder`
+    pub line_terminator: Option<u8>,
+    /// Anchored search: every match must start where its search starts, as
+    /// if the pattern began with `\G`. Only settable through `PatternBuilder`
+    pub anchored: bool,
 }
 
 impl Flags {
@@ -28,7 +41,31 @@ impl Flags {
 
     /// Check if any flag is set
     pub fn any_set(&self) -> bool {
-        self.case_insensitive || self.multiline || self.dot_matches_newline
+        self.case_insensitive
+            || self.multiline
+            || self.dot_matches_newline
+            || self.crlf
+            || self.ascii_word_boundary
+            || self.line_terminator.is_some()
+            || self.anchored
+    }
+
+    /// The byte that ends a line outside CRLF mode: `\n` unless overridden
+    pub fn terminator(&self) -> u8 {
+        self.line_terminator.unwrap_or(b'\n')
+    }
+
+    /// Flags set in either `self` or `other`
+    pub fn union(self, other: Flags) -> Self {
+        Flags {
+            case_insensitive: self.case_insensitive || other.case_insensitive,
+            multiline: self.multiline || other.multiline,
+            dot_matches_newline: self.dot_matches_newline || other.dot_matches_newline,
+            crlf: self.crlf || other.crlf,
+            ascii_word_boundary: self.ascii_word_boundary || other.ascii_word_boundary,
+            line_terminator: self.line_terminator.or(other.line_terminator),
+            anchored: self.anchored || other.anchored,
+        }
     }
 
     /// Parse flags from a pattern string like `(?ims)`
@@ -70,6 +107,7 @@ impl Flags {
         // Parse flags
         let mut flags = Flags::new();
         let mut has_flags = false;
+        let mut negated = false;
 
         for ch in flags_str.chars() {
             match ch {
@@ -85,8 +123,21 @@ impl Flags {
                     flags.dot_matches_newline = true;
                     has_flags = true;
                 }
+                'R' => {
+                    flags.crlf = true;
+                    has_flags = true;
+                }
+                'u' => {
+                    // Unicode is the default; only `-u` changes anything
+                    flags.ascii_word_boundary = negated;
+                    has_flags = true;
+                }
+                '-' => {
+                    negated = true;
+                    has_flags = true;
+                }
                 // Ignore other valid flag modifiers for now
-                'x' | 'U' | '-' => {
+                'x' | 'U' => {
                     has_flags = true;
                 }
                 _ => {
@@ -136,6 +187,26 @@ mod tests {
         assert_eq!(rest, "a.*b");
     }
 
+    #[test]
+    fn test_parse_crlf_flag() {
+        let (flags, rest) = Flags::parse_from_pattern("(?mR)^a$").unwrap();
+        assert!(flags.crlf);
+        assert!(flags.multiline);
+        assert!(!flags.case_insensitive);
+        assert_eq!(rest, "^a$");
+    }
+
+    #[test]
+    fn test_parse_ascii_word_boundary_flag() {
+        let (flags, rest) = Flags::parse_from_pattern("(?i-u)\\bx").unwrap();
+        assert!(flags.ascii_word_boundary);
+        assert!(flags.case_insensitive);
+        assert_eq!(rest, "\\bx");
+
+        let (flags, _) = Flags::parse_from_pattern("(?u)x").unwrap();
+        assert!(!flags.ascii_word_boundary);
+    }
+
     #[test]
     fn test_no_flags() {
         assert!(Flags::parse_from_pattern("hello").is_none());
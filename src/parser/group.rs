@@ -8,6 +8,8 @@ use crate::parser::quantifier::Quantifier;
 /// - Quantified groups: (abc)+
 use crate::parser::sequence::Sequence;
 use crate::parser::sequence_parser::{is_sequence_pattern, parse_sequence};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// A group in a pattern
 #[derive(Debug, Clone, PartialEq)]
@@ -61,16 +63,43 @@ impl Group {
     /// Check if text matches this group at a given position
     /// Returns bytes consumed if match
     pub fn match_at(&self, text: &str, pos: usize) -> Option<usize> {
-        let base_consumed = self.match_base_at(text, pos)?;
-
-        // Apply quantifier if present
+        // Apply quantifier if present; (?:ab)* matches without any `ab`
         if let Some(quantifier) = &self.quantifier {
-            self.match_with_quantifier(text, pos, base_consumed, quantifier)
+            self.match_with_quantifier(text, pos, quantifier)
         } else {
-            Some(base_consumed)
+            self.match_base_at(text, pos)
+        }
+    }
+
+    /// Check if some branch of the group matches from `pos` through the end of text
+    ///
+    /// Unlike `match_at`, which commits to the first branch that matches, this
+    /// tries every alternative, so `(a|ab)` can still cover all of "ab".
+    pub fn matches_to_end(&self, text: &str, pos: usize) -> bool {
+        let wanted = text.len() - pos;
+        if self.quantifier.is_some() {
+            return self.match_at(text, pos) == Some(wanted);
+        }
+
+        let remaining = &text[pos..];
+        match &self.content {
+            GroupContent::Alternation(patterns) => patterns.iter().any(|p| p == remaining),
+            GroupContent::ParsedAlternation(sequences) => sequences
+                .iter()
+                .any(|seq| seq.match_at(remaining) == Some(wanted)),
+            _ => self.match_base_at(text, pos) == Some(wanted),
         }
     }
 
+    /// Find the leftmost position from which the group matches through the end of text
+    pub fn find_at_end(&self, text: &str) -> Option<(usize, usize)> {
+        text.char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(text.len()))
+            .find(|&start_pos| self.matches_to_end(text, start_pos))
+            .map(|start_pos| (start_pos, text.len()))
+    }
+
     /// Match group base pattern (without quantifier) at position
     fn match_base_at(&self, text: &str, pos: usize) -> Option<usize> {
         let remaining = &text[pos..];
@@ -110,7 +139,6 @@ impl Group {
         &self,
         text: &str,
         start_pos: usize,
-        _base_match_size: usize,
         quantifier: &Quantifier,
     ) -> Option<usize> {
         let (min, max) = quantifier_bounds(quantifier);
@@ -119,15 +147,21 @@ impl Group {
         let mut count = 0;
         let mut pos = start_pos;
 
-        // Greedy: match as many times as possible
-        while count < max {
+        // Greedy: match as many times as possible; lazy: as few as allowed
+        let wanted = if quantifier.is_lazy() { min } else { max };
+        while count < wanted {
             match self.match_base_at(text, pos) {
                 Some(consumed) if consumed > 0 => {
                     total_consumed += consumed;
                     pos += consumed;
                     count += 1;
                 }
-                _ => break,
+                // An empty iteration can repeat as often as still required
+                Some(_) => {
+                    count = count.max(min);
+                    break;
+                }
+                None => break,
             }
         }
 
@@ -164,9 +198,10 @@ impl Group {
     /// Find the group pattern anywhere in text
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
         // OPTIMIZATION: For alternation groups with common prefix, use prefix search
+        let nullable = self.match_at("", 0).is_some();
         if let GroupContent::Alternation(alternatives) = &self.content {
             if let Some(prefix) = find_common_prefix(alternatives) {
-                if prefix.len() >= 3 {
+                if prefix.len() >= 3 && !nullable {
                     // Only worthwhile for longer prefixes
                     // Use memchr to find prefix quickly
                     use memchr::memmem;
@@ -190,10 +225,13 @@ impl Group {
             }
         }
 
-        // Fallback: Original sequential search
-        let byte_positions: Vec<usize> = text.char_indices().map(|(i, _)| i).collect();
+        // Fallback: Original sequential search, up to an empty match at the end
+        let byte_positions = text
+            .char_indices()
+            .map(|(i, _)| i)
+            .chain(core::iter::once(text.len()));
 
-        for &start_pos in &byte_positions {
+        for start_pos in byte_positions {
             if let Some(consumed) = self.match_at(text, start_pos) {
                 return Some((start_pos, start_pos + consumed));
             }
@@ -215,7 +253,8 @@ impl Group {
                 let end_pos = start_pos + consumed;
                 results.push((start_pos, end_pos));
 
-                // Skip past this match
+                // Skip past this match (an empty one still moves on a char)
+                i += 1;
                 while i < byte_positions.len() && byte_positions[i] < end_pos {
                     i += 1;
                 }
@@ -473,4 +512,17 @@ mod tests {
         let matches = group.find_all("ab cd ab ef ab");
         assert_eq!(matches, vec![(0, 2), (6, 8), (12, 14)]);
     }
+
+    #[test]
+    fn test_matches_to_end_tries_every_branch() {
+        let group = Group::new_capturing(GroupContent::Alternation(vec![
+            "a".to_string(),
+            "ab".to_string(),
+        ]));
+
+        assert_eq!(group.match_at("ab", 0), Some(1));
+        assert!(group.matches_to_end("ab", 0));
+        assert_eq!(group.find_at_end("xab"), Some((1, 3)));
+        assert_eq!(group.find_at_end("abc"), None);
+    }
 }
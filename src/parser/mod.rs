@@ -6,12 +6,15 @@
 
 pub mod boundary;
 pub mod charclass;
+pub mod class_set;
 pub mod escape;
 pub mod flags;
 pub mod group;
 pub mod quantifier;
 pub mod sequence;
 pub mod sequence_parser;
+pub mod simplify;
+pub mod unicode;
 
 // Re-export commonly used types
 pub use boundary::BoundaryType;
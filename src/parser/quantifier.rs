@@ -4,6 +4,9 @@
 
 use crate::parser::charclass::CharClass;
 use crate::parser::escape::{parse_escape, starts_with_escape};
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// Represents a quantified pattern element
 #[derive(Debug, Clone, PartialEq)]
@@ -109,6 +112,15 @@ impl QuantifiedPattern {
     /// Match this quantified pattern at the start of text (OPTIMIZED)
     /// Returns the number of bytes consumed if matched
     pub fn match_at(&self, text: &str) -> Option<usize> {
+        self.match_run(text).ok()
+    }
+
+    /// Match at the start of text, reporting the scanned run on failure
+    ///
+    /// `Err(run)` carries the byte length of the (too short) run of matching
+    /// characters. No start position inside that run can succeed either, so
+    /// callers can resume scanning after it instead of retrying each position.
+    fn match_run(&self, text: &str) -> Result<usize, usize> {
         let bytes = text.as_bytes();
         let min = self.quantifier.min_matches();
         let max = self.quantifier.max_matches();
@@ -118,46 +130,34 @@ impl QuantifiedPattern {
         let mut byte_len = 0;
         let mut match_count = 0;
 
-        for &byte in bytes {
-            if match_count >= max {
-                break;
-            }
-            if byte >= 128 {
-                // Hit non-ASCII, fall through to char-based path for remainder
-                let remaining = &text[byte_len..];
-                for ch in remaining.chars() {
-                    if match_count >= max {
-                        break;
-                    }
-                    if self.element.matches(ch) {
-                        byte_len += ch.len_utf8();
-                        match_count += 1;
-                    } else {
-                        break;
-                    }
+        while byte_len < bytes.len() && match_count < max {
+            let byte = bytes[byte_len];
+            if byte < 128 {
+                if !self.element.matches_byte(byte) {
+                    break;
                 }
-                return if match_count >= min {
-                    Some(byte_len)
-                } else {
-                    None
-                };
-            }
-            if self.element.matches_byte(byte) {
                 byte_len += 1;
-                match_count += 1;
             } else {
-                break;
+                // Non-ASCII: decode the char at this position
+                let Some(ch) = text[byte_len..].chars().next() else {
+                    break;
+                };
+                if !self.element.matches(ch) {
+                    break;
+                }
+                byte_len += ch.len_utf8();
             }
+            match_count += 1;
         }
 
-        if match_count >= min {
-            if self.quantifier.is_lazy() {
-                Some(text.chars().take(min).map(|ch| ch.len_utf8()).sum())
-            } else {
-                Some(byte_len)
-            }
+        if match_count < min {
+            return Err(byte_len);
+        }
+
+        if self.quantifier.is_lazy() {
+            Ok(text.chars().take(min).map(|ch| ch.len_utf8()).sum())
         } else {
-            None
+            Ok(byte_len)
         }
     }
 
@@ -171,52 +171,27 @@ impl QuantifiedPattern {
 
     /// Find first position in text where this pattern matches
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
-        // Handle empty text first - zero-width quantifiers can match at position 0
-        if text.is_empty() {
-            if let Some(len) = self.match_at(text) {
-                return Some((0, len));
-            }
-            return None;
-        }
-
+        // Zero-width quantifiers always match at position 0
         if self.quantifier.min_matches() == 0 {
             return self.match_at(text).map(|len| (0, len));
         }
 
-        // OPTIMIZATION: Fast path for digit patterns
-        if matches!(&self.element, QuantifiedElement::CharClass(cc) if cc.is_digit_class()) {
-            // Use memchr to find first digit
-            let bytes = text.as_bytes();
-            for (i, &b) in bytes.iter().enumerate() {
-                if b.is_ascii_digit() {
-                    // Found a digit, try to match from here
-                    if let Some(len) = self.match_at(&text[i..]) {
-                        return Some((i, i + len));
-                    }
-                }
-            }
-            return None;
-        }
+        let bytes = text.as_bytes();
+        let mut pos = 0;
 
-        // OPTIMIZATION: Fast path for word char patterns
-        if matches!(&self.element, QuantifiedElement::CharClass(cc) if cc.is_word_class()) {
-            // Scan for first word char
-            let bytes = text.as_bytes();
-            for (i, &b) in bytes.iter().enumerate() {
-                if b.is_ascii_alphanumeric() || b == b'_' {
-                    if let Some(len) = self.match_at(&text[i..]) {
-                        return Some((i, i + len));
-                    }
-                }
+        while pos < bytes.len() {
+            // Cheap rejection: an ASCII byte the element can't match can't start a run
+            if bytes[pos] < 128 && !self.element.matches_byte(bytes[pos]) {
+                pos += 1;
+                continue;
             }
-            return None;
-        }
 
-        // Generic path for other patterns
-        let chars: Vec<(usize, char)> = text.char_indices().collect();
-        for (start_byte, _) in &chars {
-            if let Some(len) = self.match_at(&text[*start_byte..]) {
-                return Some((*start_byte, *start_byte + len));
+            match self.match_run(&text[pos..]) {
+                Ok(len) => return Some((pos, pos + len)),
+                Err(run) => {
+                    // Skip the whole short run plus the char that ended it
+                    pos = next_char_boundary(text, pos + run)?;
+                }
             }
         }
 
@@ -224,43 +199,52 @@ impl QuantifiedPattern {
     }
 
     /// Find all matches in text
+    /// Single left-to-right pass: a failed run is never rescanned
     pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
         let mut results = Vec::new();
+        self.find_all_into(text, &mut results);
+        results
+    }
+
+    /// Push all matches onto `results`, like [`find_all`](Self::find_all)
+    pub fn find_all_into(&self, text: &str, results: &mut Vec<(usize, usize)>) {
         let mut pos = 0;
         let mut last_match_was_non_empty = false;
 
         while pos <= text.len() {
-            if let Some(len) = self.match_at(&text[pos..]) {
-                let end = pos + len;
+            match self.match_run(&text[pos..]) {
+                Ok(len) => {
+                    let end = pos + len;
+
+                    if len == 0 && last_match_was_non_empty {
+                        last_match_was_non_empty = false;
+                        if let Some(next_pos) = next_char_boundary(text, pos) {
+                            pos = next_pos;
+                            continue;
+                        }
+                        break;
+                    }
 
-                if len == 0 && last_match_was_non_empty {
-                    last_match_was_non_empty = false;
-                    if let Some(next_pos) = next_char_boundary(text, pos) {
+                    results.push((pos, end));
+                    last_match_was_non_empty = len > 0;
+
+                    if len > 0 {
+                        pos = end;
+                    } else if let Some(next_pos) = next_char_boundary(text, pos) {
                         pos = next_pos;
-                        continue;
+                    } else {
+                        break;
                     }
-                    break;
                 }
-
-                results.push((pos, end));
-                last_match_was_non_empty = len > 0;
-
-                if len > 0 {
-                    pos = end;
-                } else if let Some(next_pos) = next_char_boundary(text, pos) {
-                    pos = next_pos;
-                } else {
-                    break;
+                Err(run) => {
+                    last_match_was_non_empty = false;
+                    match next_char_boundary(text, pos + run) {
+                        Some(next_pos) => pos = next_pos,
+                        None => break,
+                    }
                 }
-            } else if let Some(next_pos) = next_char_boundary(text, pos) {
-                last_match_was_non_empty = false;
-                pos = next_pos;
-            } else {
-                break;
             }
         }
-
-        results
     }
 }
 
@@ -504,4 +488,15 @@ mod tests {
         let matches = pattern.find_all("a1b22c333");
         assert_eq!(matches, vec![(1, 2), (3, 5), (6, 9)]);
     }
+
+    #[test]
+    fn test_find_skips_short_runs() {
+        let pattern = parse_quantified_pattern("a{3}").unwrap();
+        assert_eq!(pattern.find("aabaaa"), Some((3, 6)));
+        assert_eq!(
+            pattern.find_all("aa aaaa aaaaaaa"),
+            vec![(3, 6), (8, 11), (11, 14)]
+        );
+        assert_eq!(pattern.find("aéaa"), None);
+    }
 }
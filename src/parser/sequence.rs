@@ -8,6 +8,8 @@ use crate::parser::boundary::BoundaryType;
 use crate::parser::charclass::CharClass;
 use crate::parser::group::Group;
 use crate::parser::quantifier::Quantifier;
+use alloc::string::String;
+use alloc::vec::Vec;
 
 /// A single element in a sequence
 #[derive(Debug, Clone, PartialEq)]
@@ -30,6 +32,8 @@ pub enum SequenceElement {
     QuantifiedGroup(Group, Quantifier),
     /// A word boundary (e.g., \b or \B)
     Boundary(BoundaryType),
+    /// Any line break (\R): \r\n as one unit, or a single vertical whitespace char
+    LineBreak,
 }
 
 impl SequenceElement {
@@ -128,10 +132,26 @@ impl SequenceElement {
                     None
                 }
             }
+            SequenceElement::LineBreak => match_line_break(remaining),
         }
     }
 }
 
+/// Match a line break (\R) at the start of text
+/// \r\n is consumed as a single unit, before falling back to one-char breaks
+#[inline]
+pub(crate) fn match_line_break(text: &str) -> Option<usize> {
+    if text.starts_with("\r\n") {
+        return Some(2);
+    }
+    match text.chars().next()? {
+        ch @ ('\n' | '\x0B' | '\x0C' | '\r' | '\u{85}' | '\u{2028}' | '\u{2029}') => {
+            Some(ch.len_utf8())
+        }
+        _ => None,
+    }
+}
+
 /// Match a quantified character
 /// For lazy quantifiers, this returns minimum match; backtracking will try more
 fn match_quantified_char(ch: char, quantifier: &Quantifier, text: &str) -> Option<usize> {
@@ -168,7 +188,7 @@ fn match_quantified_charclass(
     let is_lazy = quantifier.is_lazy();
 
     // OPTIMIZATION: For negated single-char class like [^"], use memchr to find terminator
-    if cc.negated && cc.chars.len() == 1 && cc.ranges.is_empty() {
+    if cc.negated && cc.chars.len() == 1 && cc.ranges.is_empty() && cc.properties.is_empty() {
         let forbidden_char = *cc.chars.first().unwrap();
         if (forbidden_char as u32) < 128 {
             // Use memchr to find the forbidden character (terminator)
@@ -266,21 +286,35 @@ fn quantifier_bounds(q: &Quantifier) -> (usize, usize) {
 }
 
 /// Pre-computed NFA transition table for fast is_match
+///
+/// Each element is unrolled into states that match one char each: `X{2,4}`
+/// becomes two required `X` states and two optional ones, `X+` a single
+/// state that can repeat.
 #[derive(Debug, Clone, PartialEq)]
 struct NfaTable {
-    /// For each ASCII byte, bitmask of which elements match it
-    byte_elem_mask: [u32; 128],
-    /// Bitmask for the accept state (last element)
-    accept_mask: u32,
-    /// Bitmask of elements that can "stay" (quantified with +, *, etc.)
-    /// Char elements (exact match) cannot stay - they advance immediately
-    quantified_bits: u32,
-    /// Bitmask of elements that are optional (min=0, can be skipped via epsilon transition)
-    optional_bits: u32,
-    /// Number of elements
-    n: usize,
+    /// For each ASCII byte, bitmask of which states match it
+    byte_elem_mask: [u64; 128],
+    /// States matching any non-ASCII char (negated ASCII classes)
+    non_ascii_mask: u64,
+    /// States whose match on a non-ASCII whitespace char is the opposite of
+    /// `non_ascii_mask` (`\s` and `\S`)
+    non_ascii_space_mask: u64,
+    /// States after which every remaining state is optional: reaching one
+    /// completes a match
+    accept_mask: u64,
+    /// States that can repeat (unbounded quantifiers)
+    quantified_bits: u64,
+    /// States that can be skipped (the optional part of a quantifier)
+    optional_bits: u64,
+    /// All states
+    state_mask: u64,
+    /// A required ASCII char every match contains, checked with memchr
+    required_byte: Option<u8>,
 }
 
+/// Upper bound on unrolled NFA states (bits in a `u64`)
+const MAX_NFA_STATES: usize = 64;
+
 /// A sequence of pattern elements
 #[derive(Debug, Clone, PartialEq)]
 pub struct Sequence {
@@ -301,101 +335,137 @@ impl Sequence {
 
     /// Build NFA transition table for sequences of QuantifiedCharClass and Char elements
     fn build_nfa_table(elements: &[SequenceElement]) -> Option<NfaTable> {
-        let n = elements.len();
-        if !(2..=16).contains(&n) {
+        if !(2..=MAX_NFA_STATES).contains(&elements.len()) {
             return None;
         }
 
-        // Validate elements and compute quantified_bits and optional_bits
-        let mut quantified_bits: u32 = 0;
-        let mut optional_bits: u32 = 0;
-        let _has_mid_optional = false; // Track if there are optional elements not at end
-        for (i, elem) in elements.iter().enumerate() {
-            match elem {
-                SequenceElement::QuantifiedCharClass(_, quantifier) => {
-                    let (min, _max) = quantifier_bounds(quantifier);
-
-                    // Elements that are optional (min=0) can be skipped
-                    if min == 0 {
-                        optional_bits |= 1u32 << i;
+        // Unroll elements into states: (ASCII bitmap, negated, spaces, repeats, optional)
+        let mut states: Vec<([u64; 2], bool, bool, bool, bool)> = Vec::new();
+        let mut required_byte = None;
+        for elem in elements {
+            // One char each, as an ASCII bitmap plus negation and whether
+            // non-ASCII whitespace is a member, and its count
+            let (bitmap, negated, spaces, (min, max)) = match elem {
+                SequenceElement::Char(ch) | SequenceElement::QuantifiedChar(ch, _) => {
+                    if !ch.is_ascii() {
+                        return None;
                     }
-
-                    // Elements with quantifiers that can match multiple times can "stay"
-                    match quantifier {
-                        Quantifier::OneOrMore
-                        | Quantifier::OneOrMoreLazy
-                        | Quantifier::ZeroOrMore
-                        | Quantifier::ZeroOrMoreLazy => {
-                            quantified_bits |= 1u32 << i; // Can stay
+                    let byte = *ch as u8;
+                    let mut bitmap = [0u64; 2];
+                    bitmap[(byte / 64) as usize] |= 1u64 << (byte % 64);
+                    match elem {
+                        SequenceElement::QuantifiedChar(_, quantifier) => {
+                            (bitmap, false, false, quantifier_bounds(quantifier))
+                        }
+                        _ => {
+                            required_byte.get_or_insert(byte);
+                            (bitmap, false, false, (1, 1))
                         }
-                        _ => {} // ZeroOrOne, Exactly(1), etc. don't stay
                     }
                 }
-                SequenceElement::Char(_) => {
-                    // Char elements don't stay (match exactly 1 char)
+                SequenceElement::CharClass(cc) | SequenceElement::QuantifiedCharClass(cc, _) => {
+                    // Byte-level table can't classify multi-byte members,
+                    // besides whitespace which is checked char by char
+                    let spaces = cc.has_non_ascii_members();
+                    if spaces && !cc.non_ascii_members_are_whitespace() {
+                        return None;
+                    }
+                    let bounds = match elem {
+                        SequenceElement::QuantifiedCharClass(_, quantifier) => {
+                            quantifier_bounds(quantifier)
+                        }
+                        _ => (1, 1),
+                    };
+                    (*cc.get_ascii_bitmap()?, cc.negated, spaces, bounds)
                 }
                 _ => return None, // Not supported
+            };
+
+            if max == usize::MAX {
+                // X{n,} is n-1 copies of X then one that repeats; X* a single
+                // optional repeating state
+                for _ in 1..min {
+                    states.push((bitmap, negated, spaces, false, false));
+                }
+                states.push((bitmap, negated, spaces, true, min == 0));
+            } else {
+                if max > MAX_NFA_STATES {
+                    return None;
+                }
+                // X{n,m} is n copies of X then m-n optional ones
+                for i in 0..max {
+                    states.push((bitmap, negated, spaces, false, i >= min));
+                }
+            }
+            if states.len() > MAX_NFA_STATES {
+                return None;
             }
         }
 
-        // Check for optional elements - NFA doesn't handle them correctly
-        // The NFA model can't properly track "just finished matching element i" state
-        // which is needed to epsilon-transition past optional elements
-        if optional_bits != 0 {
-            // There's at least one optional element
-            // NFA doesn't handle skipping correctly, fall back to backtracking
+        // A sequence that can match empty matches everywhere; leave it to
+        // the general matcher
+        if states.iter().all(|&(_, _, _, _, optional)| optional) {
             return None;
         }
 
-        // Pre-compute byte→element match table
-        let mut byte_elem_mask = [0u32; 128];
-        for b in 0..128u8 {
-            let idx = b as usize;
-            let word_idx = idx / 64;
-            let bit = 1u64 << (idx % 64);
-            for (i, elem) in elements.iter().enumerate() {
-                match elem {
-                    SequenceElement::QuantifiedCharClass(cc, _) => {
-                        if let Some(bm) = cc.get_ascii_bitmap() {
-                            let hit = (bm[word_idx] & bit) != 0;
-                            if hit != cc.negated {
-                                byte_elem_mask[b as usize] |= 1u32 << i;
-                            }
-                        } else {
-                            return None;
-                        }
-                    }
-                    SequenceElement::Char(ch) => {
-                        if (*ch as u32) < 128 && b == *ch as u8 {
-                            byte_elem_mask[b as usize] |= 1u32 << i;
-                        }
-                    }
-                    _ => return None,
+        let mut table = NfaTable {
+            byte_elem_mask: [0u64; 128],
+            non_ascii_mask: 0,
+            non_ascii_space_mask: 0,
+            accept_mask: 0,
+            quantified_bits: 0,
+            optional_bits: 0,
+            state_mask: 0,
+            required_byte,
+        };
+        let mut rest_optional = true;
+        for (i, &(bitmap, negated, spaces, repeats, optional)) in states.iter().enumerate().rev() {
+            let bit = 1u64 << i;
+            for b in 0..128usize {
+                let hit = (bitmap[b / 64] & (1u64 << (b % 64))) != 0;
+                if hit != negated {
+                    table.byte_elem_mask[b] |= bit;
                 }
             }
+            if negated {
+                table.non_ascii_mask |= bit;
+            }
+            if spaces {
+                table.non_ascii_space_mask |= bit;
+            }
+            if repeats {
+                table.quantified_bits |= bit;
+            }
+            if optional {
+                table.optional_bits |= bit;
+            }
+            if rest_optional {
+                table.accept_mask |= bit;
+            }
+            rest_optional &= optional;
+            table.state_mask |= bit;
         }
-
-        Some(NfaTable {
-            byte_elem_mask,
-            accept_mask: 1u32 << (n - 1),
-            quantified_bits,
-            optional_bits,
-            n,
-        })
+        Some(table)
     }
 
     /// Check if the sequence matches at the start of text
     /// Returns bytes consumed if match, None otherwise
     pub fn match_at(&self, text: &str) -> Option<usize> {
         // Use backtracking-enabled matching from start
-        self.match_elements_backtracking(text, 0, 0)
+        self.match_elements_backtracking(text, 0, 0, false)
+    }
+
+    /// Whether some way of matching the sequence covers all of `text`, not
+    /// just the leftmost-first one
+    pub fn matches_entire(&self, text: &str) -> bool {
+        self.match_elements_backtracking(text, 0, 0, true).is_some()
     }
 
     /// Check if the sequence matches at a specific position in text
     /// Returns bytes consumed if match, None otherwise
     /// This preserves the full text context for boundary checks
     fn match_at_pos(&self, text: &str, pos: usize) -> Option<usize> {
-        self.match_elements_backtracking(text, 0, pos)
+        self.match_elements_backtracking(text, 0, pos, false)
     }
 
     /// Check if the sequence matches anywhere in text (optimized)
@@ -408,478 +478,57 @@ impl Sequence {
         self.find(text).is_some()
     }
 
-    /// Check if the sequence matches with flags applied
-    /// Flags can modify behavior (e.g., DOTALL makes . match newlines)
-    pub fn is_match_with_flags(&self, text: &str, flags: &crate::parser::flags::Flags) -> bool {
-        self.find_with_flags(text, flags).is_some()
-    }
-
-    /// Find match with flags applied
-    pub fn find_with_flags(
-        &self,
-        text: &str,
-        flags: &crate::parser::flags::Flags,
-    ) -> Option<(usize, usize)> {
-        // If DOTALL flag is set, replace Dot elements with "any char" matching
-        if flags.dot_matches_newline {
-            // For DOTALL mode, we need to match . against any character including \n
-            // We do this by modifying the matching logic for Dot elements
-            for start_pos in 0..text.len() {
-                if let Some(end_pos) = self.match_at_with_dotall(text, start_pos) {
-                    return Some((start_pos, end_pos));
-                }
-            }
-            return None;
-        }
-
-        // No special flags, use normal find
-        self.find(text)
-    }
-
-    /// Match at position with DOTALL flag (. matches newlines)
-    fn match_at_with_dotall(&self, text: &str, start_pos: usize) -> Option<usize> {
-        self.match_elements_backtracking_dotall(text, 0, start_pos)
-    }
-
-    /// Backtracking match with DOTALL mode
-    fn match_elements_backtracking_dotall(
-        &self,
-        text: &str,
-        elem_idx: usize,
-        text_pos: usize,
-    ) -> Option<usize> {
-        // Base case: matched all elements
-        if elem_idx >= self.elements.len() {
-            return Some(text_pos);
-        }
-
-        let element = &self.elements[elem_idx];
-
-        // Ensure text_pos is at a char boundary for Unicode safety
-        let text_pos = if text_pos < text.len() && !text.is_char_boundary(text_pos) {
-            // Find the next char boundary
-            let mut pos = text_pos;
-            while pos < text.len() && !text.is_char_boundary(pos) {
-                pos += 1;
-            }
-            pos
-        } else {
-            text_pos
-        };
-
-        let remaining = if text_pos < text.len() {
-            &text[text_pos..]
-        } else {
-            ""
-        };
-
-        match element {
-            // Handle Dot specially in DOTALL mode - match ANY character
-            SequenceElement::Dot => {
-                if let Some(ch) = remaining.chars().next() {
-                    // In DOTALL mode, dot matches ANY character including newline
-                    let consumed = ch.len_utf8();
-                    self.match_elements_backtracking_dotall(text, elem_idx + 1, text_pos + consumed)
-                } else {
-                    None
-                }
-            }
-            // QuantifiedChar with backtracking in DOTALL mode
-            SequenceElement::QuantifiedChar(ch, quantifier) => {
-                let (min, max) = (quantifier.min_matches(), quantifier.max_matches());
-                let is_lazy = quantifier.is_lazy();
-                let ch_len = ch.len_utf8();
-
-                let mut max_count = 0;
-                let mut byte_positions: Vec<usize> = Vec::new();
-                byte_positions.push(0);
-                let mut byte_offset = 0;
-                for c in remaining.chars() {
-                    if c == *ch {
-                        max_count += 1;
-                        byte_offset += ch_len;
-                        byte_positions.push(byte_offset);
-                        if max_count >= max {
-                            break;
-                        }
-                    } else {
-                        break;
-                    }
-                }
-
-                if max_count < min {
-                    return None;
-                }
-                let max_count = max_count.min(max);
-
-                if is_lazy {
-                    for try_count in min..=max_count {
-                        let consumed = byte_positions[try_count];
-                        if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                            text,
-                            elem_idx + 1,
-                            text_pos + consumed,
-                        ) {
-                            return Some(final_pos);
-                        }
-                    }
-                } else {
-                    for try_count in (min..=max_count).rev() {
-                        let consumed = byte_positions[try_count];
-                        if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                            text,
-                            elem_idx + 1,
-                            text_pos + consumed,
-                        ) {
-                            return Some(final_pos);
-                        }
-                    }
-                }
-                None
-            }
-            // QuantifiedCharClass - handle both dot class (matches newlines in DOTALL) and normal
-            SequenceElement::QuantifiedCharClass(cc, quantifier) => {
-                // Check if this is a "dot class" [^\n] - if so, match everything in DOTALL mode
-                let is_dot_class = cc.negated
-                    && cc.chars.len() == 1
-                    && cc.chars[0] == '\n'
-                    && cc.ranges.is_empty();
-                let (min, max) = (quantifier.min_matches(), quantifier.max_matches());
-                let is_lazy = quantifier.is_lazy();
-
-                if is_dot_class {
-                    // In DOTALL mode, dot class matches any character including newlines
-                    let max_count = remaining.chars().count().min(max);
-
-                    if max_count < min {
-                        return None;
-                    }
-
-                    // Build byte positions
-                    let mut byte_positions: Vec<usize> = Vec::new();
-                    byte_positions.push(0);
-                    let mut byte_offset = 0;
-                    for (i, ch) in remaining.chars().enumerate() {
-                        if i >= max_count {
-                            break;
-                        }
-                        byte_offset += ch.len_utf8();
-                        byte_positions.push(byte_offset);
-                    }
-
-                    if is_lazy {
-                        for try_count in min..=max_count {
-                            let consumed = byte_positions[try_count];
-                            if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                                text,
-                                elem_idx + 1,
-                                text_pos + consumed,
-                            ) {
-                                return Some(final_pos);
-                            }
-                        }
-                    } else {
-                        for try_count in (min..=max_count).rev() {
-                            let consumed = byte_positions[try_count];
-                            if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                                text,
-                                elem_idx + 1,
-                                text_pos + consumed,
-                            ) {
-                                return Some(final_pos);
-                            }
-                        }
-                    }
-                    None
-                } else {
-                    // Normal quantified charclass - backtrack with DOTALL continuation
-                    let mut max_count = 0;
-                    let mut byte_positions: Vec<usize> = Vec::new();
-                    byte_positions.push(0);
-                    let mut byte_offset = 0;
-                    for c in remaining.chars() {
-                        if cc.matches(c) {
-                            max_count += 1;
-                            byte_offset += c.len_utf8();
-                            byte_positions.push(byte_offset);
-                            if max_count >= max {
-                                break;
-                            }
-                        } else {
-                            break;
-                        }
-                    }
-
-                    if max_count < min {
-                        return None;
-                    }
-                    let max_count = max_count.min(max);
-
-                    if is_lazy {
-                        for try_count in min..=max_count {
-                            let consumed = byte_positions[try_count];
-                            if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                                text,
-                                elem_idx + 1,
-                                text_pos + consumed,
-                            ) {
-                                return Some(final_pos);
-                            }
-                        }
-                    } else {
-                        for try_count in (min..=max_count).rev() {
-                            let consumed = byte_positions[try_count];
-                            if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                                text,
-                                elem_idx + 1,
-                                text_pos + consumed,
-                            ) {
-                                return Some(final_pos);
-                            }
-                        }
-                    }
-                    None
-                }
-            }
-            // Quantified group with backtracking in DOTALL mode
-            SequenceElement::QuantifiedGroup(group, quantifier) => {
-                let (min, max) = (quantifier.min_matches(), quantifier.max_matches());
-                let is_lazy = quantifier.is_lazy();
-
-                // Find all possible match lengths
-                let mut byte_positions: Vec<usize> = Vec::new();
-                byte_positions.push(0);
-                let mut current_pos = text_pos;
-                let mut count = 0;
-
-                while count < max && current_pos <= text.len() {
-                    if let Some(consumed) = group.match_at(text, current_pos) {
-                        if consumed == 0 {
-                            break;
-                        }
-                        current_pos += consumed;
-                        count += 1;
-                        byte_positions.push(current_pos - text_pos);
-                    } else {
-                        break;
-                    }
-                }
-
-                if count < min {
-                    return None;
-                }
-                let max_count = count.min(max);
-
-                if is_lazy {
-                    for try_count in min..=max_count {
-                        let consumed = byte_positions[try_count];
-                        if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                            text,
-                            elem_idx + 1,
-                            text_pos + consumed,
-                        ) {
-                            return Some(final_pos);
-                        }
-                    }
-                } else {
-                    for try_count in (min..=max_count).rev() {
-                        let consumed = byte_positions[try_count];
-                        if let Some(final_pos) = self.match_elements_backtracking_dotall(
-                            text,
-                            elem_idx + 1,
-                            text_pos + consumed,
-                        ) {
-                            return Some(final_pos);
-                        }
-                    }
-                }
-                None
-            }
-            // Other elements use standard matching
-            _ => {
-                if let Some(consumed) = element.match_at(text, text_pos) {
-                    self.match_elements_backtracking_dotall(text, elem_idx + 1, text_pos + consumed)
-                } else {
-                    None
-                }
-            }
-        }
-    }
-
     /// Element-level Thompson NFA simulation using pre-computed transition table.
     /// Returns Some(bool) if NFA table is available, None otherwise.
     #[inline]
     fn is_match_nfa(&self, text: &str) -> Option<bool> {
         let table = self.nfa_table.as_ref()?;
-
-        // OPTIMIZATION: If pattern has a single Char element (inner literal),
-        // use memchr to find it first, then verify context.
-        if let Some(result) = self.is_match_nfa_with_char_prefilter(table, text) {
-            return Some(result);
-        }
-
-        // Full NFA scan
-        Some(self.run_nfa(table, text.as_bytes()))
-    }
-
-    /// Try memchr-based prefilter for patterns with a Char element.
-    /// Returns Some(bool) if applicable, None to fall through to full NFA.
-    #[inline]
-    fn is_match_nfa_with_char_prefilter(&self, table: &NfaTable, text: &str) -> Option<bool> {
-        // Find the Char element (if exactly one exists and not at position 0 or n-1)
-        let n = table.n;
-        let mut char_idx = None;
-        let mut char_byte = 0u8;
-        for (i, elem) in self.elements.iter().enumerate() {
-            if let SequenceElement::Char(ch) = elem {
-                if i > 0 && i < n - 1 && (*ch as u32) < 128 {
-                    char_idx = Some(i);
-                    char_byte = *ch as u8;
-                    break;
-                }
-            }
-        }
-        let char_pos = char_idx?;
-
-        // Use memchr to find the literal char
         let bytes = text.as_bytes();
-        let mut search_pos = 0;
-        while search_pos < bytes.len() {
-            let found = memchr::memchr(char_byte, &bytes[search_pos..])?;
-            let abs_pos = search_pos + found;
-
-            // Verify left side: elements[0..char_pos] must match ending at abs_pos
-            let left_ok = if char_pos == 0 {
-                true
-            } else {
-                // Need at least char_pos bytes before (min 1 char per element)
-                abs_pos >= char_pos && self.verify_left(table, &bytes[..abs_pos], char_pos)
-            };
 
-            if left_ok {
-                // Verify right side: elements[char_pos+1..n] must match starting after abs_pos
-                let right_start = abs_pos + 1;
-                let right_ok = if char_pos + 1 >= n {
-                    true
-                } else {
-                    right_start < bytes.len()
-                        && self.verify_right(table, &bytes[right_start..], char_pos + 1)
-                };
-
-                if right_ok {
-                    return Some(true);
-                }
-            }
-
-            search_pos = abs_pos + 1;
-        }
-
-        Some(false)
-    }
-
-    /// Verify left side of pattern matches (elements[0..end_idx] in text ending at text end)
-    #[inline]
-    fn verify_left(&self, table: &NfaTable, text: &[u8], end_idx: usize) -> bool {
-        // Check that at least one char at the end matches elements[end_idx-1]
-        // and recursively backward. For simplicity, just check min-length matching.
-        let byte_elem_mask = &table.byte_elem_mask;
-        // Run NFA on the left text, checking if we reach state end_idx-1 (min met)
-        let target_mask = 1u32 << (end_idx - 1);
-        let quantified_bits = table.quantified_bits;
-        let mut active: u32 = 0;
-
-        for &byte in text {
-            let elem_mask = if byte < 128 {
-                byte_elem_mask[byte as usize]
-            } else {
-                0
-            };
-            active = (active & elem_mask & quantified_bits)
-                | ((active << 1) & elem_mask)
-                | (elem_mask & 1u32);
-            // Only keep bits for elements before the char
-            active &= (1u32 << end_idx) - 1;
-            if active & target_mask != 0 {
-                return true;
+        // Every match contains the required char: without it there is
+        // nothing to scan for
+        if let Some(byte) = table.required_byte {
+            if memchr::memchr(byte, bytes).is_none() {
+                return Some(false);
             }
         }
-        false
-    }
 
-    /// Verify right side of pattern matches (elements[start_idx..n] in text)
-    #[inline]
-    fn verify_right(&self, table: &NfaTable, text: &[u8], start_idx: usize) -> bool {
-        let byte_elem_mask = &table.byte_elem_mask;
-        let accept_mask = table.accept_mask;
-        let quantified_bits = table.quantified_bits;
-        let start_bit = 1u32 << start_idx;
-        let mut active: u32 = 0;
-
-        for &byte in text {
-            let elem_mask = if byte < 128 {
-                byte_elem_mask[byte as usize]
-            } else {
-                0
-            };
-            // Start at start_idx instead of 0
-            let starts = if elem_mask & start_bit != 0 {
-                start_bit
-            } else {
-                0
-            };
-            active = (active & elem_mask & quantified_bits) | ((active << 1) & elem_mask) | starts;
-            // Only keep bits for elements at or after start_idx
-            active &= !((1u32 << start_idx) - 1);
-            if active & accept_mask != 0 {
-                return true;
-            }
-        }
-        false
+        Some(Self::run_nfa(table, text))
     }
 
-    /// Run the full NFA scan on byte slice
+    /// Run the full NFA scan over the text's bytes
     #[inline]
-    fn run_nfa(&self, table: &NfaTable, bytes: &[u8]) -> bool {
-        let accept_mask = table.accept_mask;
-        let quantified_bits = table.quantified_bits;
-        let optional_bits = table.optional_bits;
-        let byte_elem_mask = &table.byte_elem_mask;
-
-        // Start at element 0, then compute initial epsilon closure
-        let mut active: u32 = 1u32; // Initially at element 0
-        active = self.compute_epsilon_closure(active, optional_bits, table.n);
-
-        // Check if we can accept before processing any bytes (e.g., pattern "a*" matching "")
-        if active & accept_mask != 0 {
-            return true;
-        }
-
-        for &byte in bytes {
-            let elem_mask = if byte < 128 {
-                byte_elem_mask[byte as usize]
-            } else {
-                let mut mask = 0u32;
-                for (i, elem) in self.elements.iter().enumerate() {
-                    if let SequenceElement::QuantifiedCharClass(cc, _) = elem {
-                        if cc.negated {
-                            mask |= 1u32 << i;
-                        }
-                    }
+    fn run_nfa(table: &NfaTable, text: &str) -> bool {
+        // Bit i set = state i consumed the last char; nothing has matched yet
+        let mut active: u64 = 0;
+
+        for (i, &byte) in text.as_bytes().iter().enumerate() {
+            let elem_mask = match byte {
+                0..=0x7F => table.byte_elem_mask[byte as usize],
+                // The rest of a multi-byte char: its first byte already moved
+                0x80..=0xBF => continue,
+                _ if table.non_ascii_space_mask != 0
+                    && text[i..].chars().next().is_some_and(char::is_whitespace) =>
+                {
+                    table.non_ascii_mask ^ table.non_ascii_space_mask
                 }
-                mask
+                _ => table.non_ascii_mask,
             };
 
-            // Standard NFA transition
-            let mut next_active = (active & elem_mask & quantified_bits)  // Stay in quantified element
-                | ((active << 1) & elem_mask)                             // Advance to next element
-                | (elem_mask & 1u32); // Start at element 0 (for ".*" patterns)
-
-            // Compute epsilon closure after transition
-            next_active = self.compute_epsilon_closure(next_active, optional_bits, table.n);
-
-            active = next_active;
+            // States that may take the next char: the ones after a consumed
+            // state, or the first (a match can start anywhere), plus the ones
+            // reached by skipping optional states
+            let mut ready = ((active << 1) | 1) & table.state_mask;
+            loop {
+                let skipped = ready | (((ready & table.optional_bits) << 1) & table.state_mask);
+                if skipped == ready {
+                    break;
+                }
+                ready = skipped;
+            }
 
-            if active & accept_mask != 0 {
+            active = (active & elem_mask & table.quantified_bits) | (ready & elem_mask);
+            if active & table.accept_mask != 0 {
                 return true;
             }
         }
@@ -887,55 +536,24 @@ impl Sequence {
         false
     }
 
-    /// Compute epsilon closure: for each active state, follow epsilon transitions
-    /// An epsilon transition exists from element i to element i+1 if element i is optional (min=0)
-    #[inline]
-    fn compute_epsilon_closure(&self, mut active: u32, optional_bits: u32, n: usize) -> u32 {
-        // Repeatedly follow epsilon transitions until no new states are added
-        loop {
-            let before = active;
-
-            // For each active position i, if element i is optional, we can also be at i+1
-            for i in 0..n {
-                if (active & (1u32 << i)) != 0 && (optional_bits & (1u32 << i)) != 0 {
-                    // Element i is active and optional, so we can skip it
-                    if i + 1 < n {
-                        active |= 1u32 << (i + 1);
-                    }
-                }
-            }
-
-            if active == before {
-                break;
-            }
-        }
-        active
-    }
-
     /// Find the sequence anywhere in text
     pub fn find(&self, text: &str) -> Option<(usize, usize)> {
         // FAST PRECHECK: For adjacent non-overlapping quantified charclasses
         // Check if pattern CAN match before trying expensive backtracking
         if self.elements.len() == 2 {
             if let (
-                Some(SequenceElement::QuantifiedCharClass(cc1, _)),
-                Some(SequenceElement::QuantifiedCharClass(cc2, _)),
+                Some(SequenceElement::QuantifiedCharClass(cc1, q1)),
+                Some(SequenceElement::QuantifiedCharClass(cc2, q2)),
             ) = (self.elements.first(), self.elements.get(1))
             {
-                if !cc1.overlaps_with(cc2) {
+                let both_required = quantifier_bounds(q1).0 > 0 && quantifier_bounds(q2).0 > 0;
+                if both_required && !cc1.overlaps_with(cc2) {
                     // Pattern requires cc1 followed by cc2 with no overlap
                     // Quick scan to see if this is even possible
-                    let bytes = text.as_bytes();
-                    let mut found_possible = false;
-
-                    for i in 0..bytes.len().saturating_sub(1) {
-                        let ch1 = bytes[i] as char;
-                        let ch2 = bytes[i + 1] as char;
-                        if cc1.matches(ch1) && cc2.matches(ch2) {
-                            found_possible = true;
-                            break;
-                        }
-                    }
+                    let found_possible = text
+                        .chars()
+                        .zip(text.chars().skip(1))
+                        .any(|(ch1, ch2)| cc1.matches(ch1) && cc2.matches(ch2));
 
                     if !found_possible {
                         // Pattern is impossible - fail fast
@@ -954,7 +572,7 @@ impl Sequence {
                 let mut pos = 0;
 
                 while pos < text.len() {
-                    if let Some(found) = finder.find(text[pos..].as_bytes()) {
+                    if let Some(found) = finder.find(&text.as_bytes()[pos..]) {
                         let match_start = pos + found;
                         let after_prefix = match_start + prefix_bytes.len();
 
@@ -1000,47 +618,19 @@ impl Sequence {
 
         // OPTIMIZATION 2: Inner literal with bidirectional matching
         // For patterns like \w+\s*>=\s*\d+ with anchor '>=' in middle
-        if let Some((anchor_literal, before_count, after_count)) = self.extract_inner_literal() {
+        if let Some((anchor_literal, before_count, _)) = self.extract_inner_literal() {
+            let min_before = Self::min_len_bytes(&self.elements[..before_count]);
             if anchor_literal.len() >= 2 {
                 use memchr::memmem;
-                let finder = memmem::Finder::new(&anchor_literal);
-
-                for anchor_pos in finder.find_iter(text.as_bytes()) {
-                    if let Some((match_start, match_end)) = self.match_around_anchor(
-                        text,
-                        anchor_pos,
-                        anchor_literal.len(),
-                        before_count,
-                        after_count,
-                    ) {
-                        return Some((match_start, match_end));
-                    }
-                }
-                return None;
+                let anchors = memmem::find_iter(text.as_bytes(), &anchor_literal);
+                return self.find_before_anchors(text, 0, anchors, min_before);
             } else if anchor_literal.len() == 1 {
                 // Single char inner literal - use memchr for rare/distinctive chars
                 let byte = anchor_literal[0];
                 if !byte.is_ascii_alphanumeric() && byte != b' ' && byte != b'.' {
                     // Rare char (like @, #, :, !, etc.) - memchr is effective
-                    let mut pos = 0;
-                    while pos < text.len() {
-                        if let Some(found) = memchr::memchr(byte, &text.as_bytes()[pos..]) {
-                            let anchor_pos = pos + found;
-                            if let Some((match_start, match_end)) = self.match_around_anchor(
-                                text,
-                                anchor_pos,
-                                1,
-                                before_count,
-                                after_count,
-                            ) {
-                                return Some((match_start, match_end));
-                            }
-                            pos = anchor_pos + 1;
-                        } else {
-                            return None;
-                        }
-                    }
-                    return None;
+                    let anchors = memchr::memchr_iter(byte, text.as_bytes());
+                    return self.find_before_anchors(text, 0, anchors, min_before);
                 }
             }
         }
@@ -1131,7 +721,11 @@ impl Sequence {
             // For 3 elements: first, middle, last - find most selective, then expand
             // NOTE: This optimization only works correctly for ASCII text!
             // For Unicode text, byte-based scanning can give false matches
-            if use_last && self.elements.len() == 3 && text.is_ascii() {
+            if use_last
+                && self.elements.len() == 3
+                && text.is_ascii()
+                && Self::is_run_chain(&self.elements)
+            {
                 if let Some(SequenceElement::QuantifiedCharClass(mid_cc, mid_q)) =
                     self.elements.get(1)
                 {
@@ -1229,7 +823,11 @@ impl Sequence {
                     }
                     return None;
                 }
-            } else if use_last && self.elements.len() >= 2 && text.is_ascii() {
+            } else if use_last
+                && self.elements.len() >= 2
+                && text.is_ascii()
+                && Self::is_run_chain(&self.elements[..2])
+            {
                 // General case for more elements (ASCII-only optimization)
                 if let Some(SequenceElement::QuantifiedCharClass(mid_cc, mid_q)) =
                     self.elements.get(1)
@@ -1283,7 +881,7 @@ impl Sequence {
                         if let Some(final_pos) = self.match_at_pos(text, start_pos) {
                             return Some((start_pos, final_pos));
                         }
-                        pos = start_pos + 1;
+                        pos = crate::next_char_boundary(text, start_pos);
                     } else {
                         break;
                     }
@@ -1292,8 +890,9 @@ impl Sequence {
             }
         }
 
-        // Fallback: sequential search
-        for (start_pos, _) in text.char_indices() {
+        // Fallback: sequential search (the end of the text too, for empty matches)
+        let starts = text.char_indices().map(|(i, _)| i);
+        for start_pos in starts.chain(core::iter::once(text.len())) {
             if let Some(final_pos) = self.match_at_pos(text, start_pos) {
                 return Some((start_pos, final_pos));
             }
@@ -1302,6 +901,60 @@ impl Sequence {
         None
     }
 
+    /// Whether a match of `elements` is one maximal run of each class in
+    /// turn, so runs found in the bytes can stand in for matching: every
+    /// element is a required, greedy, unbounded class repeat, with no char
+    /// shared between neighbours
+    fn is_run_chain(elements: &[SequenceElement]) -> bool {
+        let runs = elements.iter().all(|e| {
+            matches!(e, SequenceElement::QuantifiedCharClass(_, q)
+                if !q.is_lazy() && quantifier_bounds(q).0 > 0 && quantifier_bounds(q).1 == usize::MAX)
+        });
+        runs && elements.windows(2).all(|pair| match pair {
+            [SequenceElement::QuantifiedCharClass(a, _), SequenceElement::QuantifiedCharClass(b, _)] => {
+                !a.overlaps_with(b)
+            }
+            _ => false,
+        })
+    }
+
+    /// Quantifiers of the elements, in order
+    pub fn quantifiers(&self) -> impl Iterator<Item = &Quantifier> {
+        self.elements.iter().filter_map(|e| match e {
+            SequenceElement::QuantifiedChar(_, q)
+            | SequenceElement::QuantifiedCharClass(_, q)
+            | SequenceElement::QuantifiedGroup(_, q) => Some(q),
+            _ => None,
+        })
+    }
+
+    /// Whether the sequence might match the empty string somewhere
+    ///
+    /// Groups count as possibly empty, so this can say yes for a sequence
+    /// that never matches empty.
+    pub fn can_match_empty(&self) -> bool {
+        Self::min_len_bytes(&self.elements) == 0
+    }
+
+    /// Whether any element is a `\b`/`\B`, which looks at the char before it
+    pub fn has_boundary(&self) -> bool {
+        self.elements
+            .iter()
+            .any(|e| matches!(e, SequenceElement::Boundary(_)))
+    }
+
+    /// Find the sequence starting at or after `from`, with the text before
+    /// `from` still in view for boundaries
+    pub fn find_at(&self, text: &str, from: usize) -> Option<(usize, usize)> {
+        if from == 0 {
+            return self.find(text);
+        }
+        let starts = text[from..].char_indices().map(|(i, _)| from + i);
+        starts
+            .chain(core::iter::once(text.len()))
+            .find_map(|start_pos| Some((start_pos, self.match_at_pos(text, start_pos)?)))
+    }
+
     /// Check if charclass is good candidate for memchr (selective + ASCII)
     fn is_memchr_candidate(cc: &CharClass) -> bool {
         if cc.negated {
@@ -1338,8 +991,8 @@ impl Sequence {
 
     /// Estimate how many characters a CharClass matches (lower = more selective)
     fn selectivity(cc: &CharClass) -> usize {
-        if cc.negated {
-            return 1000; // Negated classes match almost everything
+        if cc.negated || !cc.properties.is_empty() {
+            return 1000; // Negated and property classes match almost everything
         }
         let mut count = cc.chars.len();
         for &(start, end) in &cc.ranges {
@@ -1357,7 +1010,7 @@ impl Sequence {
         for elem in &self.elements {
             match elem {
                 SequenceElement::Char(ch) => {
-                    prefix.push(*ch as u8);
+                    prefix.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
                     elements_scanned += 1;
                 }
                 SequenceElement::Literal(s) => {
@@ -1396,7 +1049,7 @@ impl Sequence {
             for elem in &self.elements[start_idx..] {
                 match elem {
                     SequenceElement::Char(ch) => {
-                        literal_bytes.push(*ch as u8);
+                        literal_bytes.extend_from_slice(ch.encode_utf8(&mut [0; 4]).as_bytes());
                         elements_consumed += 1;
                     }
                     SequenceElement::Literal(s) => {
@@ -1417,48 +1070,15 @@ impl Sequence {
         None
     }
 
-    /// Match pattern around an anchor literal (bidirectional matching)
-    /// Returns (match_start, match_end) if successful
-    fn match_around_anchor(
-        &self,
-        text: &str,
-        anchor_byte_pos: usize,
-        anchor_len: usize,
-        before_count: usize,
-        after_count: usize,
-    ) -> Option<(usize, usize)> {
-        let anchor_end = anchor_byte_pos + anchor_len;
-
-        // Match elements AFTER anchor (forward direction) WITH BACKTRACKING
-        // Use backtracking for quantified elements like .* to avoid greedy over-matching
-        let after_start_idx = self.elements.len() - after_count;
-        let match_end = if after_count > 0 {
-            // Use backtracking for forward matching
-            self.match_elements_backtracking(text, after_start_idx, anchor_end)?
-        } else {
-            anchor_end
-        };
-
-        // Match elements BEFORE anchor (backward direction - OPTIMIZED)
-        // For greedy quantifiers, match backwards from anchor
-        let before_elements = &self.elements[..before_count];
-
-        if before_elements.is_empty() {
-            return Some((anchor_byte_pos, match_end));
-        }
-
-        // SIMPLIFIED: Use forward matching with backtracking instead of complex backward logic
-        // Try to find where before_elements match and end exactly at anchor_byte_pos
-        // We search backwards from anchor position
-        let text_before = &text[..anchor_byte_pos];
-
-        // Calculate minimum bytes needed for before_elements
-        let min_bytes_needed: usize = before_elements
+    /// Fewest bytes `elements` can match: one per char they require
+    fn min_len_bytes(elements: &[SequenceElement]) -> usize {
+        elements
             .iter()
             .map(|e| match e {
                 SequenceElement::Char(c) => c.len_utf8(),
                 SequenceElement::CharClass(_) => 1,
                 SequenceElement::Dot => 1,
+                SequenceElement::LineBreak => 1,
                 SequenceElement::QuantifiedChar(_, q)
                 | SequenceElement::QuantifiedCharClass(_, q)
                 | SequenceElement::QuantifiedGroup(_, q) => {
@@ -1469,46 +1089,41 @@ impl Sequence {
                 SequenceElement::Literal(s) => s.len(),
                 SequenceElement::Group(_) => 0,
             })
-            .sum();
-
-        if text_before.len() < min_bytes_needed {
-            return None;
-        }
-
-        // Try matching before_elements forward from different start positions
-        // Start from the earliest possible position
-        let search_start = anchor_byte_pos.saturating_sub(1024.max(min_bytes_needed * 10));
+            .sum()
+    }
 
-        let mut match_start = None;
-        for try_pos in search_start..=anchor_byte_pos.saturating_sub(min_bytes_needed) {
-            if !text.is_char_boundary(try_pos) {
+    /// Leftmost match starting at or after `from`, given the increasing
+    /// positions of an inner literal that every match contains
+    ///
+    /// A match starting at `s` holds an anchor no earlier than
+    /// `s + min_before`, so each anchor makes the starts up to
+    /// `anchor - min_before` worth trying. Starts are tried once each, in
+    /// order, with the full backtracking matcher, so the span is exactly
+    /// the one a plain left-to-right scan would report.
+    fn find_before_anchors(
+        &self,
+        text: &str,
+        from: usize,
+        anchors: impl Iterator<Item = usize>,
+        min_before: usize,
+    ) -> Option<(usize, usize)> {
+        let mut next_start = from;
+        for anchor_pos in anchors {
+            let Some(last_start) = anchor_pos.checked_sub(min_before) else {
                 continue;
-            }
-
-            let mut pos = try_pos;
-            let mut matched = true;
-
-            // Try to match all before_elements forward from try_pos
-            for elem in before_elements {
-                match elem.match_at(text, pos) {
-                    Some(consumed) => pos += consumed,
-                    None => {
-                        matched = false;
-                        break;
-                    }
+            };
+            while next_start <= last_start {
+                let start_pos = next_start;
+                next_start += 1;
+                if !text.is_char_boundary(start_pos) {
+                    continue;
+                }
+                if let Some(end_pos) = self.match_at_pos(text, start_pos) {
+                    return Some((start_pos, end_pos));
                 }
-            }
-
-            // Check if forward matching lands exactly at anchor_byte_pos
-            if matched && pos == anchor_byte_pos {
-                match_start = Some(try_pos);
-                break; // Found leftmost match
             }
         }
-
-        let match_start = match_start?; // Failed to find a match before the anchor
-
-        Some((match_start, match_end))
+        None
     }
 
     /// Match starting from position, skipping first N elements
@@ -1520,7 +1135,7 @@ impl Sequence {
         let start_idx = self.elements.len() - skip_count;
 
         // Use backtracking-enabled matching
-        self.match_elements_backtracking(text, start_idx, 0)
+        self.match_elements_backtracking(text, start_idx, 0, false)
     }
 
     /// Match remaining elements starting from a specific position in the full text
@@ -1535,20 +1150,23 @@ impl Sequence {
 
         // Use backtracking with the full text and actual position
         // Returns absolute end position, convert to bytes consumed
-        self.match_elements_backtracking(text, start_idx, text_pos)
+        self.match_elements_backtracking(text, start_idx, text_pos, false)
             .map(|end_pos| end_pos - text_pos)
     }
 
     /// Match elements with backtracking support for quantified elements
+    ///
+    /// With `to_end`, only paths that consume the rest of `text` count.
     fn match_elements_backtracking(
         &self,
         text: &str,
         elem_idx: usize,
         text_pos: usize,
+        to_end: bool,
     ) -> Option<usize> {
         // Base case: all elements matched
         if elem_idx >= self.elements.len() {
-            return Some(text_pos);
+            return (!to_end || text_pos == text.len()).then_some(text_pos);
         }
 
         let elem = &self.elements[elem_idx];
@@ -1556,18 +1174,22 @@ impl Sequence {
         match elem {
             // Quantified elements: try different match lengths (greedy first, then backtrack)
             SequenceElement::QuantifiedChar(ch, quantifier) => {
-                self.backtrack_quantified_char(*ch, quantifier, text, text_pos, elem_idx)
-            }
-            SequenceElement::QuantifiedCharClass(cc, quantifier) => {
-                self.backtrack_quantified_charclass(cc, quantifier, text, text_pos, elem_idx)
+                self.backtrack_quantified_char(*ch, quantifier, text, text_pos, elem_idx, to_end)
             }
+            SequenceElement::QuantifiedCharClass(cc, quantifier) => self
+                .backtrack_quantified_charclass(cc, quantifier, text, text_pos, elem_idx, to_end),
             SequenceElement::QuantifiedGroup(group, quantifier) => {
-                self.backtrack_quantified_group(group, quantifier, text, text_pos, elem_idx)
+                self.backtrack_quantified_group(group, quantifier, text, text_pos, elem_idx, to_end)
             }
             // Non-quantified elements: simple match
             _ => {
                 if let Some(consumed) = elem.match_at(text, text_pos) {
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
+                    self.match_elements_backtracking(
+                        text,
+                        elem_idx + 1,
+                        text_pos + consumed,
+                        to_end,
+                    )
                 } else {
                     None
                 }
@@ -1583,6 +1205,7 @@ impl Sequence {
         text: &str,
         text_pos: usize,
         elem_idx: usize,
+        to_end: bool,
     ) -> Option<usize> {
         let (min, max) = quantifier_bounds(quantifier);
         let is_lazy = quantifier.is_lazy();
@@ -1617,9 +1240,12 @@ impl Sequence {
             // Lazy: try from min to max_count (prefer shorter matches first)
             for try_count in min..=max_count {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -1627,9 +1253,12 @@ impl Sequence {
             // Greedy: try from max_count down to min (prefer longer matches first)
             for try_count in (min..=max_count).rev() {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -1645,6 +1274,7 @@ impl Sequence {
         text: &str,
         text_pos: usize,
         elem_idx: usize,
+        to_end: bool,
     ) -> Option<usize> {
         let (min, max) = quantifier_bounds(quantifier);
         let is_lazy = quantifier.is_lazy();
@@ -1663,7 +1293,8 @@ impl Sequence {
         if is_ascii {
             // FAST: For negated single-char class (like [^\n] from .+), use memchr
             let max_count;
-            if cc.negated && cc.chars.len() == 1 && cc.ranges.is_empty() {
+            if cc.negated && cc.chars.len() == 1 && cc.ranges.is_empty() && cc.properties.is_empty()
+            {
                 let forbidden = cc.chars[0] as u8;
                 let term_pos = memchr::memchr(forbidden, rem_bytes).unwrap_or(rem_bytes.len());
                 max_count = term_pos.min(max);
@@ -1707,7 +1338,11 @@ impl Sequence {
                             break;
                         }
                     }
-                } else if !cc.negated && cc.chars.is_empty() && cc.ranges.len() == 1 {
+                } else if !cc.negated
+                    && cc.chars.is_empty()
+                    && cc.ranges.len() == 1
+                    && cc.properties.is_empty()
+                {
                     // Single range like [a-z] or [A-Z] - vectorized check
                     let (start_byte, end_byte) = (cc.ranges[0].0 as u8, cc.ranges[0].1 as u8);
                     let mut i = 0;
@@ -1752,7 +1387,7 @@ impl Sequence {
                     // Process bytes looking for space, tab, newline, carriage return
                     while i < rem_bytes.len() && count < max {
                         let byte = rem_bytes[i];
-                        if byte == b' ' || byte == b'\t' || byte == b'\n' || byte == b'\r' {
+                        if matches!(byte, b'\t' | b'\n' | 0x0B | 0x0C | b'\r' | b' ') {
                             count += 1;
                             i += 1;
                         } else {
@@ -1782,9 +1417,12 @@ impl Sequence {
             if is_lazy {
                 for try_count in min..=max_count {
                     let consumed = try_count; // ASCII fast path: each matched char is one byte.
-                    if let Some(final_pos) =
-                        self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                    {
+                    if let Some(final_pos) = self.match_elements_backtracking(
+                        text,
+                        elem_idx + 1,
+                        text_pos + consumed,
+                        to_end,
+                    ) {
                         return Some(final_pos);
                     }
                 }
@@ -1818,6 +1456,7 @@ impl Sequence {
                                         text,
                                         elem_idx + 1,
                                         text_pos + consumed,
+                                        to_end,
                                     ) {
                                         return Some(final_pos);
                                     }
@@ -1871,7 +1510,11 @@ impl Sequence {
                     }
 
                     // Use memchr-style search for common cases
-                    if !next_cc.negated && next_cc.ranges.is_empty() && next_cc.chars.len() == 1 {
+                    if !next_cc.negated
+                        && next_cc.ranges.is_empty()
+                        && next_cc.chars.len() == 1
+                        && next_cc.properties.is_empty()
+                    {
                         // Single character class like [0] or [a] - use memchr
                         let target = next_cc.chars[0];
                         let target_byte = if target.is_ascii() { target as u8 } else { 0 };
@@ -1890,6 +1533,7 @@ impl Sequence {
                                             text,
                                             elem_idx + 1,
                                             candidate_pos,
+                                            to_end,
                                         ) {
                                             return Some(final_pos);
                                         }
@@ -1910,9 +1554,12 @@ impl Sequence {
             // Standard greedy backtracking for ASCII - no Vec allocation needed
             // since for ASCII, byte position = char count
             for try_count in (min..=max_count).rev() {
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + try_count)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + try_count,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -1946,9 +1593,12 @@ impl Sequence {
             // Lazy: try from min to max_count (prefer shorter matches first)
             for try_count in min..=max_count {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -1956,9 +1606,12 @@ impl Sequence {
             // Greedy: try from max_count down to min (prefer longer matches first)
             for try_count in (min..=max_count).rev() {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -1974,6 +1627,7 @@ impl Sequence {
         text: &str,
         text_pos: usize,
         elem_idx: usize,
+        to_end: bool,
     ) -> Option<usize> {
         let (min, max) = quantifier_bounds(quantifier);
         let is_lazy = quantifier.is_lazy();
@@ -2006,9 +1660,12 @@ impl Sequence {
             // Lazy: try from min to max_count (prefer shorter matches first)
             for try_count in min..=max_count {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -2016,9 +1673,12 @@ impl Sequence {
             // Greedy: try from max_count down to min (prefer longer matches first)
             for try_count in (min..=max_count).rev() {
                 let consumed = byte_positions[try_count];
-                if let Some(final_pos) =
-                    self.match_elements_backtracking(text, elem_idx + 1, text_pos + consumed)
-                {
+                if let Some(final_pos) = self.match_elements_backtracking(
+                    text,
+                    elem_idx + 1,
+                    text_pos + consumed,
+                    to_end,
+                ) {
                     return Some(final_pos);
                 }
             }
@@ -2036,8 +1696,14 @@ impl Sequence {
 
     /// Find all occurrences of the sequence in text
     pub fn find_all(&self, text: &str) -> Vec<(usize, usize)> {
-        let mut results: Vec<(usize, usize)> = Vec::new();
+        let mut results = Vec::new();
+        self.find_all_into(text, &mut results);
+        results
+    }
 
+    /// Push all occurrences of the sequence onto `results`, which should
+    /// start out empty (overlaps are checked against its last entry)
+    pub fn find_all_into(&self, text: &str, results: &mut Vec<(usize, usize)>) {
         // OPTIMIZATION 1: Use literal prefix with memchr
         if let Some((prefix_bytes, skip_count)) = self.extract_literal_prefix() {
             if prefix_bytes.len() >= 3 {
@@ -2057,7 +1723,7 @@ impl Sequence {
                         }
                     }
                 }
-                return results;
+                return;
             } else if prefix_bytes.len() == 1 {
                 // Single byte prefix: use memchr_iter
                 use memchr::memchr_iter;
@@ -2075,31 +1741,33 @@ impl Sequence {
                         }
                     }
                 }
-                return results;
+                return;
             }
         }
 
         // OPTIMIZATION 2: Inner literal with bidirectional matching
-        if let Some((anchor_literal, before_count, after_count)) = self.extract_inner_literal() {
+        if let Some((anchor_literal, before_count, _)) = self.extract_inner_literal() {
             if anchor_literal.len() >= 2 {
                 use memchr::memmem;
                 let finder = memmem::Finder::new(&anchor_literal);
-
-                for anchor_pos in finder.find_iter(text.as_bytes()) {
-                    if let Some((match_start, match_end)) = self.match_around_anchor(
-                        text,
-                        anchor_pos,
-                        anchor_literal.len(),
-                        before_count,
-                        after_count,
-                    ) {
-                        // Check if this match overlaps with previous
-                        if results.is_empty() || match_start >= results.last().unwrap().1 {
-                            results.push((match_start, match_end));
-                        }
-                    }
+                let min_before = Self::min_len_bytes(&self.elements[..before_count]);
+
+                // Each search resumes at the end of the previous match; the
+                // anchor is required, so matches are never empty
+                let mut from = 0;
+                while from < text.len() {
+                    let anchors = finder
+                        .find_iter(&text.as_bytes()[from..])
+                        .map(|anchor_pos| from + anchor_pos);
+                    let Some((match_start, match_end)) =
+                        self.find_before_anchors(text, from, anchors, min_before)
+                    else {
+                        break;
+                    };
+                    results.push((match_start, match_end));
+                    from = match_end;
                 }
-                return results;
+                return;
             }
         }
 
@@ -2110,11 +1778,11 @@ impl Sequence {
         while i < byte_positions.len() {
             let start_pos = byte_positions[i];
 
-            if let Some(consumed) = self.match_at(&text[start_pos..]) {
-                let end_pos = start_pos + consumed;
+            if let Some(end_pos) = self.match_at_pos(text, start_pos) {
                 results.push((start_pos, end_pos));
 
-                // Skip past this match
+                // Skip past this match; an empty one still moves on by a char
+                i += 1;
                 while i < byte_positions.len() && byte_positions[i] < end_pos {
                     i += 1;
                 }
@@ -2122,8 +1790,6 @@ impl Sequence {
                 i += 1;
             }
         }
-
-        results
     }
 }
 
@@ -2131,6 +1797,84 @@ impl Sequence {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_nfa_table_long_sequence() {
+        use crate::parser::sequence_parser::parse_sequence;
+
+        let pattern = r"customer_account_[a-z]+_reference_\d+_history_v[0-9]";
+        let seq = parse_sequence(pattern).unwrap();
+        assert_eq!(seq.elements.len(), 41);
+        assert!(seq.nfa_table.is_some());
+
+        assert!(seq.is_match("select customer_account_main_reference_42_history_v3 from t"));
+        assert!(!seq.is_match("select customer_account_main_reference_42_history_vx from t"));
+        assert!(!seq.is_match("customer_account__reference_42_history_v3"));
+    }
+
+    #[test]
+    fn test_nfa_table_matches_counted_and_optional_elements() {
+        use crate::parser::sequence_parser::parse_sequence;
+
+        for pattern in [
+            r"\d{2,4}-\d{2,4}",
+            r"[a-z]{3}1",
+            r"[a-z]{1,3}[0-9]*x",
+            r"[^a]{2}b",
+            r"\d+:[a-z]+",
+            r"[a-z]{2,}[0-9]?",
+            r"\w+\s*=\s*\w+",
+            r"ab[x]?c",
+            r"ax?c",
+            r"[a-z]+,?\d",
+            r"a\s*b*c",
+        ] {
+            let seq = parse_sequence(pattern).unwrap();
+            assert!(seq.nfa_table.is_some(), "{pattern:?} has no NFA table");
+            let reference = crate::Pattern::new(pattern).unwrap();
+            for text in [
+                "",
+                "1-1",
+                "12-3",
+                "12-34",
+                "1234-5678",
+                "ab1",
+                "abc1",
+                "x",
+                "ax",
+                "abc0x",
+                "1a:b",
+                "1:b",
+                "éé b",
+                "ébb",
+                "z",
+                "zz",
+                "zz9",
+                "k=v",
+                "key = value",
+                "k =",
+                "abc",
+                "abxc",
+                "abxxc",
+                "ac",
+                "axc",
+                "word,1",
+                "word1",
+                "word,",
+                "a  c",
+                "abbc",
+                "k\u{A0}=\u{3000}v",
+                "a\u{2003}c",
+                "a\u{E9}c",
+            ] {
+                assert_eq!(
+                    seq.is_match(text),
+                    reference.is_match(text),
+                    "{pattern:?} on {text:?}"
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_simple_sequence() {
         // "abc"
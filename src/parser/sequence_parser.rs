@@ -2,10 +2,12 @@
 ///
 /// Handles patterns like: ab+c*, \d+\w*, hello\d+
 use crate::parser::charclass::CharClass;
-use crate::parser::escape::{parse_escape, starts_with_escape};
+use crate::parser::escape::{parse_escape, starts_with_escape, EscapeSequence};
 use crate::parser::group::{Group, GroupContent};
 use crate::parser::quantifier::Quantifier;
 use crate::parser::sequence::{Sequence, SequenceElement};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 /// Check if a pattern is a sequence (multiple elements)
 pub fn is_sequence_pattern(pattern: &str) -> bool {
@@ -204,6 +206,8 @@ pub fn parse_sequence(pattern: &str) -> Result<Sequence, String> {
                 elements.push(SequenceElement::Char(ch));
             } else if let Some(boundary_type) = seq.to_boundary() {
                 elements.push(SequenceElement::Boundary(boundary_type));
+            } else if seq == EscapeSequence::LineBreak {
+                elements.push(SequenceElement::LineBreak);
             } else {
                 return Err("Invalid escape in sequence".to_string());
             }
@@ -0,0 +1,326 @@
+//! Rewriting redundant constructs before a pattern is compiled
+//!
+//! Generated patterns often spell simple things in roundabout ways: `a{1}`,
+//! `(?:abc)`, `[.]`. Fast path detection compares pattern text, so these
+//! would miss fast paths their plain forms hit. [`simplify`] rewrites them:
+//!
+//! - `X{1}` and `X{1,1}` (lazy or not) become `X`
+//! - `(?:X)` around a single atom becomes `X`, quantified or not
+//! - `(?:XYZ)` with no top-level `|` and no quantifier after it becomes `XYZ`
+//! - a class with a single member, like `[a]` or `[.]`, becomes that char,
+//!   escaped if it is a metacharacter
+//!
+//! Lookarounds, flag groups and the insides of classes are left alone;
+//! capture groups keep their parentheses but have their contents simplified.
+
+use alloc::string::String;
+
+/// Chars that need a backslash to stand for themselves outside a class
+const META: &[char] = &[
+    '\\', '.', '+', '*', '?', '(', ')', '|', '[', ']', '{', '}', '^', '$',
+];
+
+/// `pattern` with redundant constructs rewritten, or `None` when there are
+/// none
+pub(crate) fn simplify(pattern: &str) -> Option<String> {
+    let simplified = simplify_sequence(pattern);
+    (simplified != pattern).then_some(simplified)
+}
+
+/// Simplify a run of pattern text, such as a whole pattern or a group's
+/// contents
+fn simplify_sequence(pattern: &str) -> String {
+    let mut out = String::with_capacity(pattern.len());
+    // Whether the last thing written is an escape, which a digit or brace
+    // written straight after it could extend (`\1` then `0`)
+    let mut after_escape = false;
+    let mut i = 0;
+    while i < pattern.len() {
+        let rest = &pattern[i..];
+        let c = rest.chars().next().unwrap_or_default();
+        match c {
+            '\\' => {
+                let end = escape_end(rest);
+                out.push_str(&rest[..end]);
+                i += end;
+                after_escape = true;
+                continue;
+            }
+            '[' => {
+                let end = class_end(rest).unwrap_or(rest.len());
+                let class = &rest[..end];
+                match single_member(class) {
+                    Some(member) if !(after_escape && extends_escape(&member)) => {
+                        after_escape = member.starts_with('\\');
+                        out.push_str(&member);
+                    }
+                    _ => {
+                        out.push_str(class);
+                        after_escape = false;
+                    }
+                }
+                i += end;
+                continue;
+            }
+            '(' => {
+                let Some(end) = group_end(rest) else {
+                    out.push_str(rest);
+                    break;
+                };
+                let group = &rest[..end];
+                let quantified = rest[end..].starts_with(['*', '+', '?', '{']);
+                after_escape = write_group(group, quantified, after_escape, &mut out);
+                i += end;
+                continue;
+            }
+            '{' if !out.is_empty() => {
+                if let Some(len) = single_repetition_len(rest) {
+                    // X{1} is just X
+                    i += len;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        out.push(c);
+        i += c.len_utf8();
+        after_escape = false;
+    }
+    out
+}
+
+/// Write `group` (with its parentheses) to `out`, unwrapped when the
+/// parentheses do nothing; returns whether `out` now ends with an escape
+fn write_group(group: &str, quantified: bool, after_escape: bool, out: &mut String) -> bool {
+    let inner = &group[1..group.len() - 1];
+    if let Some(content) = inner.strip_prefix("?:") {
+        let content = simplify_sequence(content);
+        let unwrap = !(content.is_empty() || after_escape && extends_escape(&content))
+            && (is_single_atom(&content) || (!quantified && !has_top_level_alternation(&content)));
+        if unwrap {
+            out.push_str(&content);
+            return ends_with_escape(&content);
+        }
+        out.push_str("(?:");
+        out.push_str(&content);
+        out.push(')');
+    } else if inner.starts_with('?') {
+        // Lookarounds and flag groups stay as written
+        out.push_str(group);
+    } else {
+        out.push('(');
+        out.push_str(&simplify_sequence(inner));
+        out.push(')');
+    }
+    false
+}
+
+/// The single member of `class` as pattern text outside a class, if it has
+/// exactly one
+fn single_member(class: &str) -> Option<String> {
+    let members = class.strip_prefix('[')?.strip_suffix(']')?;
+    let mut chars = members.chars();
+    let first = chars.next()?;
+    match first {
+        // Negated, or a backspace or boundary once outside the class
+        '^' => None,
+        '\\' => {
+            let escaped = chars.next()?;
+            if escape_end(members) != members.len() || matches!(escaped, 'b' | 'B') {
+                None
+            } else if escaped.is_ascii_alphanumeric() {
+                Some(String::from(members))
+            } else {
+                Some(literal_text(escaped))
+            }
+        }
+        _ if chars.next().is_none() => Some(literal_text(first)),
+        _ => None,
+    }
+}
+
+/// `ch` as pattern text matching itself outside a class
+fn literal_text(ch: char) -> String {
+    let mut text = String::new();
+    if META.contains(&ch) {
+        text.push('\\');
+    }
+    text.push(ch);
+    text
+}
+
+/// Whether `text`, written right after an escape, would read as part of it
+fn extends_escape(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '{')
+}
+
+/// Whether `text` ends with a whole escape (not an escaped backslash's tail)
+fn ends_with_escape(text: &str) -> bool {
+    let mut i = 0;
+    let mut last_escape = false;
+    while i < text.len() {
+        let rest = &text[i..];
+        if rest.starts_with('\\') {
+            i += escape_end(rest);
+            last_escape = true;
+        } else {
+            i += rest.chars().next().map_or(1, char::len_utf8);
+            last_escape = false;
+        }
+    }
+    last_escape
+}
+
+/// Whether `text` is exactly one char, escape, class or group
+fn is_single_atom(text: &str) -> bool {
+    let Some(first) = text.chars().next() else {
+        return false;
+    };
+    let end = match first {
+        '\\' => escape_end(text),
+        '[' => match class_end(text) {
+            Some(end) => end,
+            None => return false,
+        },
+        '(' => match group_end(text) {
+            Some(end) => end,
+            None => return false,
+        },
+        '^' | '$' | '|' | ')' | '*' | '+' | '?' | '{' => return false,
+        _ => first.len_utf8(),
+    };
+    end == text.len()
+}
+
+/// Whether `text` has a `|` outside any group or class
+fn has_top_level_alternation(text: &str) -> bool {
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        i += match rest.chars().next() {
+            Some('|') => return true,
+            Some('\\') => escape_end(rest),
+            Some('[') => class_end(rest).unwrap_or(rest.len()),
+            Some('(') => group_end(rest).unwrap_or(rest.len()),
+            Some(c) => c.len_utf8(),
+            None => break,
+        };
+    }
+    false
+}
+
+/// Length of the escape at the start of `text`, including a braced argument
+/// like `\p{Greek}` or `\x{41}`
+fn escape_end(text: &str) -> usize {
+    let mut chars = text.char_indices().skip(1);
+    let Some((at, escaped)) = chars.next() else {
+        return text.len();
+    };
+    let end = at + escaped.len_utf8();
+    if matches!(escaped, 'p' | 'P' | 'x' | 'u' | 'b' | 'B' | 'k') && text[end..].starts_with('{') {
+        if let Some(close) = text[end..].find('}') {
+            return end + close + 1;
+        }
+    }
+    end
+}
+
+/// Length of the class at the start of `text`, through its closing `]`
+fn class_end(text: &str) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let mut i = 1;
+    // A leading `]` (after an optional `^`) is a literal member
+    if bytes.get(i) == Some(&b'^') {
+        i += 1;
+    }
+    if bytes.get(i) == Some(&b']') {
+        i += 1;
+    }
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b']' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+/// Length of the group at the start of `text`, through its closing `)`
+fn group_end(text: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < text.len() {
+        let rest = &text[i..];
+        i += match rest.chars().next()? {
+            '\\' => escape_end(rest),
+            '[' => class_end(rest)?,
+            '(' => {
+                depth += 1;
+                1
+            }
+            ')' => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+                1
+            }
+            c => c.len_utf8(),
+        };
+    }
+    None
+}
+
+/// Length of a `{1}` or `{1,1}` repetition (with a lazy `?`) at the start
+/// of `text`
+fn single_repetition_len(text: &str) -> Option<usize> {
+    let len = ["{1}", "{1,1}"]
+        .iter()
+        .find(|count| text.starts_with(*count))?
+        .len();
+    Some(len + usize::from(text[len..].starts_with('?')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simplify() {
+        let cases = [
+            ("(?:hello)", "hello"),
+            ("a{1}b{1,1}?", "ab"),
+            ("[a]", "a"),
+            ("[.]+", r"\.+"),
+            (r"[\d]", r"\d"),
+            ("(?:a)+", "a+"),
+            ("(?:[ab])?x", "[ab]?x"),
+            ("((?:ab)c)", "(abc)"),
+            ("x(?:(?:y))z", "xyz"),
+        ];
+        for (pattern, expected) in cases {
+            assert_eq!(simplify(pattern).as_deref(), Some(expected), "{pattern:?}");
+        }
+    }
+
+    #[test]
+    fn test_simplify_keeps_meaningful_constructs() {
+        for pattern in [
+            "(?:ab)+",
+            "(?:a|b)",
+            "x(?:a|b)y",
+            "[^a]",
+            "[ab]",
+            r"[\b]",
+            "(?=a)",
+            "(?i:a)",
+            r"(a)\1(?:0)",
+            "a{2}",
+            "[a{1}]",
+            r"\{1}",
+        ] {
+            assert_eq!(simplify(pattern), None, "{pattern:?}");
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! Serde support for [`Pattern`] (enabled with the `serde` feature)
+//!
+//! A pattern serializes as its source string and deserializes by compiling
+//! that string with [`Pattern::new`], so compile errors surface as serde errors.
+
+use crate::Pattern;
+use core::fmt;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+impl Serialize for Pattern {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+struct PatternVisitor;
+
+impl<'de> Visitor<'de> for PatternVisitor {
+    type Value = Pattern;
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a regex pattern string")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Pattern, E> {
+        Pattern::new(value).map_err(E::custom)
+    }
+}
+
+impl<'de> Deserialize<'de> for Pattern {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(PatternVisitor)
+    }
+}
@@ -0,0 +1,207 @@
+//! Matching many patterns against the same text
+//!
+//! [`PatternSet`] reports which of its patterns match. The prefilter literals
+//! of all its patterns go into one [`LiteralSearcher`], so the text is scanned
+//! once for every pattern that has them, and each such pattern's own matcher
+//! only runs from its first candidate. Patterns that are plain literals or
+//! literal alternations contribute the literals themselves, and a hit settles
+//! them without any matcher. Patterns without literals run their matcher
+//! directly.
+
+use crate::optimization::LiteralSearcher;
+use crate::{Pattern, PatternError};
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// Several patterns matched against a text together
+///
+/// # Example
+///
+/// ```
+/// use rexile::PatternSet;
+///
+/// let set = PatternSet::new([r"ERROR \d+", "WARN", r"\d{4}-\d{2}"]).unwrap();
+/// assert_eq!(set.matches("2024-01 ERROR 42"), vec![0, 2]);
+/// assert!(!set.is_match("all good"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct PatternSet {
+    patterns: Vec<Pattern>,
+    /// Shared search over the literals of every pattern with a prefilter
+    shared: Option<SharedLiterals>,
+    /// Patterns without a prefilter, which always run their own matcher
+    unfiltered: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+struct SharedLiterals {
+    /// Longest literal first, so a hit is the longest literal at its start
+    searcher: LiteralSearcher,
+    /// Per literal: the patterns whose candidates start with it or with one
+    /// of its prefixes, i.e. every pattern with a candidate at the hit
+    owners: Vec<Vec<usize>>,
+    /// Number of distinct patterns behind the literals
+    filtered: usize,
+    /// Per pattern: whether its literals are all it matches, so a hit is a
+    /// match without running its matcher
+    exact: Vec<bool>,
+}
+
+impl PatternSet {
+    /// Compile every pattern into a set, failing on the first invalid one
+    pub fn new<I, S>(patterns: I) -> Result<Self, PatternError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let patterns = patterns
+            .into_iter()
+            .map(|pattern| Pattern::new(pattern.as_ref()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut by_literal: BTreeMap<String, Vec<usize>> = BTreeMap::new();
+        let mut unfiltered = Vec::new();
+        let mut exact = vec![false; patterns.len()];
+        for (index, pattern) in patterns.iter().enumerate() {
+            let needles = match pattern.literal_alternatives() {
+                Some(literals) => {
+                    exact[index] = true;
+                    literals
+                }
+                None => pattern.required_literals(),
+            };
+            if needles.is_empty() || needles.iter().any(String::is_empty) {
+                unfiltered.push(index);
+                continue;
+            }
+            for needle in needles {
+                by_literal.entry(needle).or_default().push(index);
+            }
+        }
+
+        let mut literals: Vec<(String, Vec<usize>)> = by_literal.into_iter().collect();
+        literals.sort_by_key(|(literal, _)| core::cmp::Reverse(literal.len()));
+        let owners = literals
+            .iter()
+            .map(|(literal, _)| {
+                let mut owners: Vec<usize> = literals
+                    .iter()
+                    .filter(|(prefix, _)| literal.starts_with(prefix.as_str()))
+                    .flat_map(|(_, indices)| indices.iter().copied())
+                    .collect();
+                owners.sort_unstable();
+                owners.dedup();
+                owners
+            })
+            .collect();
+
+        let mut filtered: Vec<usize> = literals
+            .iter()
+            .flat_map(|(_, indices)| indices.iter().copied())
+            .collect();
+        filtered.sort_unstable();
+        filtered.dedup();
+
+        let needles: Vec<&str> = literals
+            .iter()
+            .map(|(literal, _)| literal.as_str())
+            .collect();
+        let shared = if needles.is_empty() {
+            None
+        } else if let Ok(searcher) = LiteralSearcher::new(&needles, false) {
+            Some(SharedLiterals {
+                searcher,
+                owners,
+                filtered: filtered.len(),
+                exact,
+            })
+        } else {
+            // No shared automaton: every pattern runs on its own
+            unfiltered.extend(filtered);
+            unfiltered.sort_unstable();
+            None
+        };
+
+        Ok(PatternSet {
+            patterns,
+            shared,
+            unfiltered,
+        })
+    }
+
+    /// Number of patterns in the set
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    /// Whether the set has no patterns
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The compiled patterns, in the order they were given
+    pub fn patterns(&self) -> &[Pattern] {
+        &self.patterns
+    }
+
+    /// Whether any pattern matches `text`
+    pub fn is_match(&self, text: &str) -> bool {
+        let mut any = false;
+        self.scan(text, |_| {
+            any = true;
+            true
+        });
+        any
+    }
+
+    /// Indices of the patterns that match `text`, in ascending order
+    pub fn matches(&self, text: &str) -> Vec<usize> {
+        let mut matched = vec![false; self.patterns.len()];
+        self.scan(text, |index| {
+            matched[index] = true;
+            false
+        });
+        (0..matched.len()).filter(|&index| matched[index]).collect()
+    }
+
+    /// Report each matching pattern to `found` until it returns true
+    fn scan(&self, text: &str, mut found: impl FnMut(usize) -> bool) {
+        for &index in &self.unfiltered {
+            if self.patterns[index].is_match(text) && found(index) {
+                return;
+            }
+        }
+
+        let Some(shared) = &self.shared else {
+            return;
+        };
+        // A pattern can't match before its first candidate, so a search from
+        // there settles it and later hits of its literals are skipped
+        let mut settled = vec![false; self.patterns.len()];
+        let mut remaining = shared.filtered;
+        let mut pos = 0;
+        while remaining > 0 {
+            let Some((literal, start, _)) = shared.searcher.find_indexed_at(text.as_bytes(), pos)
+            else {
+                return;
+            };
+            for &index in &shared.owners[literal] {
+                if settled[index] {
+                    continue;
+                }
+                settled[index] = true;
+                remaining -= 1;
+                // A hit of a literal (or of one it prefixes) is where an
+                // exact pattern matches
+                let is_match =
+                    shared.exact[index] || self.patterns[index].search_at(text, start).is_some();
+                if is_match && found(index) {
+                    return;
+                }
+            }
+            pos = start + 1;
+        }
+    }
+}
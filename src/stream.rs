@@ -0,0 +1,177 @@
+//! Streaming search over text that arrives in chunks
+//!
+//! [`StreamMatcher`] keeps only a bounded tail of the input between calls to
+//! [`StreamMatcher::push`], so a multi-gigabyte log can be searched one
+//! buffer at a time while still catching matches that straddle two chunks.
+
+use crate::{Pattern, SearchCursor};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Tail kept between chunks when the pattern's longest match is unknown
+pub const DEFAULT_STREAM_WINDOW: usize = 4096;
+
+/// A match found by a [`StreamMatcher`]
+///
+/// Offsets are byte positions in the whole stream, counted from the first
+/// pushed chunk. The matched text is copied out, since the chunk it came
+/// from may already be gone.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamMatch {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+impl StreamMatch {
+    /// Start byte offset in the stream
+    pub fn start(&self) -> usize {
+        self.start
+    }
+
+    /// End byte offset in the stream (exclusive)
+    pub fn end(&self) -> usize {
+        self.end
+    }
+
+    /// The matched text
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+}
+
+/// Finds matches of a [`Pattern`] in text pushed chunk by chunk
+///
+/// A match is reported once everything it could extend over has been seen:
+/// the matcher holds back the last `window` bytes, where the window is the
+/// pattern's longest possible match for literals and literal alternations,
+/// and [`DEFAULT_STREAM_WINDOW`] (or the value given to
+/// [`with_window`](Self::with_window)) otherwise. A match longer than the
+/// window may be split at a chunk boundary. Call
+/// [`finish`](Self::finish) after the last chunk for the held-back matches.
+///
+/// The char before the next search position is kept too, so `\b` and `(?m)^`
+/// see what precedes a chunk, and `^` only matches at the start of the
+/// stream.
+///
+/// # Example
+///
+/// ```
+/// use rexile::{Pattern, StreamMatcher};
+///
+/// let pattern = Pattern::new("ERROR|FATAL").unwrap();
+/// let mut stream = StreamMatcher::new(&pattern);
+///
+/// let mut found = stream.push("ok\nERR");
+/// found.extend(stream.push("OR disk\nFAT"));
+/// found.extend(stream.push("AL\n"));
+/// found.extend(stream.finish());
+///
+/// let spans: Vec<_> = found.iter().map(|m| (m.start(), m.as_str())).collect();
+/// assert_eq!(spans, vec![(3, "ERROR"), (14, "FATAL")]);
+/// ```
+#[derive(Debug)]
+pub struct StreamMatcher<'p> {
+    pattern: &'p Pattern,
+    window: usize,
+    /// Unsearched or held-back input
+    buffer: String,
+    /// Stream offset of `buffer[0]`
+    buffer_start: usize,
+    /// Stream offset where the next search starts (end of the last match)
+    resume: usize,
+    /// Stream offset where the last reported match ended
+    last_end: Option<usize>,
+}
+
+impl<'p> StreamMatcher<'p> {
+    /// Stream `pattern`, sizing the held-back tail from the pattern itself
+    pub fn new(pattern: &'p Pattern) -> Self {
+        let window = pattern.max_match_len().unwrap_or(DEFAULT_STREAM_WINDOW);
+        Self::with_window(pattern, window)
+    }
+
+    /// Stream `pattern`, holding back `window` bytes between chunks
+    ///
+    /// Use the longest match you expect; matches that may be longer than
+    /// `window` can be cut at a chunk boundary.
+    pub fn with_window(pattern: &'p Pattern, window: usize) -> Self {
+        StreamMatcher {
+            pattern,
+            window: window.max(1),
+            buffer: String::new(),
+            buffer_start: 0,
+            resume: 0,
+            last_end: None,
+        }
+    }
+
+    /// Feed the next chunk and return the matches that are now complete
+    pub fn push(&mut self, chunk: &str) -> Vec<StreamMatch> {
+        self.buffer.push_str(chunk);
+
+        // A match starting at or before `settled` can't reach past the buffer
+        let mut settled = self.buffer.len().saturating_sub(self.window);
+        while !self.buffer.is_char_boundary(settled) {
+            settled -= 1;
+        }
+
+        let matches = self.search(Some(settled));
+
+        // No match starts between the last match and `settled`, so that
+        // part of the buffer is never needed again, except the char before
+        // it that anchors and boundaries look at
+        let from = (self.resume - self.buffer_start)
+            .max(settled)
+            .min(self.buffer.len());
+        let keep = self.buffer[..from]
+            .char_indices()
+            .next_back()
+            .map_or(0, |(at, _)| at);
+        self.resume = self.buffer_start + from;
+        self.buffer.drain(..keep);
+        self.buffer_start += keep;
+
+        matches
+    }
+
+    /// Report the matches still held back, after the last chunk
+    pub fn finish(mut self) -> Vec<StreamMatch> {
+        self.search(None)
+    }
+
+    /// Matches between `resume` and the end of the buffer, stopping before
+    /// the first one that starts past `settled`
+    fn search(&mut self, settled: Option<usize>) -> Vec<StreamMatch> {
+        // Search the whole buffer from `resume`, so the text before it still
+        // counts for anchors and boundaries
+        let mut cursor = SearchCursor {
+            pos: self.resume - self.buffer_start,
+            last_end: self
+                .last_end
+                .and_then(|end| end.checked_sub(self.buffer_start)),
+        };
+        let mut matches = Vec::new();
+
+        while cursor.in_text(&self.buffer) {
+            let Some((start, end)) = self.pattern.find_at(&self.buffer, cursor.pos) else {
+                break;
+            };
+            if settled.is_some_and(|settled| start > settled) {
+                break;
+            }
+            if !cursor.accept(&self.buffer, (start, end)) {
+                continue;
+            }
+            matches.push(StreamMatch {
+                start: self.buffer_start + start,
+                end: self.buffer_start + end,
+                text: self.buffer[start..end].to_string(),
+            });
+            self.resume = self.buffer_start + cursor.pos.min(self.buffer.len());
+            self.last_end = Some(self.buffer_start + end);
+        }
+
+        matches
+    }
+}
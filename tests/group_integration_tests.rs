@@ -57,6 +57,33 @@ fn test_group_with_anchors() {
     assert!(!re_end.is_match("world hello"));
 }
 
+#[test]
+fn test_anchored_group_alternation() {
+    // ^(foo|bar)$ - entire text must equal one branch
+    let re_full = ReXile::new("^(foo|bar)$").unwrap();
+    assert!(re_full.is_match("foo"));
+    assert!(re_full.is_match("bar"));
+    assert!(!re_full.is_match("foobar"));
+    assert!(!re_full.is_match("xfoo"));
+    assert_eq!(re_full.captures("bar").unwrap().get(1), Some("bar"));
+
+    // ^(foo|bar) - match only at the start
+    let re_start = ReXile::new("^(foo|bar)").unwrap();
+    assert_eq!(re_start.find("barfoo"), Some((0, 3)));
+    assert!(!re_start.is_match("xfoo"));
+
+    // Branches of differing lengths, where a shorter branch matches first
+    let re_lengths = ReXile::new("^(a|ab|abc)$").unwrap();
+    assert!(re_lengths.is_match("a"));
+    assert!(re_lengths.is_match("ab"));
+    assert!(re_lengths.is_match("abc"));
+    assert!(!re_lengths.is_match("abcd"));
+
+    // End anchor reached only from a later start position
+    let re_end = ReXile::new("(ab|b)$").unwrap();
+    assert_eq!(re_end.find("abab"), Some((2, 4)));
+}
+
 #[test]
 fn test_multiple_groups() {
     // (foo)(bar) - two consecutive groups
@@ -97,6 +124,48 @@ fn test_non_capturing_group() {
     assert_eq!(re.find("say hello there"), Some((4, 9)));
 }
 
+#[test]
+fn test_quantified_non_capturing_group() {
+    let re_plus = ReXile::new("(?:ab)+c").unwrap();
+    assert_eq!(re_plus.find("ababc"), Some((0, 5)));
+    assert!(!re_plus.is_match("ac"));
+
+    let re_optional = ReXile::new("(?:ab)?c").unwrap();
+    assert_eq!(re_optional.find("c"), Some((0, 1)));
+    assert_eq!(re_optional.find("abc"), Some((0, 3)));
+
+    // Lazy repetition stops at the first iteration, captures or not
+    let re_lazy = ReXile::new("x(?:ab)+?").unwrap();
+    assert_eq!(re_lazy.find("xabab"), Some((0, 3)));
+    let re_lazy_captures = ReXile::new("(x)(?:ab)+?").unwrap();
+    assert_eq!(re_lazy_captures.find("xabab"), Some((0, 3)));
+}
+
+#[test]
+fn test_redundant_constructs_are_simplified() {
+    // (?:hello) is just hello, so it gets the literal fast path
+    let re = ReXile::new("(?:hello)").unwrap();
+    assert!(re.explain().starts_with("fast path: Literal"));
+    assert_eq!(re.as_str(), "(?:hello)");
+
+    let re_count = ReXile::new("a{1}b{1,1}").unwrap();
+    assert!(re_count.explain().starts_with("fast path: Literal"));
+    assert_eq!(re_count.find("xaab"), Some((2, 4)));
+
+    let re_class = ReXile::new("v[.]1").unwrap();
+    assert_eq!(re_class.find("v21 v.1"), Some((4, 7)));
+
+    let re_quantified = ReXile::new("(?:a)+(b)").unwrap();
+    assert_eq!(re_quantified.find("caaab"), Some((1, 5)));
+    let caps = re_quantified.captures("caaab").unwrap();
+    assert_eq!(caps.get(1), Some("b"));
+
+    // Groups that do something keep their meaning
+    let re_alternation = ReXile::new("x(?:a|b)y").unwrap();
+    assert!(re_alternation.is_match("xby"));
+    assert!(!re_alternation.is_match("xa|by"));
+}
+
 #[test]
 fn test_group_alternation_priority() {
     // (foo|fo|f) - should match longest first
@@ -105,6 +174,31 @@ fn test_group_alternation_priority() {
     assert_eq!(re.find("foo"), Some((0, 3)));
 }
 
+#[test]
+fn test_dot_in_alternation_branch_is_a_wildcard() {
+    // Branches with metacharacters aren't searched for as literal text
+    for pattern in [
+        "a.c|z",
+        "a.c|xyz",
+        "(a.c|z)",
+        "(?:a.c|z)",
+        "^(a.c|z)$",
+        "(?i)a.c|z",
+    ] {
+        let re = ReXile::new(pattern).unwrap();
+        assert_eq!(re.find("abc"), Some((0, 3)), "{pattern:?}");
+        assert!(!re.explain().starts_with("fast path: Alternation"));
+    }
+
+    let re = ReXile::new("a.c|z").unwrap();
+    assert_eq!(re.find_all("a.c aXc z"), vec![(0, 3), (4, 7), (8, 9)]);
+    assert!(!re.is_match("ac"));
+
+    // An escaped dot is still literal
+    let escaped = ReXile::new(r"a\.c|z").unwrap();
+    assert_eq!(escaped.find("abc a.c"), Some((4, 7)));
+}
+
 #[test]
 fn test_quantified_group_edge_cases() {
     // (ab)+ - at least one ab
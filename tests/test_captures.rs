@@ -1,4 +1,4 @@
-use rexile::Pattern;
+use rexile::{CaptureLocations, Pattern, PatternError};
 
 #[test]
 fn test_single_capture_group() {
@@ -86,6 +86,46 @@ fn test_backreference() {
     assert!(!pattern.is_match("hello world"));
 }
 
+#[test]
+fn test_backreference_find_and_captures() {
+    let pattern = Pattern::new(r"(\w+) \1").unwrap();
+    assert!(pattern.is_match("the the"));
+    assert!(!pattern.is_match("the cat"));
+    assert_eq!(pattern.find("say the the end"), Some((4, 11)));
+
+    let caps = pattern.captures("the the").unwrap();
+    assert_eq!(&caps[0], "the the");
+    assert_eq!(&caps[1], "the");
+
+    let doubled = Pattern::new(r"(\w)\1").unwrap();
+    assert_eq!(doubled.find_all("abccd ee"), vec![(2, 4), (6, 8)]);
+    let groups: Vec<_> = doubled
+        .captures_iter("abccd ee")
+        .map(|caps| caps.get(1).unwrap())
+        .collect();
+    assert_eq!(groups, vec!["c", "e"]);
+}
+
+#[test]
+fn test_backreference_needs_backtracking() {
+    // Greedy `a+` must give back characters for `\1` to match
+    let pattern = Pattern::new(r"(a+)\1").unwrap();
+    assert_eq!(pattern.find("aaaa"), Some((0, 4)));
+    assert_eq!(&pattern.captures("aaaa").unwrap()[1], "aa");
+}
+
+#[test]
+fn test_backreference_to_undefined_group_fails_to_compile() {
+    assert!(Pattern::new(r"(\w)\1").is_ok());
+    assert!(Pattern::new(r"(a)(b)\2\1").is_ok());
+
+    let err = Pattern::new(r"(\w)\2").unwrap_err();
+    assert!(matches!(err, PatternError::ParseError { .. }), "{err:?}");
+    // Forward references name a group that isn't open yet
+    assert!(Pattern::new(r"\1(a)").is_err());
+    assert!(Pattern::new(r"(a)\2(b)").is_err());
+}
+
 #[test]
 fn test_replace_with_captures() {
     let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
@@ -95,6 +135,83 @@ fn test_replace_with_captures() {
     assert_eq!(result, "a:[1] b:[2]");
 }
 
+#[test]
+fn test_unmatched_optional_group_is_none() {
+    let pattern = Pattern::new(r"(a)?(b)").unwrap();
+
+    let caps = pattern.captures("xb").unwrap();
+    assert_eq!(caps.get(1), None);
+    assert_eq!(caps.get_range(1), None);
+    assert_eq!(caps.get(2), Some("b"));
+    assert_eq!(caps.get_range(2), Some((1, 2)));
+
+    let caps = pattern.captures("ab").unwrap();
+    assert_eq!(caps.get(1), Some("a"));
+    assert_eq!(caps.get_range(1), Some((0, 1)));
+
+    // Absent groups expand to nothing
+    assert_eq!(pattern.replace_all("b ab", "[$1|$2]"), "[|b] [a|b]");
+}
+
+/// Group slices borrow the text, so they outlive the `Captures` and the
+/// pattern that produced them
+fn fields(text: &str) -> Vec<&str> {
+    let pattern = Pattern::new(r"(\w+):(\d+)").unwrap();
+    let mut out = Vec::new();
+    for caps in pattern.captures_iter(text) {
+        out.extend(caps.iter().skip(1).flatten());
+    }
+    if let Some(caps) = pattern.captures(text) {
+        out.push(caps.as_str());
+    }
+    out
+}
+
+#[test]
+fn test_capture_slices_borrow_the_text() {
+    let text = String::from("a:1 b:22");
+    assert_eq!(fields(&text), vec!["a", "1", "b", "22", "a:1"]);
+}
+
+#[test]
+fn test_repeated_group_captures_last_iteration() {
+    let pattern = Pattern::new(r"(\d+)(,\d+)*").unwrap();
+    let caps = pattern.captures("1,2,3").unwrap();
+    assert_eq!(caps.get(0), Some("1,2,3"));
+    assert_eq!(caps.get(1), Some("1"));
+    assert_eq!(caps.get(2), Some(",3"));
+    assert_eq!(caps.get_range(2), Some((3, 5)));
+
+    // A lone number repeats the group zero times
+    assert_eq!(pattern.captures("7").unwrap().get(2), None);
+
+    let cases = [
+        (r"(a|b)+", "xabab", vec![Some("abab"), Some("b")]),
+        (r"x(\w)+y", "xabcy", vec![Some("xabcy"), Some("c")]),
+        (
+            r"(\d+)(?:,(\d+))*",
+            "1,2,33",
+            vec![Some("1,2,33"), Some("1"), Some("33")],
+        ),
+        // Groups inside keep their last value, even from an earlier iteration
+        (
+            r"((a)|(b))+",
+            "ab",
+            vec![Some("ab"), Some("b"), Some("a"), Some("b")],
+        ),
+        (r"(a|ab)+c", "abac", vec![Some("abac"), Some("a")]),
+        (
+            r"(\w+)\s*\((.+?)?\)",
+            "f(x, y)",
+            vec![Some("f(x, y)"), Some("f"), Some("x, y")],
+        ),
+    ];
+    for (source, text, expected) in cases {
+        let caps = Pattern::new(source).unwrap().captures(text).unwrap();
+        assert_eq!(caps.iter().collect::<Vec<_>>(), expected, "{source:?}");
+    }
+}
+
 #[test]
 fn test_split_with_captures() {
     let pattern = Pattern::new(r"(\d+)").unwrap();
@@ -156,3 +273,389 @@ fn test_optional_lazy_capture_in_function_call() {
     assert_eq!(&caps[1], "set");
     assert_eq!(&caps[2], "user.status, \"approved\"");
 }
+
+#[test]
+fn test_dfa_captures_iter_groups() {
+    let pattern = Pattern::new(r"(\d{4})-(\d{2})").unwrap();
+    let text = "from 2024-01 to 2025-12";
+
+    let groups: Vec<(String, String)> = pattern
+        .captures_iter(text)
+        .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+        .collect();
+
+    assert_eq!(
+        groups,
+        vec![
+            ("2024".to_string(), "01".to_string()),
+            ("2025".to_string(), "12".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_captures_fall_back_when_dfa_spans_are_inexact() {
+    // `\w*` opens group 1 on a looping state - captures must still be exact
+    let pattern = Pattern::new(r"(\w*)=(\d+)").unwrap();
+    let caps = pattern.captures("ab=12").expect("Expected captures");
+    assert_eq!(&caps[1], "ab");
+    assert_eq!(&caps[2], "12");
+}
+
+#[test]
+fn test_bounded_range_capture_matches_short_runs() {
+    let pattern = Pattern::new(r"(\d{2,4})x").unwrap();
+    assert_eq!(pattern.find_all("12x 1234x"), vec![(0, 3), (4, 9)]);
+    let caps = pattern.captures("12x").expect("Expected captures");
+    assert_eq!(&caps[1], "12");
+}
+
+#[test]
+fn test_captures_iter_offsets_with_leading_text() {
+    let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    let text = "  a=1 b=22";
+
+    let spans: Vec<Vec<Option<(usize, usize)>>> = pattern
+        .captures_iter(text)
+        .map(|caps| (0..caps.len()).map(|i| caps.pos(i)).collect())
+        .collect();
+    assert_eq!(
+        spans,
+        vec![
+            vec![Some((2, 5)), Some((2, 3)), Some((4, 5))],
+            vec![Some((6, 10)), Some((6, 7)), Some((8, 10))],
+        ]
+    );
+
+    let slices: Vec<(String, String, String)> = pattern
+        .captures_iter(text)
+        .map(|caps| {
+            (
+                caps[0].to_string(),
+                caps[1].to_string(),
+                caps[2].to_string(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        slices,
+        vec![
+            ("a=1".to_string(), "a".to_string(), "1".to_string()),
+            ("b=22".to_string(), "b".to_string(), "22".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_captures_iter_offsets_without_dfa() {
+    // `[a-z]` classes aren't DFA-compilable, so this exercises the element-walking path
+    let pattern = Pattern::new(r"([a-z]+)=([a-z]+)").unwrap();
+    let text = "  k=v xy=zw";
+
+    let groups: Vec<(String, String)> = pattern
+        .captures_iter(text)
+        .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("k".to_string(), "v".to_string()),
+            ("xy".to_string(), "zw".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_captures_iter_over_all_groups() {
+    // Groups from the branch that did not match are None
+    let pattern = Pattern::new(r"(a)|(b)").unwrap();
+    let caps = pattern.captures("b").unwrap();
+    assert_eq!(caps.len(), 3);
+    assert_eq!(
+        caps.iter().collect::<Vec<_>>(),
+        vec![Some("b"), None, Some("b")]
+    );
+
+    // A skipped optional group did not participate, unlike an empty one
+    let optional = Pattern::new(r"(a)?b").unwrap();
+    let caps = optional.captures("b").unwrap();
+    assert_eq!(caps.iter().collect::<Vec<_>>(), vec![Some("b"), None]);
+
+    let empty = Pattern::new(r"(a*)b").unwrap();
+    let caps = empty.captures("b").unwrap();
+    assert_eq!(caps.iter().collect::<Vec<_>>(), vec![Some("b"), Some("")]);
+
+    let trailing = Pattern::new(r"x(\d{2})-(\d{2})?").unwrap();
+    let all: Vec<Vec<_>> = trailing
+        .captures_iter("x12- x34-56")
+        .map(|caps| caps.iter().collect())
+        .collect();
+    assert_eq!(
+        all,
+        vec![
+            vec![Some("x12-"), Some("12"), None],
+            vec![Some("x34-56"), Some("34"), Some("56")],
+        ]
+    );
+}
+
+#[test]
+fn test_captures_read_reuses_locations() {
+    let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+    let mut locs = CaptureLocations::new();
+
+    assert!(pattern.captures_read(&mut locs, "key=42"));
+    assert_eq!(locs.len(), 3);
+    assert_eq!(locs.get(0), Some((0, 6)));
+    assert_eq!(locs.get(1), Some((0, 3)));
+    assert_eq!(locs.get(2), Some((4, 6)));
+
+    assert!(!pattern.captures_read(&mut locs, "no match"));
+    assert!(locs.is_empty());
+
+    let text = "x  b=7";
+    assert!(pattern.captures_read(&mut locs, text));
+    let (start, end) = locs.get(1).unwrap();
+    assert_eq!(&text[start..end], "b");
+}
+
+#[test]
+fn test_captures_len() {
+    // Group 0 (the whole match) is always counted
+    assert_eq!(Pattern::new(r"\d+").unwrap().captures_len(), 1);
+    assert_eq!(Pattern::new(r"(?:ab)+").unwrap().captures_len(), 1);
+
+    let pattern = Pattern::new(r"(\d{4})-(\d{2})-(\d{2})").unwrap();
+    assert_eq!(pattern.captures_len(), 4);
+    let caps = pattern.captures("2026-01-22").unwrap();
+    assert_eq!(caps.len(), pattern.captures_len());
+
+    // Nested groups and groups in alternation branches
+    assert_eq!(Pattern::new(r"((a)(b))(c)").unwrap().captures_len(), 5);
+    assert_eq!(Pattern::new(r"(a)|(b)").unwrap().captures_len(), 3);
+    assert_eq!(Pattern::new(r"^(\w+)=(\w+)$").unwrap().captures_len(), 3);
+}
+
+#[test]
+fn test_dotall_captures_span_newlines() {
+    let pattern = Pattern::new(r"(?s)(.+)").unwrap();
+    let caps = pattern.captures("first\nsecond").unwrap();
+    assert_eq!(caps.get(1), Some("first\nsecond"));
+
+    let pattern = Pattern::new(r"(?s)<(.*?)>").unwrap();
+    let caps = pattern.captures("x <a\nb> <c>").unwrap();
+    assert_eq!(caps.get(1), Some("a\nb"));
+
+    let pattern = Pattern::new(r"(?s)(\w+):(.)").unwrap();
+    let caps = pattern.captures("key:\nvalue").unwrap();
+    assert_eq!(caps.get(1), Some("key"));
+    assert_eq!(caps.get(2), Some("\n"));
+
+    // Without (?s) the group stops at the line break
+    let pattern = Pattern::new(r"(.+)").unwrap();
+    let caps = pattern.captures("first\nsecond").unwrap();
+    assert_eq!(caps.get(1), Some("first"));
+}
+
+#[test]
+fn test_literal_parentheses_are_not_groups() {
+    for source in [r"\(", r"foo\(bar\)", r"[()]", r"a[(]b", r"\(\d+\)"] {
+        let pattern = Pattern::new(source).unwrap();
+        assert_eq!(pattern.captures_len(), 1, "pattern {:?}", source);
+    }
+
+    let pattern = Pattern::new(r"foo\(bar\)").unwrap();
+    assert_eq!(pattern.find("call foo(bar)"), Some((5, 13)));
+    let pattern = Pattern::new(r"a[(]b").unwrap();
+    assert!(pattern.is_match("a(b"));
+    assert!(!pattern.is_match("ab"));
+
+    // A real group next to literal parentheses is still numbered 1
+    let pattern = Pattern::new(r"\((\w+)\)").unwrap();
+    assert_eq!(pattern.captures_len(), 2);
+    let caps = pattern.captures("f(arg)").unwrap();
+    assert_eq!(caps.get(1), Some("arg"));
+
+    let pattern = Pattern::new(r"a[(]b(c)").unwrap();
+    assert_eq!(pattern.captures_len(), 2);
+    let caps = pattern.captures("xa(bc").unwrap();
+    assert_eq!(caps.get(1), Some("c"));
+}
+
+#[test]
+fn test_alternation_branch_index() {
+    let pattern = Pattern::new(r"(\d+\.\d+)|(\d+)|([a-z_]\w*)").unwrap();
+    let tokens: Vec<_> = pattern
+        .captures_iter("pi 3.14 n 42")
+        .map(|caps| (caps.branch(), caps.get(0).unwrap()))
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            (Some(2), "pi"),
+            (Some(0), "3.14"),
+            (Some(2), "n"),
+            (Some(1), "42"),
+        ]
+    );
+
+    // Branches without a group still count
+    let pattern = Pattern::new(r"(a)|b|(c)").unwrap();
+    assert_eq!(pattern.captures("b").unwrap().branch(), Some(1));
+    assert_eq!(pattern.captures("xc").unwrap().branch(), Some(2));
+
+    // Not a top-level alternation
+    let pattern = Pattern::new(r"x(a|b)").unwrap();
+    assert_eq!(pattern.captures("xb").unwrap().branch(), None);
+}
+
+#[test]
+fn test_nested_and_alternating_group_spans() {
+    // The first branch fails on what follows, so the second one is taken
+    let pattern = Pattern::new(r"(a|ab)(c)").unwrap();
+    let caps = pattern.captures("xabc").unwrap();
+    assert_eq!(caps.pos(0), Some((1, 4)));
+    assert_eq!(caps.pos(1), Some((1, 3)));
+    assert_eq!(caps.pos(2), Some((3, 4)));
+
+    // Nested groups follow the path that produced the outer span
+    let pattern = Pattern::new(r"(x(a|ab)c)").unwrap();
+    let caps = pattern.captures("xabc").unwrap();
+    assert_eq!(caps.pos(1), Some((0, 4)));
+    assert_eq!(caps.pos(2), Some((1, 3)));
+
+    let pattern = Pattern::new(r"^(x(a|ab)c)$").unwrap();
+    assert_eq!(pattern.captures("xabc").unwrap().pos(2), Some((1, 3)));
+
+    // A group inside an alternation inside a group is counted and set
+    let pattern = Pattern::new(r"((a)|b)").unwrap();
+    let caps = pattern.captures("xa").unwrap();
+    assert_eq!(caps.len(), 3);
+    assert_eq!(caps.pos(2), Some((1, 2)));
+    let caps = pattern.captures("b").unwrap();
+    assert_eq!(caps.pos(1), Some((0, 1)));
+    assert_eq!(caps.pos(2), None);
+}
+
+#[test]
+fn test_alternation_tie_goes_to_first_branch() {
+    // Both branches match at 0; the first one in the pattern wins even
+    // though the other is shorter, in `find` and `find_all` alike
+    let pattern = Pattern::new(r"(abc)|(ab)").unwrap();
+    assert_eq!(pattern.find("abcd"), Some((0, 3)));
+    assert_eq!(
+        pattern.find_all("abc ab abcd"),
+        vec![(0, 3), (4, 6), (7, 10)]
+    );
+    let branches: Vec<_> = pattern
+        .captures_iter("abc ab")
+        .map(|caps| caps.branch())
+        .collect();
+    assert_eq!(branches, vec![Some(0), Some(1)]);
+
+    // ... and even though the other is longer
+    let pattern = Pattern::new(r"(ab)|(abc)").unwrap();
+    assert_eq!(pattern.find("abcd"), Some((0, 2)));
+    assert_eq!(
+        pattern.find_all("abc ab abcd"),
+        vec![(0, 2), (4, 6), (7, 9)]
+    );
+    let caps = pattern.captures("abc").unwrap();
+    assert_eq!(caps.pos(1), Some((0, 2)));
+    assert_eq!(caps.pos(2), None);
+}
+
+#[test]
+fn test_tokenize_reports_branch_and_spans() {
+    let lexer = Pattern::new(r"(\d+)|([a-z]+)|\s+").unwrap();
+    let tokens: Vec<_> = lexer
+        .tokenize("let x = 42")
+        .map(|(kind, caps)| (kind, caps.pos(0).unwrap(), caps.pos(1), caps.pos(2)))
+        .collect();
+    assert_eq!(
+        tokens,
+        vec![
+            (1, (0, 3), None, Some((0, 3))),
+            (2, (3, 4), None, None),
+            (1, (4, 5), None, Some((4, 5))),
+            (2, (5, 6), None, None),
+            (2, (7, 8), None, None),
+            (0, (8, 10), Some((8, 10)), None),
+        ]
+    );
+
+    // Alternations without groups, literal ones included, still report the branch
+    let kinds = |pattern: &str, text: &str| -> Vec<usize> {
+        let pattern = Pattern::new(pattern).unwrap();
+        pattern.tokenize(text).map(|(kind, _)| kind).collect()
+    };
+    assert_eq!(kinds(r"\d+|[a-z]+", "ab 12 c"), vec![1, 0, 1]);
+    assert_eq!(kinds("foo|bar", "bar foo bar"), vec![1, 0, 1]);
+    assert_eq!(kinds("(?i)foo|bar", "BAR Foo"), vec![1, 0]);
+
+    // Not an alternation: every token is kind 0
+    assert_eq!(kinds(r"\w+", "a b"), vec![0, 0]);
+}
+
+#[test]
+fn test_captures_iter_overlapping() {
+    // Digit pairs sliding over a number, the second digit only looked at
+    let pattern = Pattern::new(r"(\d)(?=\d)").unwrap();
+    let digits: Vec<_> = pattern
+        .captures_iter_overlapping("12345")
+        .map(|caps| caps.get(1).unwrap())
+        .collect();
+    assert_eq!(digits, vec!["1", "2", "3", "4"]);
+
+    // Consuming both digits still yields every window
+    let pattern = Pattern::new(r"(\d)(\d)").unwrap();
+    let pairs: Vec<_> = pattern
+        .captures_iter_overlapping("12a345")
+        .map(|caps| {
+            (
+                caps.pos(0).unwrap(),
+                caps.get(1).unwrap(),
+                caps.get(2).unwrap(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        pairs,
+        vec![((0, 2), "1", "2"), ((3, 5), "3", "4"), ((4, 6), "4", "5")]
+    );
+    assert_eq!(pattern.captures_iter("12a345").count(), 2);
+
+    // Resuming one char after the start keeps offsets on char boundaries
+    let pattern = Pattern::new(r"(.)é").unwrap();
+    let spans: Vec<_> = pattern
+        .captures_iter_overlapping("aéé")
+        .map(|caps| caps.pos(0).unwrap())
+        .collect();
+    assert_eq!(spans, vec![(0, 3), (1, 5)]);
+
+    // Empty matches are reported once per position
+    let pattern = Pattern::new("a*").unwrap();
+    let spans: Vec<_> = pattern
+        .captures_iter_overlapping("baab")
+        .map(|caps| caps.pos(0).unwrap())
+        .collect();
+    assert_eq!(spans, vec![(0, 0), (1, 3), (2, 3), (3, 3), (4, 4)]);
+}
+
+#[test]
+fn test_matched_groups_counts_participating_groups() {
+    // Exactly one group of an alternation takes part
+    let pattern = Pattern::new(r"(a)|(b)|(c)").unwrap();
+    for caps in pattern.captures_iter("a b c") {
+        assert_eq!(caps.len(), 4);
+        assert_eq!(caps.matched_groups(), 1);
+    }
+
+    // Optional groups count only when they matched, even if empty
+    let pattern = Pattern::new(r"(\w+)(=)?(\d*)").unwrap();
+    let counts: Vec<_> = pattern
+        .captures_iter("x=1 y")
+        .map(|caps| caps.matched_groups())
+        .collect();
+    assert_eq!(counts, vec![3, 2]);
+}
@@ -125,3 +125,23 @@ fn test_password_strength_lookahead() {
     assert!(pattern.is_match("test123"));
     assert!(!pattern.is_match("testabc"));
 }
+
+#[test]
+fn test_lookahead_prefix_captures() {
+    // Pattern: (\w+)(?=;) - the prefix's groups are captured, the lookahead isn't
+    let pattern = Pattern::new(r"(\w+)(?=;)").unwrap();
+
+    let caps = pattern.captures("abc;").unwrap();
+    assert_eq!(&caps[0], "abc");
+    assert_eq!(caps.get(1), Some("abc"));
+
+    let caps = pattern.captures("x = key;").unwrap();
+    assert_eq!(caps.pos(1), Some((4, 7)));
+
+    let pattern = Pattern::new(r"(\w+)=(\d+)(?=;)").unwrap();
+    let groups: Vec<(&str, &str)> = pattern
+        .captures_iter("a=1; b=2, c=3;")
+        .map(|caps| (caps.get(1).unwrap(), caps.get(2).unwrap()))
+        .collect();
+    assert_eq!(groups, [("a", "1"), ("c", "3")]);
+}
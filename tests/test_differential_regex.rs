@@ -73,6 +73,14 @@ fn literal_search_matches_regex() {
 #[test]
 fn alternation_search_matches_regex() {
     assert_search_compatible(&[
+        Case {
+            pattern: "(abc)|(ab)",
+            haystack: "abc ab abcd",
+        },
+        Case {
+            pattern: "(ab)|(abc)",
+            haystack: "abc ab abcd",
+        },
         Case {
             pattern: "foo|bar|baz",
             haystack: "qux bar baz foo",
@@ -93,6 +101,26 @@ fn alternation_search_matches_regex() {
             pattern: "foo|foobar",
             haystack: "foobar foo",
         },
+        Case {
+            pattern: "a|ab",
+            haystack: "xab ab aab",
+        },
+        Case {
+            pattern: "ab|a",
+            haystack: "xab ab aab",
+        },
+        Case {
+            pattern: "(?:a|ab)",
+            haystack: "xab ab aab",
+        },
+        Case {
+            pattern: "(?i)a|ab",
+            haystack: "xAB ab aAb",
+        },
+        Case {
+            pattern: "(?i)ab|a",
+            haystack: "xAB ab aAb",
+        },
     ]);
 }
 
@@ -152,6 +180,207 @@ fn escape_classes_match_regex_for_ascii_inputs() {
     ]);
 }
 
+#[test]
+fn ascii_class_runs_match_regex() {
+    let patterns = [
+        r"[\w.-]+",
+        r"[a-z0-9.-]+",
+        r"[\w-]+",
+        r"[a-zA-Z0-9_]+",
+        r"[_0-9A-Za-z]+",
+    ];
+    let haystacks = [
+        "foo-bar.baz_1 x",
+        "a.b.c-d --",
+        "",
+        "...",
+        "user@mail.example.com",
+    ];
+    let cases: Vec<Case> = patterns
+        .iter()
+        .flat_map(|&pattern| {
+            haystacks
+                .iter()
+                .map(move |&haystack| Case { pattern, haystack })
+        })
+        .collect();
+    assert_search_compatible(&cases);
+
+    // However the class is spelled, a word run is \w+ (ASCII-only here,
+    // so non-ASCII text is compared against rexile's own \w+)
+    let word = Pattern::new(r"\w+").unwrap();
+    for spelling in [
+        r"[a-zA-Z0-9_]+",
+        r"[_0-9A-Za-z]+",
+        r"[\w]+",
+        r"[0-9a-z_A-Z]+",
+    ] {
+        let pattern = Pattern::new(spelling).unwrap();
+        for haystack in ["foo-bar.baz_1 x", "héllo wörld_2", "  ", "a"] {
+            assert_eq!(
+                pattern.find_all(haystack),
+                word.find_all(haystack),
+                "{spelling:?}"
+            );
+            assert_eq!(pattern.find(haystack), word.find(haystack), "{spelling:?}");
+            assert_eq!(
+                pattern.count_matches(haystack),
+                word.count_matches(haystack),
+                "{spelling:?}"
+            );
+        }
+    }
+}
+
+#[test]
+fn signed_numbers_match_regex() {
+    let patterns = [
+        r"-?\d+(\.\d+)?",
+        r"-?\d+(?:\.\d+)?",
+        r"[+-]?\d+(\.\d+)?",
+        r"-?\d+",
+    ];
+    let haystacks = [
+        "x=-12.5, y=+3, z=7.",
+        "1.2.3 --4 -.5 5-6",
+        "",
+        "- + . -",
+        "é-1.0é",
+    ];
+    let cases: Vec<Case> = patterns
+        .iter()
+        .flat_map(|&pattern| {
+            haystacks
+                .iter()
+                .map(move |&haystack| Case { pattern, haystack })
+        })
+        .collect();
+    assert_search_compatible(&cases);
+
+    // The fraction group is still captured by the full matcher
+    let number = Pattern::new(r"-?\d+(\.\d+)?").unwrap();
+    let regex = Regex::new(r"-?\d+(\.\d+)?").unwrap();
+    for haystack in ["x=-12.5, y=3", "7.", "-0.25"] {
+        let ours: Vec<_> = number
+            .captures_iter(haystack)
+            .map(|caps| (caps.get(0), caps.get(1)))
+            .collect();
+        let theirs: Vec<_> = regex
+            .captures_iter(haystack)
+            .map(|caps| {
+                (
+                    caps.get(0).map(|m| m.as_str()),
+                    caps.get(1).map(|m| m.as_str()),
+                )
+            })
+            .collect();
+        assert_eq!(ours, theirs, "{haystack:?}");
+    }
+}
+
+#[test]
+fn quantified_non_capturing_groups_match_regex() {
+    // The same shapes with and without other groups around them
+    let patterns = [
+        r"(?:ab)+c",
+        r"(?:ab)?c",
+        r"(?:ab)*c",
+        r"(?:ab){2}c",
+        r"(x)(?:ab)+c",
+        r"(?:ab)+c(d)?",
+        r"(?:ab)+?",
+        r"x(?:ab)+?",
+        r"(x)(?:ab)??",
+        r"(?:a|bc)+?",
+        r"(?:ab)+$",
+        r"(?:ab)+?$",
+        r"(?:a|bc)+$",
+        r"x(?:ab)+$",
+        r"(?:ab){2}$",
+    ];
+    let haystacks = ["ababc", "c", "abc", "xababcd", "ab x abab", "xabcbc", ""];
+    let cases: Vec<Case> = patterns
+        .iter()
+        .flat_map(|&pattern| {
+            haystacks
+                .iter()
+                .map(move |&haystack| Case { pattern, haystack })
+        })
+        .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn nullable_patterns_match_empty_at_start() {
+    assert_eq!(Pattern::new("a*").unwrap().find("bbb"), Some((0, 0)));
+
+    let patterns = [
+        "a*",
+        "(?:x)?",
+        "(x)?",
+        "(?:ab)*",
+        "(?:a|b)*",
+        "(?:a|b+)*",
+        "(?:a*)+",
+        "(a)*(b)?",
+        "a?b?",
+        r"\d*",
+    ];
+    let haystacks = ["bbb", "cab", "abb", "", "é1"];
+    let cases: Vec<Case> = patterns
+        .iter()
+        .flat_map(|&pattern| {
+            haystacks
+                .iter()
+                .map(move |&haystack| Case { pattern, haystack })
+        })
+        .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn end_anchors_see_the_text_before_the_match() {
+    assert_search_compatible(&[
+        Case {
+            pattern: r"\bx$",
+            haystack: "xabcx",
+        },
+        Case {
+            pattern: r"\bx$",
+            haystack: "ab x",
+        },
+        Case {
+            pattern: r"(ab|b)$",
+            haystack: "abab",
+        },
+        Case {
+            pattern: r"(ab)+$",
+            haystack: "ab abab",
+        },
+    ]);
+}
+
+#[test]
+fn end_anchored_groups_on_long_text() {
+    // Matching through to the end from every start would take minutes here,
+    // with or without groups to track
+    let haystack = "ab ".repeat(100_000) + "abab";
+    for pattern in [
+        r"(?:ab)+$",
+        r"(?:ab)+?$",
+        r"(?:a|b)+$",
+        r"x(?:ab)+$",
+        r"(ab)+$",
+        r"(a|b)+$",
+    ] {
+        assert_eq!(
+            rexile_search(pattern, &haystack),
+            regex_search(pattern, &haystack),
+            "pattern {pattern:?}"
+        );
+    }
+}
+
 #[test]
 fn quantifiers_match_regex() {
     assert_search_compatible(&[
@@ -217,9 +446,136 @@ fn anchors_match_regex() {
             pattern: "^exact$",
             haystack: "not exact",
         },
+        Case {
+            pattern: "^(foo|bar)$",
+            haystack: "foobar",
+        },
+        Case {
+            pattern: "^(a|ab|abc)$",
+            haystack: "ab",
+        },
+        Case {
+            pattern: "^(?:a|ab)$",
+            haystack: "ab",
+        },
+        Case {
+            pattern: r"\w+\.rs$",
+            haystack: "a.rs b.rs",
+        },
+        Case {
+            pattern: r"\w+\.rs$",
+            haystack: "a.rs b.rsx",
+        },
+        Case {
+            pattern: r"\d+$",
+            haystack: "1 22 333",
+        },
+        Case {
+            pattern: r"\d*$",
+            haystack: "ab",
+        },
+        Case {
+            pattern: r"[a-z]+\d{2}$",
+            haystack: "ab1 cd23",
+        },
+        Case {
+            pattern: "(foo|bar)$",
+            haystack: "foobar",
+        },
+        Case {
+            pattern: "(ab|b)$",
+            haystack: "abab",
+        },
+        Case {
+            pattern: r"^\d{4}-\d{2}-\d{2}",
+            haystack: "2024-01-15 2024-01-16",
+        },
+        Case {
+            pattern: r"^\d{4}-\d{2}-\d{2}",
+            haystack: "on 2024-01-15",
+        },
+        Case {
+            pattern: r"^\d+\.\d+",
+            haystack: "12.5.6",
+        },
+        Case {
+            pattern: r"^[a-z]+?\d",
+            haystack: "ab12 cd3",
+        },
+        Case {
+            pattern: r"^.{2}é",
+            haystack: "aéé",
+        },
+        Case {
+            pattern: r"^\d{3}-\d{4}$",
+            haystack: "555-1234",
+        },
+        Case {
+            pattern: r"^\d{3}-\d{4}$",
+            haystack: "555-12345",
+        },
+        Case {
+            pattern: r"^[a-z]+\d$",
+            haystack: "abc1",
+        },
+        Case {
+            pattern: r"^a.*?b$",
+            haystack: "abab",
+        },
+        Case {
+            pattern: r"^\w+\s\w+$",
+            haystack: "two\u{A0}words",
+        },
     ]);
 }
 
+#[test]
+fn anchored_sub_patterns_validate_whole_strings() {
+    let digits = Pattern::new(r"^\d+$").unwrap();
+    assert!(digits.is_match("123"));
+    assert!(!digits.is_match("12a3"));
+
+    let patterns = [
+        r"^\d+$",
+        r"^[a-z]+$",
+        r"^foo\d*$",
+        r"^ab|cd$",
+        r"^(\d+)|x$",
+        r"^\$$",
+        r"a\\$",
+    ];
+    let haystacks = [
+        "123", "12a3", "abc", "abC", "foo", "foo12", "foo1x", "ab cd", "12 x", "$", r"a\",
+    ];
+    let cases: Vec<Case> = patterns
+        .iter()
+        .flat_map(|&pattern| {
+            haystacks
+                .iter()
+                .map(move |&haystack| Case { pattern, haystack })
+        })
+        .collect();
+    assert_search_compatible(&cases);
+
+    // Anchors inside alternation branches hold for captures too
+    let pattern = Pattern::new(r"^(\d+)|x$").unwrap();
+    let regex = Regex::new(r"^(\d+)|x$").unwrap();
+    for haystack in ["12 x", "x 12", "12x"] {
+        let ours: Vec<_> = pattern
+            .captures_iter(haystack)
+            .map(|caps| (caps.pos(0), caps.pos(1)))
+            .collect();
+        let theirs: Vec<_> = regex
+            .captures_iter(haystack)
+            .map(|caps| {
+                let span = |m: regex::Match| (m.start(), m.end());
+                (caps.get(0).map(span), caps.get(1).map(span))
+            })
+            .collect();
+        assert_eq!(ours, theirs, "{haystack:?}");
+    }
+}
+
 #[test]
 fn dot_and_dotall_match_regex() {
     assert_search_compatible(&[
@@ -239,6 +595,60 @@ fn dot_and_dotall_match_regex() {
             pattern: r"(?s)a.*c",
             haystack: "a\n\nc",
         },
+        Case {
+            pattern: r"(?s).",
+            haystack: "a\nb",
+        },
+        Case {
+            pattern: r"(?s).+",
+            haystack: "a\nb\n",
+        },
+        Case {
+            pattern: r"(?s)a(.+)b",
+            haystack: "xa\n\nbx",
+        },
+        Case {
+            pattern: r"(?s)^.+$",
+            haystack: "one\ntwo",
+        },
+        Case {
+            pattern: r"(?s)x.?y",
+            haystack: "xy x\ny",
+        },
+        Case {
+            pattern: r"(?s)a\.b",
+            haystack: "a\nb a.b",
+        },
+        Case {
+            pattern: r"(?s)[.]c",
+            haystack: "\nc .c",
+        },
+    ]);
+}
+
+#[test]
+fn literal_parentheses_match_regex() {
+    assert_search_compatible(&[
+        Case {
+            pattern: r"\(",
+            haystack: "f(x) (",
+        },
+        Case {
+            pattern: r"foo\(bar\)",
+            haystack: "foo(bar) foobar",
+        },
+        Case {
+            pattern: r"[()]",
+            haystack: "f(x) y",
+        },
+        Case {
+            pattern: r"a[(]b",
+            haystack: "ab a(b a)b",
+        },
+        Case {
+            pattern: r"\(\d+\)",
+            haystack: "(12) (x) (3)",
+        },
     ]);
 }
 
@@ -275,6 +685,35 @@ fn boundaries_match_regex_for_ascii_inputs() {
             pattern: r"\b\w+\b",
             haystack: "one two_three 123",
         },
+        Case {
+            pattern: r"\bfoo\b|\bbar\b",
+            haystack: "xfoo bar foox foo",
+        },
+        Case {
+            pattern: r"\bcat\b|\bdog\b",
+            haystack: "concatenate hotdogs cat",
+        },
+        Case {
+            pattern: r"\Bcat|dog\B",
+            haystack: "concat dogx dog",
+        },
+        // Empty text is a non-boundary
+        Case {
+            pattern: r"\B",
+            haystack: "",
+        },
+        Case {
+            pattern: r"\b",
+            haystack: "",
+        },
+        Case {
+            pattern: r"\B\d*",
+            haystack: "",
+        },
+        Case {
+            pattern: r"(?-u)\B",
+            haystack: "",
+        },
     ]);
 }
 
@@ -293,6 +732,14 @@ fn sequences_match_regex_for_ascii_inputs() {
             pattern: r"[A-Z][a-z]+\d?",
             haystack: "Alice2 bob Carol",
         },
+        Case {
+            pattern: "colou?r",
+            haystack: "color colour colouur",
+        },
+        Case {
+            pattern: r"lib\.?rs",
+            haystack: "librs lib.rs",
+        },
     ]);
 }
 
@@ -307,6 +754,14 @@ fn case_insensitive_literals_match_regex_for_ascii_inputs() {
             pattern: r"(?i)get|post",
             haystack: "GET post Put",
         },
+        Case {
+            pattern: r"(?im)foo|bar|baz",
+            haystack: "qux FOO Bar bAZ",
+        },
+        Case {
+            pattern: r"(?i)foo\d+",
+            haystack: "FOO12 foo3",
+        },
     ]);
 }
 
@@ -321,6 +776,44 @@ fn captures_match_regex_for_simple_ascii_patterns() {
             pattern: r"(\w+)@(\w+)\.(\w+)",
             haystack: "email test@example.com done",
         },
+        Case {
+            pattern: "(a|ab)(c)",
+            haystack: "xabc",
+        },
+        Case {
+            pattern: "(a|ab)(b?)",
+            haystack: "ab",
+        },
+        Case {
+            pattern: "((a)|b)",
+            haystack: "xa",
+        },
+        Case {
+            pattern: "(x(a|ab)c)",
+            haystack: "xabc",
+        },
+        Case {
+            pattern: "(x(a|ab)c)|z",
+            haystack: "xabc",
+        },
+        // A repetition that never ran leaves its group unset, unless an
+        // empty iteration is possible
+        Case {
+            pattern: "(a|b)*",
+            haystack: "",
+        },
+        Case {
+            pattern: "(ab)*",
+            haystack: "x",
+        },
+        Case {
+            pattern: "(a*)*",
+            haystack: "",
+        },
+        Case {
+            pattern: "(a*)?",
+            haystack: "b",
+        },
     ];
 
     for case in &cases {
@@ -389,9 +882,501 @@ fn split_matches_regex_for_simple_ascii_patterns() {
     }
 }
 
+#[test]
+fn find_iter_rev_matches_reversed_regex_order() {
+    let cases = [
+        ("needle", "needle hay needle hay needle"),
+        ("aa", "aaaaa"),
+        (r"\d+", "a1 b22 c333"),
+        (r"\w+@\w+", "x@y and z@w"),
+    ];
+
+    for (pattern, haystack) in cases {
+        let rexile = Pattern::new(pattern).unwrap();
+        let regex = Regex::new(pattern).unwrap();
+
+        let rexile_rev: Vec<_> = rexile
+            .find_iter(haystack)
+            .rev()
+            .map(|mat| (mat.start(), mat.end()))
+            .collect();
+        let mut regex_rev: Vec<_> = regex
+            .find_iter(haystack)
+            .map(|mat| (mat.start(), mat.end()))
+            .collect();
+        regex_rev.reverse();
+
+        assert_eq!(
+            rexile_rev, regex_rev,
+            "reversed find_iter with pattern {pattern:?} on haystack {haystack:?}"
+        );
+    }
+}
+
+#[test]
+fn unicode_properties_match_regex() {
+    assert_search_compatible(&[
+        Case {
+            pattern: r"\p{L}+",
+            haystack: "Grüße, мир 世界 42",
+        },
+        Case {
+            pattern: r"\pN+",
+            haystack: "x 42 ٣٤ Ⅻ",
+        },
+        Case {
+            pattern: r"\p{Nd}+",
+            haystack: "x 42 ٣٤ Ⅻ",
+        },
+        Case {
+            pattern: r"\p{Lu}\p{Ll}+",
+            haystack: "ÉCOLE École école",
+        },
+        Case {
+            pattern: r"\P{L}+",
+            haystack: "héllo, wörld!",
+        },
+        Case {
+            pattern: r"[\p{L}\d]+",
+            haystack: "naïve-2 ok_3",
+        },
+        Case {
+            pattern: r"\p{P}",
+            haystack: "abc«def»",
+        },
+    ]);
+}
+
+#[test]
+fn unicode_word_boundaries_match_regex() {
+    assert_search_compatible(&[
+        Case {
+            pattern: r"\bαβγ\b",
+            haystack: "αβγδ x αβγ y",
+        },
+        Case {
+            pattern: r"\bfoo\b",
+            haystack: "éfoo foo",
+        },
+        Case {
+            pattern: r"\bcafé\b",
+            haystack: "cafés café",
+        },
+        Case {
+            pattern: r"мир\B",
+            haystack: "мир миру",
+        },
+    ]);
+}
+
+#[test]
+fn lazy_dfa_sequences_match_regex() {
+    let haystack =
+        "id 550e8400-e29b-41d4-a716-446655440000, tel 555-1234 / 5551-234, AB12c XY99 éé12 ab1 xyz9";
+    let cases: Vec<Case> = [
+        "[0-9a-f]{8}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{4}-[0-9a-f]{12}",
+        r"\d{3}-\d{4}",
+        r"[A-Z]{2}\d{2}[a-z]?",
+        r"[a-z]{2,}\d+",
+        r"[a-zé]{2,3}\d",
+        r".{3}\d{2}",
+        r"[A-Z]{2}\d+?",
+        r"[a-z]+?\d{1,2}",
+    ]
+    .into_iter()
+    .map(|pattern| Case { pattern, haystack })
+    .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn lazy_and_greedy_spans_match_regex() {
+    let haystack = "a1b a22b axbb ab 1.2.3 12.34.5 aab abb";
+    let cases: Vec<Case> = [
+        r"a.*?b",
+        r"a.*b",
+        r"a.+?b",
+        r"a.+b",
+        r"a.??b",
+        r"\d+?\.\d+",
+        r"\d+\.\d+?",
+        r"\d*?\.\d*?",
+        r"\w*?b",
+        r"[a-z]+?b",
+        r"\d+?",
+        r"\d+",
+    ]
+    .into_iter()
+    .map(|pattern| Case { pattern, haystack })
+    .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn prefilter_find_is_leftmost() {
+    let cases = [
+        // The prefix repeats before and inside the first real match
+        (r"foo\d+bar", "foo1 foofoo22bar foo3bar"),
+        (r"foo\d+bar", "foo foo1 foo"),
+        (r"abc\w?z", "abcabcz abcz"),
+        (r"USD\d+\.\d\d", "USD USD1. USD12.50 USD3.00"),
+        (r"éclair\d", "éclair éclairéclair9"),
+    ];
+    for (pattern, _) in cases {
+        let re = Pattern::new(pattern).unwrap();
+        assert!(
+            !re.explain().contains("prefilter: none"),
+            "{pattern:?} should take the prefilter path"
+        );
+    }
+    let cases: Vec<Case> = cases
+        .into_iter()
+        .map(|(pattern, haystack)| Case { pattern, haystack })
+        .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn unicode_whitespace_matches_regex_on_every_route() {
+    let haystack =
+        "rule\u{A0}\u{2003}\"x\" when\u{A0}a salience\u{3000}10 a\x0B\x0Cb\u{85}c rule\t\"y\"";
+    for (pattern, fast_path) in [
+        (r"\s+", None),
+        (r"[\s]+", None),
+        (r"\S+", None),
+        (r"\w+\s+\w+", None),
+        (r"rule\s+", Some("LiteralPlusWhitespace")),
+        (r#"rule\s+"[^"]+""#, Some("LiteralWhitespaceQuoted")),
+        (r"salience\s+\d+", Some("LiteralWhitespaceDigits")),
+        (r"when\s+\w+", Some("LiteralWhitespaceWord")),
+    ] {
+        let re = Pattern::new(pattern).unwrap();
+        if let Some(name) = fast_path {
+            assert!(
+                re.explain().contains(&format!("fast path: {name}")),
+                "{pattern:?} should take the {name} fast path"
+            );
+        }
+        assert_search_compatible(&[Case { pattern, haystack }]);
+    }
+
+    let pieces: Vec<&str> = Pattern::new(r"\s+").unwrap().split(haystack).collect();
+    let expected: Vec<&str> = Regex::new(r"\s+").unwrap().split(haystack).collect();
+    assert_eq!(pieces, expected);
+}
+
+#[test]
+fn count_matches_agrees_with_regex() {
+    let haystack = "id=12 user_7 took 340ms; GET /a 200, POST /b 404 -- ERROR x2";
+    for pattern in [
+        "o",
+        "oo",
+        r"\d+",
+        r"\w+",
+        "GET|POST",
+        "(?i)error",
+        r"[a-z]+_\d",
+        r"\s*",
+        r"\b",
+        "^id",
+        "x2$",
+    ] {
+        let count = Pattern::new(pattern).unwrap().count_matches(haystack);
+        let expected = Regex::new(pattern).unwrap().find_iter(haystack).count();
+        assert_eq!(count, expected, "pattern {pattern:?}");
+    }
+}
+
+#[test]
+fn case_insensitive_spans_are_in_the_original_text() {
+    // ẞ lowercases to ß, which is a byte shorter
+    assert_search_compatible(&[
+        Case {
+            pattern: "(?i)ß",
+            haystack: "xẞy ß",
+        },
+        Case {
+            pattern: "(?i)straße",
+            haystack: "STRAẞE!",
+        },
+        Case {
+            pattern: "(?i)é+",
+            haystack: "ẞß éÉ É",
+        },
+    ]);
+}
+
+#[test]
+fn empty_matches_match_regex() {
+    assert_search_compatible(&[
+        Case {
+            pattern: "a*",
+            haystack: "bbb",
+        },
+        Case {
+            pattern: "a*",
+            haystack: "baaab",
+        },
+        Case {
+            pattern: "",
+            haystack: "héllo",
+        },
+        Case {
+            pattern: "x?",
+            haystack: "xyx",
+        },
+        Case {
+            pattern: r"\b",
+            haystack: "ab cd",
+        },
+        Case {
+            pattern: r"\d*",
+            haystack: "12a3",
+        },
+        Case {
+            pattern: "^",
+            haystack: "abc",
+        },
+        Case {
+            pattern: "$",
+            haystack: "abc",
+        },
+        // The end of the text, not of its shorter lowercase form
+        Case {
+            pattern: "(?i)$",
+            haystack: "ẞß é É",
+        },
+        Case {
+            pattern: "(?i)x*",
+            haystack: "ẞxẞ",
+        },
+    ]);
+}
+
+#[test]
+fn full_match_agrees_with_anchored_regex() {
+    let cases = [
+        ("foo|foobar", "foobar"),
+        ("a|ab", "ab"),
+        ("(?i)get|getall", "GETALL"),
+        ("(a|ab)", "ab"),
+        (r"\d+", "123"),
+        (r"\d+", "123x"),
+        (r"(\w+)@(\w+)", "user@host"),
+        (r"[a-z]+\d", "abc1"),
+        (r"[a-z]+\d", "abc12"),
+        ("", ""),
+        ("", "x"),
+    ];
+    for (pattern, haystack) in cases {
+        let anchored = Regex::new(&format!("^(?:{pattern})$")).unwrap();
+        assert_eq!(
+            Pattern::new(pattern).unwrap().is_full_match(haystack),
+            anchored.is_match(haystack),
+            "pattern {pattern:?} on haystack {haystack:?}"
+        );
+    }
+}
+
+#[test]
+fn class_set_operations_match_regex() {
+    let haystack = "The quick brown fox: 0 1 9 a_b-c +- [x] & é Ωmega";
+    let cases: Vec<Case> = [
+        "[a-z&&[^aeiou]]+",
+        "[0-9&&[^0]]",
+        "[a-z--[aeiou]]",
+        "[a-z--aeiou]+",
+        "[^a-z&&[^aeiou]]+",
+        r"[\p{L}&&[^a-z]]+",
+        r"[\p{L}--\p{Lu}]+",
+        "[a[bc]x]+",
+        "[+--]",
+        "[a-c&&b-d--c]",
+        r"x[\[\]&&\]]",
+        r"[&&a]|\d",
+    ]
+    .into_iter()
+    .map(|pattern| Case { pattern, haystack })
+    .collect();
+    assert_search_compatible(&cases);
+
+    // Set operations inside groups, alternations and repetitions (and `\w`,
+    // which is ASCII-only here, on ASCII text)
+    let cases: Vec<Case> = [
+        r"[\w&&\D]+",
+        r"[a-z&&[^aeiou]]{2,}",
+        r"x(?:[a-z&&[^aeiou]])",
+        r"([0-9&&[^0]])\s([a-z--[x]]+)",
+        r"[a-z&&[^aeiou]]x|[0-9--[5-9]]",
+    ]
+    .into_iter()
+    .map(|pattern| Case {
+        pattern,
+        haystack: "1 ab 9 cd 4 xyz 7 strength",
+    })
+    .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn malformed_class_set_operations_fail_to_compile() {
+    for pattern in ["[a-z&&[^aeiou]", "[a&&[z-a]]", r"[a&&\b]"] {
+        assert!(Pattern::new(pattern).is_err(), "{pattern:?}");
+        assert!(Regex::new(pattern).is_err(), "{pattern:?}");
+    }
+}
+
+#[test]
+fn alternation_of_sub_patterns_matches_regex() {
+    let haystack = "a 0x1f 42 z ab 12 cd x foo 12 abc ab1 2c $5 a.b [x] é9";
+    let cases: Vec<Case> = [
+        r"\d+|0x[0-9a-f]+",
+        r"0x[0-9a-f]+|\d+",
+        r"\d+|[a-z]+",
+        r"foo|\d+",
+        r"[a-z]+\d|\d+[a-z]",
+        r"a.c|\d+",
+        r"\$\d|a\.b",
+        r"\[x\]|é\d",
+        r"[|]|z",
+        r"\bab\b|\d{2}",
+    ]
+    .into_iter()
+    .map(|pattern| Case { pattern, haystack })
+    .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn alternation_of_sub_patterns_on_long_text() {
+    // A branch that never matches would be searched to the end once per
+    // match if its result weren't kept between steps
+    let haystack = "12 ".repeat(50_000) + "0x1f";
+    for pattern in [r"\d+|0x[0-9a-f]+", r"(0x[0-9a-f]+)|(\d+)"] {
+        assert_eq!(
+            rexile_search(pattern, &haystack),
+            regex_search(pattern, &haystack),
+            "pattern {pattern:?}"
+        );
+        let regex = Regex::new(pattern).unwrap();
+        let rexile = Pattern::new(pattern).unwrap();
+        assert_eq!(
+            rexile.find_iter(&haystack).count(),
+            regex.find_iter(&haystack).count()
+        );
+    }
+}
+
+#[test]
+fn required_literals_start_every_match() {
+    let haystack = "GET /a POST /b https://x.io http://y colour color lib.rs librs 12.5";
+    for pattern in [
+        r"https?://\S+",
+        "GET|POST",
+        r"colou?r",
+        r"lib\.?rs",
+        r"hello \w+",
+        r"\d+\.\d+",
+    ] {
+        let re = Pattern::new(pattern).unwrap();
+        let literals = re.required_literals();
+        assert_eq!(re.has_prefilter(), !literals.is_empty(), "{pattern:?}");
+        if literals.is_empty() {
+            continue;
+        }
+        for mat in Regex::new(pattern).unwrap().find_iter(haystack) {
+            assert!(
+                literals
+                    .iter()
+                    .any(|lit| mat.as_str().starts_with(lit.as_str())),
+                "{pattern:?} matched {:?}, which starts with none of {literals:?}",
+                mat.as_str()
+            );
+        }
+    }
+}
+
+#[test]
+fn escaped_parens_match_literally() {
+    let haystack = "a(b)c abc the price$(100) f:x f(:x call (42) (7 12(x) (b)";
+    let patterns = [
+        r"a\(b\)c",
+        r"price\$\(100\)",
+        r"f\(?:x",
+        r"\(\d+\)",
+        r"\(?\d+\)?",
+        r"(\d+)\(x\)",
+        r"\((?:a|b)\)",
+    ];
+    for pattern in [r"a\(b\)c", r"price\$\(100\)", r"f\(?:x", r"\(\d+\)"] {
+        assert_eq!(
+            Pattern::new(pattern).unwrap().captures_len(),
+            1,
+            "{pattern:?}"
+        );
+    }
+    let cases: Vec<Case> = patterns
+        .into_iter()
+        .map(|pattern| Case { pattern, haystack })
+        .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn bounded_group_repetition_matches_regex() {
+    let haystack = "ab abab ababab abababab x12-abab xababy xaby ba";
+    let cases: Vec<Case> = [
+        r"(ab){2,3}",
+        r"(ab){2}",
+        r"(?:ab){2,}",
+        r"(?:ab){0,1}x",
+        r"x(ab){2,3}y",
+        r"(a|b){3}",
+        r"(\d+)-(ab){2}",
+    ]
+    .into_iter()
+    .map(|pattern| Case { pattern, haystack })
+    .collect();
+    assert_search_compatible(&cases);
+}
+
+#[test]
+fn match_at_start_agrees_with_anchored_regex() {
+    let cases = [
+        ("foo", "foobar"),
+        ("foo", "barfoo"),
+        (r"\d+", "123abc"),
+        (r"\d+", "abc123"),
+        (r"\d{2,3}", "12345"),
+        (r"[a-z_]\w*", "foo_1 = 2"),
+        (r"[a-z]+\d", "abc12"),
+        (r"\bfoo\b", "foo bar"),
+        (r"\w+\s*=", "key  = value"),
+        ("cat|category", "category"),
+        (r"(\w+)@(\w+)", "user@host rest"),
+        (r"(\w+)@(\w+)", " user@host"),
+        ("a*", "bbb"),
+        ("", "x"),
+        ("é+", "ééx"),
+    ];
+    for (pattern, haystack) in cases {
+        let anchored = Regex::new(&format!("^(?:{pattern})")).unwrap();
+        let expected = anchored.find(haystack).map(|m| m.end());
+        let pattern = Pattern::new(pattern).unwrap();
+        assert_eq!(
+            pattern.match_at_start(haystack),
+            expected,
+            "pattern {pattern:?} on haystack {haystack:?}"
+        );
+        assert_eq!(pattern.is_match_at_start(haystack), expected.is_some());
+    }
+}
+
 #[test]
 fn invalid_patterns_fail_to_compile() {
-    for pattern in ["[", "(", "a{", "a{2,1}"] {
+    for pattern in [
+        "[", "(", "a{", "a{2,1}", "[abc", "[^]", "*foo", "+a", "a|?", "(*a)", "a)", "(?:a", "\\",
+    ] {
         assert!(
             Pattern::new(pattern).is_err(),
             "rexile unexpectedly compiled invalid pattern {pattern:?}"
@@ -402,3 +1387,68 @@ fn invalid_patterns_fail_to_compile() {
         );
     }
 }
+
+#[test]
+fn deeply_nested_groups_fail_to_compile() {
+    for (open, close) in [("(", ")"), ("(?:", ")")] {
+        let pattern = format!("{}a{}", open.repeat(10_000), close.repeat(10_000));
+        let err = Pattern::new(&pattern).unwrap_err();
+        assert!(err.to_string().contains("too deeply nested"), "{err}");
+        assert!(Regex::new(&pattern).is_err());
+    }
+}
+
+#[test]
+fn group_zero_spans_agree_with_find() {
+    let patterns = [
+        // Runs the sequence scanners read straight off the bytes
+        r"[^a ]+[ab]*",
+        r"\d+[ab]*?",
+        r".+?[ab]*",
+        r".+\d*?",
+        // Inner literals with backtracking before them
+        r"[ab]*[^a ]?ab",
+        r"\w*xab+",
+        r"b?ab\d*?",
+        // Boundaries need the text before each search position
+        r"\b\d+?",
+        r"\bx*",
+        r"b\s+",
+        // Sub-patterns ahead of a group
+        r"\w+(?:c|d)+?",
+        r"[a-z]+?b*?(?:ab|c)+",
+        r"(?:ab|c)?",
+        ".",
+    ];
+    let haystacks = ["aab b1. xab", "xxx abab a", "abc=foo  dd", "a\nb xa", "n\n"];
+
+    for pattern in patterns {
+        let rexile = Pattern::new(pattern).unwrap();
+        let regex = Regex::new(pattern).unwrap();
+        for haystack in haystacks {
+            let found = rexile.find(haystack);
+            let group_zero = rexile.captures(haystack).and_then(|caps| caps.pos(0));
+            let expected = regex.find(haystack).map(|m| (m.start(), m.end()));
+            assert_eq!(found, expected, "find {pattern:?} on {haystack:?}");
+            assert_eq!(group_zero, found, "captures {pattern:?} on {haystack:?}");
+
+            let iterated: Vec<_> = rexile
+                .captures_iter(haystack)
+                .filter_map(|caps| caps.pos(0))
+                .collect();
+            let expected: Vec<_> = regex
+                .find_iter(haystack)
+                .map(|m| (m.start(), m.end()))
+                .collect();
+            assert_eq!(
+                rexile.find_all(haystack),
+                expected,
+                "find_all {pattern:?} on {haystack:?}"
+            );
+            assert_eq!(
+                iterated, expected,
+                "captures_iter {pattern:?} on {haystack:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use rexile::{Pattern, PatternBuilder};
+
+#[test]
+fn test_any_line_break_matches_crlf_as_one_unit() {
+    let p = Pattern::new(r"a\Rb").unwrap();
+    assert_eq!(p.find("a\r\nb"), Some((0, 4)));
+    assert_eq!(p.find("a\nb"), Some((0, 3)));
+    assert_eq!(p.find("a\rb"), Some((0, 3)));
+    assert!(!p.is_match("a\n\nb"));
+    assert!(!p.is_match("a b"));
+
+    // A lone \R takes the whole \r\n pair
+    let p = Pattern::new(r"\R").unwrap();
+    assert_eq!(p.find_all("x\r\ny\nz\r"), vec![(1, 3), (4, 5), (6, 7)]);
+}
+
+#[test]
+fn test_any_line_break_in_captures() {
+    let p = Pattern::new(r"(\w+)\R(\w+)").unwrap();
+    let caps = p.captures("key\r\nvalue").unwrap();
+    assert_eq!(caps.get(1), Some("key"));
+    assert_eq!(caps.get(2), Some("value"));
+}
+
+#[test]
+fn test_horizontal_and_vertical_whitespace() {
+    let h = Pattern::new(r"a\h+b").unwrap();
+    assert!(h.is_match("a \t\u{A0}b"));
+    assert!(!h.is_match("a\nb"));
+
+    let v = Pattern::new(r"\v").unwrap();
+    assert_eq!(v.find("ab\x0Bc"), Some((2, 3)));
+    assert!(v.is_match("line\u{2028}sep"));
+    assert!(!v.is_match("a \tb"));
+
+    let class = Pattern::new(r"[\h\v]+").unwrap();
+    assert_eq!(class.find("x \r\n\ty"), Some((1, 5)));
+}
+
+#[test]
+fn test_crlf_dot_excludes_carriage_return() {
+    let plain = Pattern::new(r"a.+").unwrap();
+    assert_eq!(plain.find("ab\r\ncd"), Some((0, 3)));
+
+    let inline = Pattern::new(r"(?R)a.+").unwrap();
+    assert_eq!(inline.find("ab\r\ncd"), Some((0, 2)));
+
+    let built = PatternBuilder::new(r"a.+").crlf(true).build().unwrap();
+    assert_eq!(built.find("ab\r\ncd"), Some((0, 2)));
+    assert!(!built.is_match("a\r"));
+
+    // (?s) still lets . take every terminator
+    let dotall = Pattern::new(r"(?sR)a.+").unwrap();
+    assert_eq!(dotall.find("ab\r\ncd"), Some((0, 6)));
+}
+
+#[test]
+fn test_multiline_anchors() {
+    let p = Pattern::new(r"(?m)^b").unwrap();
+    assert_eq!(p.find("a\nb"), Some((2, 3)));
+    assert!(!p.is_match("ab"));
+
+    let p = Pattern::new(r"(?m)^\w+$").unwrap();
+    assert_eq!(p.find_all("one\ntwo\nthree"), vec![(0, 3), (4, 7), (8, 13)]);
+    // Without CRLF mode, \r is ordinary text before the \n
+    assert_eq!(p.find_all("one\r\ntwo"), vec![(5, 8)]);
+}
+
+#[test]
+fn test_multiline_find_all_every_anchored_line() {
+    let text = "# header\ncode # not a comment\n  # indented\n\t#tab\n#";
+    let p = Pattern::new(r"(?m)^\s*#").unwrap();
+    assert_eq!(p.find_all(text), vec![(0, 1), (30, 33), (43, 45), (49, 50)]);
+    let comments: Vec<_> = p.find_iter(text).map(|m| m.start()).collect();
+    assert_eq!(comments, vec![0, 30, 43, 49]);
+
+    // Literal and group anchors get one match per line too
+    assert_eq!(
+        Pattern::new(r"(?m)^ab").unwrap().find_all("ab\nab ab\nxab"),
+        vec![(0, 2), (3, 5)]
+    );
+    assert_eq!(
+        Pattern::new(r"(?m)(?:a|b)$")
+            .unwrap()
+            .find_all("ba\nab\nbc"),
+        vec![(1, 2), (4, 5)]
+    );
+
+    // Without (?m) the anchor only holds at the start of the text
+    let p = Pattern::new(r"^\s*#").unwrap();
+    assert_eq!(p.find_all(text), vec![(0, 1)]);
+}
+
+#[test]
+fn test_crlf_multiline_anchors() {
+    let p = Pattern::new(r"(?mR)^\w+$").unwrap();
+    let lines: Vec<_> = p.find_iter("a\r\nbb\r\nccc").map(|m| m.as_str()).collect();
+    assert_eq!(lines, vec!["a", "bb", "ccc"]);
+
+    let built = PatternBuilder::new(r"^.*$")
+        .multi_line(true)
+        .crlf(true)
+        .build()
+        .unwrap();
+    assert_eq!(built.find("first\r\nsecond"), Some((0, 5)));
+
+    // A lone \r ends a line too
+    let p = Pattern::new(r"(?mR)x$").unwrap();
+    assert_eq!(p.find("ax\rb"), Some((1, 2)));
+}
+
+#[test]
+fn test_multiline_captures() {
+    let p = Pattern::new(r"(?mR)^(\w+)=(\w+)$").unwrap();
+    let caps = p.captures("# config\r\nkey=value\r\n").unwrap();
+    assert_eq!(caps.get(1), Some("key"));
+    assert_eq!(caps.get(2), Some("value"));
+
+    let pairs: Vec<_> = p
+        .captures_iter("a=1\r\nb=2\r\n")
+        .map(|caps| (caps.get(1).unwrap(), caps.get(2).unwrap()))
+        .collect();
+    assert_eq!(pairs, vec![("a", "1"), ("b", "2")]);
+}
+
+#[test]
+fn test_custom_line_terminator() {
+    let records = "key=1\0other\nkey=2\0key=3";
+    let p = PatternBuilder::new(r"(?m)^key=\d$")
+        .line_terminator(b'\0')
+        .build()
+        .unwrap();
+    assert_eq!(p.find_all(records), vec![(0, 5), (18, 23)]);
+    let values: Vec<_> = p.find_iter(records).map(|m| m.as_str()).collect();
+    assert_eq!(values, vec!["key=1", "key=3"]);
+
+    // `.` stops at the terminator but crosses `\n`
+    let p = PatternBuilder::new(r"(?m)^o.*$")
+        .line_terminator(b'\0')
+        .build()
+        .unwrap();
+    assert_eq!(p.find(records), Some((6, 17)));
+    let dot = PatternBuilder::new(r"a.b")
+        .line_terminator(b'\0')
+        .build()
+        .unwrap();
+    assert!(dot.is_match("a\nb"));
+    assert!(!dot.is_match("a\0b"));
+
+    // Captures split lines the same way
+    let p = PatternBuilder::new(r"(?m)^(\w+)=(\d)$")
+        .line_terminator(b'\0')
+        .build()
+        .unwrap();
+    let pairs: Vec<_> = p
+        .captures_iter(records)
+        .map(|caps| caps.get(2).unwrap())
+        .collect();
+    assert_eq!(pairs, vec!["1", "3"]);
+
+    // Without the option, `\n` is still the only terminator
+    let p = Pattern::new(r"(?m)^key").unwrap();
+    assert_eq!(p.find_all(records), vec![(0, 3), (12, 15)]);
+
+    assert!(PatternBuilder::new("a")
+        .line_terminator(0xFF)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn test_end_anchor_before_final_newline() {
+    let foo = Pattern::new("foo$").unwrap();
+    assert!(foo.is_match("foo"));
+    assert!(foo.is_match("foo\n"));
+    assert!(!foo.is_match("foo\nbar"));
+    assert!(!foo.is_match("foo\n\n"));
+    assert_eq!(foo.find("a foo\n"), Some((2, 5)));
+
+    // Whole-text and grouped patterns stop before the newline too
+    let number = Pattern::new(r"^(\d+)$").unwrap();
+    let caps = number.captures("123\n").unwrap();
+    assert_eq!(caps.pos(0), Some((0, 3)));
+    assert_eq!(&caps[1], "123");
+    assert_eq!(
+        Pattern::new("$").unwrap().find_all("a\n"),
+        vec![(1, 1), (2, 2)]
+    );
+    assert_eq!(Pattern::new("a$|b").unwrap().find("xa\n"), Some((1, 2)));
+
+    // \Z is the same anchor; \z only holds at the very end
+    assert!(Pattern::new(r"foo\Z").unwrap().is_match("foo\n"));
+    let strict = Pattern::new(r"foo\z").unwrap();
+    assert!(strict.is_match("foo"));
+    assert!(!strict.is_match("foo\n"));
+    assert!(Pattern::new(r"a\z|b$").is_err());
+}
@@ -1,4 +1,4 @@
-use rexile::Pattern;
+use rexile::{Pattern, PatternError, UnsupportedFeature};
 
 #[test]
 fn test_positive_lookahead_standalone() {
@@ -82,3 +82,89 @@ fn test_lookahead_with_quantifier() {
     assert!(pattern.is_match("foo123"));
     assert!(!pattern.is_match("foobar"));
 }
+
+#[test]
+fn test_standalone_lookaround_positions() {
+    let cases: &[(&str, &str, &[usize])] = &[
+        (r"(?=ab)", "abxab ab", &[0, 3, 6]),
+        (r"(?<=ab)", "abxab ab", &[2, 5, 8]),
+        (r"(?=ab|x)", "abxab", &[0, 2, 3]),
+        (r"(?!a)", "aab", &[2, 3]),
+        (r"(?<!a)", "aab", &[0, 3]),
+        (r"(?<=é)", "éaé", &[2, 5]),
+        (r"(?=\d)", "a1b22", &[1, 3, 4]),
+        (r"(?<=)", "é", &[0, 2]),
+        (r"(?=a|)", "éa", &[0, 2, 3]),
+    ];
+    for &(pattern, text, expected) in cases {
+        let p = Pattern::new(pattern).unwrap();
+        let starts: Vec<usize> = p.find_all(text).iter().map(|&(start, _)| start).collect();
+        assert_eq!(starts, expected, "find_all {pattern:?} on {text:?}");
+        let starts: Vec<usize> = p.find_iter(text).map(|m| m.start()).collect();
+        assert_eq!(starts, expected, "find_iter {pattern:?} on {text:?}");
+        assert_eq!(p.is_match(text), !expected.is_empty());
+    }
+}
+
+#[test]
+fn test_literal_lookaround_on_long_text() {
+    // Candidates come from a literal search, not a scan of every offset
+    let text = format!("{}foobar", "x".repeat(200_000));
+    let ahead = Pattern::new(r"(?=bar)").unwrap();
+    assert_eq!(ahead.find(&text), Some((200_003, 200_003)));
+    let behind = Pattern::new(r"(?<=foo)").unwrap();
+    assert_eq!(behind.find(&text), Some((200_003, 200_003)));
+    assert!(!Pattern::new(r"(?<=baz)").unwrap().is_match(&text));
+}
+
+#[test]
+fn test_variable_length_lookbehind() {
+    type Case = (&'static str, &'static str, &'static [(usize, usize)]);
+    let cases: &[Case] = &[
+        (
+            r"(?<=\$\s{0,3})\d+",
+            "cost $  42 and 7, $5",
+            &[(8, 10), (19, 20)],
+        ),
+        (r"(?<=a{1,3})b", "aab b", &[(2, 3)]),
+        (r"(?<=é{2})x", "ééx éx", &[(4, 5)]),
+        // A shorter branch listed first doesn't hide a longer one
+        (r"(?<=a|ab)c", "abc", &[(2, 3)]),
+        (r"(?<!\$\s?)\d+", "$ 4 9", &[(4, 5)]),
+    ];
+    for &(pattern, text, expected) in cases {
+        let p = Pattern::new(pattern).unwrap();
+        assert_eq!(p.find_all(text), expected, "{pattern:?} on {text:?}");
+    }
+
+    // Without a longest match every earlier position would be a candidate
+    for pattern in [r"(?<=\d+)x", r"(?<!\$\s*)\d+", r"(?<=\$\s*)\d+"] {
+        assert_eq!(
+            Pattern::new(pattern).unwrap_err(),
+            PatternError::UnsupportedFeature(UnsupportedFeature::VariableLengthLookbehind),
+            "{pattern:?}"
+        );
+    }
+}
+
+#[test]
+fn test_bounded_lookbehind_on_long_text() {
+    // Only starts within the lookbehind's reach are tried
+    let text = format!("{}$ 12", "x".repeat(100_000));
+    let pattern = Pattern::new(r"(?<=\$\s?)\d+").unwrap();
+    assert_eq!(pattern.find(&text), Some((100_002, 100_004)));
+}
+
+#[test]
+fn test_lookaround_is_match_tries_later_candidates() {
+    // The first candidate fails the assertion; a later one passes it
+    for (pattern, text) in [
+        (r"a*(?=b)", "cab"),
+        (r"foo(?=bar)", "foox foobar"),
+        (r"(?<=\$)\d+", "1 $2"),
+    ] {
+        let pattern = Pattern::new(pattern).unwrap();
+        assert!(pattern.find(text).is_some(), "{pattern:?}");
+        assert!(pattern.is_match(text), "{pattern:?}");
+    }
+}
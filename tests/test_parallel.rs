@@ -0,0 +1,74 @@
+#![cfg(feature = "parallel")]
+
+use rexile::Pattern;
+
+/// Run `f` on a pool big enough that the test texts get split
+fn with_threads<T: Send>(f: impl FnOnce() -> T + Send) -> T {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(8)
+        .build()
+        .unwrap()
+        .install(f)
+}
+
+fn assert_same_as_find_all(pattern: &str, text: &str) {
+    let re = Pattern::new(pattern).unwrap();
+    let expected = re.find_all(text);
+    assert_eq!(
+        with_threads(|| re.find_all_parallel(text)),
+        expected,
+        "pattern {pattern:?}"
+    );
+}
+
+#[test]
+fn test_runs_straddling_chunk_boundaries() {
+    // Long runs are cut by every chunk boundary
+    let text = format!("{} x {}", "7".repeat(300_000), "9".repeat(300_000));
+    for pattern in [r"\d+", r"\d{2,3}", r"[0-9]{5,}", r"\w+", r"\d"] {
+        assert_same_as_find_all(pattern, &text);
+    }
+    let re = Pattern::new(r"\d+").unwrap();
+    assert_eq!(
+        with_threads(|| re.find_all_parallel(&text)),
+        vec![(0, 300_000), (300_003, 600_003)]
+    );
+}
+
+#[test]
+fn test_literals_out_of_step_with_chunks() {
+    // "aa" in a run of a's: a chunk starting at an odd offset finds
+    // different matches than the sequential search and has to be redone
+    let text = "a".repeat(1_000_001);
+    for pattern in ["aa", "aaa", "a"] {
+        assert_same_as_find_all(pattern, &text);
+    }
+
+    let text = "foo bar foobar barfoo ".repeat(40_000);
+    for pattern in ["foo", "foo|bar", "foobar|foo", r"[a-z]+"] {
+        assert_same_as_find_all(pattern, &text);
+    }
+}
+
+#[test]
+fn test_chunks_start_on_char_boundaries() {
+    let text = "é1ü22 ".repeat(100_000);
+    for pattern in [r"\d+", r"[éü]+", "ü2", r"\w+"] {
+        assert_same_as_find_all(pattern, &text);
+    }
+}
+
+#[test]
+fn test_context_dependent_patterns_fall_back() {
+    let text = "word 12 words 3 ".repeat(40_000);
+    for pattern in [r"\bwords?\b", r"^word", r"(?m)^\w+", r"\d*", r"(\w+) (\d+)"] {
+        assert_same_as_find_all(pattern, &text);
+    }
+}
+
+#[test]
+fn test_short_and_empty_texts() {
+    let re = Pattern::new(r"\d+").unwrap();
+    assert_eq!(re.find_all_parallel(""), vec![]);
+    assert_eq!(re.find_all_parallel("a1b22"), vec![(1, 2), (3, 5)]);
+}
@@ -0,0 +1,95 @@
+use rexile::{Pattern, PatternSet};
+
+/// Indices of the patterns that match, one pattern at a time
+fn loop_matches(patterns: &[&str], text: &str) -> Vec<usize> {
+    patterns
+        .iter()
+        .enumerate()
+        .filter(|(_, pattern)| Pattern::new(pattern).unwrap().is_match(text))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+#[test]
+fn test_set_agrees_with_each_pattern() {
+    let patterns = [
+        r"abc\d",
+        "ab",
+        "foo",
+        "oob",
+        r"error|warn(ing)?",
+        r"\d{4}-\d{2}",
+        r"\w+@\w+",
+        "^start",
+        r"end\d+$",
+        "(?i)hello",
+    ];
+    let set = PatternSet::new(patterns).unwrap();
+    assert_eq!(set.len(), patterns.len());
+
+    for text in [
+        "",
+        "abcx abc1",
+        "foob",
+        "ab",
+        "a warning on 2024-01",
+        "me@host",
+        "start here",
+        "not start",
+        "the end42",
+        "the end42 ",
+        "HeLLo",
+        "nothing to see",
+    ] {
+        assert_eq!(
+            set.matches(text),
+            loop_matches(&patterns, text),
+            "text {text:?}"
+        );
+        assert_eq!(
+            set.is_match(text),
+            !loop_matches(&patterns, text).is_empty()
+        );
+    }
+}
+
+#[test]
+fn test_set_shares_literals_between_patterns() {
+    // The same literal required by several patterns, and literals that are
+    // prefixes of each other, all start candidates at one hit
+    let patterns = [r"GET /\w+", r"GET /api/\d+", "GE", r"GET /api/v\d"];
+    let set = PatternSet::new(patterns).unwrap();
+    assert_eq!(set.matches("GET /api/v2"), vec![0, 2, 3]);
+    assert_eq!(set.matches("x GET /api/7"), vec![0, 1, 2]);
+    assert_eq!(set.matches("GE"), vec![2]);
+}
+
+#[test]
+fn test_literal_set_matches_in_one_pass() {
+    // Plain literals and literal alternations, overlapping and prefixing
+    // each other, next to patterns that still need their matcher
+    let patterns = [
+        "foo", "foobar", "bar|baz", "ob", "oba|xyz", r"ba\w", "(?i)FOO", "a|",
+    ];
+    let set = PatternSet::new(patterns).unwrap();
+    for text in ["", "foobar", "xfoo baz", "xyzob", "FoO", "nothing", "ba"] {
+        assert_eq!(
+            set.matches(text),
+            loop_matches(&patterns, text),
+            "text {text:?}"
+        );
+    }
+}
+
+#[test]
+fn test_empty_set() {
+    let set = PatternSet::new(Vec::<&str>::new()).unwrap();
+    assert!(set.is_empty());
+    assert!(!set.is_match("anything"));
+    assert!(set.matches("anything").is_empty());
+}
+
+#[test]
+fn test_set_reports_invalid_pattern() {
+    assert!(PatternSet::new(["ok", "(unclosed"]).is_err());
+}
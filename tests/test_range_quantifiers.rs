@@ -138,3 +138,33 @@ fn test_mixed_quantifiers() {
     println!("Email find result: {:?}", result);
     // Should find "user@example.com"
 }
+
+#[test]
+fn test_bounded_quantifier_on_groups() {
+    let pattern = Pattern::new(r"(ab){2,3}").unwrap();
+    assert!(pattern.is_match("ababab"));
+    assert!(!pattern.is_match("ab"));
+    assert_eq!(pattern.find("xabababababx"), Some((1, 7)));
+
+    let pattern = Pattern::new(r"(?:ab){2}").unwrap();
+    assert_eq!(pattern.find_all("ababab abab"), vec![(0, 4), (7, 11)]);
+
+    let pattern = Pattern::new(r"(?:ab){2,}").unwrap();
+    assert_eq!(pattern.find("ab abababab"), Some((3, 11)));
+
+    let pattern = Pattern::new(r"x(ab){2,3}y").unwrap();
+    assert!(pattern.is_match("xababy"));
+    assert!(!pattern.is_match("xaby"));
+    assert!(!pattern.is_match("xababababy"));
+
+    let pattern = Pattern::new(r"(\d+)-(ab){2}").unwrap();
+    let caps = pattern.captures("id 12-abab").unwrap();
+    assert_eq!(caps.get(1), Some("12"));
+    assert_eq!(caps.get(0), Some("12-abab"));
+}
+
+#[test]
+fn test_invalid_bounded_quantifier_on_groups() {
+    assert!(Pattern::new(r"(ab){3,2}").is_err());
+    assert!(Pattern::new(r"x(?:ab){3,2}").is_err());
+}
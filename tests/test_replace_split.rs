@@ -1,4 +1,5 @@
-use rexile::Pattern;
+use rexile::{Overlap, Pattern};
+use std::borrow::Cow;
 
 #[test]
 fn test_replace_simple() {
@@ -84,6 +85,59 @@ fn test_split_empty_parts() {
     assert_eq!(parts, vec!["a", "", "b"]);
 }
 
+#[test]
+fn test_empty_matches_once_per_position() {
+    let pattern = Pattern::new("a*").unwrap();
+
+    // One empty match per position, including the end of the text
+    assert_eq!(
+        pattern.find_all("bbb"),
+        vec![(0, 0), (1, 1), (2, 2), (3, 3)]
+    );
+    let starts: Vec<_> = pattern.captures_iter("bbb").map(|c| c.pos(0)).collect();
+    assert_eq!(starts.len(), 4);
+    assert_eq!(pattern.replace_all("bbb", "-"), "-b-b-b-");
+
+    // No empty match right where a non-empty one ended
+    assert_eq!(pattern.find_all("baaab"), vec![(0, 0), (1, 4), (5, 5)]);
+
+    // Empty matches advance by a whole char
+    let empty = Pattern::new("").unwrap();
+    assert_eq!(empty.find_all("aé"), vec![(0, 0), (1, 1), (3, 3)]);
+}
+
+#[test]
+fn test_split_on_zero_width_pattern() {
+    let empty = Pattern::new("").unwrap();
+    let parts: Vec<_> = empty.split("aé").collect();
+    assert_eq!(parts, vec!["", "a", "é", ""]);
+
+    let boundary = Pattern::new(r"\b").unwrap();
+    let parts: Vec<_> = boundary.split("ab cd").collect();
+    assert_eq!(parts, vec!["", "ab", " ", "cd", ""]);
+}
+
+#[test]
+fn test_replace_all_zero_width_matches() {
+    let boundary = Pattern::new(r"\b").unwrap();
+    assert_eq!(boundary.replace_all("ab cd", "|"), "|ab| |cd|");
+    // A template goes through captures_iter instead of find_all
+    assert_eq!(boundary.replace_all("ab cd", "[$0]"), "[]ab[] []cd[]");
+    assert_eq!(boundary.replace_all_cow("ab cd", "|"), "|ab| |cd|");
+
+    let not_boundary = Pattern::new(r"\B").unwrap();
+    assert_eq!(not_boundary.replace_all("abc d", "|"), "a|b|c d");
+
+    // Empty and non-empty matches mixed, with multi-byte chars in between
+    let pattern = Pattern::new("a*").unwrap();
+    assert_eq!(pattern.replace_all("baaac", "-"), "-b-c-");
+    assert_eq!(pattern.replace_all("éaé", "<$0>"), "<>é<a>é<>");
+
+    let empty = Pattern::new("").unwrap();
+    assert_eq!(empty.replace_all("aé", "|"), "|a|é|");
+    assert_eq!(empty.replace_all("", "|"), "|");
+}
+
 #[test]
 fn test_replace_literal_dollar() {
     let pattern = Pattern::new(r"\w+").unwrap();
@@ -92,6 +146,44 @@ fn test_replace_literal_dollar() {
     assert_eq!(pattern.replace("hello", "$price"), "$price");
 }
 
+#[test]
+fn test_replace_all_template_syntax() {
+    let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+
+    // Braced references and escaped dollars share Captures::expand
+    assert_eq!(pattern.replace_all("a=1 b=2", "${2}0 $$$1"), "10 $a 20 $b");
+}
+
+#[test]
+fn test_replace_all_unmatched_group_is_empty() {
+    let pattern = Pattern::new(r"(\d+)?(\w+)").unwrap();
+
+    // Group 1 doesn't take part in "abc": $1 is replaced with nothing
+    assert_eq!(pattern.replace_all("abc 12x", "[$1|$2]"), "[|abc] [12|x]");
+    assert_eq!(
+        pattern.replace_all_strict("abc 12x", "[$1|$2]").unwrap(),
+        "[|abc] [12|x]"
+    );
+}
+
+#[test]
+fn test_replace_all_strict_unknown_group() {
+    let pattern = Pattern::new(r"(\d+)?(\w+)").unwrap();
+
+    // Lenient replace_all drops references to groups that don't exist
+    assert_eq!(pattern.replace_all("abc", "$1$2$3"), "abc");
+
+    for (template, reference) in [("$1$2$3", "3"), ("${10}", "10"), ("${name}", "name")] {
+        let err = pattern.replace_all_strict("abc", template).unwrap_err();
+        assert_eq!(err.reference, reference);
+        assert_eq!(err.groups, 2);
+    }
+    // Checked even when nothing matches
+    assert!(pattern.replace_all_strict("", "$3").is_err());
+    // $0 is the whole match and $$ is not a reference
+    assert_eq!(pattern.replace_all_strict("abc", "$0$$").unwrap(), "abc$");
+}
+
 #[test]
 fn test_replace_all_no_match() {
     let pattern = Pattern::new(r"\d+").unwrap();
@@ -99,3 +191,81 @@ fn test_replace_all_no_match() {
     // No match, return original
     assert_eq!(pattern.replace_all("no numbers", "X"), "no numbers");
 }
+
+#[test]
+fn test_replace_all_cow() {
+    let pattern = Pattern::new(r"(\w+)=(\d+)").unwrap();
+
+    // Nothing to substitute: the input is borrowed as-is
+    let text = "no pairs here";
+    match pattern.replace_all_cow(text, "$2=$1") {
+        Cow::Borrowed(borrowed) => assert!(std::ptr::eq(borrowed, text)),
+        Cow::Owned(_) => panic!("expected a borrowed result"),
+    }
+
+    let replaced = pattern.replace_all_cow("a=1 b=2", "$2=$1");
+    assert!(matches!(replaced, Cow::Owned(_)));
+    assert_eq!(replaced, "1=a 2=b");
+}
+
+#[test]
+fn test_split_edge_cases_follow_str_split() {
+    let comma = Pattern::new(",").unwrap();
+    for text in [",a,", "a,,b", "", ",", ",,", "a"] {
+        let parts: Vec<_> = comma.split(text).collect();
+        let expected: Vec<_> = text.split(',').collect();
+        assert_eq!(parts, expected, "split {:?}", text);
+    }
+
+    let parts: Vec<_> = comma.split(",a,").collect();
+    assert_eq!(parts, vec!["", "a", ""]);
+    let parts: Vec<_> = comma.split("").collect();
+    assert_eq!(parts, vec![""]);
+}
+
+#[test]
+fn test_split_terminator_skips_trailing_empty_piece() {
+    let comma = Pattern::new(",").unwrap();
+    for text in [",a,", "a,,b", "", ",", ",,", "a", "a,"] {
+        let parts: Vec<_> = comma.split_terminator(text).collect();
+        let expected: Vec<_> = text.split_terminator(',').collect();
+        assert_eq!(parts, expected, "split_terminator {:?}", text);
+    }
+
+    let parts: Vec<_> = comma.split_terminator("a,,b,").collect();
+    assert_eq!(parts, vec!["a", "", "b"]);
+}
+
+#[test]
+fn test_find_all_keeps_adjacent_matches() {
+    let digits = Pattern::new(r"\d+").unwrap();
+    assert_eq!(digits.find_all("12a34"), vec![(0, 2), (3, 5)]);
+
+    // Matches that touch are all found, with nothing skipped between them
+    let pair = Pattern::new(r"\d\d").unwrap();
+    assert_eq!(pair.find_all("123456"), vec![(0, 2), (2, 4), (4, 6)]);
+    let word_then_a = Pattern::new(r"\w+(a)").unwrap();
+    assert_eq!(word_then_a.find_all("ba ba"), vec![(0, 2), (3, 5)]);
+    let lazy = Pattern::new(r"(a)\w+?").unwrap();
+    assert_eq!(lazy.find_all("ab1ab2"), vec![(0, 2), (3, 5)]);
+}
+
+#[test]
+fn test_find_all_overlap_policy() {
+    let pair = Pattern::new(r"\d\d").unwrap();
+    assert_eq!(
+        pair.find_all_with("12345", Overlap::NonOverlapping),
+        pair.find_all("12345")
+    );
+    assert_eq!(
+        pair.find_all_with("12345", Overlap::Overlapping),
+        vec![(0, 2), (1, 3), (2, 4), (3, 5)]
+    );
+
+    // The overlapping spans are those captures_iter_overlapping reports
+    let spans: Vec<_> = pair
+        .captures_iter_overlapping("12345")
+        .filter_map(|caps| caps.pos(0))
+        .collect();
+    assert_eq!(pair.find_all_with("12345", Overlap::Overlapping), spans);
+}
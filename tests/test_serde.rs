@@ -0,0 +1,29 @@
+#![cfg(feature = "serde")]
+
+use rexile::Pattern;
+
+#[test]
+fn test_pattern_round_trips_as_source_string() {
+    let pattern = Pattern::new(r"(\w+)@(\w+)\.com").unwrap();
+
+    let json = serde_json::to_string(&pattern).unwrap();
+    assert_eq!(json, r#""(\\w+)@(\\w+)\\.com""#);
+
+    let restored: Pattern = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.find("mail: me@example.com"), Some((6, 20)));
+}
+
+#[test]
+fn test_invalid_pattern_is_a_deserialize_error() {
+    let err = serde_json::from_str::<Pattern>(r#""a{2,1}""#).unwrap_err();
+    assert!(err.to_string().contains("Parse error"), "{err}");
+
+    assert!(serde_json::from_str::<Pattern>("42").is_err());
+}
+
+#[test]
+fn test_patterns_in_config_struct() {
+    let filters: Vec<Pattern> = serde_json::from_str(r#"["^GET ", "\\d{3}$"]"#).unwrap();
+    assert!(filters[0].is_match("GET /index"));
+    assert!(filters[1].is_match("status 200"));
+}
@@ -0,0 +1,119 @@
+use rexile::{Pattern, StreamMatcher};
+
+/// Stream `text` in chunks of `size` bytes (on char boundaries) and collect
+/// the match spans
+fn stream_spans(matcher: StreamMatcher, text: &str, size: usize) -> Vec<(usize, usize)> {
+    let mut matcher = matcher;
+    let mut spans = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut cut = size.min(rest.len());
+        while !rest.is_char_boundary(cut) {
+            cut += 1;
+        }
+        let (chunk, tail) = rest.split_at(cut);
+        spans.extend(matcher.push(chunk).iter().map(|m| (m.start(), m.end())));
+        rest = tail;
+    }
+    spans.extend(matcher.finish().iter().map(|m| (m.start(), m.end())));
+    spans
+}
+
+#[test]
+fn test_stream_matches_straddling_chunks() {
+    let pattern = Pattern::new("needle").unwrap();
+    let text = "hay needle hay hayneedle needleneedle";
+    let expected = pattern.find_all(text);
+
+    for size in 1..=text.len() {
+        let spans = stream_spans(StreamMatcher::new(&pattern), text, size);
+        assert_eq!(spans, expected, "chunk size {size}");
+    }
+}
+
+#[test]
+fn test_stream_alternation_prefers_first_branch() {
+    // "foo" is listed first, so it wins even when "foobar" would fit
+    let pattern = Pattern::new("foo|foobar|bar").unwrap();
+    let text = "xfoobarx barfoo";
+    let expected = pattern.find_all(text);
+
+    for size in 1..=text.len() {
+        let spans = stream_spans(StreamMatcher::new(&pattern), text, size);
+        assert_eq!(spans, expected, "chunk size {size}");
+    }
+}
+
+#[test]
+fn test_stream_variable_length_pattern_with_window() {
+    let pattern = Pattern::new(r"\d+").unwrap();
+    let text = "id 12345, total 67 of 890";
+    let expected = pattern.find_all(text);
+
+    for size in 1..=text.len() {
+        let spans = stream_spans(StreamMatcher::with_window(&pattern, 8), text, size);
+        assert_eq!(spans, expected, "chunk size {size}");
+    }
+}
+
+#[test]
+fn test_stream_reports_text_and_offsets() {
+    let pattern = Pattern::new("→|é").unwrap();
+    let mut stream = StreamMatcher::new(&pattern);
+
+    let mut found = stream.push("aé b");
+    found.extend(stream.push("→ c"));
+    found.extend(stream.finish());
+
+    let found: Vec<_> = found
+        .iter()
+        .map(|m| (m.start(), m.end(), m.as_str().to_string()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![(1, 3, "é".to_string()), (5, 8, "→".to_string())]
+    );
+}
+
+/// Stream `chunks` and collect the match spans
+fn stream_chunks(matcher: StreamMatcher, chunks: &[&str]) -> Vec<(usize, usize)> {
+    let mut matcher = matcher;
+    let mut spans = Vec::new();
+    for chunk in chunks {
+        spans.extend(matcher.push(chunk).iter().map(|m| (m.start(), m.end())));
+    }
+    spans.extend(matcher.finish().iter().map(|m| (m.start(), m.end())));
+    spans
+}
+
+#[test]
+fn test_stream_anchors_and_boundaries_see_earlier_chunks() {
+    let cases: &[(&str, &[&str])] = &[
+        (r"\bcat\b", &["scat", " dog"]),
+        (r"\bcat", &["xxscat"]),
+        (r"\bcat", &["s", "cat cat"]),
+        ("^foo", &["xfoo", "foo"]),
+        ("^foo", &["foo", "foo"]),
+        ("(?m)^foo", &["x\n", "foo", "\nfoo"]),
+        (r"\d*", &["1a", "", "b22"]),
+    ];
+    for &(source, chunks) in cases {
+        let pattern = Pattern::new(source).unwrap();
+        let expected = pattern.find_all(&chunks.concat());
+        for window in [1, 3, 4096] {
+            let spans = stream_chunks(StreamMatcher::with_window(&pattern, window), chunks);
+            assert_eq!(
+                spans, expected,
+                "{source:?} over {chunks:?}, window {window}"
+            );
+        }
+    }
+
+    // Chunks longer than the default window
+    let pattern = Pattern::new(r"\bcat\b").unwrap();
+    let text = format!("{}scat dog cat", "x".repeat(5000));
+    for size in [1000, 5001, 6000] {
+        let spans = stream_spans(StreamMatcher::new(&pattern), &text, size);
+        assert_eq!(spans, pattern.find_all(&text), "chunk size {size}");
+    }
+}
@@ -1,4 +1,4 @@
-use rexile::Pattern;
+use rexile::{Pattern, PatternBuilder};
 
 #[test]
 fn test_unicode_arrow() {
@@ -84,3 +84,126 @@ fn test_unicode_in_middle_of_match() {
     let result = p.find(input);
     assert!(result.is_some());
 }
+
+#[test]
+fn test_unicode_letter_and_number_properties() {
+    let input = "Grüße, мир 世界 42 ٣٤";
+    let letters = Pattern::new(r"\p{L}+").unwrap();
+    let words: Vec<&str> = letters.find_iter(input).map(|m| m.as_str()).collect();
+    assert_eq!(words, vec!["Grüße", "мир", "世界"]);
+
+    let numbers = Pattern::new(r"\p{N}+").unwrap();
+    let nums: Vec<&str> = numbers.find_iter(input).map(|m| m.as_str()).collect();
+    assert_eq!(nums, vec!["42", "٣٤"]);
+
+    assert!(letters.is_match("ж"));
+    assert!(!letters.is_match("42 -"));
+}
+
+#[test]
+fn test_unicode_property_forms() {
+    // Single-letter form, two-letter categories and negation
+    let p = Pattern::new(r"\pL\p{Ll}*").unwrap();
+    assert_eq!(p.find("12 Ärger"), Some((3, 9)));
+
+    let p = Pattern::new(r"\p{Lu}").unwrap();
+    assert_eq!(p.find("abcΔ"), Some((3, 5)));
+
+    let p = Pattern::new(r"\P{L}+").unwrap();
+    assert_eq!(
+        p.find("héllo, wörld").map(|(s, e)| &"héllo, wörld"[s..e]),
+        Some(", ")
+    );
+
+    // Properties combine with other members inside brackets
+    let p = Pattern::new(r"^[\p{L}\d_]+$").unwrap();
+    assert!(p.is_match("naïve_2"));
+    assert!(!p.is_match("naïve-2"));
+
+    let p = Pattern::new(r"(\p{L}+)=(\p{N}+)").unwrap();
+    let caps = p.captures("größe=١٢").unwrap();
+    assert_eq!(caps.get(1), Some("größe"));
+    assert_eq!(caps.get(2), Some("١٢"));
+}
+
+#[test]
+fn test_unicode_word_boundaries() {
+    // Non-ASCII letters are word chars for \b and \B
+    let p = Pattern::new(r"\bαβγ\b").unwrap();
+    assert_eq!(p.find("x αβγ y"), Some((2, 8)));
+    assert!(!p.is_match("αβγδ"));
+
+    let p = Pattern::new(r"\bfoo\b").unwrap();
+    assert!(!p.is_match("éfoo"));
+
+    // ASCII-only mode: inline (?-u), the builder option or \b{ascii}
+    let p = Pattern::new(r"(?-u)\bfoo\b").unwrap();
+    assert_eq!(p.find("éfoo"), Some((2, 5)));
+
+    let p = PatternBuilder::new(r"\bfoo\b")
+        .unicode_word_boundary(false)
+        .build()
+        .unwrap();
+    assert_eq!(p.find("éfoo"), Some((2, 5)));
+
+    let p = Pattern::new(r"\b{ascii}foo").unwrap();
+    assert_eq!(p.find("éfoo"), Some((2, 5)));
+    assert!(Pattern::new(r"\B{ascii}foo").unwrap().is_match("xfoo"));
+}
+
+#[test]
+fn test_boundary_iteration_over_multibyte_text() {
+    // Zero-width matches must not land inside a char
+    let p = Pattern::new(r"\B").unwrap();
+    let positions: Vec<_> = p.find_iter("αβγ").map(|m| m.start()).collect();
+    assert_eq!(positions, vec![2, 4]);
+}
+
+#[test]
+fn test_zero_width_matches_step_over_emoji_and_cjk() {
+    let text = "🎉日本語é";
+    let char_starts: Vec<_> = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .chain(std::iter::once(text.len()))
+        .collect();
+
+    // Each pattern can match empty, so every step forward lands between chars
+    for pattern in ["x*", "x*y*", r"(x)?", "(?=é)", "(?<=日)", r"(?<=日)\w*"] {
+        let p = Pattern::new(pattern).unwrap();
+        for (start, end) in p.find_all(text) {
+            assert!(char_starts.contains(&start), "{pattern}: {start}");
+            assert!(char_starts.contains(&end), "{pattern}: {end}");
+        }
+        for caps in p.captures_iter(text) {
+            let (start, _) = caps.pos(0).unwrap();
+            assert!(char_starts.contains(&start), "{pattern}: {start}");
+        }
+        let rejoined: String = p.split(text).collect();
+        assert_eq!(rejoined, text, "{pattern}");
+    }
+
+    let p = Pattern::new("x*").unwrap();
+    assert_eq!(p.find_all(text).len(), 6);
+    assert_eq!(p.replace_all("日本", "|"), "|日|本|");
+}
+
+#[test]
+fn test_dot_matches_invalid_bytes() {
+    let dot = Pattern::new("a.c").unwrap();
+    assert_eq!(dot.find_bytes(b"a\xFFc"), Some((0, 3)));
+    assert_eq!(dot.find_bytes(b"a\nc"), None);
+    // A valid multi-byte char is still one char
+    assert_eq!(dot.find_bytes("aéc".as_bytes()), Some((0, 4)));
+    assert_eq!(
+        dot.find_all_bytes(b"a\x80c a\xC3c abc"),
+        vec![(0, 3), (4, 7), (8, 11)]
+    );
+
+    // Invalid bytes are nothing but "not \n" to the rest of the pattern
+    let word = Pattern::new(r"\w+").unwrap();
+    assert_eq!(word.find_all_bytes(b"ab\xFFcd"), vec![(0, 2), (3, 5)]);
+    let not_a = Pattern::new("[^a]+").unwrap();
+    assert_eq!(not_a.find_bytes(b"a\xE9\xA9b"), Some((1, 4)));
+    assert!(!Pattern::new("x").unwrap().is_match_bytes(b"\xFF\xFE"));
+}
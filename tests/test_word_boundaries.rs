@@ -1,4 +1,4 @@
-use rexile::Pattern;
+use rexile::{Pattern, PatternBuilder};
 
 #[test]
 fn test_word_boundary_detection() {
@@ -20,7 +20,7 @@ fn test_non_word_boundary() {
 
     // Should find non-boundaries
     assert!(pattern.is_match("hello")); // Inside "hello"
-    assert!(!pattern.is_match("")); // No non-boundaries in empty text
+    assert!(pattern.is_match("")); // Empty text is a non-boundary, as in regex
 }
 
 #[test]
@@ -78,6 +78,111 @@ fn test_boundary_empty_text() {
     assert!(!pattern.is_match(""));
 }
 
+#[test]
+fn test_boundaries_in_alternation_branches() {
+    let pattern = Pattern::new(r"\bcat\b|\bdog\b").unwrap();
+
+    // The boundaries are assertions, not part of the branch text
+    assert!(!pattern.is_match("concatenate"));
+    assert!(!pattern.is_match("hotdogs"));
+    assert_eq!(
+        pattern.find_all("concatenate cat, dog"),
+        vec![(12, 15), (17, 20)]
+    );
+    assert_eq!(pattern.find_all(r"\bcat\b"), vec![]);
+}
+
+#[test]
+fn test_alternation_boundaries_see_the_previous_match() {
+    // Each search resumes after the last match, which is still text before
+    // the boundary, so the second "cat" of "catcat" doesn't start a word
+    assert_eq!(
+        Pattern::new(r"\bcat|x").unwrap().find_all("catcat"),
+        vec![(0, 3)]
+    );
+    assert_eq!(
+        Pattern::new(r"\bc|1").unwrap().find_all("abab cc"),
+        vec![(5, 6)]
+    );
+    assert_eq!(
+        Pattern::new(r"(\bcat)|x").unwrap().find_all("catcat cat"),
+        vec![(0, 3), (7, 10)]
+    );
+    assert_eq!(
+        Pattern::new(r"\bcat\b|\bdog\b")
+            .unwrap()
+            .find_all("cat dogdog dog"),
+        vec![(0, 3), (11, 14)]
+    );
+}
+
 // NOTE: Complex patterns like \bhello\b require sequence parsing
 // which will be implemented in future phases. For now, \b and \B
 // work as standalone boundary matchers.
+
+#[test]
+fn test_continue_anchor_stops_at_gaps() {
+    let token = Pattern::new(r"\G\s*(\w+)").unwrap();
+    let text = "let x = 1";
+
+    // Each token has to start where the previous one ended
+    assert_eq!(token.find_at(text, 0), Some((0, 3)));
+    assert_eq!(token.find_at(text, 3), Some((3, 5)));
+    assert_eq!(token.find_at(text, 5), None);
+    assert_eq!(token.find_all(text), vec![(0, 3), (3, 5)]);
+    let words: Vec<_> = token
+        .captures_iter(text)
+        .map(|caps| caps.get(1).unwrap().to_string())
+        .collect();
+    assert_eq!(words, vec!["let", "x"]);
+
+    // Without an earlier match, only the start of the text qualifies
+    assert!(!token.is_match("= 1"));
+    assert!(token.captures("= 1").is_none());
+
+    // Flags still apply to the rest of the pattern
+    let ab = Pattern::new(r"(?i)\Gab").unwrap();
+    assert_eq!(ab.find_all("ABabxab"), vec![(0, 2), (2, 4)]);
+}
+
+#[test]
+fn test_continue_anchor_only_at_pattern_start() {
+    assert!(matches!(
+        Pattern::new(r"a\Gb"),
+        Err(rexile::PatternError::UnsupportedFeature(_))
+    ));
+    assert!(matches!(
+        Pattern::new(r"\Ga|b"),
+        Err(rexile::PatternError::UnsupportedFeature(_))
+    ));
+    assert!(Pattern::new(r"\G(?:a|b)").is_ok());
+}
+
+#[test]
+fn test_anchored_search() {
+    let word = PatternBuilder::new(r"\w+|=")
+        .anchored(true)
+        .build()
+        .unwrap();
+    assert_eq!(word.find("x = 1"), Some((0, 1)));
+    assert_eq!(word.find(" x"), None);
+    assert!(!word.is_match(" x"));
+    assert_eq!(word.find_at("x = 1", 2), Some((2, 3)));
+    assert_eq!(word.find_at("x = 1", 1), None);
+    assert_eq!(word.find_all("ab=c d"), vec![(0, 2), (2, 3), (3, 4)]);
+    assert_eq!(word.captures("=x").unwrap().get(0), Some("="));
+    assert!(word.captures(" =").is_none());
+
+    // Lookbehind still sees the text before the search start
+    let after_dash = PatternBuilder::new(r"(?<=-)\d+")
+        .anchored(true)
+        .build()
+        .unwrap();
+    assert_eq!(after_dash.find_at("a-12", 2), Some((2, 4)));
+    assert_eq!(after_dash.find_at("a 12", 2), None);
+
+    // Anchoring is part of the pattern's identity
+    let unanchored = PatternBuilder::new(r"\w+|=").build().unwrap();
+    assert_ne!(word, unanchored);
+    assert_eq!(unanchored.find(" x"), Some((1, 2)));
+}